@@ -0,0 +1,35 @@
+/* C interface of the acquire_rs rules engine, see src/ffi.rs */
+#ifndef ACQUIRE_RS_H
+#define ACQUIRE_RS_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A game that is played by players outside of the library and by bots */
+typedef struct Engine Engine;
+
+/* Creates a game from the configuration json, returns NULL when it can not be created */
+Engine *acquire_game_new(const char *config);
+
+/* Returns the state of the game as json, free it with acquire_string_free.
+ * Returns NULL when the game is NULL, has been poisoned or the state can not be created */
+char *acquire_game_state(const Engine *game);
+
+/* Applies the action json, returns NULL on success, otherwise the reason why it is not valid.
+ * Free the reason with acquire_string_free.
+ * A game in which the engine has stopped with an internal error is poisoned: every later call
+ * returns an error until the game is freed */
+char *acquire_game_apply(Engine *game, const char *action);
+
+/* Frees the game, nothing happens when it is NULL */
+void acquire_game_free(Engine *game);
+
+/* Frees a string returned by the library, nothing happens when it is NULL */
+void acquire_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
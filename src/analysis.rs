@@ -0,0 +1,303 @@
+use std::cmp::Reverse;
+
+use miette::{miette, Result};
+
+use crate::{
+    base_game::{
+        bank::{Bank, ShareholderRank},
+        board::Position,
+        hotel_chains::HotelChain,
+        player::Player,
+    },
+    bot::net_worth,
+    evaluation::GameState,
+    game::hotel_chain_manager::HotelChainManager,
+    logic::place_hotel::{analyze_position, PlaceHotelCase},
+};
+
+/// What a fusion would change for one player.
+pub struct FusionOutcome {
+    pub player_id: u32,
+    /// The bonuses the player would get for the dead chains
+    pub bonuses: Vec<(HotelChain, u32)>,
+    /// By how much the money and the value of the stocks of the player would change.
+    /// The stocks of the dead chains are valued with the price for which they can be sold during
+    /// the fusion.
+    pub net_worth_change: i64,
+}
+
+impl FusionOutcome {
+    /// Returns the sum of all bonuses the player would get.
+    pub fn total_bonus(&self) -> u32 {
+        self.bonuses.iter().map(|(_, bonus)| bonus).sum()
+    }
+}
+
+/// The result of a fusion for one possible surviving chain.
+pub struct FusionAnalysis {
+    /// The chain that survives the fusion
+    pub alive: HotelChain,
+    /// The chains that are fused into the surviving chain
+    pub dead: Vec<HotelChain>,
+    /// The outcome for each player, the index is the id of the player
+    pub outcomes: Vec<FusionOutcome>,
+}
+
+/// Analyzes what would happen when the card is played now.
+///
+/// The fusion is played on copies, the game is not changed. When multiple chains are the largest
+/// the player decides which chain survives, then one analysis is returned for each of these
+/// chains.
+/// # Returns
+/// * `Ok(analyses)` - The analyses, one for each chain that can survive
+/// * `Err` - When the card does not fuse chains
+pub fn analyze_fusion(card: &Position, state: &GameState) -> Result<Vec<FusionAnalysis>> {
+    let (mut chains, origin) = match analyze_position(card, state.board, state.hotel_chain_manager)
+    {
+        PlaceHotelCase::Fusion(chains, origin) => (chains, origin),
+        _ => {
+            return Err(miette!(
+                "Unable to analyze fusion: The card {} does not fuse chains",
+                card
+            ))
+        }
+    };
+    chains.sort_by_key(|chain| Reverse(state.hotel_chain_manager.chain_length(chain)));
+    let longest = state.hotel_chain_manager.chain_length(&chains[0]);
+    let mut analyses = Vec::new();
+    for alive in chains
+        .iter()
+        .filter(|chain| state.hotel_chain_manager.chain_length(chain) == longest)
+    {
+        let dead: Vec<HotelChain> = chains
+            .iter()
+            .filter(|chain| *chain != alive)
+            .copied()
+            .collect();
+        analyses.push(analyze_surviving_chain(*alive, dead, origin, state)?);
+    }
+    Ok(analyses)
+}
+
+/// Returns the id of each player that would be paid a majority shareholder bonus when the chain
+/// is fused now, the rank for which the bonus would be paid and the bonus.
+///
+/// The bonuses are paid on copies at the current prices, the game is not changed.
+pub fn preview_bonuses(
+    dead: &HotelChain,
+    players: &[Player],
+    bank: &Bank,
+    hotel_chain_manager: &HotelChainManager,
+) -> Result<Vec<(u32, ShareholderRank, u32)>> {
+    let mut bank = bank.clone();
+    let mut copies: Vec<Player> = players
+        .iter()
+        .map(|player| player.headless_copy())
+        .collect();
+    bank.update_largest_shareholders(&copies);
+    bank.give_majority_shareholder_bonuses(&mut copies, dead, hotel_chain_manager, false)?;
+    Ok(copies
+        .iter()
+        .zip(players)
+        .filter(|(copy, player)| copy.money > player.money)
+        .filter_map(|(copy, player)| {
+            bank.shareholder_rank(player.id, dead, hotel_chain_manager)
+                .map(|rank| (player.id, rank, copy.money - player.money))
+        })
+        .collect())
+}
+
+/// Plays the fusion into the surviving chain on copies of the game.
+fn analyze_surviving_chain(
+    alive: HotelChain,
+    dead: Vec<HotelChain>,
+    origin: Position,
+    state: &GameState,
+) -> Result<FusionAnalysis> {
+    let mut board = state.board.clone();
+    let mut bank = state.bank.clone();
+    let mut hotel_chain_manager = state.hotel_chain_manager.clone();
+    let mut players: Vec<Player> = state
+        .players
+        .iter()
+        .map(|player| player.headless_copy())
+        .collect();
+    let mut bonuses = vec![Vec::new(); players.len()];
+    bank.update_largest_shareholders(&players);
+    for chain in &dead {
+        let money_before: Vec<u32> = players.iter().map(|player| player.money).collect();
+        bank.give_majority_shareholder_bonuses(&mut players, chain, &hotel_chain_manager, false)?;
+        for (index, player) in players.iter().enumerate() {
+            if player.money > money_before[index] {
+                bonuses[index].push((*chain, player.money - money_before[index]));
+            }
+        }
+    }
+    board.place_hotel(&origin)?;
+    for chain in &dead {
+        hotel_chain_manager.fuse_chains(&alive, chain, &mut board)?;
+    }
+    if let PlaceHotelCase::ExtendsChain(chain, positions) =
+        analyze_position(&origin, &board, &hotel_chain_manager)
+    {
+        for position in positions {
+            hotel_chain_manager.add_hotel_to_chain(&chain, position, &mut board)?;
+        }
+    }
+    let outcomes = players
+        .iter()
+        .zip(bonuses)
+        .map(|(player, bonuses)| FusionOutcome {
+            player_id: player.id,
+            bonuses,
+            net_worth_change: worth_after(
+                player,
+                &dead,
+                state.hotel_chain_manager,
+                &hotel_chain_manager,
+            ) as i64
+                - net_worth(
+                    &state.players[player.id as usize],
+                    state.hotel_chain_manager,
+                ) as i64,
+        })
+        .collect();
+    Ok(FusionAnalysis {
+        alive,
+        dead,
+        outcomes,
+    })
+}
+
+/// Returns the money of the player plus the value of all their stocks after the fusion.
+/// The stocks of the dead chains are valued with their price before the fusion.
+fn worth_after(
+    player: &Player,
+    dead: &[HotelChain],
+    before: &HotelChainManager,
+    after: &HotelChainManager,
+) -> u32 {
+    player.money
+        + stocks_value(player, before, |chain| dead.contains(chain))
+        + stocks_value(player, after, |chain| after.chain_status(chain))
+}
+
+/// Returns the value of the stocks the player owns of the chains that match the filter.
+fn stocks_value(
+    player: &Player,
+    hotel_chain_manager: &HotelChainManager,
+    filter: impl Fn(&HotelChain) -> bool,
+) -> u32 {
+    HotelChain::iterator()
+        .filter(|chain| filter(chain))
+        .map(|chain| {
+            Bank::stock_price(hotel_chain_manager, chain)
+                * player.owned_stocks.stocks_for_hotel(chain)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::{
+            bank::{Bank, ShareholderRank},
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+        },
+        evaluation::GameState,
+        game::hotel_chain_manager::HotelChainManager,
+    };
+
+    use super::{analyze_fusion, preview_bonuses};
+
+    #[test]
+    fn fusion_is_analyzed_without_changing_the_game() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Player 1")),
+            Player::new(vec![], 1, false, String::from("Player 2")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Imperial,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        hotel_chain_manager.start_chain(
+            HotelChain::Luxor,
+            vec![
+                Position::new('A', 4),
+                Position::new('A', 5),
+                Position::new('A', 6),
+            ],
+            &mut board,
+            &mut players[1],
+            &mut bank,
+        )?;
+        players[0].owned_stocks.set_stocks(&HotelChain::Imperial, 4);
+        players[1].owned_stocks.set_stocks(&HotelChain::Imperial, 1);
+        players[1].owned_stocks.set_stocks(&HotelChain::Luxor, 2);
+        bank.update_largest_shareholders(&players);
+        let state = GameState::new(&board, &bank, &hotel_chain_manager, &players);
+        let analyses = analyze_fusion(&Position::new('A', 3), &state)?;
+        assert_eq!(analyses.len(), 1);
+        let analysis = &analyses[0];
+        assert_eq!(analysis.alive, HotelChain::Luxor);
+        assert_eq!(analysis.dead, vec![HotelChain::Imperial]);
+        let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Imperial);
+        assert_eq!(analysis.outcomes[0].total_bonus(), price * 10);
+        assert_eq!(analysis.outcomes[0].net_worth_change, price as i64 * 10);
+        assert_eq!(analysis.outcomes[1].total_bonus(), price * 5);
+        let increase = HotelChain::Luxor.stock_value(6) - HotelChain::Luxor.stock_value(3);
+        assert_eq!(
+            analysis.outcomes[1].net_worth_change,
+            (price * 5 + increase * 2) as i64
+        );
+        // The game has not been changed
+        assert!(board.is_hotel_placed(&Position::new('A', 3)).is_none());
+        assert_eq!(hotel_chain_manager.chain_length(&HotelChain::Imperial), 2);
+        assert!(analyze_fusion(&Position::new('C', 3), &state).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn bonuses_are_previewed_without_paying_them() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Player 1")),
+            Player::new(vec![], 1, false, String::from("Player 2")),
+            Player::new(vec![], 2, false, String::from("Player 3")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Imperial,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        players[0].owned_stocks.set_stocks(&HotelChain::Imperial, 4);
+        players[1].owned_stocks.set_stocks(&HotelChain::Imperial, 2);
+        let money = players[0].money;
+        let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Imperial);
+        let bonuses =
+            preview_bonuses(&HotelChain::Imperial, &players, &bank, &hotel_chain_manager)?;
+        assert_eq!(
+            bonuses,
+            vec![
+                (0, ShareholderRank::Largest, price * 10),
+                (1, ShareholderRank::SecondLargest, price * 5)
+            ]
+        );
+        assert_eq!(players[0].money, money);
+        Ok(())
+    }
+}
@@ -6,76 +6,126 @@ pub mod board {
         logic::place_hotel::{analyze_position, PlaceHotelCase},
     };
 
-    use self::letter::{next_letter, prev_letter, LETTERS};
+    use self::letter::LETTERS;
     use super::hotel_chains::HotelChain;
 
     use miette::{miette, Result};
     use owo_colors::{AnsiColors, OwoColorize, Rgb};
-    use std::cmp::Ordering;
     use std::fmt::{self, Display, Formatter};
 
+    /// The number of lines of the board
+    pub const ROWS: usize = 9;
+    /// The number of positions in each line of the board
+    pub const COLUMNS: usize = 12;
+
     /// The board object that contains all information about the current state of the board.
+    #[derive(Clone)]
     pub struct Board {
-        pub pieces: Vec<Vec<Piece>>,
+        /// The pieces line by line, the piece of a position is found with [`Board::get`]
+        pub pieces: Vec<Piece>,
+        /// The position of the hotel that has been placed last, it is highlighted when the board
+        /// is shown so that the players see what the previous player did
+        pub last_placed: Option<Position>,
+    }
+
+    impl Default for Board {
+        fn default() -> Self {
+            Self::new()
+        }
     }
 
     impl Board {
         /// Creates a new board and initializes it
         pub fn new() -> Self {
-            let mut pieces: Vec<Vec<Piece>> = Vec::new();
+            let mut pieces: Vec<Piece> = Vec::new();
             // initialize pieces
             for c in LETTERS {
-                let mut x_pieces: Vec<Piece> = Vec::new();
-                for i in 1..=12 {
-                    x_pieces.push(Piece {
+                for i in 1..=COLUMNS as u32 {
+                    pieces.push(Piece {
                         chain: None,
                         position: Position::new(c, i),
                         piece_set: false,
                     })
                 }
-                pieces.push(x_pieces);
             }
-            Self { pieces }
+            Self {
+                pieces,
+                last_placed: None,
+            }
+        }
+
+        /// Returns the piece at the position, `None` when the position is not on the board
+        pub fn get(&self, position: &Position) -> Option<&Piece> {
+            self.pieces.get(Self::index(position)?)
         }
 
-        /// Returns a vector that contains strings that describe the current state of the board.
-        pub fn get_board_state(&self, small_board: bool) -> Vec<String> {
+        /// Returns the piece at the position to change it, `None` when the position is not on
+        /// the board
+        pub fn get_mut(&mut self, position: &Position) -> Option<&mut Piece> {
+            let index = Self::index(position)?;
+            self.pieces.get_mut(index)
+        }
+
+        /// Returns the index of the piece at the position in `pieces`
+        fn index(position: &Position) -> Option<usize> {
+            position.is_on_board().then(|| position.index())
+        }
+
+        /// Returns the lines of the board from A to I
+        pub fn lines(&self) -> impl Iterator<Item = &[Piece]> {
+            self.pieces.chunks(COLUMNS)
+        }
+
+        /// Returns the lines in which the board is shown in the console, the lines of the board
+        /// from A to I followed by the numbers of the columns.
+        ///
+        /// The hotel that has been placed last is shown with inverted colors, in plain text it is
+        /// put in brackets like `[X]`.
+        pub fn render_console(&self, style: BoardStyle) -> Vec<String> {
             let mut board_state = Vec::new();
-            let mut letters = LETTERS.iter();
-            let mut first_line = true;
-            for x in &self.pieces {
-                if !first_line {
-                    if !small_board {
-                        board_state.push(String::from(
-                            "--------------------------------------------------",
-                        ));
-                    }
-                } else {
-                    first_line = false;
+            for (index, (letter, line)) in LETTERS.iter().zip(self.lines()).enumerate() {
+                if index > 0 && style == BoardStyle::Large {
+                    board_state.push("-".repeat(50));
                 }
-                let mut current_line = String::new();
-                current_line.push_str(&format!("{} ", letters.next().unwrap()));
-                for y in x {
-                    if !small_board {
-                        current_line.push_str(&format!("| {} ", y.print_text(true)));
-                    } else {
-                        current_line.push_str(&format!("{}  ", y.print_text(true)));
+                let mut current_line = format!("{} ", letter);
+                for piece in line {
+                    let last_placed = self.last_placed == Some(piece.position);
+                    let text = match last_placed {
+                        true => piece.print_text().reversed().to_string(),
+                        false => piece.print_text(),
+                    };
+                    match style {
+                        BoardStyle::Large => current_line.push_str(&format!("| {} ", text)),
+                        BoardStyle::Small => current_line.push_str(&format!("{}  ", text)),
+                        // The brackets take the place of the spaces around the symbol
+                        BoardStyle::Plain if last_placed => {
+                            current_line.push_str(&format!("[{}]", piece.plain_symbol()))
+                        }
+                        BoardStyle::Plain => {
+                            current_line.push_str(&format!("{:>2} ", piece.plain_symbol()))
+                        }
                     }
                 }
+                if style == BoardStyle::Plain {
+                    current_line.truncate(current_line.trim_end().len());
+                }
                 board_state.push(current_line);
             }
-            let mut current_line = String::new();
-            if !small_board {
-                current_line.push_str("   ");
-                for x in 1..=12 {
-                    current_line.push_str(&format!("{:2}  ", &x));
-                }
-            } else {
-                current_line.push(' ');
-                for x in 1..=12 {
-                    current_line.push_str(&format!("{:2} ", &x));
+            let mut current_line = match style {
+                BoardStyle::Large => String::from("   "),
+                BoardStyle::Small | BoardStyle::Plain => String::from(" "),
+            };
+            for column in 1..=COLUMNS {
+                match style {
+                    BoardStyle::Large => current_line.push_str(&format!("{:2}  ", column)),
+                    BoardStyle::Small | BoardStyle::Plain => {
+                        current_line.push_str(&format!("{:2} ", column))
+                    }
                 }
             }
+            if style == BoardStyle::Plain {
+                current_line.truncate(current_line.trim_end().len());
+            }
             board_state.push(current_line);
             board_state
         }
@@ -85,18 +135,12 @@ pub mod board {
         /// Ok when the hotel was placed correctly
         /// Error when the hotel was already placed
         pub fn place_hotel(&mut self, position: &Position) -> Result<()> {
-            for x in self.pieces.iter_mut() {
-                for y in x.iter_mut() {
-                    if y.position.number.eq(&position.number)
-                        && y.position.letter == position.letter
-                    {
-                        if y.piece_set {
-                            return Err(miette!("Unable to set hotel at [{}{:2}] active: The hotel has already been placed!", position.letter, position.number));
-                        } else {
-                            y.piece_set = true;
-                        }
-                    }
+            if let Some(piece) = self.get_mut(position) {
+                if piece.piece_set {
+                    return Err(miette!("Unable to set hotel at [{}{:2}] active: The hotel has already been placed!", position.letter(), position.number()));
                 }
+                piece.piece_set = true;
+                self.last_placed = Some(*position);
             }
             Ok(())
         }
@@ -111,13 +155,9 @@ pub mod board {
         /// * `Ok(())` - When the piece as updated successfully
         /// * `Err(Error)` - When the piece is not placed on the board
         pub fn update_hotel(&mut self, hotel_chain: HotelChain, position: &Position) -> Result<()> {
-            for line in self.pieces.iter_mut() {
-                for piece in line {
-                    if piece.position.eq(position) && piece.piece_set {
-                        piece.chain = Some(hotel_chain);
-                        return Ok(());
-                    }
-                }
+            if let Some(piece) = self.get_mut(position).filter(|piece| piece.piece_set) {
+                piece.chain = Some(hotel_chain);
+                return Ok(());
             }
             Err(miette!(
                 "Unable to update hotel at position {} to chain {}: Hotel has not been placed yet",
@@ -132,128 +172,147 @@ pub mod board {
         /// * `Some(None)` - When the hotel has been placed but it does not belong to any chain
         /// * `Some(HotelChain)` - When the hotel has been placed and belongs to a chain
         pub fn is_hotel_placed(&self, position: &Position) -> Option<Option<HotelChain>> {
-            for line in &self.pieces {
-                for piece in line {
-                    if piece.position.eq(position) {
-                        if !piece.piece_set {
-                            return None;
-                        }
-                        return Some(piece.chain);
-                    }
-                }
-            }
-            None
+            self.get(position)
+                .filter(|piece| piece.piece_set)
+                .map(|piece| piece.chain)
         }
     }
 
-    /// Functions related to the letter
-    pub mod letter {
-        pub const LETTERS: [char; 9] = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I'];
-
-        /// Returns the index for this letter in the `LETTERS` array
-        fn letter_to_index(letter: char) -> Option<usize> {
-            for (index, l) in LETTERS.iter().enumerate() {
-                if letter == *l {
-                    return Some(index);
-                }
-            }
-            None
-        }
+    /// How the board is shown in the console
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum BoardStyle {
+        /// The positions are separated by lines
+        Large,
+        /// The positions are shown without lines in between
+        Small,
+        /// The board is shown without colors and box-drawing lines. Empty positions are shown as
+        /// `.`, hotels that do not belong to a chain as `X` and the hotels of a chain as the
+        /// identifier of the chain.
+        Plain,
+    }
 
-        /// Returns the next letter if there is one.
-        /// B would return C
-        pub fn next_letter(letter: char) -> Option<char> {
-            let index = letter_to_index(letter);
-            index?;
-            if index.unwrap() == 8 {
-                return None;
+    impl BoardStyle {
+        /// Returns the style in which the board is shown to a player that has chosen a small
+        /// board or plain text
+        pub fn new(small_board: bool, plain: bool) -> Self {
+            match (small_board, plain) {
+                (_, true) => BoardStyle::Plain,
+                (true, false) => BoardStyle::Small,
+                (false, false) => BoardStyle::Large,
             }
-            Some(*LETTERS.get(index.unwrap() + 1).unwrap())
         }
+    }
 
-        /// Returns the previous letter if there is one.
-        /// B would return A
-        pub fn prev_letter(letter: char) -> Option<char> {
-            let index = letter_to_index(letter);
-            index?;
-            if index.unwrap() == 0 {
-                return None;
-            }
-            Some(*LETTERS.get(index.unwrap() - 1).unwrap())
-        }
+    /// Functions related to the letter
+    pub mod letter {
+        /// The letters of the lines of the board, the first line is `A`
+        pub const LETTERS: [char; super::ROWS] = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I'];
     }
 
-    /// Symbolizes a position on the board
+    /// Symbolizes a position on the board.
+    ///
+    /// The position is stored as the index of the line and the column, so that it can be used to
+    /// find the piece on the board. It is shown like `B7`, the letter of the line followed by the
+    /// column counted from 1.
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub struct Position {
-        pub letter: char,
-        pub number: u32,
+        /// The index of the line, the line `A` has the index 0
+        pub row: usize,
+        /// The index of the column, the column `1` has the index 0
+        pub column: usize,
     }
 
     impl Ord for Position {
         fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-            match self.number.cmp(&other.number) {
-                Ordering::Less => Ordering::Less,
-                Ordering::Greater => Ordering::Greater,
-                Ordering::Equal => match self.letter.cmp(&other.letter) {
-                    Ordering::Less => Ordering::Less,
-                    Ordering::Greater => Ordering::Greater,
-                    Ordering::Equal => Ordering::Equal,
-                },
-            }
+            (self.column, self.row).cmp(&(other.column, other.row))
         }
     }
 
     impl PartialOrd for Position {
         fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-            match self.number.cmp(&other.number) {
-                Ordering::Less => Some(Ordering::Less),
-                Ordering::Greater => Some(Ordering::Greater),
-                Ordering::Equal => match self.letter.cmp(&other.letter) {
-                    Ordering::Less => Some(Ordering::Less),
-                    Ordering::Greater => Some(Ordering::Greater),
-                    Ordering::Equal => Some(Ordering::Equal),
-                },
-            }
+            Some(self.cmp(other))
         }
     }
 
     impl Position {
-        /// Creates a new position
+        /// Creates the position with the letter of the line and the number of the column, like
+        /// `Position::new('B', 7)`.
         pub fn new(letter: char, number: u32) -> Self {
-            Self { letter, number }
+            Self {
+                row: (letter as usize).wrapping_sub('A' as usize),
+                column: (number as usize).wrapping_sub(1),
+            }
+        }
+
+        /// Creates the position with the index of the line and the column
+        pub fn from_indices(row: usize, column: usize) -> Self {
+            Self { row, column }
+        }
+
+        /// Returns the position with the name, like `B3`.
+        /// If no position on the board has that name `None` is returned.
+        pub fn from_name(name: &str) -> Option<Position> {
+            let mut chars = name.chars();
+            let letter = chars.next().filter(|letter| LETTERS.contains(letter))?;
+            let number = chars
+                .as_str()
+                .parse()
+                .ok()
+                .filter(|n| (1..=COLUMNS as u32).contains(n))?;
+            Some(Position::new(letter, number))
+        }
+
+        /// Returns the letter of the line, like `B` for `B7`
+        pub fn letter(&self) -> char {
+            LETTERS.get(self.row).copied().unwrap_or('?')
+        }
+
+        /// Returns the number of the column, like `7` for `B7`
+        pub fn number(&self) -> u32 {
+            self.column as u32 + 1
+        }
+
+        /// Returns true when the position lies on the board
+        pub fn is_on_board(&self) -> bool {
+            self.row < ROWS && self.column < COLUMNS
+        }
+
+        /// Returns the index of the position when the positions are counted line by line, `A1`
+        /// has the index 0 and `A2` the index 1. Only positions on the board have an index
+        /// below [`ROWS`] * [`COLUMNS`].
+        pub fn index(&self) -> usize {
+            self.row * COLUMNS + self.column
+        }
+
+        /// Returns the position with the index, see [`Position::index`]
+        pub fn from_index(index: usize) -> Self {
+            Self::from_indices(index / COLUMNS, index % COLUMNS)
         }
 
         /// Returns the next position.
-        /// Input B3 would return B4.
+        /// Input B3 would return B4, B12 has no next position.
         pub fn next(&self) -> Option<Position> {
-            // Check if position has a next
-            if self.number > 12 {
-                return None;
-            }
-            Some(Position::new(self.letter, self.number + 1))
+            (self.column + 1 < COLUMNS).then(|| Position::from_indices(self.row, self.column + 1))
         }
 
         /// Returns the previous position.
-        /// Input B3 would return B2.
+        /// Input B3 would return B2, B1 has no previous position.
         pub fn prev(&self) -> Option<Position> {
-            // Check if position has a prev
-            if self.number < 1 {
-                return None;
-            }
-            Some(Position::new(self.letter, self.number - 1))
+            let column = self.column.checked_sub(1)?;
+            Some(Position::from_indices(self.row, column))
         }
 
         /// Returns the position that is above this position.
         /// Input B3 would return A3.
         pub fn up(&self) -> Option<Position> {
-            prev_letter(self.letter).map(|letter| Position::new(letter, self.number))
+            let row = self.row.checked_sub(1)?;
+            Some(Position::from_indices(row, self.column))
         }
 
         /// Returns the position that is below this position.
         /// Input B3 would return C3.
         pub fn down(&self) -> Option<Position> {
-            next_letter(self.letter).map(|letter| Position::new(letter, self.number))
+            (self.row + 1 < ROWS).then(|| Position::from_indices(self.row + 1, self.column))
         }
 
         /// Returns the neighbouring positions
@@ -277,7 +336,7 @@ pub mod board {
 
     impl Display for Position {
         fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-            write!(f, "{}{:?}", self.letter, self.number)
+            write!(f, "{}{:?}", self.letter(), self.number())
         }
     }
 
@@ -290,7 +349,7 @@ pub mod board {
 
     impl PartialOrd for AnalyzedPosition {
         fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-            Some(self.position.cmp(&other.position))
+            Some(self.cmp(other))
         }
     }
 
@@ -300,34 +359,50 @@ pub mod board {
         }
     }
 
+    /// Shows the position and what happens when the card is played, like `B7 [Start chain]`.
+    ///
+    /// The text is written straight into the output and colored only there. The alternate form
+    /// `{:#}` is written without colors for players that have chosen plain text.
     impl Display for AnalyzedPosition {
         fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-            let action = match &self.place_hotel_case {
-                PlaceHotelCase::NewChain(_positions) => String::from("Start chain")
-                    .color(AnsiColors::Green)
-                    .to_string(),
-                PlaceHotelCase::ExtendsChain(chain, positions) => format!(
-                    "Extend {} by {} hotel(s)",
-                    chain.name().color(chain.color()),
-                    positions.len(),
-                ),
-                PlaceHotelCase::Fusion(_chains, _origin) => String::from("Fuse chains")
-                    .color(AnsiColors::Green)
-                    .to_string(),
-                PlaceHotelCase::Illegal(reason) => format!("Illegal: {}", reason.reason()),
-                PlaceHotelCase::SingleHotel => String::new(),
-            };
-            // No special formatting is needed
-            if action.is_empty() {
-                return write!(f, "{}", self.position);
-            }
-            if action.contains("Illegal") {
-                let content = format!("{} [{}]", self.position, action)
-                    .color(Rgb(105, 105, 105))
-                    .to_string();
-                return write!(f, "{}", content);
+            let colored = !f.alternate();
+            let position = self.position;
+            match &self.place_hotel_case {
+                PlaceHotelCase::SingleHotel => write!(f, "{}", position),
+                PlaceHotelCase::NewChain(_) | PlaceHotelCase::Fusion(_, _) => {
+                    let action = match self.place_hotel_case {
+                        PlaceHotelCase::NewChain(_) => "Start chain",
+                        _ => "Fuse chains",
+                    };
+                    match colored {
+                        true => write!(f, "{} [{}]", position, action.color(AnsiColors::Green)),
+                        false => write!(f, "{} [{}]", position, action),
+                    }
+                }
+                PlaceHotelCase::ExtendsChain(chain, positions) => match colored {
+                    true => write!(
+                        f,
+                        "{} [Extend {} by {} hotel(s)]",
+                        position,
+                        chain.name().color(chain.color()),
+                        positions.len()
+                    ),
+                    false => write!(
+                        f,
+                        "{} [Extend {} by {} hotel(s)]",
+                        position,
+                        chain.name(),
+                        positions.len()
+                    ),
+                },
+                PlaceHotelCase::Illegal(reason) => {
+                    let text = format_args!("{} [Illegal: {}]", position, reason.reason());
+                    match colored {
+                        true => write!(f, "{}", text.color(Rgb(105, 105, 105))),
+                        false => write!(f, "{}", text),
+                    }
+                }
             }
-            write!(f, "{} [{}]", self.position, action)
         }
     }
 
@@ -367,6 +442,7 @@ pub mod board {
     }
 
     /// Symbolizes a single piece that can be placed on the board
+    #[derive(Clone)]
     pub struct Piece {
         /// Stores what hotel chain this piece belongs to
         pub chain: Option<HotelChain>,
@@ -377,32 +453,24 @@ pub mod board {
     }
 
     impl Piece {
-        fn print_text(&self, compact: bool) -> String {
-            if self.piece_set {
-                if self.chain.is_some() {
-                    if compact {
-                        self.chain
-                            .as_ref()
-                            .unwrap()
-                            .identifier()
-                            .color(HotelChain::color(self.chain.as_ref().unwrap()))
-                            .to_string()
-                    } else {
-                        format!(" {} ", self.chain.as_ref().unwrap().identifier())
-                            .color(HotelChain::color(self.chain.as_ref().unwrap()))
-                            .to_string()
-                    }
-                } else if compact {
-                    "X".bright_white().to_string()
-                } else {
-                    "XXX".bright_white().to_string()
-                }
-            } else if compact {
-                ' '.white().to_string()
-            } else {
-                format!("{}{:2}", self.position.letter, self.position.number)
-                    .white()
-                    .to_string()
+        /// Returns the piece as it is shown on the board in the console: The identifier of the
+        /// chain in the color of the chain, `X` for a hotel that does not belong to a chain and
+        /// a space when no hotel has been placed.
+        fn print_text(&self) -> String {
+            match (self.piece_set, &self.chain) {
+                (true, Some(chain)) => chain.identifier().color(chain.color()).to_string(),
+                (true, None) => "X".bright_white().to_string(),
+                (false, _) => ' '.white().to_string(),
+            }
+        }
+
+        /// Returns the piece as it is shown on the board when the text is plain, see
+        /// [`BoardStyle::Plain`]
+        fn plain_symbol(&self) -> char {
+            match (self.piece_set, &self.chain) {
+                (true, Some(chain)) => chain.identifier(),
+                (true, None) => 'X',
+                (false, _) => '.',
             }
         }
     }
@@ -410,10 +478,82 @@ pub mod board {
     #[cfg(test)]
     mod tests {
         use miette::{miette, Result};
+        use owo_colors::OwoColorize;
 
         use crate::base_game::hotel_chains::HotelChain;
 
-        use super::{Board, Position};
+        use crate::logic::place_hotel::{IllegalPlacement, PlaceHotelCase};
+
+        use super::{AnalyzedPosition, Board, BoardStyle, Position};
+
+        #[test]
+        fn board_is_rendered_in_each_style() -> Result<()> {
+            let mut board = Board::new();
+            board.place_hotel(&Position::new('A', 1))?;
+            board.place_hotel(&Position::new('A', 2))?;
+            board.update_hotel(HotelChain::Luxor, &Position::new('A', 2))?;
+            let large = board.render_console(BoardStyle::Large);
+            let small = board.render_console(BoardStyle::Small);
+            let plain = board.render_console(BoardStyle::Plain);
+            // The large board has a line between each line of the board
+            assert_eq!(large.len(), 18);
+            assert_eq!(small.len(), 10);
+            assert_eq!(plain.len(), 10);
+            assert!(large[1].starts_with("-----"));
+            assert!(small[0].starts_with("A "));
+            assert_eq!(plain[0], "A  X [L] .  .  .  .  .  .  .  .  .  .");
+            assert_eq!(plain[9], "  1  2  3  4  5  6  7  8  9 10 11 12");
+            // The hotel that has been placed last is highlighted
+            assert!(large[0].contains(&"L".color(HotelChain::Luxor.color()).reversed().to_string()));
+            board.last_placed = None;
+            assert_eq!(
+                board.render_console(BoardStyle::Plain)[0],
+                "A  X  L  .  .  .  .  .  .  .  .  .  ."
+            );
+            // Plain text is used even when a small board has been chosen
+            assert_eq!(BoardStyle::new(true, true), BoardStyle::Plain);
+            Ok(())
+        }
+
+        #[test]
+        fn analyzed_positions_are_shown_with_and_without_colors() {
+            let card = |place_hotel_case| AnalyzedPosition {
+                position: Position::new('B', 7),
+                place_hotel_case,
+            };
+            let cases = [
+                card(PlaceHotelCase::SingleHotel),
+                card(PlaceHotelCase::NewChain(vec![])),
+                card(PlaceHotelCase::ExtendsChain(
+                    HotelChain::Luxor,
+                    vec![Position::new('B', 8)],
+                )),
+                card(PlaceHotelCase::Fusion(vec![], Position::new('B', 7))),
+                card(PlaceHotelCase::Illegal(IllegalPlacement::FusionIllegal)),
+            ];
+            let colored: Vec<String> = cases.iter().map(|card| format!("{}", card)).collect();
+            assert_eq!(
+                colored,
+                [
+                    "B7",
+                    "B7 [\u{1b}[32mStart chain\u{1b}[39m]",
+                    "B7 [Extend \u{1b}[38;2;231;219;0mLuxor\u{1b}[39m by 1 hotel(s)]",
+                    "B7 [\u{1b}[32mFuse chains\u{1b}[39m]",
+                    "\u{1b}[38;2;105;105;105mB7 [Illegal: Fusion illegal]\u{1b}[39m",
+                ]
+            );
+            let plain: Vec<String> = cases.iter().map(|card| format!("{:#}", card)).collect();
+            assert_eq!(
+                plain,
+                [
+                    "B7",
+                    "B7 [Start chain]",
+                    "B7 [Extend Luxor by 1 hotel(s)]",
+                    "B7 [Fuse chains]",
+                    "B7 [Illegal: Fusion illegal]",
+                ]
+            );
+        }
 
         #[test]
         fn surrounding_positions_correct() {
@@ -426,6 +566,16 @@ pub mod board {
             assert_eq!(position_next, position.next().unwrap());
             assert_eq!(position_up, position.up().unwrap());
             assert_eq!(position_down, position.down().unwrap());
+            // The positions at the edges have no neighbours outside of the board
+            assert!(Position::new('B', 12).next().is_none());
+            assert!(Position::new('B', 1).prev().is_none());
+            assert!(Position::new('A', 5).up().is_none());
+            assert!(Position::new('I', 5).down().is_none());
+            assert_eq!(Position::new('A', 1).neighbours().len(), 2);
+            assert_eq!(Position::new('I', 6).neighbours().len(), 3);
+            assert_eq!(Position::new('E', 6).neighbours().len(), 4);
+            assert_eq!(Position::from_name("B7").unwrap().to_string(), "B7");
+            assert_eq!(Position::new('B', 7), Position::from_indices(1, 6));
         }
 
         #[test]
@@ -441,54 +591,490 @@ pub mod board {
             Ok(())
         }
 
+        #[test]
+        fn pieces_are_found_by_position() {
+            let mut board = Board::new();
+            for position in [
+                Position::new('A', 1),
+                Position::new('E', 7),
+                Position::new('I', 12),
+            ] {
+                assert_eq!(board.get(&position).unwrap().position, position);
+            }
+            assert!(board.get(&Position::from_indices(1, 12)).is_none());
+            assert!(board.get(&Position::from_indices(9, 0)).is_none());
+            board.get_mut(&Position::new('C', 4)).unwrap().piece_set = true;
+            assert_eq!(board.is_hotel_placed(&Position::new('C', 4)), Some(None));
+            assert_eq!(board.lines().count(), 9);
+        }
+
         /// Place a hotel on the board without abiding by the game rules
         pub fn place_hotel_debug(
             board: &mut Board,
             position: Position,
             chain: HotelChain,
         ) -> Result<()> {
-            'outer: for x in board.pieces.iter_mut() {
-                for y in x.iter_mut() {
-                    if y.position.number.eq(&position.number)
-                        && y.position.letter == position.letter
-                    {
-                        if y.piece_set {
-                            return Err(miette!("Unable to set hotel at [{}{:2}] active: The hotel has already been placed!", position.letter, position.number));
-                        } else {
-                            y.piece_set = true;
-                            y.chain = Some(chain);
-                            break 'outer;
-                        }
-                    }
+            if let Some(piece) = board.get_mut(&position) {
+                if piece.piece_set {
+                    return Err(miette!("Unable to set hotel at [{}{:2}] active: The hotel has already been placed!", position.letter(), position.number()));
                 }
+                piece.piece_set = true;
+                piece.chain = Some(chain);
             }
             Ok(())
         }
     }
 }
 
-/// Stores and handels the settings that are provided fia the command line
+/// Stores and handles the settings that are provided via the command line
 pub mod settings {
+    use owo_colors::Rgb;
+
+    use crate::{
+        bot::{BotPersonality, BotStrategy},
+        event_stream::EventStream,
+        journal::Journal,
+        locale::Language,
+        tutorial::Tutorial,
+    };
+
+    use super::player::CancellationToken;
+
     //TODO Maybe add settings with which the board dimensions can be changed
     /// Stores the settings
     pub struct Settings {
+        /// The rules that are used in the game
+        pub rules: Rules,
+        /// The preset from which the rules have been created
+        pub rule_preset: RulePreset,
         pub small_board: bool,
-        /// Stores if some extra information should be shown to the player.
+        /// Stores how much information is shown to the players.
+        ///
+        /// Client players can choose a stricter level for themselves.
+        pub privacy: Privacy,
+        /// Stores if the game is played in fast mode. The drawn cards are not confirmed, prompts
+        /// that only wait for enter are skipped, confirmations are answered with yes and the
+        /// summaries of transactions are shown in one line.
         ///
-        /// E.g. If the player is the largest shareholder
-        pub hide_extra_info: bool,
-        /// Stores if some dialogues should be skipped
+        /// Prompts that hide private information in hot-seat mode are still shown.
         pub skip_dialogues: bool,
+        /// Stores if a neutral player takes part in the game.
+        ///
+        /// The neutral player places one hotel each round but does not own any money or stocks.
+        /// Can only be used in games with two players.
+        pub neutral_player: bool,
+        /// Stores if the players can open the debug console at the card prompt to inspect and
+        /// change the state of the game. Can only be used in local games.
+        pub debug_console: bool,
+        /// Stores if the players play in teams of two.
+        ///
+        /// Team mates can see the stocks of each other and their money is summed up in the final
+        /// account. Can only be used in games with four or six players.
+        pub teams: bool,
+        /// Stores if the game is played in hot-seat mode.
+        ///
+        /// The console is cleared between turns and the private information of a player is only
+        /// shown after they confirmed that they sit in front of the keyboard.
+        pub hot_seat: bool,
+        /// Stores how the turn order is determined at the start of the game
+        pub turn_order: TurnOrder,
+        /// The seed with which the cards are shuffled, a random turn order is drawn and the bots
+        /// are created. The same seed and the same decisions lead to the same game. A random
+        /// seed is used when `None`.
+        pub seed: Option<u64>,
+        /// The number of players that are played by bots
+        pub bots: u32,
+        /// The strategy that the bots use
+        pub bot_strategy: BotStrategy,
+        /// The personalities of the bots, the bots take them in order. When there are more bots
+        /// than personalities the personalities are repeated.
+        pub bot_personalities: Vec<BotPersonality>,
+        /// The number of playouts the Monte Carlo bots play for each decision
+        pub bot_playouts: u32,
+        /// The time in milliseconds the Monte Carlo bots may think for each decision.
+        ///
+        /// When set the bots play as many playouts as fit into the time instead of `bot_playouts`.
+        pub bot_time: Option<u32>,
+        /// Stores if the advisor is shown that tells the players how much each card would change
+        /// their position.
+        ///
+        /// In lan games the host decides if the advisor is shown to all players.
+        pub advisor: bool,
+        /// Stores if the hand cards of the players are sorted automatically when the game starts.
+        /// Each player can change this for themselves with [`SORT_COMMAND`](player::SORT_COMMAND).
+        pub auto_sort: bool,
+        /// The file to which the results of the players are appended after the final account.
+        /// Nothing is stored when `None`.
+        pub history_file: Option<String>,
+        /// The file to which the summary of the game is written as json after the final account.
+        /// Nothing is written when `None`.
+        pub summary_file: Option<String>,
+        /// The file to which the money, the value of the stocks and the net worth of each player
+        /// at the end of each round are written as csv after the final account.
+        /// Nothing is written when `None`.
+        pub finances_file: Option<String>,
+        /// The file to which the report of the game is written as html after the final account.
+        /// Nothing is written when `None`.
+        pub report_file: Option<String>,
+        /// The journal to which the position of a lan game is appended after each action, so that
+        /// the game can be recovered when the server stops. Nothing is written when `None`.
+        pub journal: Option<Journal>,
+        /// The file or pipe to which every event of the game is written as json while the game
+        /// runs. Nothing is written when `None`.
+        pub event_stream: Option<EventStream>,
+        /// The color of the first player, in a lan game the color of the host. The players get
+        /// the colors of [`super::player::PLAYER_COLORS`] when `None`.
+        pub player_color: Option<Rgb>,
+        /// The url to which the results of the game are posted as json after the final account.
+        /// Nothing is posted when `None`.
+        pub webhook_url: Option<String>,
+        /// Stores if the text is shown without colors, shareholder stars and box-drawing lines, so
+        /// that screen readers and dumb terminals can be used.
+        ///
+        /// Client players can choose this for themselves.
+        pub plain: bool,
+        /// The language in which the messages of the game are shown.
+        ///
+        /// In lan games the clients are shown the messages in the language of the host.
+        pub language: Language,
+        /// The tutorial that explains the game, is `None` when the game is not played as tutorial
+        pub tutorial: Option<Tutorial>,
+        /// The number of seconds in which a client has to answer when they are asked for input.
+        /// A client that does not answer in time while they have to choose a card leaves the
+        /// game, at other prompts the safe default is taken for them, see
+        /// [`Player::answer_or_default`](super::player::Player::or_default). The clients have
+        /// unlimited time when `None`.
+        pub input_timeout: Option<u64>,
+        /// The number of seconds after which a client that has not answered is shown the prompt
+        /// again, so that they notice that input is expected when the prompt has scrolled out
+        /// of view. The prompt is not shown again when `None`.
+        pub remind_after: Option<u64>,
+        /// Is cancelled when the host stops the game, the clients that are asked for input are
+        /// stopped then and the game ends with [`InputError::Cancelled`](super::player::InputError::Cancelled).
+        pub cancellation: CancellationToken,
     }
 
     impl Settings {
-        pub fn new(large_board: bool, hide_extra_info: bool, skip_dialogues: bool) -> Self {
+        pub fn new(large_board: bool, privacy: Privacy, skip_dialogues: bool) -> Self {
             Self {
+                rules: Rules::default(),
+                rule_preset: RulePreset::default(),
                 small_board: large_board,
-                hide_extra_info,
+                privacy,
                 skip_dialogues,
+                neutral_player: false,
+                debug_console: false,
+                teams: false,
+                hot_seat: false,
+                turn_order: TurnOrder::default(),
+                seed: None,
+                bots: 0,
+                bot_strategy: BotStrategy::default(),
+                bot_personalities: vec![BotPersonality::default()],
+                bot_playouts: 100,
+                bot_time: None,
+                advisor: false,
+                auto_sort: true,
+                history_file: None,
+                summary_file: None,
+                finances_file: None,
+                report_file: None,
+                journal: None,
+                event_stream: None,
+                player_color: None,
+                webhook_url: None,
+                plain: false,
+                language: Language::default(),
+                tutorial: None,
+                input_timeout: None,
+                remind_after: None,
+                cancellation: CancellationToken::default(),
+            }
+        }
+
+        /// Returns the personality of the bot with the number, the first bot has the number 1.
+        pub fn bot_personality(&self, bot_number: u32) -> BotPersonality {
+            if self.bot_personalities.is_empty() {
+                return BotPersonality::default();
+            }
+            self.bot_personalities[(bot_number as usize - 1) % self.bot_personalities.len()]
+        }
+    }
+
+    /// The different ways the turn order can be determined at the start of the game.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum TurnOrder {
+        /// The player that drew the lowest card starts, this is the official rule
+        #[default]
+        LowestTile,
+        /// The turn order is random
+        Random,
+        /// The host decides in which order the players play
+        HostChosen,
+    }
+
+    impl TurnOrder {
+        /// Returns the turn order with the name.
+        /// If no turn order with that name exists `None` is returned.
+        pub fn from_name(name: &str) -> Option<TurnOrder> {
+            match name {
+                "lowest-tile" => Some(TurnOrder::LowestTile),
+                "random" => Some(TurnOrder::Random),
+                "host" => Some(TurnOrder::HostChosen),
+                _ => None,
+            }
+        }
+    }
+
+    /// The different levels of information that can be shown to a player.
+    ///
+    /// The levels are ordered from the least to the most strict level.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum Privacy {
+        /// All information is shown.
+        ///
+        /// A little star next to the stocks of the player displays if they are the largest
+        /// (golden star) or second largest shareholder (silver star) for that chain.
+        #[default]
+        Open,
+        /// The shareholder stars are hidden
+        Hidden,
+        /// The stats table and the shareholder stars are hidden. Only the names of the largest
+        /// shareholders are shown.
+        Strict,
+    }
+
+    impl Privacy {
+        /// Returns the name of the privacy level
+        pub fn name(&self) -> &'static str {
+            match self {
+                Privacy::Open => "open",
+                Privacy::Hidden => "hidden",
+                Privacy::Strict => "strict",
+            }
+        }
+
+        /// Returns the privacy level with the name.
+        /// If no level with that name exists `None` is returned.
+        pub fn from_name(name: &str) -> Option<Privacy> {
+            match name {
+                "open" => Some(Privacy::Open),
+                "hidden" => Some(Privacy::Hidden),
+                "strict" => Some(Privacy::Strict),
+                _ => None,
+            }
+        }
+    }
+
+    /// The maximum number of stocks a player can buy in one turn.
+    ///
+    /// It is the same in every edition of the game, so it is not part of the [`Rules`].
+    pub const PURCHASE_LIMIT: u32 = 3;
+
+    /// The number of hotels at which a chain is safe and can no longer be fused
+    pub const SAFE_CHAIN_SIZE: u32 = 11;
+
+    /// The number of hotels a chain needs so that the game can be ended
+    pub const END_CHAIN_SIZE: u32 = 41;
+
+    /// The rules of the game that can be changed.
+    #[derive(Clone, Copy)]
+    pub struct Rules {
+        /// Stores if the third largest shareholders of a chain receive a bonus too.
+        ///
+        /// The bonus is three times the stock price.
+        pub third_shareholder_bonus: bool,
+        /// Determines how bonuses are split when multiple players share a place
+        pub tie_handling: TieHandling,
+        /// The number of stocks the founder of a chain receives for free
+        pub founder_bonus_stocks: u32,
+        /// Stores if the players are allowed to sell stocks back to the bank when they are allowed to
+        /// buy stocks.
+        pub sell_during_buy_phase: bool,
+        /// Stores if cards that can never be played are replaced at the end of each turn.
+        ///
+        /// When this is disabled a player can only redraw when they have no other cards left.
+        pub replace_dead_tiles: bool,
+        /// The money each player has when the game starts
+        pub starting_money: u32,
+    }
+
+    impl Default for Rules {
+        fn default() -> Self {
+            RulePreset::default().rules()
+        }
+    }
+
+    impl Rules {
+        /// Returns a short summary of the rules
+        pub fn summary(&self) -> Vec<String> {
+            let tie_handling = match self.tie_handling {
+                TieHandling::Exact => "exact split",
+                TieHandling::RoundUp => "each bonus rounded up",
+                TieHandling::Official => "bonuses summed up and rounded up",
+            };
+            let yes_no = |value: bool| if value { "yes" } else { "no" };
+            vec![
+                format!("Starting money: {}€", self.starting_money),
+                format!("Founder bonus stocks: {}", self.founder_bonus_stocks),
+                format!("Tie handling: {}", tie_handling),
+                format!(
+                    "Third shareholder bonus: {}",
+                    yes_no(self.third_shareholder_bonus)
+                ),
+                format!(
+                    "Sell stocks during the buy phase: {}",
+                    yes_no(self.sell_during_buy_phase)
+                ),
+                format!(
+                    "Dead cards are replaced each turn: {}",
+                    yes_no(self.replace_dead_tiles)
+                ),
+            ]
+        }
+    }
+
+    /// Named sets of rules
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum RulePreset {
+        /// The rules of the original game
+        #[default]
+        Classic,
+        /// The rules of the 1999 edition. Dead cards are replaced at the end of each turn.
+        Edition1999,
+        /// Popular house rules. Third largest shareholders get a bonus, stocks can be sold during
+        /// the buy phase and each split bonus is rounded up.
+        House,
+    }
+
+    impl RulePreset {
+        /// Returns the rules that belong to this preset
+        pub fn rules(&self) -> Rules {
+            let classic = Rules {
+                third_shareholder_bonus: false,
+                tie_handling: TieHandling::Official,
+                founder_bonus_stocks: 1,
+                sell_during_buy_phase: false,
+                replace_dead_tiles: false,
+                starting_money: 6000,
+            };
+            match self {
+                RulePreset::Classic => classic,
+                RulePreset::Edition1999 => Rules {
+                    replace_dead_tiles: true,
+                    ..classic
+                },
+                RulePreset::House => Rules {
+                    third_shareholder_bonus: true,
+                    tie_handling: TieHandling::RoundUp,
+                    sell_during_buy_phase: true,
+                    replace_dead_tiles: true,
+                    ..classic
+                },
             }
         }
+
+        /// Returns the name of the preset
+        pub fn name(&self) -> &'static str {
+            match self {
+                RulePreset::Classic => "classic",
+                RulePreset::Edition1999 => "1999",
+                RulePreset::House => "house",
+            }
+        }
+
+        /// Returns the preset with the name.
+        /// If no preset with that name exists `None` is returned.
+        pub fn from_name(name: &str) -> Option<RulePreset> {
+            match name {
+                "classic" => Some(RulePreset::Classic),
+                "1999" => Some(RulePreset::Edition1999),
+                "house" => Some(RulePreset::House),
+                _ => None,
+            }
+        }
+    }
+
+    /// The ways in which a bonus can be split between shareholders that hold the same amount of
+    /// stocks.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum TieHandling {
+        /// The bonuses are summed up and split exactly, nothing is rounded
+        Exact,
+        /// Each bonus is split on its own and rounded up to the next 100
+        RoundUp,
+        /// The bonuses are summed up, split and then rounded up to the next 100
+        #[default]
+        Official,
+    }
+
+    impl TieHandling {
+        /// Splits the bonuses between the number of shareholders.
+        /// # Arguments
+        /// * `bonuses` - The bonuses that are shared. When the largest shareholders are tied this
+        ///   contains the largest and the second largest shareholder bonus.
+        /// * `number_of_shareholders` - The number of shareholders that share the bonuses
+        /// # Returns
+        /// The money that each shareholder receives
+        pub fn split(&self, bonuses: &[u32], number_of_shareholders: u32) -> u32 {
+            match self {
+                TieHandling::Exact => bonuses.iter().sum::<u32>() / number_of_shareholders,
+                TieHandling::RoundUp => bonuses
+                    .iter()
+                    .map(|bonus| (bonus / number_of_shareholders).div_ceil(100) * 100)
+                    .sum(),
+                TieHandling::Official => {
+                    (bonuses.iter().sum::<u32>() / number_of_shareholders).div_ceil(100) * 100
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Privacy, RulePreset, TieHandling};
+
+        #[test]
+        fn rule_presets_correct() {
+            for preset in [
+                RulePreset::Classic,
+                RulePreset::Edition1999,
+                RulePreset::House,
+            ] {
+                assert_eq!(RulePreset::from_name(preset.name()), Some(preset));
+            }
+            let classic = RulePreset::Classic.rules();
+            assert!(!classic.replace_dead_tiles);
+            assert_eq!(classic.starting_money, 6000);
+            assert!(RulePreset::Edition1999.rules().replace_dead_tiles);
+            assert!(RulePreset::House.rules().third_shareholder_bonus);
+        }
+
+        #[test]
+        fn privacy_names_correct() {
+            for privacy in [Privacy::Open, Privacy::Hidden, Privacy::Strict] {
+                assert_eq!(Privacy::from_name(privacy.name()), Some(privacy));
+            }
+            assert_eq!(Privacy::from_name("secret"), None);
+            assert_eq!(Privacy::Open.max(Privacy::Strict), Privacy::Strict);
+        }
+
+        #[test]
+        fn tie_handling_split_correct() {
+            // Two largest shareholders of a chain with a stock price of 700
+            let bonuses = [7000, 3500];
+            assert_eq!(TieHandling::Exact.split(&bonuses, 2), 5250);
+            assert_eq!(TieHandling::RoundUp.split(&bonuses, 2), 5300);
+            assert_eq!(TieHandling::Official.split(&bonuses, 2), 5300);
+            // Three largest shareholders
+            assert_eq!(TieHandling::Exact.split(&bonuses, 3), 3500);
+            assert_eq!(TieHandling::RoundUp.split(&bonuses, 3), 3600);
+            assert_eq!(TieHandling::Official.split(&bonuses, 3), 3500);
+            // Three second largest shareholders
+            assert_eq!(TieHandling::Exact.split(&[3500], 3), 1166);
+            assert_eq!(TieHandling::RoundUp.split(&[3500], 3), 1200);
+            assert_eq!(TieHandling::Official.split(&[3500], 3), 1200);
+        }
     }
 }
 
@@ -504,6 +1090,9 @@ pub mod hotel_chains {
 
     use super::stock;
 
+    /// The number of hotel chains in the game
+    pub const NUMBER_OF_CHAINS: usize = 7;
+
     /// All different hotel types that exist in the game
     #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
     pub enum HotelChain {
@@ -544,7 +1133,7 @@ pub mod hotel_chains {
         }
 
         pub fn iterator() -> Iter<'static, HotelChain> {
-            const HOTELS: [HotelChain; 7] = [
+            const HOTELS: [HotelChain; NUMBER_OF_CHAINS] = [
                 HotelChain::Airport,
                 HotelChain::Festival,
                 HotelChain::Imperial,
@@ -556,6 +1145,20 @@ pub mod hotel_chains {
             HOTELS.iter()
         }
 
+        /// Returns the position of the chain in [`HotelChain::iterator`].
+        /// Used to store values of each chain in arrays.
+        pub fn index(&self) -> usize {
+            match self {
+                HotelChain::Airport => 0,
+                HotelChain::Festival => 1,
+                HotelChain::Imperial => 2,
+                HotelChain::Luxor => 3,
+                HotelChain::Oriental => 4,
+                HotelChain::Prestige => 5,
+                HotelChain::Continental => 6,
+            }
+        }
+
         /// Returns the value of a single stock for the hotel chain.
         /// Value is returned with help of [`super::stock::stock_price`].
         /// # Arguments
@@ -577,6 +1180,14 @@ pub mod hotel_chains {
             }
         }
 
+        /// Returns the chain with the name.
+        /// If no chain with that name exists `None` is returned.
+        pub fn from_name(name: &str) -> Option<HotelChain> {
+            HotelChain::iterator()
+                .find(|chain| chain.name() == name)
+                .copied()
+        }
+
         /// Returns the name of the hotel
         pub fn name(&self) -> &str {
             match *self {
@@ -623,40 +1234,51 @@ pub mod hotel_chains {
 
 /// Contains all functions for the stocks.
 pub mod stock {
-    use std::collections::HashMap;
-
-    use super::hotel_chains::{HotelChain, PriceLevel};
+    use super::hotel_chains::{HotelChain, PriceLevel, NUMBER_OF_CHAINS};
 
     /// Used to symbolize how many stocks a player has/the bank has left for a specific hotel
-    #[derive(PartialEq)]
+    #[derive(Clone, PartialEq)]
     pub struct Stocks {
-        // Contains the stocks.
-        pub stocks: HashMap<HotelChain, u32>,
+        /// Contains the stocks, indexed by [`HotelChain::index`]
+        stocks: [u32; NUMBER_OF_CHAINS],
+    }
+
+    impl Default for Stocks {
+        fn default() -> Self {
+            Self::new()
+        }
     }
 
     impl Stocks {
         /// Initializes a new stock struct. Member variables are set to 0
         pub fn new() -> Self {
-            let mut stocks: HashMap<HotelChain, u32> = HashMap::new();
-            for hotel in HotelChain::iterator() {
-                stocks.insert(*hotel, 0);
+            Self {
+                stocks: [0; NUMBER_OF_CHAINS],
             }
-            Self { stocks }
         }
 
         /// Initializes a new stock struct. Member variables are set to 25. This is used so that
         /// the bank gets all available stocks at the start.
         pub fn new_bank() -> Self {
-            let mut stocks: HashMap<HotelChain, u32> = HashMap::new();
-            for chain in HotelChain::iterator() {
-                stocks.insert(*chain, 25);
+            Self {
+                stocks: [25; NUMBER_OF_CHAINS],
             }
-            Self { stocks }
         }
 
         /// Returns the amout of stocks available for the hotel
         pub fn stocks_for_hotel(&self, chain: &HotelChain) -> &u32 {
-            self.stocks.get(chain).unwrap()
+            &self.stocks[chain.index()]
+        }
+
+        /// Returns each chain together with the number of stocks, in the order of
+        /// [`HotelChain::iterator`]
+        pub fn iter(&self) -> impl Iterator<Item = (HotelChain, u32)> + '_ {
+            HotelChain::iterator().map(|chain| (*chain, self.stocks[chain.index()]))
+        }
+
+        /// Returns the number of stocks of all chains together
+        pub fn total(&self) -> u32 {
+            self.stocks.iter().sum()
         }
 
         /// Set the stocks of the hotel to the amount.
@@ -664,17 +1286,17 @@ pub mod stock {
         /// * `hotel` - The hotel for which the stock value should be changed
         /// * `value` - The value to which the stock amount should be set
         pub fn set_stocks(&mut self, chain: &HotelChain, value: u32) {
-            *self.stocks.get_mut(chain).unwrap() = value;
+            self.stocks[chain.index()] = value;
         }
 
         /// Increases stocks for the `hotel` by `value`
         pub fn increase_stocks(&mut self, chain: &HotelChain, value: u32) {
-            *self.stocks.get_mut(chain).unwrap() += value;
+            self.stocks[chain.index()] += value;
         }
 
         /// Decreases stocks for the `hotel` by `value`
         pub fn decrease_stocks(&mut self, chain: &HotelChain, value: u32) {
-            *self.stocks.get_mut(chain).unwrap() -= value;
+            self.stocks[chain.index()] -= value;
         }
     }
 
@@ -714,7 +1336,25 @@ pub mod stock {
 
     #[cfg(test)]
     mod tests {
-        use crate::base_game::{hotel_chains::PriceLevel, stock::stock_price};
+        use crate::base_game::{
+            hotel_chains::{HotelChain, PriceLevel},
+            stock::{stock_price, Stocks},
+        };
+
+        #[test]
+        fn stocks_are_iterated_in_chain_order() {
+            let mut stocks = Stocks::new();
+            assert_eq!(stocks.total(), 0);
+            stocks.set_stocks(&HotelChain::Continental, 4);
+            stocks.increase_stocks(&HotelChain::Airport, 3);
+            stocks.decrease_stocks(&HotelChain::Continental, 1);
+            let chains: Vec<HotelChain> = stocks.iter().map(|(chain, _)| chain).collect();
+            assert_eq!(chains, HotelChain::iterator().copied().collect::<Vec<_>>());
+            assert_eq!(stocks.iter().next(), Some((HotelChain::Airport, 3)));
+            assert_eq!(*stocks.stocks_for_hotel(&HotelChain::Continental), 3);
+            assert_eq!(stocks.total(), 6);
+            assert_eq!(Stocks::new_bank().total(), 25 * 7);
+        }
 
         #[test]
         fn stock_price_correct() {
@@ -730,31 +1370,58 @@ pub mod stock {
 
 /// Manages the currently available stocks and the money.
 pub mod bank {
-    use std::{cmp::Ordering, collections::HashMap};
+    use std::collections::HashMap;
 
     use miette::{miette, Result};
-    use owo_colors::OwoColorize;
+    use owo_colors::{OwoColorize, Rgb};
 
     use crate::{
-        base_game::stock::Stocks,
-        game::{hotel_chain_manager::HotelChainManager, player_by_id},
+        base_game::{settings::Rules, stock::Stocks},
+        game::hotel_chain_manager::HotelChainManager,
+        locale::text,
         network::broadcast_others,
+        shareholders::LargestShareholders,
     };
 
-    use super::{hotel_chains::HotelChain, player::Player};
+    use super::{
+        hotel_chains::{HotelChain, NUMBER_OF_CHAINS},
+        player::{CostBasis, MoneyStatistics, Player},
+    };
 
+    #[derive(Clone)]
     pub struct Bank {
         pub stocks_for_sale: Stocks,
         /// Stores the currently largest and second largest shareholders
         pub largest_shareholders: LargestShareholders,
+        /// The rules that determine how bonuses are payed
+        pub rules: Rules,
+        /// The prices of the stocks at the end of each round
+        pub market_history: MarketHistory,
+        /// The majority shareholder bonuses that have been paid, in the order in which they have
+        /// been paid
+        pub bonus_payouts: Vec<BonusPayout>,
+        /// The chains of which stocks have been traded since the shareholders have been updated
+        /// the last time, indexed by [`HotelChain::index`]. Every chain is marked for a new
+        /// bank, see [`Bank::mark_traded`].
+        traded_chains: [bool; NUMBER_OF_CHAINS],
+    }
+
+    impl Default for Bank {
+        fn default() -> Self {
+            Self::new()
+        }
     }
 
     impl Bank {
-        /// Creates a new bank
+        /// Creates a new bank that uses the default rules
         pub fn new() -> Self {
             Self {
                 stocks_for_sale: Stocks::new_bank(),
                 largest_shareholders: LargestShareholders::new(),
+                rules: Rules::default(),
+                market_history: MarketHistory::default(),
+                bonus_payouts: Vec::new(),
+                traded_chains: [true; NUMBER_OF_CHAINS],
             }
         }
 
@@ -784,20 +1451,10 @@ pub mod bank {
             for chain in HotelChain::iterator() {
                 let mut ls = Vec::<String>::new();
                 let mut sls = Vec::<String>::new();
-                for player_id in self
-                    .largest_shareholders
-                    .largest_shareholder
-                    .get(chain)
-                    .unwrap()
-                {
+                for player_id in self.largest_shareholders.largest(chain) {
                     ls.push(format!("{}, ", player_id));
                 }
-                for player in self
-                    .largest_shareholders
-                    .second_largest_shareholder
-                    .get(chain)
-                    .unwrap()
-                {
+                for player in self.largest_shareholders.second_largest(chain) {
                     sls.push(format!("{}, ", player));
                 }
                 println!(
@@ -841,9 +1498,13 @@ pub mod bank {
                 ));
             }
             // Finally buy the stock
+            player.remove_money(stock_price)?;
+            self.mark_traded(hotel);
             self.stocks_for_sale.decrease_stocks(hotel, 1);
             player.add_stocks(hotel, 1);
-            player.remove_money(stock_price);
+            player.statistics.stocks_bought += 1;
+            player.statistics.money_spent += stock_price;
+            player.cost_basis.add_lot(hotel, 1, stock_price);
             Ok(())
         }
 
@@ -857,20 +1518,26 @@ pub mod bank {
             chain: &HotelChain,
             hotel_chain_manager: &HotelChainManager,
         ) -> Result<()> {
-            let player_stocks = *player.owned_stocks.stocks.get(chain).unwrap();
+            let player_stocks = *player.owned_stocks.stocks_for_hotel(chain);
             if player_stocks < amount {
                 return Err(miette!(
                     "Unable to sell stocks: The player tried to sell {} stocks but only has {}.",
                     amount,
-                    player.owned_stocks.stocks.get(chain).unwrap()
+                    player_stocks
                 ));
             }
             let stock_price = Bank::stock_price(hotel_chain_manager, chain);
-            // Move stocks from players inventory to the bank
-            player.owned_stocks.set_stocks(chain, 0);
-            self.stocks_for_sale.increase_stocks(chain, player_stocks);
             // Give money to player
-            player.add_money(stock_price * player_stocks);
+            player.add_money(stock_price * amount)?;
+            // Move stocks from players inventory to the bank
+            self.mark_traded(chain);
+            player
+                .owned_stocks
+                .set_stocks(chain, player_stocks - amount);
+            self.stocks_for_sale.increase_stocks(chain, amount);
+            player.statistics.stocks_sold += amount;
+            player.statistics.money_earned += stock_price * amount;
+            player.cost_basis.sell(chain, amount, stock_price * amount);
             Ok(())
         }
 
@@ -879,7 +1546,7 @@ pub mod bank {
         /// * `to_exchange` - The number of stocks that should be exchanged
         /// # Returns
         /// * `Err` - When `to_exchange` is odd, when no stocks are left for the hotel_chain into
-        /// which the stocks should be exchanged
+        ///   which the stocks should be exchanged
         pub fn exchange_stock(
             &mut self,
             player: &mut Player,
@@ -888,8 +1555,8 @@ pub mod bank {
             alive: &HotelChain,
         ) -> Result<()> {
             let available_to_exchange = self.stocks_for_sale.stocks_for_hotel(alive);
-            if to_exchange % 2 != 0 {
-                return Err(miette!("Unable to echange stocks: {} is odd", to_exchange));
+            if !to_exchange.is_multiple_of(2) {
+                return Err(miette!("Unable to exchange stocks: {} is odd", to_exchange));
             }
             if available_to_exchange < &(to_exchange / 2) {
                 // Not enough stocks available for exchange
@@ -898,222 +1565,88 @@ pub mod bank {
                 ));
             }
             // Trade stocks
+            self.mark_traded(dead);
+            self.mark_traded(alive);
             player.remove_stocks(dead, to_exchange);
             self.stocks_for_sale.increase_stocks(dead, to_exchange);
             self.stocks_for_sale.decrease_stocks(alive, to_exchange / 2);
             player.add_stocks(alive, to_exchange / 2);
+            player.cost_basis.exchange(dead, alive, to_exchange);
             Ok(())
         }
 
         /// Gives one stock of the hotel chain to the player for free
-        /// # Arguments
-        /// * `players` - The list of players playing the game. Used to update largest
-        /// shareholders.
-        pub fn give_bonus_stock(&mut self, chain: &HotelChain, player: &mut Player) -> Result<()> {
-            // Check if stocks are left
-            if *self.stocks_for_sale.stocks.get(chain).unwrap() == 0 {
-                player.print_text_ln(
-                    "You did not recieve a bonus stock because no stocks are left!",
-                )?;
+        /// # Returns
+        /// * `Ok(true)` - The player has been given the stock
+        /// * `Ok(false)` - No stocks of the chain are left, nothing has been given
+        pub fn give_bonus_stock(
+            &mut self,
+            chain: &HotelChain,
+            player: &mut Player,
+        ) -> Result<bool> {
+            // Check if stocks are left
+            if *self.stocks_for_sale.stocks_for_hotel(chain) == 0 {
+                return Ok(false);
             }
-            *self.stocks_for_sale.stocks.get_mut(chain).unwrap() -= 1;
+            self.stocks_for_sale.decrease_stocks(chain, 1);
+            self.mark_traded(chain);
             // Give stock to player
-            *player.owned_stocks.stocks.get_mut(chain).unwrap() += 1;
-            Ok(())
+            player.add_stocks(chain, 1);
+            player.statistics.founder_stocks += 1;
+            player.cost_basis.add_lot(chain, 1, 0);
+            Ok(true)
+        }
+
+        /// Marks the chain as traded, so that its shareholders are determined again by the next
+        /// [`Bank::update_largest_shareholders`].
+        ///
+        /// The bank marks the chains it trades itself. Code that changes the stocks of the players
+        /// without the bank, or that changes the ids of the players, has to mark the chains.
+        pub fn mark_traded(&mut self, chain: &HotelChain) {
+            self.traded_chains[chain.index()] = true;
         }
 
         /// Updates who the largest and second largest shareholders are.
-        /// For that the stocks of earch player are compared to one another.
+        /// See [`LargestShareholders::update_chain`] for how they are determined.
+        ///
+        /// Only the chains that have been traded since the last update are determined again, see
+        /// [`Bank::mark_traded`].
         pub fn update_largest_shareholders(&mut self, players: &[Player]) {
-            // Clear currently largest shareholder vectors and initialize new
-            self.largest_shareholders = LargestShareholders::new();
             for chain in HotelChain::iterator() {
-                let mut largest_shareholders: Vec<u32> = Vec::new();
-                let mut second_largest_shareholders: Vec<u32> = Vec::new();
-                for player in players {
-                    // Check if player owns stocks for that chain
-                    if *player.owned_stocks.stocks.get(chain).unwrap() == 0 {
-                        continue;
-                    }
-                    // Set first player to largest and second largest shareholder
-                    if largest_shareholders.is_empty() && second_largest_shareholders.is_empty() {
-                        largest_shareholders.push(player.id);
-                        second_largest_shareholders.push(player.id);
-                        continue;
-                    }
-                    // Determine currently largest shareholders
-                    // On cases where continue has been called the second largest shareholder
-                    // has been set already.
-                    let largest_shareholder_id = *largest_shareholders.get(0).unwrap();
-                    let largest_shareholder_stocks = player_by_id(largest_shareholder_id, players)
-                        .unwrap()
-                        .owned_stocks
-                        .stocks
-                        .get(chain)
-                        .unwrap();
-                    let second_largest_shareholder_id =
-                        *second_largest_shareholders.get(0).unwrap();
-                    let second_largest_shareholder_stocks =
-                        player_by_id(second_largest_shareholder_id, players)
-                            .unwrap()
-                            .owned_stocks
-                            .stocks
-                            .get(chain)
-                            .unwrap();
-                    let current_player_stocks = *player.owned_stocks.stocks.get(chain).unwrap();
-                    match largest_shareholders.len().cmp(&1) {
-                        Ordering::Equal => {
-                            // Currently only one player is largest shareholder
-                            match current_player_stocks.cmp(largest_shareholder_stocks) {
-                                // The player will be set second largest shareholder if the
-                                // currently second largest shareholder has less stocks then them
-                                Ordering::Less => (),
-                                // Player has equal stocks => booth players will be set to largest
-                                // and second largest shareholder
-                                Ordering::Equal => {
-                                    largest_shareholders.push(player.id);
-                                    second_largest_shareholders.clear();
-                                    for ls in &largest_shareholders {
-                                        second_largest_shareholders.push(*ls);
-                                    }
-                                    continue;
-                                }
-                                // Player has more stocks => The player will be set largest
-                                // shareholder and the previously largest shareholder will become
-                                // second largest shareholder
-                                Ordering::Greater => {
-                                    match largest_shareholder_stocks
-                                        .cmp(second_largest_shareholder_stocks)
-                                    {
-                                        Ordering::Greater => {
-                                            second_largest_shareholders.clear();
-                                            for s in &largest_shareholders {
-                                                second_largest_shareholders.push(*s);
-                                            }
-                                        }
-                                        Ordering::Equal => {
-                                            for s in &largest_shareholders {
-                                                second_largest_shareholders.push(*s);
-                                            }
-                                        }
-                                        _ => (),
-                                    }
-                                    largest_shareholders.clear();
-                                    largest_shareholders.push(player.id);
-                                    continue;
-                                }
-                            }
-                        }
-                        Ordering::Greater => {
-                            // Currently more than one players are largest shareholders
-                            match current_player_stocks.cmp(largest_shareholder_stocks) {
-                                Ordering::Less => (),
-                                // Player has equal stocks => current player will be added to the largest
-                                // and second largest shareholder vector
-                                Ordering::Equal => {
-                                    largest_shareholders.push(player.id);
-                                    second_largest_shareholders.push(player.id);
-                                    continue;
-                                }
-                                // The players that where stored as largest and second largest
-                                // shareholder will now only be second largest shareholder. The
-                                // current player will be set largest shareholder
-                                Ordering::Greater => {
-                                    match largest_shareholder_stocks
-                                        .cmp(second_largest_shareholder_stocks)
-                                    {
-                                        Ordering::Greater => {
-                                            second_largest_shareholders.clear();
-                                            for s in &largest_shareholders {
-                                                second_largest_shareholders.push(*s);
-                                            }
-                                        }
-                                        Ordering::Equal => {
-                                            for s in &largest_shareholders {
-                                                second_largest_shareholders.push(*s);
-                                            }
-                                        }
-                                        _ => (),
-                                    }
-                                    largest_shareholders.clear();
-                                    largest_shareholders.push(player.id);
-                                    continue;
-                                }
-                            }
-                        }
-                        _ => (),
-                    }
-                    // Determine the currently second largest shareholder
-                    match current_player_stocks.cmp(second_largest_shareholder_stocks) {
-                        Ordering::Less => {
-                            if current_player_stocks > 0 && second_largest_shareholders.len() == 1 {
-                                // Check if currently largest shareholder is
-                                // also second largest shareholder.
-                                // If yes largest shareholder is no longer also
-                                // second largest shareholder
-                                if second_largest_shareholders.contains(&largest_shareholders[0]) {
-                                    second_largest_shareholders.clear();
-                                    second_largest_shareholders.push(player.id);
-                                }
-                            }
-                        }
-                        // Player has equal stocks => current player will be added to the second largest shareholder vector
-                        Ordering::Equal => {
-                            second_largest_shareholders.push(player.id);
-                            continue;
-                        }
-                        // Player has more stocks => all currently second largest
-                        // shareholders will be removed and replaced by the current player
-                        Ordering::Greater => {
-                            second_largest_shareholders.clear();
-                            second_largest_shareholders.push(player.id);
-                            continue;
-                        }
-                    }
+                if self.traded_chains[chain.index()] {
+                    self.largest_shareholders.update_chain(chain, players);
+                } else {
+                    debug_assert!(
+                        self.largest_shareholders.holdings_match(chain, players),
+                        "The stocks of {} have been changed without marking the chain as traded",
+                        chain
+                    );
                 }
-                // Sort and remove duplicate player ids
-                // This is a workaround for now until i can find out why some players are sometimes inserted multiple times into the vector.
-                largest_shareholders.sort_unstable();
-                largest_shareholders.dedup();
-                second_largest_shareholders.sort_unstable();
-                second_largest_shareholders.dedup();
-                // Insert largest shareholders for chain
-                self.largest_shareholders
-                    .largest_shareholder
-                    .insert(*chain, largest_shareholders);
-                self.largest_shareholders
-                    .second_largest_shareholder
-                    .insert(*chain, second_largest_shareholders);
             }
+            self.traded_chains = [false; NUMBER_OF_CHAINS];
         }
 
         /// Gives the largest and second largest shareholders the bonus.
-        /// A player that is given a bonus will recieve a message.
+        /// A player that is given a bonus will receive a message.
+        /// Each bonus is added to the [`Bank::bonus_payouts`].
         /// # Arguments
         /// * `players` - The playrs that play the game
         /// * `chain` - The chain for which the bonuses should be payed
-        /// * `inform_player` - If true the player will recieve a message that they got their
-        /// shareholder bonus. This message has to be confirmed by enter.
+        /// * `inform_player` - If true the player will receive a message that they got their
+        ///   shareholder bonus. This message has to be confirmed by enter.
         pub fn give_majority_shareholder_bonuses(
-            &self,
-            players: &mut Vec<Player>,
+            &mut self,
+            players: &mut [Player],
             chain: &HotelChain,
             hotel_chain_manager: &HotelChainManager,
             inform_player: bool,
         ) -> Result<()> {
-            let largest_shareholders = self
-                .largest_shareholders
-                .largest_shareholder
-                .get(chain)
-                .unwrap();
-            let second_largest_shareholders = self
-                .largest_shareholders
-                .second_largest_shareholder
-                .get(chain)
-                .unwrap();
-            // Check if largest shareholders are set.
+            let money_before: Vec<u32> = players.iter().map(|player| player.money).collect();
+            let largest_shareholders = self.largest_shareholders.largest(chain);
+            let second_largest_shareholders = self.largest_shareholders.second_largest(chain);
+            // No bonuses are paid when nobody owns stocks of the chain
             if largest_shareholders.is_empty() && second_largest_shareholders.is_empty() {
-                return Err(miette!("Unable to give majority shareholder bonuses: The largest shareholders are not set for chain {}", chain));
+                return Ok(());
             }
             let largest_shareholder_bonus = Bank::stock_price(hotel_chain_manager, chain) * 10;
             let second_largest_shareholder_bonus =
@@ -1121,133 +1654,414 @@ pub mod bank {
             match largest_shareholders.len() {
                 1 => {
                     let largest_shareholder_name =
-                        players[largest_shareholders[0] as usize].name.clone();
-                    players[largest_shareholders[0] as usize].add_money(largest_shareholder_bonus);
+                        players[largest_shareholders[0] as usize].colored_name();
+                    players[largest_shareholders[0] as usize]
+                        .receive_bonus(largest_shareholder_bonus)?;
                     if inform_player {
                         broadcast_others(
-                            &format!(
-                                "{}, recieved {}€ because they where the largest shareholder.",
-                                largest_shareholder_name, largest_shareholder_bonus
+                            &text(
+                                "bonus.largest",
+                                &[&largest_shareholder_name, &largest_shareholder_bonus],
                             ),
-                            &largest_shareholder_name,
+                            largest_shareholders[0],
                             players,
                         )?;
-                        players[largest_shareholders[0] as usize].get_enter(&format!(
-                            "{}, you recieved {}€ because you where the largest shareholder. (press enter to continue)",
-                            &largest_shareholder_name, largest_shareholder_bonus
+                        players[largest_shareholders[0] as usize].get_enter(&text(
+                            "bonus.largest.you",
+                            &[&largest_shareholder_name, &largest_shareholder_bonus],
                         ))?;
                     }
                     match second_largest_shareholders.len() {
                         1 => {
-                            let second_largest_shareholder_name = players
-                                [second_largest_shareholders[0] as usize]
-                                .name
-                                .clone();
+                            let second_largest_shareholder_name =
+                                players[second_largest_shareholders[0] as usize].colored_name();
                             players[second_largest_shareholders[0] as usize]
-                                .add_money(second_largest_shareholder_bonus);
+                                .receive_bonus(second_largest_shareholder_bonus)?;
                             if inform_player {
                                 broadcast_others(
-                            &format!(
-                                "{}, recieved {}€ because they where the second largest shareholder.",
-                                second_largest_shareholder_name, second_largest_shareholder_bonus
-                            ),
-                            &second_largest_shareholder_name,
-                            players,
-                        )?;
-                                players[second_largest_shareholders[0] as usize].get_enter(&format!(
-                            "{}, you recieved {}€ because you where the seond largest shareholder. (press enter to continue)",
-                            &second_largest_shareholder_name, second_largest_shareholder_bonus
-                        ))?;
+                                    &text(
+                                        "bonus.second_largest",
+                                        &[
+                                            &second_largest_shareholder_name,
+                                            &second_largest_shareholder_bonus,
+                                        ],
+                                    ),
+                                    second_largest_shareholders[0],
+                                    players,
+                                )?;
+                                players[second_largest_shareholders[0] as usize].get_enter(
+                                    &text(
+                                        "bonus.second_largest.you",
+                                        &[
+                                            &second_largest_shareholder_name,
+                                            &second_largest_shareholder_bonus,
+                                        ],
+                                    ),
+                                )?;
                             }
                         }
                         _ => {
-                            let number_of_second_largest_shareholders =
-                                second_largest_shareholders.len();
-                            let bonus = second_largest_shareholder_bonus
-                                / number_of_second_largest_shareholders as u32;
-                            // Round to next 100
-                            let bonus = (bonus + 99) / 100 * 100;
+                            let bonus = self.rules.tie_handling.split(
+                                &[second_largest_shareholder_bonus],
+                                second_largest_shareholders.len() as u32,
+                            );
                             for i in second_largest_shareholders {
-                                let name = players[*i as usize].name.clone();
-                                players[*i as usize].add_money(bonus);
+                                let name = players[*i as usize].colored_name();
+                                players[*i as usize].receive_bonus(bonus)?;
                                 if inform_player {
-                                    broadcast_others(&format!("{}, recieved {}€ because they where one of the second largest shareholders.", &name, bonus), &name, players)?;
-                                    players[*i as usize].get_enter(&format!("{}, you recieved {}€ because you where one of the second largest shareholders. (press enter to continue)", &name, bonus))?;
+                                    broadcast_others(
+                                        &text("bonus.second_largest_tied", &[&name, &bonus]),
+                                        *i,
+                                        players,
+                                    )?;
+                                    players[*i as usize].get_enter(&text(
+                                        "bonus.second_largest_tied.you",
+                                        &[&name, &bonus],
+                                    ))?;
                                 }
                             }
                         }
                     }
                 }
                 _ => {
-                    let number_of_largest_shareholders = largest_shareholders.len();
-                    let bonus = (largest_shareholder_bonus + second_largest_shareholder_bonus)
-                        / number_of_largest_shareholders as u32;
-                    // Round to next 100
-                    let bonus = (bonus + 99) / 100 * 100;
+                    let bonus = self.rules.tie_handling.split(
+                        &[largest_shareholder_bonus, second_largest_shareholder_bonus],
+                        largest_shareholders.len() as u32,
+                    );
                     for i in largest_shareholders {
-                        let player = players.get_mut(*i as usize).unwrap();
-                        player.add_money(bonus);
+                        let player = &mut players[*i as usize];
+                        player.receive_bonus(bonus)?;
+                        if inform_player {
+                            player.get_enter(&text(
+                                "bonus.largest_tied.you",
+                                &[&player.colored_name(), &bonus],
+                            ))?;
+                        }
+                    }
+                }
+            }
+            if self.rules.third_shareholder_bonus {
+                let third_largest_shareholders = self.largest_shareholders.third_largest(chain);
+                if !third_largest_shareholders.is_empty() {
+                    let bonus = self.rules.tie_handling.split(
+                        &[Bank::stock_price(hotel_chain_manager, chain) * 3],
+                        third_largest_shareholders.len() as u32,
+                    );
+                    for i in third_largest_shareholders {
+                        let name = players[*i as usize].colored_name();
+                        players[*i as usize].receive_bonus(bonus)?;
                         if inform_player {
-                            player.get_enter(&format!("{}, you recieved {}€ because you where one of the largest shareholders. (press enter to continue)", player.name, bonus))?;
+                            broadcast_others(
+                                &text("bonus.third_largest_tied", &[&name, &bonus]),
+                                *i,
+                                players,
+                            )?;
+                            players[*i as usize].get_enter(&text(
+                                "bonus.third_largest_tied.you",
+                                &[&name, &bonus],
+                            ))?;
                         }
                     }
                 }
             }
+            for (player, money_before) in players.iter().zip(money_before) {
+                if let (true, Some(rank)) = (
+                    player.money > money_before,
+                    self.shareholder_rank(player.id, chain, hotel_chain_manager),
+                ) {
+                    self.bonus_payouts.push(BonusPayout {
+                        chain: *chain,
+                        player_id: player.id,
+                        rank,
+                        bonus: player.money - money_before,
+                    });
+                }
+            }
             Ok(())
         }
 
         /// Checks if the player is one of the largest shareholders for the chain.
         pub fn is_largest_shareholder(&self, player_id: u32, chain: &HotelChain) -> bool {
             self.largest_shareholders
-                .largest_shareholder
-                .get(chain)
-                .unwrap()
+                .largest(chain)
                 .contains(&player_id)
         }
 
         /// Checks if the player is one of the second largest shareholders for the chain.
         pub fn is_second_largest_shareholder(&self, player_id: u32, chain: &HotelChain) -> bool {
             self.largest_shareholders
-                .second_largest_shareholder
-                .get(chain)
-                .unwrap()
+                .second_largest(chain)
+                .contains(&player_id)
+        }
+
+        /// Returns the number of stocks that the other players own of the chain, sorted from the
+        /// most to the least stocks. Players without stocks are not included.
+        ///
+        /// The numbers are updated with [`Bank::update_largest_shareholders`].
+        pub fn rival_stocks(&self, player_id: u32, chain: &HotelChain) -> Vec<u32> {
+            let mut rival_stocks: Vec<u32> = self
+                .largest_shareholders
+                .stocks(chain)
+                .iter()
+                .filter(|(id, _)| *id != player_id)
+                .map(|(_, stocks)| *stocks)
+                .collect();
+            rival_stocks.sort_unstable_by(|a, b| b.cmp(a));
+            rival_stocks
+        }
+
+        /// Starts a transaction in which the player trades stocks with the bank.
+        /// See [`Transaction`].
+        pub fn transaction<'a>(&'a mut self, player: &'a mut Player) -> Transaction<'a> {
+            Transaction::new(self, player)
+        }
+
+        /// Returns the rank of the player among the shareholders of the chain.
+        /// `None` when the chain is not active or the player is no shareholder that is paid a
+        /// bonus.
+        pub fn shareholder_rank(
+            &self,
+            player_id: u32,
+            chain: &HotelChain,
+            hotel_chain_manager: &HotelChainManager,
+        ) -> Option<ShareholderRank> {
+            if !hotel_chain_manager.chain_status(chain) {
+                return None;
+            }
+            if self.is_largest_shareholder(player_id, chain) {
+                return Some(ShareholderRank::Largest);
+            }
+            if self.is_second_largest_shareholder(player_id, chain) {
+                return Some(ShareholderRank::SecondLargest);
+            }
+            if self.rules.third_shareholder_bonus
+                && self.is_third_largest_shareholder(player_id, chain)
+            {
+                return Some(ShareholderRank::ThirdLargest);
+            }
+            None
+        }
+
+        /// Checks if the player is one of the third largest shareholders for the chain.
+        pub fn is_third_largest_shareholder(&self, player_id: u32, chain: &HotelChain) -> bool {
+            self.largest_shareholders
+                .third_largest(chain)
                 .contains(&player_id)
         }
     }
 
-    /// Used to store if the player is a largest or second largest shareholder
-    pub struct LargestShareholders {
-        /// Contains what the player ids of the largest shareholder for the specified hotel are
-        pub largest_shareholder: HashMap<HotelChain, Vec<u32>>,
-        /// Contains what the player ids of the second largest shareholder for the specified chain are
-        pub second_largest_shareholder: HashMap<HotelChain, Vec<u32>>,
+    /// A majority shareholder bonus that has been paid to a player.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct BonusPayout {
+        /// The chain for which the bonus has been paid
+        pub chain: HotelChain,
+        pub player_id: u32,
+        pub rank: ShareholderRank,
+        pub bonus: u32,
     }
 
-    impl LargestShareholders {
-        pub fn new() -> Self {
-            let mut largest_shareholder = HashMap::new();
-            let mut second_largest_shareholder = HashMap::new();
-            for chain in HotelChain::iterator() {
-                largest_shareholder.insert(*chain, Vec::new());
-                second_largest_shareholder.insert(*chain, Vec::new());
+    /// The rank of a player among the shareholders of a chain that are paid a bonus.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum ShareholderRank {
+        Largest,
+        SecondLargest,
+        ThirdLargest,
+    }
+
+    impl ShareholderRank {
+        /// Returns the name of the rank
+        pub fn name(&self) -> &'static str {
+            match self {
+                ShareholderRank::Largest => "largest",
+                ShareholderRank::SecondLargest => "second largest",
+                ShareholderRank::ThirdLargest => "third largest",
+            }
+        }
+
+        /// Returns the textual marker that is shown instead of the star when the text is plain
+        pub fn marker(&self) -> &'static str {
+            match self {
+                ShareholderRank::Largest => "(L)",
+                ShareholderRank::SecondLargest => "(S)",
+                ShareholderRank::ThirdLargest => "(T)",
+            }
+        }
+
+        /// Returns the rank with the name.
+        /// If no rank with that name exists `None` is returned.
+        pub fn from_name(name: &str) -> Option<ShareholderRank> {
+            match name {
+                "largest" => Some(ShareholderRank::Largest),
+                "second largest" => Some(ShareholderRank::SecondLargest),
+                "third largest" => Some(ShareholderRank::ThirdLargest),
+                _ => None,
+            }
+        }
+
+        /// Returns the color of the star that marks the rank
+        pub fn color(&self) -> Rgb {
+            match self {
+                ShareholderRank::Largest => Rgb(225, 215, 0),
+                ShareholderRank::SecondLargest => Rgb(192, 192, 192),
+                ShareholderRank::ThirdLargest => Rgb(191, 137, 112),
             }
+        }
+    }
+
+    /// A number of stock trades between the bank and one player that happen either all or not
+    /// at all.
+    ///
+    /// The trades are made right away, so that each trade sees the result of the trades before it.
+    /// When the transaction is dropped without being committed, the stocks of the bank and the
+    /// stocks, the money, the statistics and the cost basis of the player are reset to the state
+    /// in which they where when the transaction has been started.
+    pub struct Transaction<'a> {
+        bank: &'a mut Bank,
+        player: &'a mut Player,
+        stocks_for_sale: Stocks,
+        owned_stocks: Stocks,
+        money: u32,
+        statistics: MoneyStatistics,
+        cost_basis: CostBasis,
+        committed: bool,
+    }
+
+    impl<'a> Transaction<'a> {
+        fn new(bank: &'a mut Bank, player: &'a mut Player) -> Self {
+            let stocks_for_sale = bank.stocks_for_sale.clone();
+            let owned_stocks = player.owned_stocks.clone();
+            let money = player.money;
+            let statistics = player.statistics;
+            let cost_basis = player.cost_basis.clone();
             Self {
-                largest_shareholder,
-                second_largest_shareholder,
+                bank,
+                player,
+                stocks_for_sale,
+                owned_stocks,
+                money,
+                statistics,
+                cost_basis,
+                committed: false,
+            }
+        }
+
+        /// Buys the stocks, see [`Bank::buy_stock`].
+        pub fn buy_stocks(
+            &mut self,
+            hotel_chain_manager: &HotelChainManager,
+            stocks: &HashMap<HotelChain, u32>,
+        ) -> Result<()> {
+            for (chain, amount) in stocks {
+                for _i in 0..*amount {
+                    self.bank
+                        .buy_stock(hotel_chain_manager, chain, self.player)?;
+                }
+            }
+            Ok(())
+        }
+
+        /// Sells the stocks, see [`Bank::sell_stock`].
+        pub fn sell_stock(
+            &mut self,
+            amount: u32,
+            chain: &HotelChain,
+            hotel_chain_manager: &HotelChainManager,
+        ) -> Result<()> {
+            self.bank
+                .sell_stock(self.player, amount, chain, hotel_chain_manager)
+        }
+
+        /// Exchanges the stocks, see [`Bank::exchange_stock`].
+        pub fn exchange_stock(
+            &mut self,
+            to_exchange: u32,
+            dead: &HotelChain,
+            alive: &HotelChain,
+        ) -> Result<()> {
+            self.bank
+                .exchange_stock(self.player, to_exchange, dead, alive)
+        }
+
+        /// Keeps all trades of the transaction.
+        pub fn commit(mut self) {
+            self.committed = true;
+        }
+    }
+
+    impl Drop for Transaction<'_> {
+        fn drop(&mut self) {
+            if self.committed {
+                return;
+            }
+            self.bank.stocks_for_sale = self.stocks_for_sale.clone();
+            self.player.owned_stocks = self.owned_stocks.clone();
+            self.player.money = self.money;
+            self.player.statistics = self.statistics;
+            self.player.cost_basis = self.cost_basis.clone();
+        }
+    }
+
+    /// Stores the prices of the stocks of all chains at the end of each round.
+    #[derive(Clone, Default)]
+    pub struct MarketHistory {
+        /// The prices at the end of each round in the order of [`HotelChain::iterator`].
+        /// `None` when the chain was not active.
+        rounds: Vec<[Option<u32>; NUMBER_OF_CHAINS]>,
+    }
+
+    impl MarketHistory {
+        /// Stores the current prices of the active chains as the prices of the round that has
+        /// just ended.
+        pub fn record_round(&mut self, hotel_chain_manager: &HotelChainManager) {
+            let mut prices = [None; NUMBER_OF_CHAINS];
+            for chain in HotelChain::iterator() {
+                if hotel_chain_manager.chain_status(chain) {
+                    prices[chain.index()] = Some(Bank::stock_price(hotel_chain_manager, chain));
+                }
+            }
+            self.rounds.push(prices);
+        }
+
+        /// Returns the price of the chain at the end of each round.
+        /// The price is `None` for the rounds in which the chain was not active.
+        pub fn prices(&self, chain: &HotelChain) -> Vec<Option<u32>> {
+            self.rounds
+                .iter()
+                .map(|prices| prices[chain.index()])
+                .collect()
+        }
+
+        /// Returns by how much the price of the chain has changed since the end of the last
+        /// round.
+        /// `None` when the chain is not active or was not active at the end of the last round.
+        pub fn change(
+            &self,
+            chain: &HotelChain,
+            hotel_chain_manager: &HotelChainManager,
+        ) -> Option<i64> {
+            if !hotel_chain_manager.chain_status(chain) {
+                return None;
             }
+            let last = self.prices(chain).last().copied().flatten()?;
+            Some(Bank::stock_price(hotel_chain_manager, chain) as i64 - last as i64)
         }
     }
 
     #[cfg(test)]
     mod tests {
+        use std::collections::HashMap;
+
         use miette::Result;
 
         use crate::{
             base_game::{
                 bank::Bank,
+                bank::{BonusPayout, ShareholderRank},
                 board::{Board, Position},
                 hotel_chains::HotelChain,
-                player::Player,
+                player::{MoneyError, MoneyStatistics, Player, StockLot},
+                settings::{Privacy, Settings},
+                ui,
             },
             game::hotel_chain_manager::HotelChainManager,
         };
@@ -1311,7 +2125,7 @@ pub mod bank {
         fn buy_stock_errors_work() {
             let mut bank = Bank::new();
             let hotel_chain_manager = HotelChainManager::new();
-            let mut players = vec![
+            let mut players = [
                 Player::new(vec![], 0, false, String::from("Player 1")),
                 Player::new(vec![], 1, false, String::from("Player 2")),
             ];
@@ -1331,127 +2145,849 @@ pub mod bank {
         }
 
         #[test]
-        fn exchange_stocks_works() -> Result<()> {
+        fn shareholder_status_is_sent_to_clients() -> Result<()> {
             let mut bank = Bank::new();
-            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
-            let dead = HotelChain::Airport;
-            let alive = HotelChain::Festival;
-            player.owned_stocks.increase_stocks(&dead, 6);
-            bank.exchange_stock(&mut player, 6, &dead, &HotelChain::Festival)?;
-            assert_eq!(*player.owned_stocks.stocks_for_hotel(&alive), 3);
-            Ok(())
-        }
-
-        #[test]
-        fn largest_shareholders_correct() {
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
             let mut players = vec![
                 Player::new(vec![], 0, false, String::from("Player 1")),
                 Player::new(vec![], 1, false, String::from("Player 2")),
-                Player::new(vec![], 2, false, String::from("Player 3")),
-                Player::new(vec![], 3, false, String::from("Player 4")),
             ];
-            let mut bank = Bank::new();
-            let mut index = 0;
-            while index < 4 {
-                let player = &mut players[index];
-                match index {
-                    0 => {
-                        player.owned_stocks.set_stocks(&HotelChain::Airport, 7);
-                        player.owned_stocks.set_stocks(&HotelChain::Continental, 10);
-                        player.owned_stocks.set_stocks(&HotelChain::Festival, 5);
-                        player.owned_stocks.set_stocks(&HotelChain::Imperial, 7);
-                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 10);
-                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 1);
-                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 5);
-                    }
-                    1 => {
-                        player.owned_stocks.set_stocks(&HotelChain::Airport, 2);
-                        player.owned_stocks.set_stocks(&HotelChain::Continental, 10);
-                        player.owned_stocks.set_stocks(&HotelChain::Festival, 3);
-                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 8);
-                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 5);
-                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 2);
-                    }
-                    2 => {
-                        player.owned_stocks.set_stocks(&HotelChain::Festival, 3);
-                        player.owned_stocks.set_stocks(&HotelChain::Continental, 10);
-                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 4);
-                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 8);
-                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 5);
-                    }
-                    3 => {
-                        player.owned_stocks.set_stocks(&HotelChain::Festival, 3);
-                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 2);
-                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 9);
-                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 2);
-                    }
-                    _ => (),
-                }
-                index += 1;
+            for (chain, number) in [(HotelChain::Airport, 1), (HotelChain::Luxor, 5)] {
+                hotel_chain_manager.start_chain(
+                    chain,
+                    vec![Position::new('A', number), Position::new('A', number + 1)],
+                    &mut board,
+                    &mut players[0],
+                    &mut bank,
+                )?;
             }
+            players[1].owned_stocks.set_stocks(&HotelChain::Luxor, 3);
+            players[1].owned_stocks.set_stocks(&HotelChain::Festival, 3);
             bank.update_largest_shareholders(&players);
-            bank.print_largest_shareholders();
-            // Test case 1: one largest and one second largest shareholder (Airport)
-            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Airport));
-            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Airport));
-            // Test case 2: multiple largest shareholerds (Continental)
-            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Continental));
-            assert!(bank.is_second_largest_shareholder(players[0].id, &HotelChain::Continental));
-            assert!(bank.is_largest_shareholder(players[1].id, &HotelChain::Continental));
-            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Continental));
-            assert!(bank.is_largest_shareholder(players[2].id, &HotelChain::Continental));
-            assert!(bank.is_second_largest_shareholder(players[2].id, &HotelChain::Continental));
-            // Test case 3: one largest and multiple second largest shareholders (Festival)
-            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Festival));
-            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Festival));
-            assert!(bank.is_second_largest_shareholder(players[2].id, &HotelChain::Festival));
-            assert!(bank.is_second_largest_shareholder(players[3].id, &HotelChain::Festival));
-            // Test case 4: one player is largest and second largest shareholder (Imperial)
-            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Imperial));
-            assert!(bank.is_second_largest_shareholder(players[0].id, &HotelChain::Imperial));
-            assert!(!bank.is_largest_shareholder(players[1].id, &HotelChain::Imperial));
-            assert!(!bank.is_second_largest_shareholder(players[1].id, &HotelChain::Imperial));
-            // Test case 5: one largest and one second largest shareholder, but the other players also own stocks (Oriental)
-            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Oriental));
-            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Oriental));
-            // Test case 6: one largest and one second largest shareholder, but the other players
-            // also own stocks, bottom up order (Prestige)
-            assert!(bank.is_largest_shareholder(players[3].id, &HotelChain::Prestige));
-            assert!(bank.is_second_largest_shareholder(players[2].id, &HotelChain::Prestige));
-            // Test case 7: two largest and two second largest shareholder, switched (Luxor)
-            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Luxor));
-            assert!(bank.is_largest_shareholder(players[2].id, &HotelChain::Luxor));
-            assert!(!bank.is_second_largest_shareholder(players[1].id, &HotelChain::Luxor));
-            assert!(!bank.is_second_largest_shareholder(players[3].id, &HotelChain::Luxor));
+            assert_eq!(
+                bank.shareholder_rank(0, &HotelChain::Luxor, &hotel_chain_manager),
+                Some(ShareholderRank::SecondLargest)
+            );
+            // Festival is not active
+            assert_eq!(
+                bank.shareholder_rank(1, &HotelChain::Festival, &hotel_chain_manager),
+                None
+            );
+            let status = ui::shareholder_status(&players[0], &bank, &hotel_chain_manager).unwrap();
+            let line = ui::shareholder_status_ui(&status).unwrap();
+            assert!(line.contains("Airport"));
+            assert!(line.contains("largest"));
+            assert!(line.contains("second largest"));
+            let status = ui::shareholder_status(&players[1], &bank, &hotel_chain_manager).unwrap();
+            let line = ui::shareholder_status_ui(&status).unwrap();
+            assert!(!line.contains("Festival"));
+            assert!(!line.contains("Airport"));
+            assert!(ui::shareholder_status_ui("invalid").is_none());
+            // Clients that hide the stars do not receive them
+            players[0].privacy = Privacy::Hidden;
+            assert!(ui::shareholder_status(&players[0], &bank, &hotel_chain_manager).is_none());
+            Ok(())
         }
 
         #[test]
-        fn sell_stock_works() -> Result<()> {
+        fn stats_respect_the_privacy_of_the_player() -> Result<()> {
             let mut bank = Bank::new();
-            let mut board = Board::new();
             let mut hotel_chain_manager = HotelChainManager::new();
-            let player = Player::new(vec![], 0, false, String::from("Player 1"));
-            let mut players = vec![player];
-            let chain = HotelChain::Airport;
-            // Test error
-            assert!(bank
-                .sell_stock(players.get_mut(0).unwrap(), 4, &chain, &hotel_chain_manager)
-                .is_err());
+            let mut board = Board::new();
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+            ];
+            ui::update_stats(&mut players, &bank, &hotel_chain_manager);
+            assert_eq!(players[0].stats, ["No chain has been founded yet."]);
             hotel_chain_manager.start_chain(
-                chain,
+                HotelChain::Luxor,
                 vec![Position::new('A', 1), Position::new('A', 2)],
                 &mut board,
-                &mut players.get_mut(0).unwrap(),
+                &mut players[0],
                 &mut bank,
             )?;
+            players[1].owned_stocks.set_stocks(&HotelChain::Luxor, 3);
             bank.update_largest_shareholders(&players);
-            bank.give_majority_shareholder_bonuses(
-                &mut players,
-                &chain,
+            players[1].privacy = Privacy::Hidden;
+            ui::update_stats(&mut players, &bank, &hotel_chain_manager);
+            // The founder bonus stock is no longer in the bank
+            let open = players[0].stats.join("\n");
+            assert!(open.contains("Luxor"));
+            assert!(!open.contains("Airport"));
+            assert!(open.contains("||  24 "));
+            assert!(open.contains(&format!(
+                "{}            || {}",
+                players[1].colored_name(),
+                players[0].colored_name()
+            )));
+            let hidden = players[1].stats.join("\n");
+            assert!(hidden.contains("||  24 "));
+            assert!(!hidden.contains("Player"));
+            let strict = ui::stats_ui(Privacy::Strict, &players, &bank, &hotel_chain_manager);
+            assert!(!strict.join("\n").contains("||  24 "));
+            assert!(strict[2].ends_with(&format!("|| {}", players[1].colored_name())));
+            Ok(())
+        }
+
+        #[test]
+        fn fast_mode_skips_dialogues() -> Result<()> {
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            player.skip_dialogues = true;
+            // Nothing is read from the terminal
+            player.get_enter("Press enter to continue")?;
+            assert!(player.get_correct()?);
+            Ok(())
+        }
+
+        #[test]
+        fn main_ui_is_kept_to_be_shown_again() -> Result<()> {
+            let mut players = vec![
+                Player::new(
+                    vec![Position::new('C', 4)],
+                    0,
+                    false,
+                    String::from("Player 1"),
+                ),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+            ];
+            ui::print_main_ui_players(
+                0,
+                &mut players,
+                &Board::new(),
+                &Settings::new(false, Privacy::default(), true),
+                None,
+                &Bank::new(),
+                &HotelChainManager::new(),
+            )?;
+            // Players on the same machine have seen the same ui
+            assert!(players[0].main_ui.contains("Game stats"));
+            assert!(!players[0].main_ui.ends_with('\n'));
+            assert_eq!(players[0].main_ui, players[1].main_ui);
+            Ok(())
+        }
+
+        #[test]
+        fn remaining_cards_are_shown() {
+            let player = Player::new(vec![], 0, false, String::from("Player 1"));
+            let mut round = crate::game::round::Round::new(3);
+            round.remaining_cards = Some(57);
+            let mut frame = String::new();
+            ui::main_ui(
+                &mut frame,
+                Some(&player),
+                Some(&player.name),
+                &Board::new(),
+                &Settings::new(false, Privacy::Open, false),
+                Some(&round),
+                &Bank::new(),
+                &HotelChainManager::new(),
+            );
+            assert!(frame.contains("Cards left in the draw pile: 57"));
+        }
+
+        #[test]
+        fn safe_chains_are_marked() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            hotel_chain_manager.start_chain(
+                HotelChain::Luxor,
+                (1..=11).map(|number| Position::new('A', number)).collect(),
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            hotel_chain_manager.start_chain(
+                HotelChain::Imperial,
+                vec![Position::new('C', 1), Position::new('C', 2)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            let settings = Settings::new(false, Privacy::Open, false);
+            let mut frame = String::new();
+            ui::main_ui(
+                &mut frame,
+                Some(&player),
+                None,
+                &board,
+                &settings,
+                None,
+                &bank,
+                &hotel_chain_manager,
+            );
+            let row = |name: &str| {
+                ui::plain_text(frame.lines().find(|line| line.contains(name)).unwrap())
+            };
+            assert!(row("Luxor").contains("11 S |"));
+            assert!(row("Imperial").contains(" 2   |"));
+            assert!(frame.contains("S: The chain is safe"));
+            let stats = ui::stats_ui(Privacy::Strict, &[player], &bank, &hotel_chain_manager);
+            assert!(stats.iter().any(|line| line.contains("11 S ||")));
+            Ok(())
+        }
+
+        #[test]
+        fn fusion_warning_shows_the_fused_chains() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            hotel_chain_manager.start_chain(
+                HotelChain::Luxor,
+                vec![
+                    Position::new('A', 1),
+                    Position::new('A', 2),
+                    Position::new('A', 3),
+                ],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            hotel_chain_manager.start_chain(
+                HotelChain::Imperial,
+                vec![Position::new('A', 5), Position::new('A', 6)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            bank.update_largest_shareholders(std::slice::from_ref(&player));
+            let warning: Vec<String> = ui::fusion_warning_ui(
+                &player,
+                &Position::new('A', 4),
+                &[HotelChain::Imperial, HotelChain::Luxor],
+                &bank,
+                &hotel_chain_manager,
+            )
+            .iter()
+            .map(|line| ui::plain_text(line))
+            .collect();
+            assert_eq!(warning.len(), 3);
+            assert!(warning[0].contains("A4"));
+            assert_eq!(warning[1], "  Luxor: 3 hotels, survives");
+            assert_eq!(
+                warning[2],
+                "  Imperial: 2 hotels, is fused, you are the largest shareholder"
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn founding_preview_shows_the_founder_bonus() {
+            let mut bank = Bank::new();
+            bank.stocks_for_sale.set_stocks(&HotelChain::Prestige, 0);
+            let preview: Vec<String> =
+                ui::founding_preview_ui(3, &[HotelChain::Airport, HotelChain::Prestige], &bank)
+                    .iter()
+                    .map(|line| ui::plain_text(line))
+                    .collect();
+            assert_eq!(preview[0], "With 3 hotels the chains are worth:");
+            assert!(preview[1].starts_with("  A Airport     [L] 300€ per stock"));
+            assert!(preview[1].ends_with("worth 300€"));
+            // No founder bonus is left in the bank
+            assert!(preview[2].contains("[H] 500€ per stock"));
+            assert!(preview[2].ends_with("worth 0€"));
+        }
+
+        #[test]
+        fn plain_text_has_no_colors_and_stars() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            hotel_chain_manager.start_chain(
+                HotelChain::Luxor,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            bank.update_largest_shareholders(std::slice::from_ref(&player));
+            assert_eq!(ui::plain_text("\x1B[31mLuxor\x1B[39m ||  2"), "Luxor |  2");
+            let settings = Settings::new(false, Privacy::Open, false);
+            player.plain = true;
+            let mut frame = String::new();
+            ui::main_ui(
+                &mut frame,
+                Some(&player),
+                None,
+                &board,
+                &settings,
+                None,
+                &bank,
+                &hotel_chain_manager,
+            );
+            let lines: Vec<&str> = frame.lines().collect();
+            assert!(lines[0].starts_with("A  L [L] ."));
+            assert!(lines
+                .iter()
+                .any(|line| line.contains("Luxor") && line.contains("(L)")));
+            assert!(!lines.iter().any(|line| line.contains("===")));
+            Ok(())
+        }
+
+        #[test]
+        fn players_keep_their_color() {
+            let mut players: Vec<Player> = (0..6)
+                .map(|id| Player::new(vec![], id, false, format!("Player {}", id + 1)))
+                .collect();
+            for (index, player) in players.iter().enumerate() {
+                assert!(players[..index]
+                    .iter()
+                    .all(|other| other.color != player.color));
+                assert!(player.colored_name().contains(&player.name));
+            }
+            // The turn order changes the ids but not the colors
+            let color = players[0].color;
+            players[0].id = 3;
+            assert_eq!(players[0].color, color);
+            assert_eq!(players[0].headless_copy().color, color);
+        }
+
+        #[test]
+        fn cards_can_be_reordered() -> Result<()> {
+            let board = Board::new();
+            let hotel_chain_manager = HotelChainManager::new();
+            let cards = [
+                Position::new('A', 1),
+                Position::new('B', 2),
+                Position::new('C', 3),
+            ];
+            let mut player = Player::new(cards.to_vec(), 0, false, String::from("Player 1"));
+            let hand = |player: &Player| -> Vec<Position> {
+                player
+                    .analyzed_cards
+                    .iter()
+                    .map(|card| card.position)
+                    .collect()
+            };
+            player.auto_sort = false;
+            player.move_card(2, 0)?;
+            assert_eq!(hand(&player), [cards[2], cards[0], cards[1]]);
+            assert!(player.move_card(3, 0).is_err());
+            // New cards are added at the end and the order is kept
+            player.add_card(&Position::new('A', 2), &board, &hotel_chain_manager);
+            player.sort_cards();
+            assert_eq!(
+                hand(&player),
+                [cards[2], cards[0], cards[1], Position::new('A', 2)]
+            );
+            player.remove_card(&cards[0])?;
+            assert_eq!(hand(&player), [cards[2], cards[1], Position::new('A', 2)]);
+            player.auto_sort = true;
+            player.sort_cards();
+            assert_eq!(hand(&player), [Position::new('A', 2), cards[1], cards[2]]);
+            Ok(())
+        }
+
+        #[test]
+        fn shareholders_are_updated_after_trades() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+            ];
+            let chain = HotelChain::Airport;
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut players[0],
+                &mut bank,
+            )?;
+            bank.update_largest_shareholders(&players);
+            assert_eq!(bank.largest_shareholders.largest(&chain), [0]);
+            for _i in 0..2 {
+                bank.buy_stock(&hotel_chain_manager, &chain, &mut players[1])?;
+            }
+            bank.update_largest_shareholders(&players);
+            assert_eq!(bank.largest_shareholders.largest(&chain), [1]);
+            assert_eq!(bank.largest_shareholders.second_largest(&chain), [0]);
+            // Trades that are rolled back are noticed as well
+            let mut transaction = bank.transaction(&mut players[1]);
+            transaction.sell_stock(2, &chain, &hotel_chain_manager)?;
+            drop(transaction);
+            bank.update_largest_shareholders(&players);
+            assert_eq!(bank.largest_shareholders.largest(&chain), [1]);
+            // Stocks that are changed without the bank are updated once the chain is marked
+            players[0].owned_stocks.set_stocks(&HotelChain::Luxor, 2);
+            bank.mark_traded(&HotelChain::Luxor);
+            bank.update_largest_shareholders(&players);
+            assert_eq!(bank.largest_shareholders.largest(&HotelChain::Luxor), [0]);
+            // Only the marked chains are determined again
+            players.pop();
+            bank.mark_traded(&chain);
+            bank.update_largest_shareholders(&players);
+            assert_eq!(bank.largest_shareholders.largest(&chain), [0]);
+            assert_eq!(bank.largest_shareholders.largest(&HotelChain::Luxor), [0]);
+            Ok(())
+        }
+
+        #[test]
+        fn cost_basis_is_tracked() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut players = [
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+            ];
+            let chain = HotelChain::Airport;
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut players[0],
+                &mut bank,
+            )?;
+            let price = Bank::stock_price(&hotel_chain_manager, &chain);
+            bank.buy_stock(&hotel_chain_manager, &chain, &mut players[0])?;
+            for _i in 0..3 {
+                bank.buy_stock(&hotel_chain_manager, &chain, &mut players[1])?;
+            }
+            assert_eq!(
+                players[1].cost_basis.lots(&chain),
+                [StockLot {
+                    stocks: 3,
+                    cost: price * 3
+                }]
+            );
+            board.place_hotel(&Position::new('A', 3))?;
+            hotel_chain_manager.add_hotel_to_chain(&chain, Position::new('A', 3), &mut board)?;
+            let new_price = Bank::stock_price(&hotel_chain_manager, &chain);
+            bank.sell_stock(&mut players[1], 2, &chain, &hotel_chain_manager)?;
+            let profit = (new_price - price) as i64;
+            assert_eq!(players[1].cost_basis.realized_profit(&chain), profit * 2);
+            assert_eq!(
+                players[1].cost_basis.unrealized_profit(&chain, new_price),
+                profit
+            );
+            // Trades of a transaction that is not committed do not change the cost basis
+            let cost_basis = players[1].cost_basis.clone();
+            let mut transaction = bank.transaction(&mut players[1]);
+            transaction.sell_stock(1, &chain, &hotel_chain_manager)?;
+            drop(transaction);
+            assert_eq!(players[1].cost_basis, cost_basis);
+            // The founder stock is exchanged first and costs nothing
+            let alive = HotelChain::Luxor;
+            bank.exchange_stock(&mut players[0], 2, &chain, &alive)?;
+            assert_eq!(
+                players[0].cost_basis.lots(&alive),
+                [StockLot {
+                    stocks: 1,
+                    cost: price
+                }]
+            );
+            assert!(players[0].cost_basis.lots(&chain).is_empty());
+            assert_eq!(players[0].cost_basis.total_realized_profit(), 0);
+            Ok(())
+        }
+
+        #[test]
+        fn money_statistics_are_tracked() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+            ];
+            let chain = HotelChain::Airport;
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut players[0],
+                &mut bank,
+            )?;
+            let price = Bank::stock_price(&hotel_chain_manager, &chain);
+            for _i in 0..3 {
+                bank.buy_stock(&hotel_chain_manager, &chain, &mut players[1])?;
+            }
+            bank.sell_stock(&mut players[1], 2, &chain, &hotel_chain_manager)?;
+            // Trades of a transaction that is not committed are not counted
+            let mut transaction = bank.transaction(&mut players[1]);
+            transaction.buy_stocks(&hotel_chain_manager, &HashMap::from([(chain, 2)]))?;
+            drop(transaction);
+            bank.update_largest_shareholders(&players);
+            bank.give_majority_shareholder_bonuses(
+                &mut players,
+                &chain,
                 &hotel_chain_manager,
                 false,
             )?;
-            assert_eq!(players.get(0).unwrap().money, 9000);
+            // Both players own one stock and share the bonuses
+            let bonus = price * 15 / 2;
+            assert_eq!(
+                players[0].statistics,
+                MoneyStatistics {
+                    founder_stocks: 1,
+                    bonuses: bonus,
+                    ..MoneyStatistics::default()
+                }
+            );
+            assert_eq!(
+                players[1].statistics,
+                MoneyStatistics {
+                    stocks_bought: 3,
+                    money_spent: price * 3,
+                    stocks_sold: 2,
+                    money_earned: price * 2,
+                    bonuses: bonus,
+                    founder_stocks: 0,
+                }
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn money_changes_are_checked() {
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            player.money = 100;
+            assert_eq!(
+                player.remove_money(200),
+                Err(MoneyError::NotEnoughMoney {
+                    name: String::from("Player 1"),
+                    money: 100,
+                    amount: 200,
+                })
+            );
+            assert_eq!(player.money, 100);
+            assert!(matches!(
+                player.add_money(u32::MAX),
+                Err(MoneyError::Overflow { .. })
+            ));
+            assert_eq!(player.money, 100);
+            assert_eq!(player.remove_money(100), Ok(()));
+            assert_eq!(player.money, 0);
+        }
+
+        #[test]
+        fn transaction_is_rolled_back_when_not_committed() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            let chain = HotelChain::Airport;
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            let price = Bank::stock_price(&hotel_chain_manager, &chain);
+            let stocks_for_sale = bank.stocks_for_sale.clone();
+            let owned_stocks = player.owned_stocks.clone();
+            // The last purchase can not be paid, all trades are undone
+            player.money = price * 3;
+            let stocks = HashMap::from([(chain, 2)]);
+            let too_many = HashMap::from([(chain, 3)]);
+            let mut transaction = bank.transaction(&mut player);
+            transaction.buy_stocks(&hotel_chain_manager, &stocks)?;
+            transaction.sell_stock(1, &chain, &hotel_chain_manager)?;
+            assert!(transaction
+                .buy_stocks(&hotel_chain_manager, &too_many)
+                .is_err());
+            drop(transaction);
+            assert_eq!(player.money, price * 3);
+            assert!(player.owned_stocks == owned_stocks);
+            assert!(bank.stocks_for_sale == stocks_for_sale);
+            // A committed transaction is kept
+            let mut transaction = bank.transaction(&mut player);
+            transaction.buy_stocks(&hotel_chain_manager, &stocks)?;
+            transaction.commit();
+            assert_eq!(player.money, price);
+            assert_eq!(
+                *player.owned_stocks.stocks_for_hotel(&chain),
+                owned_stocks.stocks_for_hotel(&chain) + 2
+            );
+            assert_eq!(
+                *bank.stocks_for_sale.stocks_for_hotel(&chain),
+                stocks_for_sale.stocks_for_hotel(&chain) - 2
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn market_history_records_prices_per_round() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut board = Board::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            let chain = HotelChain::Luxor;
+            bank.market_history.record_round(&hotel_chain_manager);
+            assert_eq!(
+                bank.market_history.change(&chain, &hotel_chain_manager),
+                None
+            );
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            // The chain was not active at the end of the last round
+            assert_eq!(
+                bank.market_history.change(&chain, &hotel_chain_manager),
+                None
+            );
+            bank.market_history.record_round(&hotel_chain_manager);
+            assert_eq!(
+                bank.market_history.change(&chain, &hotel_chain_manager),
+                Some(0)
+            );
+            for number in 3..=5 {
+                board.place_hotel(&Position::new('A', number))?;
+                hotel_chain_manager.add_hotel_to_chain(
+                    &chain,
+                    Position::new('A', number),
+                    &mut board,
+                )?;
+            }
+            let old_price = chain.stock_value(2);
+            let new_price = chain.stock_value(5);
+            assert_eq!(
+                bank.market_history.change(&chain, &hotel_chain_manager),
+                Some(new_price as i64 - old_price as i64)
+            );
+            bank.market_history.record_round(&hotel_chain_manager);
+            assert_eq!(
+                bank.market_history.prices(&chain),
+                vec![None, Some(old_price), Some(new_price)]
+            );
+            assert_eq!(
+                bank.market_history.prices(&HotelChain::Airport),
+                vec![None, None, None]
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn exchange_stocks_works() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            let dead = HotelChain::Airport;
+            let alive = HotelChain::Festival;
+            player.owned_stocks.increase_stocks(&dead, 6);
+            bank.exchange_stock(&mut player, 6, &dead, &HotelChain::Festival)?;
+            assert_eq!(*player.owned_stocks.stocks_for_hotel(&alive), 3);
+            Ok(())
+        }
+
+        #[test]
+        fn largest_shareholders_correct() {
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+                Player::new(vec![], 2, false, String::from("Player 3")),
+                Player::new(vec![], 3, false, String::from("Player 4")),
+            ];
+            let mut bank = Bank::new();
+            let mut index = 0;
+            while index < 4 {
+                let player = &mut players[index];
+                match index {
+                    0 => {
+                        player.owned_stocks.set_stocks(&HotelChain::Airport, 7);
+                        player.owned_stocks.set_stocks(&HotelChain::Continental, 10);
+                        player.owned_stocks.set_stocks(&HotelChain::Festival, 5);
+                        player.owned_stocks.set_stocks(&HotelChain::Imperial, 7);
+                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 10);
+                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 1);
+                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 5);
+                    }
+                    1 => {
+                        player.owned_stocks.set_stocks(&HotelChain::Airport, 2);
+                        player.owned_stocks.set_stocks(&HotelChain::Continental, 10);
+                        player.owned_stocks.set_stocks(&HotelChain::Festival, 3);
+                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 8);
+                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 5);
+                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 2);
+                    }
+                    2 => {
+                        player.owned_stocks.set_stocks(&HotelChain::Festival, 3);
+                        player.owned_stocks.set_stocks(&HotelChain::Continental, 10);
+                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 4);
+                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 8);
+                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 5);
+                    }
+                    3 => {
+                        player.owned_stocks.set_stocks(&HotelChain::Festival, 3);
+                        player.owned_stocks.set_stocks(&HotelChain::Oriental, 2);
+                        player.owned_stocks.set_stocks(&HotelChain::Prestige, 9);
+                        player.owned_stocks.set_stocks(&HotelChain::Luxor, 2);
+                    }
+                    _ => (),
+                }
+                index += 1;
+            }
+            bank.update_largest_shareholders(&players);
+            bank.print_largest_shareholders();
+            assert_eq!(
+                bank.rival_stocks(players[0].id, &HotelChain::Festival),
+                vec![3, 3, 3]
+            );
+            assert_eq!(
+                bank.rival_stocks(players[3].id, &HotelChain::Prestige),
+                vec![8, 5, 1]
+            );
+            // Test case 1: one largest and one second largest shareholder (Airport)
+            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Airport));
+            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Airport));
+            // Test case 2: multiple largest shareholerds (Continental)
+            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Continental));
+            assert!(bank.is_second_largest_shareholder(players[0].id, &HotelChain::Continental));
+            assert!(bank.is_largest_shareholder(players[1].id, &HotelChain::Continental));
+            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Continental));
+            assert!(bank.is_largest_shareholder(players[2].id, &HotelChain::Continental));
+            assert!(bank.is_second_largest_shareholder(players[2].id, &HotelChain::Continental));
+            // Test case 3: one largest and multiple second largest shareholders (Festival)
+            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Festival));
+            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Festival));
+            assert!(bank.is_second_largest_shareholder(players[2].id, &HotelChain::Festival));
+            assert!(bank.is_second_largest_shareholder(players[3].id, &HotelChain::Festival));
+            // Test case 4: one player is largest and second largest shareholder (Imperial)
+            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Imperial));
+            assert!(bank.is_second_largest_shareholder(players[0].id, &HotelChain::Imperial));
+            assert!(!bank.is_largest_shareholder(players[1].id, &HotelChain::Imperial));
+            assert!(!bank.is_second_largest_shareholder(players[1].id, &HotelChain::Imperial));
+            // Test case 5: one largest and one second largest shareholder, but the other players also own stocks (Oriental)
+            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Oriental));
+            assert!(bank.is_second_largest_shareholder(players[1].id, &HotelChain::Oriental));
+            // Test case 6: one largest and one second largest shareholder, but the other players
+            // also own stocks, bottom up order (Prestige)
+            assert!(bank.is_largest_shareholder(players[3].id, &HotelChain::Prestige));
+            assert!(bank.is_second_largest_shareholder(players[2].id, &HotelChain::Prestige));
+            // Test case 7: two largest and two second largest shareholder, switched (Luxor)
+            assert!(bank.is_largest_shareholder(players[0].id, &HotelChain::Luxor));
+            assert!(bank.is_largest_shareholder(players[2].id, &HotelChain::Luxor));
+            assert!(!bank.is_second_largest_shareholder(players[1].id, &HotelChain::Luxor));
+            assert!(!bank.is_second_largest_shareholder(players[3].id, &HotelChain::Luxor));
+            // Third largest shareholders
+            assert!(bank.is_third_largest_shareholder(players[2].id, &HotelChain::Oriental));
+            assert!(!bank.is_third_largest_shareholder(players[3].id, &HotelChain::Oriental));
+            assert!(bank.is_third_largest_shareholder(players[1].id, &HotelChain::Prestige));
+            assert!(bank.is_third_largest_shareholder(players[1].id, &HotelChain::Luxor));
+            assert!(bank.is_third_largest_shareholder(players[3].id, &HotelChain::Luxor));
+            assert!(!bank.is_third_largest_shareholder(players[1].id, &HotelChain::Festival));
+        }
+
+        #[test]
+        fn third_shareholder_bonus_works() -> Result<()> {
+            let mut bank = Bank::new();
+            bank.rules.third_shareholder_bonus = true;
+            let mut board = Board::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+                Player::new(vec![], 2, false, String::from("Player 3")),
+            ];
+            let chain = HotelChain::Imperial;
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut players[0],
+                &mut bank,
+            )?;
+            players[0].owned_stocks.set_stocks(&chain, 3);
+            players[1].owned_stocks.set_stocks(&chain, 2);
+            players[2].owned_stocks.set_stocks(&chain, 1);
+            bank.update_largest_shareholders(&players);
+            bank.give_majority_shareholder_bonuses(
+                &mut players,
+                &chain,
+                &hotel_chain_manager,
+                false,
+            )?;
+            assert_eq!(players[0].money, 9000);
+            assert_eq!(players[1].money, 7500);
+            assert_eq!(players[2].money, 6900);
+            Ok(())
+        }
+
+        #[test]
+        fn sell_stock_works() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut board = Board::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let player = Player::new(vec![], 0, false, String::from("Player 1"));
+            let mut players = vec![player];
+            let chain = HotelChain::Airport;
+            // Test error
+            assert!(bank
+                .sell_stock(players.get_mut(0).unwrap(), 4, &chain, &hotel_chain_manager)
+                .is_err());
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                players.get_mut(0).unwrap(),
+                &mut bank,
+            )?;
+            bank.update_largest_shareholders(&players);
+            bank.give_majority_shareholder_bonuses(
+                &mut players,
+                &chain,
+                &hotel_chain_manager,
+                false,
+            )?;
+            assert_eq!(players.first().unwrap().money, 9000);
+            Ok(())
+        }
+
+        #[test]
+        fn sell_stock_sells_only_the_amount() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut board = Board::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            let chain = HotelChain::Airport;
+            hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            for _i in 0..4 {
+                bank.buy_stock(&hotel_chain_manager, &chain, &mut player)?;
+            }
+            let price = Bank::stock_price(&hotel_chain_manager, &chain);
+            let money = player.money;
+            let stocks_for_sale = *bank.stocks_for_sale.stocks_for_hotel(&chain);
+            // Partial sale
+            bank.sell_stock(&mut player, 2, &chain, &hotel_chain_manager)?;
+            assert_eq!(*player.owned_stocks.stocks_for_hotel(&chain), 3);
+            assert_eq!(player.money, money + 2 * price);
+            assert_eq!(
+                *bank.stocks_for_sale.stocks_for_hotel(&chain),
+                stocks_for_sale + 2
+            );
+            // Selling nothing changes nothing
+            bank.sell_stock(&mut player, 0, &chain, &hotel_chain_manager)?;
+            assert_eq!(*player.owned_stocks.stocks_for_hotel(&chain), 3);
+            assert_eq!(player.money, money + 2 * price);
+            // More stocks than the player has can not be sold
+            assert!(bank
+                .sell_stock(&mut player, 4, &chain, &hotel_chain_manager)
+                .is_err());
+            // The remaining stocks
+            bank.sell_stock(&mut player, 3, &chain, &hotel_chain_manager)?;
+            assert_eq!(*player.owned_stocks.stocks_for_hotel(&chain), 0);
+            assert_eq!(player.money, money + 5 * price);
+            assert_eq!(
+                *bank.stocks_for_sale.stocks_for_hotel(&chain),
+                stocks_for_sale + 5
+            );
             Ok(())
         }
 
@@ -1490,6 +3026,15 @@ pub mod bank {
             )?;
             let player = players.get_mut(0).unwrap();
             assert_eq!(player.money, 10500);
+            assert_eq!(
+                bank.bonus_payouts,
+                [BonusPayout {
+                    chain,
+                    player_id: 0,
+                    rank: ShareholderRank::Largest,
+                    bonus: 4500
+                }]
+            );
             // 2. More than 1 player largest and second largest
             player.money = 6000;
             let player2 = players.get_mut(1).unwrap();
@@ -1537,10 +3082,7 @@ pub mod bank {
         }
 
         fn is_error(input: Result<()>) -> bool {
-            return match input {
-                Err(_) => true,
-                Ok(_) => false,
-            };
+            input.is_err()
         }
     }
 }
@@ -1553,27 +3095,103 @@ pub mod player {
         cmp::PartialEq,
         cmp::PartialOrd,
         collections::HashMap,
-        io::{BufRead, BufReader},
+        fmt::Write as _,
+        io::{self, stdin, stdout, BufRead, BufReader, Write},
         net::TcpStream,
         str::FromStr,
+        sync::{
+            atomic::{AtomicBool, Ordering as AtomicOrdering},
+            Arc,
+        },
+        time::{Duration, Instant},
     };
 
     use crate::{
         base_game::bank::Bank,
         base_game::board::Position,
-        base_game::{hotel_chains::HotelChain, stock::Stocks},
-        data_stream::read_enter,
+        base_game::{
+            hotel_chains::{HotelChain, NUMBER_OF_CHAINS},
+            settings::{Privacy, Settings, PURCHASE_LIMIT},
+            stock::Stocks,
+            ui,
+        },
+        bot::Bot,
         game::hotel_chain_manager::HotelChainManager,
+        locale::{is_yes, text, yes_no_keys},
         logic::place_hotel::{IllegalPlacement, PlaceHotelCase},
-        network::send_string,
+        network::{send_string, SentUi},
         utils::generate_number_vector,
     };
-    use miette::{miette, Result};
+    use miette::{miette, Diagnostic, IntoDiagnostic, Report, Result};
     use owo_colors::{AnsiColors, OwoColorize, Rgb};
-    use read_input::{prelude::input, InputBuild};
+    use thiserror::Error;
 
     use super::board::{AnalyzedPosition, Board};
 
+    /// How often a client that is asked for input checks if the game has been stopped, see
+    /// [`CancellationToken`]
+    const CANCELLATION_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Returned when a player can not be asked for input.
+    #[derive(Debug, Diagnostic, Error, PartialEq, Eq)]
+    pub enum InputError {
+        /// The input has been closed, the client of a player that plays via lan has left
+        #[error("Unable to read input of {name}: The input has been closed")]
+        #[diagnostic(code(acquire_rs::input::closed))]
+        Closed { name: String },
+        /// The client has not answered in the time that the host allows, see
+        /// [`Settings::input_timeout`]
+        #[error("Unable to read input of {name}: No answer within {seconds} seconds")]
+        #[diagnostic(code(acquire_rs::input::timed_out))]
+        TimedOut { name: String, seconds: u64 },
+        /// The game has been stopped while the client was asked for input, see
+        /// [`CancellationToken`]
+        #[error("Unable to read input of {name}: The game has been stopped")]
+        #[diagnostic(code(acquire_rs::input::cancelled))]
+        Cancelled { name: String },
+    }
+
+    /// Stops the clients that are asked for input when the game is stopped, the host cancels the
+    /// token when Ctrl-C is pressed. Clones share the same state.
+    ///
+    /// Reads from the terminal of the host can not be cancelled, only the clients are stopped.
+    #[derive(Clone, Debug, Default)]
+    pub struct CancellationToken(Arc<AtomicBool>);
+
+    impl CancellationToken {
+        /// Stops the game, every client that is asked for input fails with
+        /// [`InputError::Cancelled`]
+        pub fn cancel(&self) {
+            self.0.store(true, AtomicOrdering::Relaxed);
+        }
+
+        /// Returns if the game has been stopped
+        pub fn is_cancelled(&self) -> bool {
+            self.0.load(AtomicOrdering::Relaxed)
+        }
+    }
+
+    /// Returned when the money of a player can not be changed.
+    #[derive(Debug, Diagnostic, Error, PartialEq, Eq)]
+    pub enum MoneyError {
+        /// More money should be removed than the player has
+        #[error("Unable to remove {amount}€ from {name}: They only have {money}€.")]
+        #[diagnostic(code(acquire_rs::money::not_enough))]
+        NotEnoughMoney {
+            name: String,
+            money: u32,
+            amount: u32,
+        },
+        /// The money of the player would exceed the largest amount that can be stored
+        #[error("Unable to add {amount}€ to {name}: They already have {money}€.")]
+        #[diagnostic(code(acquire_rs::money::overflow))]
+        Overflow {
+            name: String,
+            money: u32,
+            amount: u32,
+        },
+    }
+
     /// Stores all variables that belong to the player
     //#[derive(PartialEq)]
     pub struct Player {
@@ -1627,6 +3245,217 @@ pub mod player {
         ///   1  2  3  4  5  6  7  8  9 10 11 12
         /// ```
         pub small_board: bool,
+        /// Determines how much information is shown to the player
+        pub privacy: Privacy,
+        /// The team this player belongs to. Is `None` when the game is not played in teams.
+        pub team: Option<u32>,
+        /// The bot that makes the decisions for this player. Is `None` when the player is human.
+        pub bot: Option<Box<dyn Bot>>,
+        /// The reasons for the decisions the bot has made that have not been shown to the
+        /// players yet
+        pub bot_reasons: Vec<String>,
+        /// Where the money of the player came from and where it went
+        pub statistics: MoneyStatistics,
+        /// What the player has paid for their stocks and the profit they have made with them
+        pub cost_basis: CostBasis,
+        /// The summary of the chains that is shown when the player enters [`STATS_COMMAND`] at
+        /// a prompt. Updated each time the main ui is printed.
+        pub stats: Vec<String>,
+        /// If the player has left the game with [`RESIGN_COMMAND`]. The turns of a player that
+        /// has resigned are skipped.
+        pub resigned: bool,
+        /// The color in which the name of the player is printed. It is chosen from
+        /// [`PLAYER_COLORS`] when the player is created and does not change when the turn order
+        /// is determined.
+        pub color: Rgb,
+        /// If the hand cards are sorted by position after each change. When disabled the player
+        /// orders the cards themselves with [`MOVE_COMMAND`] and new cards are added at the end.
+        pub auto_sort: bool,
+        /// If the text is shown to the player without colors, shareholder stars and box-drawing
+        /// lines, see [`Settings::plain`]
+        pub plain: bool,
+        /// If the messages are sent to the client as binary frames, see
+        /// [`start_client`](crate::network::start_client)
+        pub binary: bool,
+        /// The main ui that has last been sent to the client, `None` when the client does not
+        /// understand delta updates, see [`main_ui_message`](crate::network::main_ui_message)
+        pub sent_ui: Option<SentUi>,
+        /// The main ui that has last been shown to the player, shown again when the player
+        /// enters one of the [`BOARD_COMMANDS`] at a prompt
+        pub main_ui: String,
+        /// If the player plays in fast mode: prompts that only wait for enter are skipped,
+        /// confirmations are answered with yes and summaries are shown in one line, see
+        /// [`Settings::skip_dialogues`]
+        pub skip_dialogues: bool,
+        /// After how long a client that has not answered is shown the prompt again, see
+        /// [`Settings::remind_after`]
+        pub remind_after: Option<Duration>,
+        /// Stops the client when they are asked for input and the game is stopped, see
+        /// [`Settings::cancellation`]
+        pub cancellation: CancellationToken,
+    }
+
+    /// The colors in which the names of the players are printed, one for each player
+    pub const PLAYER_COLORS: [Rgb; 6] = [
+        Rgb(0, 175, 255),
+        Rgb(255, 95, 175),
+        Rgb(175, 215, 0),
+        Rgb(255, 175, 0),
+        Rgb(175, 135, 255),
+        Rgb(0, 215, 175),
+    ];
+
+    /// The command that can be entered at any prompt to show a summary of the chains
+    pub const STATS_COMMAND: &str = "stats";
+
+    /// The commands that can be entered at any prompt to show the main ui again, for example
+    /// when it has scrolled out of the terminal
+    pub const BOARD_COMMANDS: [&str; 2] = ["board", "b"];
+
+    /// The command that can be entered instead of a card to leave the game
+    pub const RESIGN_COMMAND: &str = "resign";
+
+    /// The command that can be entered instead of a card to turn [`Player::auto_sort`] on or off
+    pub const SORT_COMMAND: &str = "sort";
+
+    /// The command that can be entered instead of a card to move a card to another slot, it is
+    /// followed by the number of the card and the number of the slot: `move 4 1`
+    pub const MOVE_COMMAND: &str = "move";
+
+    /// The command that can be entered instead of a card to open the debug console, see
+    /// [`crate::debug_console`]
+    pub const DEBUG_CONSOLE_COMMAND: &str = ":";
+
+    /// What the player has chosen when they have been asked for a card
+    pub enum CardChoice {
+        /// The card that has been selected, it has been removed from the hand cards
+        Card(AnalyzedPosition),
+        /// The player wants to leave the game, see [`RESIGN_COMMAND`]
+        Resign,
+        /// The player wants to open the debug console, see [`DEBUG_CONSOLE_COMMAND`]
+        DebugConsole,
+    }
+
+    /// Sums up where the money of a player came from and where it went.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct MoneyStatistics {
+        /// The number of stocks the player has bought
+        pub stocks_bought: u32,
+        /// The money the player has spent on stocks
+        pub money_spent: u32,
+        /// The number of stocks the player has sold, including the stocks that are sold in the
+        /// final account
+        pub stocks_sold: u32,
+        /// The money the player has earned by selling stocks
+        pub money_earned: u32,
+        /// The money the player has received as majority shareholder bonuses
+        pub bonuses: u32,
+        /// The number of stocks the player has received for founding chains
+        pub founder_stocks: u32,
+    }
+
+    /// A number of stocks of one chain that a player has acquired at the same price.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct StockLot {
+        pub stocks: u32,
+        /// What the player has paid for all stocks of the lot together
+        pub cost: u32,
+    }
+
+    /// Stores for each chain what a player has paid for the stocks they own and how much profit
+    /// they have made by selling stocks.
+    ///
+    /// Stocks are sold in the order in which they have been acquired. Founder stocks cost
+    /// nothing and stocks that are received in an exchange cost what the exchanged stocks cost.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct CostBasis {
+        /// The lots of each chain in the order in which they have been acquired, indexed by
+        /// [`HotelChain::index`]
+        lots: [Vec<StockLot>; NUMBER_OF_CHAINS],
+        /// The profit that has been made by selling stocks of each chain
+        realized: [i64; NUMBER_OF_CHAINS],
+    }
+
+    impl CostBasis {
+        /// Adds stocks that have been acquired for `cost` in total.
+        /// The stocks are added to the last lot when they have the same price per stock.
+        pub fn add_lot(&mut self, chain: &HotelChain, stocks: u32, cost: u32) {
+            if stocks == 0 {
+                return;
+            }
+            let lots = &mut self.lots[chain.index()];
+            match lots.last_mut() {
+                Some(last)
+                    if last.cost as u64 * stocks as u64 == cost as u64 * last.stocks as u64 =>
+                {
+                    last.stocks += stocks;
+                    last.cost += cost;
+                }
+                _ => lots.push(StockLot { stocks, cost }),
+            }
+        }
+
+        /// Removes the stocks that have been acquired first and returns what they have cost.
+        /// When a lot is only removed partly, its cost is split by the number of stocks.
+        fn take(&mut self, chain: &HotelChain, mut stocks: u32) -> u32 {
+            let lots = &mut self.lots[chain.index()];
+            let mut cost = 0;
+            while stocks > 0 && !lots.is_empty() {
+                let lot = &mut lots[0];
+                if lot.stocks <= stocks {
+                    stocks -= lot.stocks;
+                    cost += lot.cost;
+                    lots.remove(0);
+                } else {
+                    let part = (lot.cost as u64 * stocks as u64 / lot.stocks as u64) as u32;
+                    lot.stocks -= stocks;
+                    lot.cost -= part;
+                    cost += part;
+                    stocks = 0;
+                }
+            }
+            cost
+        }
+
+        /// Records that stocks have been sold for `revenue` in total.
+        pub fn sell(&mut self, chain: &HotelChain, stocks: u32, revenue: u32) {
+            let cost = self.take(chain, stocks);
+            self.realized[chain.index()] += revenue as i64 - cost as i64;
+        }
+
+        /// Records that `to_exchange` stocks of the dead chain have been exchanged into half as
+        /// many stocks of the alive chain.
+        pub fn exchange(&mut self, dead: &HotelChain, alive: &HotelChain, to_exchange: u32) {
+            let cost = self.take(dead, to_exchange);
+            self.add_lot(alive, to_exchange / 2, cost);
+        }
+
+        /// Returns the lots of the chain that the player still owns, the oldest first
+        pub fn lots(&self, chain: &HotelChain) -> &[StockLot] {
+            &self.lots[chain.index()]
+        }
+
+        /// Returns what the player has paid for the stocks of the chain they still own
+        pub fn cost(&self, chain: &HotelChain) -> u32 {
+            self.lots(chain).iter().map(|lot| lot.cost).sum()
+        }
+
+        /// Returns the profit that has been made by selling stocks of the chain
+        pub fn realized_profit(&self, chain: &HotelChain) -> i64 {
+            self.realized[chain.index()]
+        }
+
+        /// Returns the profit that would be made when the stocks of the chain that the player
+        /// still owns are sold for `price` each
+        pub fn unrealized_profit(&self, chain: &HotelChain, price: u32) -> i64 {
+            let stocks: u32 = self.lots(chain).iter().map(|lot| lot.stocks).sum();
+            (stocks * price) as i64 - self.cost(chain) as i64
+        }
+
+        /// Returns the profit that has been made by selling stocks of all chains
+        pub fn total_realized_profit(&self) -> i64 {
+            self.realized.iter().sum()
+        }
     }
 
     impl PartialEq for Player {
@@ -1638,15 +3467,7 @@ pub mod player {
     /// Players will be sorted by id, if id is same than by name
     impl PartialOrd for Player {
         fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-            match self.id.cmp(&other.id) {
-                Ordering::Less => Some(Ordering::Less),
-                Ordering::Greater => Some(Ordering::Greater),
-                Ordering::Equal => match self.name.cmp(&other.name) {
-                    Ordering::Less => Some(Ordering::Less),
-                    Ordering::Greater => Some(Ordering::Greater),
-                    Ordering::Equal => Some(Ordering::Equal),
-                },
-            }
+            Some(self.cmp(other))
         }
     }
 
@@ -1681,6 +3502,23 @@ pub mod player {
                 name,
                 tcp_stream: None,
                 small_board,
+                privacy: Privacy::default(),
+                team: None,
+                bot: None,
+                bot_reasons: Vec::new(),
+                statistics: MoneyStatistics::default(),
+                cost_basis: CostBasis::default(),
+                stats: Vec::new(),
+                resigned: false,
+                color: PLAYER_COLORS[id as usize % PLAYER_COLORS.len()],
+                auto_sort: true,
+                plain: false,
+                binary: false,
+                sent_ui: None,
+                main_ui: String::new(),
+                skip_dialogues: false,
+                remind_after: None,
+                cancellation: CancellationToken::default(),
             }
         }
 
@@ -1691,6 +3529,7 @@ pub mod player {
             name: String,
             tcp_stream: TcpStream,
             small_board: bool,
+            privacy: Privacy,
         ) -> Self {
             let mut cards = Vec::new();
             for position in start_cards {
@@ -1704,17 +3543,90 @@ pub mod player {
                 name,
                 tcp_stream: Some(tcp_stream),
                 small_board,
+                privacy,
+                team: None,
+                bot: None,
+                bot_reasons: Vec::new(),
+                statistics: MoneyStatistics::default(),
+                cost_basis: CostBasis::default(),
+                stats: Vec::new(),
+                resigned: false,
+                color: PLAYER_COLORS[id as usize % PLAYER_COLORS.len()],
+                auto_sort: true,
+                plain: false,
+                binary: false,
+                sent_ui: None,
+                main_ui: String::new(),
+                skip_dialogues: false,
+                remind_after: None,
+                cancellation: CancellationToken::default(),
             }
         }
 
+        /// Returns the name of the player printed in their color
+        pub fn colored_name(&self) -> String {
+            self.name.color(self.color).to_string()
+        }
+
+        /// Returns a copy of the player that is not connected to a client and is not played by a
+        /// bot. The copy can be used to play the game without any interaction.
+        pub fn headless_copy(&self) -> Player {
+            let cards = self
+                .analyzed_cards
+                .iter()
+                .map(|card| card.position)
+                .collect();
+            let mut player = Player::new(cards, self.id, self.small_board, self.name.clone());
+            player.money = self.money;
+            player.owned_stocks = self.owned_stocks.clone();
+            player.privacy = self.privacy;
+            player.team = self.team;
+            player.statistics = self.statistics;
+            player.cost_basis = self.cost_basis.clone();
+            player.resigned = self.resigned;
+            player.color = self.color;
+            player.auto_sort = self.auto_sort;
+            player.plain = self.plain;
+            player
+        }
+
         /// Add money to the player
-        pub fn add_money(&mut self, money: u32) {
-            self.money += money;
+        /// # Returns
+        /// * `Err(MoneyError::Overflow)` - The money can not be stored, the money of the player
+        ///   is not changed
+        pub fn add_money(&mut self, money: u32) -> Result<(), MoneyError> {
+            self.money = self
+                .money
+                .checked_add(money)
+                .ok_or_else(|| MoneyError::Overflow {
+                    name: self.name.clone(),
+                    money: self.money,
+                    amount: money,
+                })?;
+            Ok(())
+        }
+
+        /// Add a majority shareholder bonus to the money of the player
+        pub fn receive_bonus(&mut self, bonus: u32) -> Result<(), MoneyError> {
+            self.add_money(bonus)?;
+            self.statistics.bonuses += bonus;
+            Ok(())
         }
 
         /// Remove money from the player
-        pub fn remove_money(&mut self, money: u32) {
-            self.money -= money;
+        /// # Returns
+        /// * `Err(MoneyError::NotEnoughMoney)` - The player has less money, the money of the
+        ///   player is not changed
+        pub fn remove_money(&mut self, money: u32) -> Result<(), MoneyError> {
+            self.money =
+                self.money
+                    .checked_sub(money)
+                    .ok_or_else(|| MoneyError::NotEnoughMoney {
+                        name: self.name.clone(),
+                        money: self.money,
+                        amount: money,
+                    })?;
+            Ok(())
         }
 
         /// Add stocks that the player owns
@@ -1737,20 +3649,36 @@ pub mod player {
             true
         }
 
-        /// Sorts the players current hand cards
+        /// Sorts the players current hand cards, nothing is done when [`Player::auto_sort`] is
+        /// disabled.
         pub fn sort_cards(&mut self) {
-            self.analyzed_cards.sort()
+            if self.auto_sort {
+                self.analyzed_cards.sort()
+            }
+        }
+
+        /// Moves the card at index `from` to index `to`, the cards in between are shifted.
+        pub fn move_card(&mut self, from: usize, to: usize) -> Result<()> {
+            let len = self.analyzed_cards.len();
+            if from >= len || to >= len {
+                return Err(miette!(
+                    "Unable to move card from slot {} to slot {}: The player only has {} cards",
+                    from + 1,
+                    to + 1,
+                    len
+                ));
+            }
+            let card = self.analyzed_cards.remove(from);
+            self.analyzed_cards.insert(to, card);
+            Ok(())
         }
 
         /// Removes a card from the players inventory.
         /// Returns the removed card when the card has been removed successfully.
         /// Otherwise `None` is returned.
         pub fn remove_card(&mut self, position: &Position) -> Result<AnalyzedPosition> {
-            self.sort_cards();
             for (index, analyzed_card) in self.analyzed_cards.iter().enumerate() {
-                if analyzed_card.position.letter.eq(&position.letter)
-                    && analyzed_card.position.number.eq(&position.number)
-                {
+                if analyzed_card.position == *position {
                     return Ok(self.analyzed_cards.remove(index));
                 }
             }
@@ -1789,40 +3717,68 @@ pub mod player {
                 String::from("Money:").bright_green(),
                 self.money
             ));
-            // Print cards
+            ui.push(self.cards_ui());
+            // Print stocks
+            //shareholder or a silver * when the player is the second largest shareholder.
+            //The star is positioned here: Airport*:
+            ui.push(format!(
+                "{}{} (total: {})",
+                String::from("Stocks: ").bright_green(),
+                self.stocks_ui(),
+                self.owned_stocks.total()
+            ));
+            ui
+            //TODO Maybe add fields:
+            //- "Current estimated wealth". That displayes the amount of
+            //  money the player would have now if all shares where sold and the rewards for the
+            //  largest shareholders where given now. (But is only enabled if special info flag is
+            //  given)
+            //- "Current stock value" - Value of alls stocks if sold now
+        }
+
+        /// Returns the hand cards of the player as one line, numbered in the order in which they
+        /// can be selected
+        ///
+        /// The cards are only colored when the player sees colors, see [`Player::plain`].
+        pub fn cards_ui(&self) -> String {
             let mut cards = String::new();
-            cards.push_str(&String::from("Cards: ").bright_green().to_string());
-            let mut first_card = true;
+            if self.plain {
+                cards.push_str("Cards: ");
+            } else {
+                write!(cards, "{}", "Cards: ".bright_green())
+                    .expect("Writing to a string does not fail");
+            }
             for (index, analyzed_card) in self.analyzed_cards.iter().enumerate() {
-                if first_card {
-                    first_card = false;
-                } else {
+                if index > 0 {
                     cards.push_str(", ");
                 }
-                if analyzed_card.is_illegal() {
-                    cards.push_str(&format!(
+                let result = match (self.plain, analyzed_card.is_illegal()) {
+                    (true, _) => write!(cards, "({}) {:#}", index + 1, analyzed_card),
+                    (false, true) => write!(
+                        cards,
                         "{} {}",
-                        format!("({})", index + 1).color(Rgb(105, 105, 105)),
+                        format_args!("({})", index + 1).color(Rgb(105, 105, 105)),
                         analyzed_card
-                    ));
-                } else {
-                    cards.push_str(&format!(
+                    ),
+                    (false, false) => write!(
+                        cards,
                         "({}) {}",
-                        format!("{}", index + 1).color(AnsiColors::BrightBlue),
+                        (index + 1).color(AnsiColors::BrightBlue),
                         analyzed_card
-                    ));
-                }
+                    ),
+                };
+                result.expect("Writing to a string does not fail");
             }
-            ui.push(cards);
-            // Print stocks
-            //shareholder or a silver * when the player is the second largest shareholder.
-            //The star is positioned here: Airport*:
+            cards
+        }
+
+        /// Returns the stocks the player owns as one line
+        pub fn stocks_ui(&self) -> String {
             let mut stocks = String::new();
-            stocks.push_str(&String::from("Stocks: ").bright_green().to_string());
-            first_card = true;
+            let mut first = true;
             for chain in HotelChain::iterator() {
-                if first_card {
-                    first_card = false;
+                if first {
+                    first = false;
                 } else {
                     stocks.push_str(", ");
                 }
@@ -1832,53 +3788,141 @@ pub mod player {
                     self.owned_stocks.stocks_for_hotel(chain)
                 ));
             }
-            ui.push(stocks);
-            ui
-            //TODO Maybe add fields:
-            //- "Current estimated wealth". That displayes the amount of
-            //  money the player would have now if all shares where sold and the rewards for the
-            //  largest shareholders where given now. (But is only enabled if special info flag is
-            //  given)
-            //- "Current stock value" - Value of alls stocks if sold now
-            //- "Total stocks" - Amount of all stocks the player has
-            //- Net profit: Stores all expenses the player made and calculate the net profit the
-            // would make if all stocks where sold now
+            stocks
+        }
+
+        /// Returns true if the other player is in the same team as this player
+        pub fn is_team_mate(&self, other: &Player) -> bool {
+            self.team.is_some() && self.team == other.team && self.id != other.id
         }
 
         /// Promts the user to press enter to draw a new card.
         /// The card is drawn beforehand.
+        ///
+        /// The drawn card is shown with [`ui::show_private`] so that in hot-seat mode the other
+        /// players do not see it.
         pub fn draw_card(
             &mut self,
             card: Position,
-            skip_dialogues: bool,
+            settings: &Settings,
             board: &Board,
             hotel_chain_manager: &HotelChainManager,
         ) -> Result<()> {
-            if !skip_dialogues {
-                self.get_enter("Press enter to draw a new card")?;
-            }
-            if !skip_dialogues {
-                self.print_text_ln(&format!(
-                    "Card drawn: {}",
-                    &card.to_string().color(AnsiColors::Green)
-                ))?;
+            if !settings.skip_dialogues {
+                self.get_enter(&text("card.draw_prompt", &[]))?;
             }
             self.add_card(&card, board, hotel_chain_manager);
-            if !skip_dialogues {
-                self.get_enter("Press enter to finish your turn")?;
+            if !settings.skip_dialogues {
+                ui::show_private(
+                    self,
+                    &text("card.drawn", &[&card.to_string().color(AnsiColors::Green)]),
+                    &text("turn.finish", &[]),
+                    settings.hot_seat,
+                )?;
             }
             Ok(())
         }
 
         /// Prompts the user to select a card.
         /// This card is then removed from the players inventory and returned.
-        pub fn read_card(&mut self) -> Result<AnalyzedPosition> {
+        /// # Arguments
+        /// * `debug_console` - If the player may open the debug console, only players that play
+        ///   on this machine can open it
+        pub fn read_card(
+            &mut self,
+            bank: &Bank,
+            hotel_chain_manager: &HotelChainManager,
+            fusion_analysis: &[String],
+            debug_console: bool,
+        ) -> Result<CardChoice> {
+            if let Some(bot) = &self.bot {
+                let decision = bot.choose_card(self, bank, hotel_chain_manager);
+                self.bot_reasons.push(decision.reason);
+                return self.remove_card(&decision.choice).map(CardChoice::Card);
+            }
+            let debug_console = debug_console && self.tcp_stream.is_none();
+            if debug_console {
+                self.print_text_ln(
+                    &text("card.debug_console", &[&DEBUG_CONSOLE_COMMAND])
+                        .color(Rgb(105, 105, 105))
+                        .to_string(),
+                )?;
+            }
+            self.print_text_ln(
+                &text("card.commands", &[&MOVE_COMMAND, &SORT_COMMAND])
+                    .color(Rgb(105, 105, 105))
+                    .to_string(),
+            )?;
             loop {
-                let card_index = self.read_input(
-                    format!("Enter a number 1-{}: ", self.analyzed_cards.len()),
-                    generate_number_vector(1, self.analyzed_cards.len() as u32),
-                )? as usize
-                    - 1;
+                let prompt = if fusion_analysis.is_empty() {
+                    text(
+                        "card.prompt",
+                        &[&self.analyzed_cards.len(), &RESIGN_COMMAND],
+                    )
+                } else {
+                    text(
+                        "card.prompt_fusion",
+                        &[&self.analyzed_cards.len(), &RESIGN_COMMAND],
+                    )
+                };
+                let first = if fusion_analysis.is_empty() { 1 } else { 0 };
+                let mut allowed_values: Vec<String> =
+                    generate_number_vector(first, self.analyzed_cards.len() as u32)
+                        .iter()
+                        .map(|number| number.to_string())
+                        .collect();
+                allowed_values.push(String::from(RESIGN_COMMAND));
+                allowed_values.push(String::from(SORT_COMMAND));
+                if debug_console {
+                    allowed_values.push(String::from(DEBUG_CONSOLE_COMMAND));
+                }
+                let slots = self.analyzed_cards.len();
+                for from in 1..=slots {
+                    for to in (1..=slots).filter(|to| *to != from) {
+                        allowed_values.push(format!("{} {} {}", MOVE_COMMAND, from, to));
+                    }
+                }
+                let input = self.read_input(prompt, allowed_values)?;
+                if input == SORT_COMMAND {
+                    self.auto_sort = !self.auto_sort;
+                    self.sort_cards();
+                    self.print_text_ln(&match self.auto_sort {
+                        true => text("card.sorted", &[]),
+                        false => text("card.unsorted", &[]),
+                    })?;
+                    self.print_text_ln(&self.cards_ui())?;
+                    continue;
+                }
+                if let Some(slots) = input.strip_prefix(MOVE_COMMAND) {
+                    let slots: Vec<usize> = slots
+                        .split_whitespace()
+                        .map(|slot| slot.parse().into_diagnostic())
+                        .collect::<Result<_>>()?;
+                    self.move_card(slots[0] - 1, slots[1] - 1)?;
+                    if self.auto_sort {
+                        self.auto_sort = false;
+                        self.print_text_ln(&text("card.unsorted_hint", &[&SORT_COMMAND]))?;
+                    }
+                    self.print_text_ln(&self.cards_ui())?;
+                    continue;
+                }
+                if input == RESIGN_COMMAND {
+                    if self.read_yes_no(text("card.resign_prompt", &[]))? {
+                        return Ok(CardChoice::Resign);
+                    }
+                    continue;
+                }
+                if input == DEBUG_CONSOLE_COMMAND {
+                    return Ok(CardChoice::DebugConsole);
+                }
+                let card_index: u32 = input.parse().into_diagnostic()?;
+                if card_index == 0 {
+                    for line in fusion_analysis {
+                        self.print_text_ln(line)?;
+                    }
+                    continue;
+                }
+                let card_index = card_index as usize - 1;
                 let analyzed_position = *self.analyzed_cards.get(card_index).as_ref().unwrap();
                 // Check if hotel placement is allowed
                 if analyzed_position.is_illegal() {
@@ -1891,17 +3935,30 @@ pub mod player {
                             true => IllegalPlacement::ChainStartIllegal.description(),
                             false => IllegalPlacement::FusionIllegal.description(),
                         };
-                    self.print_text_ln(&format!(
-                        "This position is illegal [{}]: {}",
-                        analyzed_position.position.color(Rgb(105, 105, 105)),
-                        reason.color(AnsiColors::Red)
+                    self.print_text_ln(&text(
+                        "card.illegal",
+                        &[
+                            &analyzed_position.position.color(Rgb(105, 105, 105)),
+                            &reason.color(AnsiColors::Red),
+                        ],
                     ))?;
-                    self.print_text_ln("Please select another card!")?;
+                    self.print_text_ln(&text("card.choose_another", &[]))?;
                     continue;
                 }
                 let position = analyzed_position.position;
+                if let PlaceHotelCase::Fusion(chains, _) = &analyzed_position.place_hotel_case {
+                    for line in
+                        ui::fusion_warning_ui(self, &position, chains, bank, hotel_chain_manager)
+                    {
+                        self.print_text_ln(&line)?;
+                    }
+                    if !self.get_correct()? {
+                        self.print_text_ln(&text("card.choose_another", &[]))?;
+                        continue;
+                    }
+                }
                 //Remove the played card from the players hand cards
-                return self.remove_card(&position);
+                return self.remove_card(&position).map(CardChoice::Card);
             }
         }
 
@@ -1918,103 +3975,126 @@ pub mod player {
             hotel_chain_manager: &HotelChainManager,
         ) -> Result<(u32, u32, u32)> {
             let number_of_stocks = *self.owned_stocks.stocks_for_hotel(dead);
-            self.print_text_ln(&format!(
-                "{}, it's your turn to decide what you would like to do with your {} stock(s):",
-                self.name, number_of_stocks
+            self.print_text_ln(&text(
+                "fusion_stocks.turn",
+                &[&self.name, &number_of_stocks],
             ))?;
             let mut stocks_unasigned;
             let mut stocks_to_exchange = 0;
             let mut stocks_to_sell;
             // loop that runs until the player has decided what they would like to do with the
             // stocks
-            loop {
-                // First ask how many stocks should be exchanged
-                stocks_unasigned = number_of_stocks;
-                let mut allowed_values = vec![];
-                // fill allowed values
-                let mut allowed_string = String::new();
-                let mut new_alive_stocks_number = 0;
-                // Stores how many stocks the bank has left of the chain that survives the fusion
-                let stocks_left_to_exchange = bank.stocks_for_sale.stocks_for_hotel(alive);
-                for i in 0..=stocks_unasigned {
-                    if i % 2 == 0 && *stocks_left_to_exchange >= i / 2 {
-                        // i/2 is calculated because two stocks will be traded into one
-                        if i != 0 {
-                            allowed_string.push_str(", ");
+            if let Some(bot) = &self.bot {
+                let decision =
+                    bot.handle_fusion_stocks(self, dead, alive, bank, hotel_chain_manager);
+                (stocks_to_exchange, stocks_to_sell) = decision.choice;
+                self.bot_reasons.push(decision.reason);
+                stocks_unasigned = number_of_stocks - stocks_to_exchange - stocks_to_sell;
+            } else {
+                loop {
+                    // First ask how many stocks should be exchanged
+                    stocks_unasigned = number_of_stocks;
+                    let mut allowed_values = vec![];
+                    // fill allowed values
+                    let mut allowed_string = String::new();
+                    let mut new_alive_stocks_number = 0;
+                    // Stores how many stocks the bank has left of the chain that survives the fusion
+                    let stocks_left_to_exchange = bank.stocks_for_sale.stocks_for_hotel(alive);
+                    for i in 0..=stocks_unasigned {
+                        if i % 2 == 0 && *stocks_left_to_exchange >= i / 2 {
+                            // i/2 is calculated because two stocks will be traded into one
+                            if i != 0 {
+                                allowed_string.push_str(", ");
+                            }
+                            allowed_values.push(i);
+                            allowed_string.push_str(&i.to_string());
                         }
-                        allowed_values.push(i);
-                        allowed_string.push_str(&i.to_string());
                     }
-                }
-                if allowed_values.len() != 1 {
-                    stocks_to_exchange = self.read_input(
-                        format!(
-                            "Please enter how many stocks you would like to exchange [{}]: ",
-                            allowed_string
-                        ),
-                        allowed_values,
-                    )?;
-                    new_alive_stocks_number = stocks_to_exchange / 2;
-                } else {
-                    // No stocks available for trade
-                    if *stocks_left_to_exchange == 0 {
+                    if allowed_values.len() != 1 {
+                        stocks_to_exchange = self.read_input(
+                            text("fusion_stocks.exchange", &[&allowed_string]),
+                            allowed_values,
+                        )?;
+                        new_alive_stocks_number = stocks_to_exchange / 2;
+                    } else {
+                        // No stocks available for trade
+                        let reason = if *stocks_left_to_exchange == 0 {
+                            text("fusion_stocks.bank_empty", &[])
+                        } else {
+                            text("fusion_stocks.too_few", &[])
+                        };
                         self.print_text_ln(&format!(
-                        "Please enter how many stocks you would like to exchange [{}]: 0 {}",
-                        allowed_string,
-                        "- the bank does not have any stocks left that could be exchanged to you".color(Rgb(105, 105, 105))
-                    ))?;
+                            "{}0 {}",
+                            text("fusion_stocks.exchange", &[&allowed_string]),
+                            reason.color(Rgb(105, 105, 105))
+                        ))?;
+                    }
+                    stocks_unasigned -= stocks_to_exchange;
+                    // Check if stocks are left that could be sold
+                    stocks_to_sell = 0;
+                    if stocks_unasigned != 0 {
+                        stocks_to_sell = self.read_input(
+                            text("fusion_stocks.sell", &[&stocks_unasigned]),
+                            generate_number_vector(0, stocks_unasigned),
+                        )?;
+                        stocks_unasigned -= stocks_to_sell;
                     } else {
+                        // No stocks left to sell
                         self.print_text_ln(&format!(
-                            "Please enter how many stocks you would like to exchange [{}]: 0 {}",
-                            allowed_string,
-                            "- you don't have enough stocks to exchange them"
-                                .color(Rgb(105, 105, 105))
+                            "{}0 {}",
+                            text("fusion_stocks.sell", &[&0]),
+                            text("fusion_stocks.none_left", &[]).color(Rgb(105, 105, 105))
                         ))?;
                     }
-                }
-                stocks_unasigned -= stocks_to_exchange;
-                // Check if stocks are left that could be sold
-                stocks_to_sell = 0;
-                if stocks_unasigned != 0 {
-                    stocks_to_sell = self.read_input(
-                        format!(
-                            "Please enter how many stocks you would like to sell [0-{}]: ",
-                            stocks_unasigned
+                    let dead_stocks = *self.owned_stocks.stocks_for_hotel(dead);
+                    let alive_stocks = *self.owned_stocks.stocks_for_hotel(alive);
+                    let earnings = Bank::stock_price(hotel_chain_manager, dead) * stocks_to_sell;
+                    self.print_stock_summary(&[
+                        text(
+                            "summary.stocks_removed",
+                            &[
+                                &dead.name().color(dead.color()),
+                                &dead_stocks,
+                                &(stocks_to_sell + stocks_to_exchange),
+                                &(dead_stocks - (stocks_to_sell + stocks_to_exchange)),
+                            ],
                         ),
-                        generate_number_vector(0, stocks_unasigned),
-                    )?;
-                    stocks_unasigned -= stocks_to_sell;
-                } else {
-                    // No stocks left to sell
-                    self.print_text_ln(&format!(
-                        "Please enter how many stocks you would like to sell [0-0]: 0 {}",
-                        "- not stocks left to sell".color(Rgb(105, 105, 105))
-                    ))?;
-                }
-                self.print_text_ln(&format!(
-                    "The following will happen to your stocks:\nTotal {} stocks: {} - {} = {}\nTotal {} stocks: {} + {} = {}\nMoney: {}€ + {}€ = {}€",
-                    dead.name().color(dead.color()), self.owned_stocks.stocks_for_hotel(dead), stocks_to_sell+stocks_to_exchange, self.owned_stocks.stocks_for_hotel(dead)-(stocks_to_sell+stocks_to_exchange),
-                    alive.name().color(alive.color()), self.owned_stocks.stocks_for_hotel(alive), new_alive_stocks_number, self.owned_stocks.stocks_for_hotel(alive)+new_alive_stocks_number,
-                    self.money, Bank::stock_price(hotel_chain_manager, dead)*stocks_to_sell, self.money+Bank::stock_price(hotel_chain_manager, dead)*stocks_to_sell,
-                ))?;
-                match self.get_correct()? {
-                    true => break,
-                    false => continue,
+                        text(
+                            "summary.stocks_added",
+                            &[
+                                &alive.name().color(alive.color()),
+                                &alive_stocks,
+                                &new_alive_stocks_number,
+                                &(alive_stocks + new_alive_stocks_number),
+                            ],
+                        ),
+                        text(
+                            "summary.money_added",
+                            &[&self.money, &earnings, &(self.money + earnings)],
+                        ),
+                    ])?;
+                    match self.get_correct()? {
+                        true => break,
+                        false => continue,
+                    }
                 }
             }
+            let mut transaction = bank.transaction(self);
             // Exchange stocks
             if stocks_to_exchange > 0 {
-                bank.exchange_stock(self, stocks_to_exchange, dead, alive)?;
+                transaction.exchange_stock(stocks_to_exchange, dead, alive)?;
             }
             // Sell stocks
             if stocks_to_sell > 0 {
-                bank.sell_stock(self, stocks_to_sell, dead, hotel_chain_manager)?;
+                transaction.sell_stock(stocks_to_sell, dead, hotel_chain_manager)?;
             }
+            transaction.commit();
             Ok((stocks_to_exchange, stocks_to_sell, stocks_unasigned))
         }
 
-        /// If chains are active, the player is asked if they would like to buy a maximum of three
-        /// stocks from available chains.
+        /// If chains are active, the player is asked if they would like to buy stocks from
+        /// available chains. The maximum number of stocks is set by the purchase limit of the bank
+        /// rules.
         /// # Returns
         /// * `None` - The player did not buy any stocks
         /// * `Some(HashMap(HotelChain, u32))` - The player bought stocks, what stocks and how many is stored in the hashmap
@@ -2036,29 +4116,35 @@ pub mod player {
                     min_stock_value = value;
                 }
             }
-            self.print_text_ln(&format!(
-                "{}, you can buy a maximum of three stocks now:",
-                self.name
-            ))?;
+            if let Some(bot) = &self.bot {
+                let decision = bot.buy_stocks(self, bank, hotel_chain_manager);
+                self.bot_reasons.push(decision.reason);
+                let stocks_bought = decision.choice;
+                if stocks_bought.is_empty() {
+                    return Ok(None);
+                }
+                let mut transaction = bank.transaction(self);
+                transaction.buy_stocks(hotel_chain_manager, &stocks_bought)?;
+                transaction.commit();
+                return Ok(Some(stocks_bought));
+            }
+            self.print_text_ln(&text("buy.maximum", &[&self.name, &PURCHASE_LIMIT]))?;
             // Runs until the player confirms the stocks bought
             loop {
                 // Stores how many stockes the player is allowed to buy
-                let mut stocks_left = 3;
+                let mut stocks_left = PURCHASE_LIMIT;
                 let mut stocks_bought = HashMap::new();
                 // Stores the money available for the current trade
                 let mut money_available = self.money;
                 for chain in hotel_chain_manager.active_chains() {
                     // Check conditions under which no stocks can be bought
-                    let main_message = format!(
-                        "How many stocks would you like to buy of {}?",
-                        chain.name().color(chain.color())
-                    );
+                    let main_message = text("buy.prompt", &[&chain.name().color(chain.color())]);
                     if stocks_left == 0 {
-                        // Player has already bought 3 stocks
+                        // Player has already bought the maximum number of stocks
                         self.print_text_ln(&format!(
                             "{} [0-0]: 0 {}",
                             main_message,
-                            "- already bought 3 stocks".color(Rgb(105, 105, 105))
+                            text("buy.limit_reached", &[&PURCHASE_LIMIT]).color(Rgb(105, 105, 105))
                         ))?;
                         continue;
                     }
@@ -2067,7 +4153,7 @@ pub mod player {
                         self.print_text_ln(&format!(
                             "{} [0-0]: 0 {}",
                             main_message,
-                            "- no stocks left".color(Rgb(105, 105, 105))
+                            text("buy.none_left", &[]).color(Rgb(105, 105, 105))
                         ))?;
                         continue;
                     }
@@ -2077,21 +4163,12 @@ pub mod player {
                         self.print_text_ln(&format!(
                             "{} [0-0]: 0 {}",
                             main_message,
-                            "- not enough money".color(Rgb(105, 105, 105))
+                            text("buy.no_money", &[]).color(Rgb(105, 105, 105))
                         ))?;
                         continue;
                     }
                     // Check how many stocks the player could buy with their current money
-                    let mut money_for_stocks = 0;
-                    if money_available >= stock_price {
-                        money_for_stocks = 1;
-                    }
-                    if money_available >= stock_price * 2 {
-                        money_for_stocks = 2;
-                    }
-                    if money_available >= stock_price * 3 {
-                        money_for_stocks = 3;
-                    }
+                    let money_for_stocks = money_available / stock_price;
                     let mut stocks_can_be_bought = min(money_for_stocks, stocks_left);
                     // Check if the stocks available in the bank are less then the stocks that the
                     // player could buy
@@ -2100,11 +4177,7 @@ pub mod player {
                         *bank.stocks_available(&chain, hotel_chain_manager),
                     );
                     let bought = self.read_input(
-                        format!(
-                            "How many stocks would you like to buy of {}? [0-{}]: ",
-                            chain.name().color(chain.color()),
-                            stocks_can_be_bought,
-                        ),
+                        format!("{} [0-{}]: ", main_message, stocks_can_be_bought),
                         generate_number_vector(0, stocks_can_be_bought),
                     )?;
                     if bought > 0 {
@@ -2115,85 +4188,165 @@ pub mod player {
                 }
                 // Check if player bought any stocks
                 if stocks_bought.is_empty() {
-                    self.print_text_ln("You did not buy any stocks.")?;
+                    self.print_text_ln(&text("buy.nothing", &[]))?;
                     if self.get_correct()? {
                         return Ok(None);
                     }
                     continue;
                 }
-                self.print_text_ln("The following will happen to your stocks:")?;
+                let mut summary = Vec::new();
                 let mut expanses = 0;
                 for (k, v) in &stocks_bought {
                     let current_stocks = self.owned_stocks.stocks_for_hotel(k);
-                    self.print_text_ln(&format!(
-                        "Total {} stocks: {} + {} = {}",
-                        k.name().color(k.color()),
-                        current_stocks,
-                        v,
-                        current_stocks + v
-                    ))?;
+                    summary.push(text(
+                        "summary.stocks_added",
+                        &[
+                            &k.name().color(k.color()),
+                            current_stocks,
+                            v,
+                            &(current_stocks + v),
+                        ],
+                    ));
                     expanses += Bank::stock_price(hotel_chain_manager, k) * v;
                 }
-                self.print_text_ln(&format!(
-                    "Money: {}€ - {}€ = {}€",
-                    self.money,
-                    expanses,
-                    self.money - expanses
-                ))?;
+                summary.push(text(
+                    "summary.money_removed",
+                    &[&self.money, &expanses, &(self.money - expanses)],
+                ));
+                self.print_stock_summary(&summary)?;
                 if !self.get_correct()? {
                     continue;
                 }
                 // Player confirmed transaction
-                for (k, v) in &stocks_bought {
-                    for _i in 1..=*v {
-                        bank.buy_stock(hotel_chain_manager, k, self).unwrap();
+                let mut transaction = bank.transaction(self);
+                transaction.buy_stocks(hotel_chain_manager, &stocks_bought)?;
+                transaction.commit();
+                return Ok(Some(stocks_bought));
+            }
+        }
+
+        /// If the player owns stocks of active chains, they are asked if they would like to sell
+        /// some of them back to the bank.
+        /// # Returns
+        /// * `None` - The player did not sell any stocks
+        /// * `Some(HashMap(HotelChain, u32))` - The player sold stocks, what stocks and how many is stored in the hashmap
+        pub fn sell_stocks(
+            &mut self,
+            bank: &mut Bank,
+            hotel_chain_manager: &HotelChainManager,
+        ) -> Result<Option<HashMap<HotelChain, u32>>> {
+            let mut chains_to_sell = Vec::new();
+            for chain in hotel_chain_manager.active_chains() {
+                if *self.owned_stocks.stocks_for_hotel(&chain) > 0 {
+                    chains_to_sell.push(chain);
+                }
+            }
+            if chains_to_sell.is_empty() {
+                return Ok(None);
+            }
+            if let Some(bot) = &self.bot {
+                let decision = bot.sell_stocks(self, bank, hotel_chain_manager);
+                self.bot_reasons.push(decision.reason);
+                let stocks_sold = decision.choice;
+                if stocks_sold.is_empty() {
+                    return Ok(None);
+                }
+                let mut transaction = bank.transaction(self);
+                for (k, v) in &stocks_sold {
+                    transaction.sell_stock(*v, k, hotel_chain_manager)?;
+                }
+                transaction.commit();
+                return Ok(Some(stocks_sold));
+            }
+            if !self.read_yes_no(text("sell.prompt", &[]))? {
+                return Ok(None);
+            }
+            // Runs until the player confirms the stocks sold
+            loop {
+                let mut stocks_sold = HashMap::new();
+                for chain in &chains_to_sell {
+                    let owned_stocks = *self.owned_stocks.stocks_for_hotel(chain);
+                    let sold = self.read_input(
+                        text(
+                            "sell.amount",
+                            &[&chain.name().color(chain.color()), &owned_stocks],
+                        ),
+                        generate_number_vector(0, owned_stocks),
+                    )?;
+                    if sold > 0 {
+                        stocks_sold.insert(*chain, sold);
                     }
                 }
-                return Ok(Some(stocks_bought));
+                // Check if player sold any stocks
+                if stocks_sold.is_empty() {
+                    self.print_text_ln(&text("sell.nothing", &[]))?;
+                    if self.get_correct()? {
+                        return Ok(None);
+                    }
+                    continue;
+                }
+                let mut summary = Vec::new();
+                let mut earnings = 0;
+                for (k, v) in &stocks_sold {
+                    let current_stocks = self.owned_stocks.stocks_for_hotel(k);
+                    summary.push(text(
+                        "summary.stocks_removed",
+                        &[
+                            &k.name().color(k.color()),
+                            current_stocks,
+                            v,
+                            &(current_stocks - v),
+                        ],
+                    ));
+                    earnings += Bank::stock_price(hotel_chain_manager, k) * v;
+                }
+                summary.push(text(
+                    "summary.money_added",
+                    &[&self.money, &earnings, &(self.money + earnings)],
+                ));
+                self.print_stock_summary(&summary)?;
+                if !self.get_correct()? {
+                    continue;
+                }
+                // Player confirmed transaction
+                let mut transaction = bank.transaction(self);
+                for (k, v) in &stocks_sold {
+                    transaction.sell_stock(*v, k, hotel_chain_manager)?;
+                }
+                transaction.commit();
+                return Ok(Some(stocks_sold));
             }
         }
 
         /// Promts the user to enter something.
         ///
         /// If the player is a client, only the text before the first `\n` is transmitted.
+        /// The player can enter [`STATS_COMMAND`] to see the summary of the chains or one of the
+        /// [`BOARD_COMMANDS`] to see the main ui again before they answer.
         /// # Arguments
-        /// * `text` - The text that is displayed
+        /// * `prompt` - The text that is displayed
         /// * `allowed_values` - The values that are allowed to be entered
         /// * `T` - The data type that should be read
         pub fn read_input<T: 'static + FromStr + PartialEq>(
             &self,
-            text: String,
+            prompt: String,
             allowed_values: Vec<T>,
         ) -> Result<T> {
-            if self.tcp_stream.is_none() {
-                // Player does not play fia lan
-                print!("{}", text);
-                Ok(input::<T>().inside(allowed_values).get())
-            } else {
-                // Player plays fia lan
-                let message = text.split('\n').next().unwrap();
-                let mut br = BufReader::new(self.tcp_stream.as_ref().unwrap());
-                loop {
-                    let result = send_string(self, message, "$Input");
-                    let mut buffer = String::new();
-                    if let Err(err) = br.read_line(&mut buffer) {
-                        return Err(miette!("Unable to send data to player, io error: {}", err));
-                    } else if let Err(err) = result {
-                        return Err(err);
-                    }
-                    let input = buffer.trim();
-                    match input.parse::<T>() {
-                        Ok(ok) => {
-                            if !allowed_values.contains(&ok) {
-                                self.print_text_ln("That value did not pass, please try again!")?;
-                                continue;
-                            }
-                            return Ok(ok);
-                        }
-                        Err(_err) => {
-                            self.print_text_ln("That value did not pass, please try again!")?;
-                        }
-                    };
+            if self.bot.is_some() {
+                return Err(miette!(
+                    "Unable to read input: {} is a bot and can not enter anything",
+                    self.name
+                ));
+            }
+            loop {
+                let buffer = self.read_line(&prompt)?;
+                let input = buffer.trim();
+                if self.handle_command(input)? {
+                    continue;
+                }
+                match input.parse::<T>() {
+                    Ok(ok) if allowed_values.contains(&ok) => return Ok(ok),
+                    _ => self.print_text_ln(&text("input.invalid", &[]))?,
                 }
             }
         }
@@ -2201,99 +4354,392 @@ pub mod player {
         /// Prints a text to the player and waits until they pressed enter.
         ///
         /// If the player is a client, only the text before the first `\n` is transmitted.
+        /// The player can enter [`STATS_COMMAND`] to see the summary of the chains or one of the
+        /// [`BOARD_COMMANDS`] to see the main ui again before they continue. A client that does not answer in time continues as if they pressed enter,
+        /// see [`Settings::input_timeout`].
         pub fn get_enter(&self, text: &str) -> Result<()> {
-            if self.tcp_stream.is_none() {
-                // Player does not play fia lan
-                print!("{}", &text);
-                read_enter();
-            } else {
-                // Player plays fia lan
-                let message = text.split('\n').next().unwrap();
-                let result = send_string(self, message, "$Input");
-                let mut br = BufReader::new(self.tcp_stream.as_ref().unwrap());
-                let mut buffer = String::new();
-                if let Err(err) = br.read_line(&mut buffer) {
-                    return Err(miette!("Unable to send data to player, io error: {}", err));
-                } else if let Err(err) = result {
-                    return Err(err);
+            if self.skip_dialogues {
+                return Ok(());
+            }
+            self.wait_for_enter(text)
+        }
+
+        /// Like [`Player::get_enter`] but the player has to press enter in fast mode as well.
+        /// Used in hot-seat mode before private information is hidden.
+        pub fn wait_for_enter(&self, text: &str) -> Result<()> {
+            if self.bot.is_some() {
+                // Bots do not need to confirm anything
+                return Ok(());
+            }
+            loop {
+                let input = match self.read_line(text) {
+                    Ok(input) => input,
+                    Err(err) if self.missed_input(&err).is_some() => return Ok(()),
+                    Err(err) => return Err(err),
+                };
+                if !self.handle_command(input.trim())? {
+                    return Ok(());
                 }
             }
+        }
+
+        /// Returns the [`InputError`] when the player is a client that has left or has not
+        /// answered in time. The game goes on without their answer then. `None` is returned for
+        /// every other error and when the game has been stopped, see [`CancellationToken`].
+        pub fn missed_input<'a>(&self, err: &'a Report) -> Option<&'a InputError> {
+            match err.downcast_ref::<InputError>() {
+                Some(InputError::Cancelled { .. }) => None,
+                input_error if self.tcp_stream.is_some() => input_error,
+                _ => None,
+            }
+        }
+
+        /// Takes the safe default for a decision that the client has not answered, see
+        /// [`Player::missed_input`]. Like a bot without a strategy the player buys and sells
+        /// nothing, keeps their stocks and takes the first chain they are offered, so that the
+        /// game is not canceled.
+        pub fn answer_or_default<T>(
+            &self,
+            answer: Result<T>,
+            default: impl FnOnce() -> T,
+        ) -> Result<T> {
+            match answer {
+                Err(err) if self.missed_input(&err).is_some() => Ok(default()),
+                answer => answer,
+            }
+        }
+
+        /// Sets in how many seconds the client has to answer, see [`Settings::input_timeout`].
+        /// Nothing is done when the player does not play via lan.
+        pub fn set_input_timeout(&self, seconds: Option<u64>) -> Result<()> {
+            if let Some(stream) = &self.tcp_stream {
+                stream
+                    .set_read_timeout(seconds.map(Duration::from_secs))
+                    .into_diagnostic()?;
+            }
             Ok(())
         }
 
+        /// Shows the text to the player and reads one line that they enter.
+        ///
+        /// Fails with an [`InputError`] when the input has been closed or the client has not
+        /// answered in time.
+        fn read_line(&self, text: &str) -> Result<String> {
+            let mut buffer = String::new();
+            match &self.tcp_stream {
+                None => {
+                    // Player does not play via lan
+                    print!("{}", ui::console_text(text));
+                    stdout().flush().into_diagnostic()?;
+                    if stdin().read_line(&mut buffer).into_diagnostic()? == 0 {
+                        return Err(InputError::Closed {
+                            name: self.name.clone(),
+                        }
+                        .into());
+                    }
+                }
+                Some(stream) => {
+                    // Player plays via lan
+                    let message = text.split('\n').next().unwrap();
+                    let result = send_string(self, message, "$Input");
+                    // The read timeout is set when the game is hosted with an input timeout
+                    let input_timeout = stream.read_timeout().into_diagnostic()?;
+                    let read = self.read_client_line(stream, message, input_timeout, &mut buffer);
+                    stream.set_read_timeout(input_timeout).into_diagnostic()?;
+                    read?;
+                    result?;
+                }
+            }
+            Ok(buffer)
+        }
+
+        /// Reads the line that the client answers to the prompt `message`. The prompt is sent
+        /// again each time the client has not answered for [`Player::remind_after`]. The read
+        /// is stopped when the [`Player::cancellation`] token is cancelled.
+        ///
+        /// The read timeout of the stream is changed, it has to be set to `input_timeout` again
+        /// afterwards.
+        fn read_client_line(
+            &self,
+            stream: &TcpStream,
+            message: &str,
+            input_timeout: Option<Duration>,
+            buffer: &mut String,
+        ) -> Result<()> {
+            let started = Instant::now();
+            let mut reminded = started;
+            let mut br = BufReader::new(stream);
+            loop {
+                if self.cancellation.is_cancelled() {
+                    return Err(InputError::Cancelled {
+                        name: self.name.clone(),
+                    }
+                    .into());
+                }
+                let remaining =
+                    input_timeout.map(|timeout| timeout.saturating_sub(started.elapsed()));
+                if remaining == Some(Duration::ZERO) {
+                    return Err(InputError::TimedOut {
+                        name: self.name.clone(),
+                        seconds: input_timeout.unwrap_or_default().as_secs(),
+                    }
+                    .into());
+                }
+                if self
+                    .remind_after
+                    .is_some_and(|remind_after| reminded.elapsed() >= remind_after)
+                {
+                    send_string(
+                        self,
+                        &format!("{} {}", text("input.still_waiting", &[]), message),
+                        "$Input",
+                    )?;
+                    reminded = Instant::now();
+                }
+                // The read is interrupted regularly to check if the game has been stopped
+                let wait = remaining.map_or(CANCELLATION_CHECK_INTERVAL, |remaining| {
+                    remaining.min(CANCELLATION_CHECK_INTERVAL)
+                });
+                stream.set_read_timeout(Some(wait)).into_diagnostic()?;
+                match br.read_line(buffer) {
+                    Ok(0) => {
+                        return Err(InputError::Closed {
+                            name: self.name.clone(),
+                        }
+                        .into())
+                    }
+                    Ok(_) => return Ok(()),
+                    // The time is checked at the start of the loop
+                    Err(err)
+                        if matches!(
+                            err.kind(),
+                            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                        ) => {}
+                    Err(err) => {
+                        return Err(miette!("Unable to send data to player, io error: {}", err))
+                    }
+                }
+            }
+        }
+
+        /// Executes the command when the input is one.
+        /// # Returns
+        /// * `Ok(true)` - The input was a command, the player should be asked again
+        /// * `Ok(false)` - The input is no command
+        fn handle_command(&self, input: &str) -> Result<bool> {
+            if BOARD_COMMANDS.contains(&input) {
+                match self.main_ui.is_empty() {
+                    true => self.print_text_ln("The board has not been shown yet.")?,
+                    false => self.print_text_ln(&self.main_ui)?,
+                }
+                return Ok(true);
+            }
+            if input != STATS_COMMAND {
+                return Ok(false);
+            }
+            if self.stats.is_empty() {
+                self.print_text_ln("No stats are available yet.")?;
+            } else {
+                self.print_text_ln(&self.stats.join("\n"))?;
+            }
+            Ok(true)
+        }
+
+        /// Shows what will happen to the stocks and the money of the player before they confirm a
+        /// transaction. In fast mode the lines are shown in one line.
+        fn print_stock_summary(&self, lines: &[String]) -> Result<()> {
+            match self.skip_dialogues {
+                true => self.print_text_ln(&lines.join(" | ")),
+                false => self.print_text_ln(&format!(
+                    "The following will happen to your stocks:\n{}",
+                    lines.join("\n")
+                )),
+            }
+        }
+
         /// Displayes the message `Is this correct? [Y/n]: ` to the player and returns if they
         /// pressed yes or no.
+        /// Confirmations are answered with yes in fast mode.
         pub fn get_correct(&self) -> Result<bool> {
-            match self.read_input(
-                String::from("Is this correct? [Y/n]: "),
-                vec!['Y', 'y', 'N', 'n'],
-            ) {
-                Ok(t) => match t {
-                    'Y' => Ok(true),
-                    'y' => Ok(true),
-                    'N' => Ok(false),
-                    'n' => Ok(false),
-                    _ => Ok(false),
-                },
-                Err(err) => Err(err),
+            if self.bot.is_some() || self.skip_dialogues {
+                return Ok(true);
             }
+            self.read_yes_no(text("input.confirm", &[]))
+        }
+
+        /// Asks the player the yes or no question and returns if they answered yes.
+        /// The keys of the current language are accepted, see [`yes_no_keys`].
+        pub fn read_yes_no(&self, prompt: String) -> Result<bool> {
+            self.read_input(prompt, yes_no_keys()).map(is_yes)
         }
 
         /// Prints the text to the player.
         /// A linebreak is written.
         pub fn print_text_ln(&self, text: &str) -> Result<()> {
+            if self.bot.is_some() {
+                // Bots do not read any text
+                return Ok(());
+            }
             if self.tcp_stream.is_none() {
-                // Player does not play fia lan
-                println!("{}", &text);
+                // Player does not play via lan
+                println!("{}", ui::console_text(text));
             } else {
-                // Player plays fia lan
-                if let Err(err) = send_string(self, text, "$Println") {
-                    return Err(err);
-                }
+                // Player plays via lan
+                send_string(self, text, "$Println")?;
             }
             Ok(())
         }
     }
-
-    /// Returns the player with the name if they exist.
-    pub fn player_by_name<'a>(name: &str, players: &'a [Player]) -> Option<&'a Player> {
-        for player in players {
-            if player.name == name {
-                return Some(player);
-            }
-        }
-        None
-    }
 }
 
 /// User interface drawing
 pub mod ui {
     use crate::{
-        base_game::{bank::Bank, board::Board, hotel_chains::HotelChain, settings::Settings},
-        game::{hotel_chain_manager::HotelChainManager, round::Round},
+        analysis::analyze_fusion,
+        base_game::{
+            bank::{Bank, ShareholderRank},
+            board::{Board, BoardStyle, Position},
+            hotel_chains::HotelChain,
+            settings::{Privacy, Settings},
+        },
+        bot::stocks_to_string,
+        evaluation::GameState,
+        game::{hotel_chain_manager::HotelChainManager, player_by_id, round::Round},
+        locale::text,
+        logic::place_hotel::PlaceHotelCase,
+        network::{client_message, main_ui_message, send_message},
+        playout::{Observation, Playout},
+        solver::solve_ending_turn,
+    };
+    use std::{
+        cmp::Ordering,
+        fmt::{self, Write},
+        sync::atomic::{AtomicBool, Ordering as AtomicOrdering},
     };
-    use miette::Result;
+
+    use miette::{miette, Result};
     use owo_colors::{AnsiColors, DynColors, OwoColorize, Rgb};
+    use serde_json::{Map, Value};
+
+    use super::player::Player;
+
+    /// Stores if the console shows plain text, see [`set_plain`]
+    static PLAIN: AtomicBool = AtomicBool::new(false);
+
+    /// Sets if the text that is printed to the console is shown without colors, shareholder
+    /// stars and box-drawing lines, so that screen readers and dumb terminals can be used.
+    pub fn set_plain(plain: bool) {
+        PLAIN.store(plain, AtomicOrdering::Relaxed);
+    }
+
+    /// Returns if the console shows plain text
+    pub fn is_plain() -> bool {
+        PLAIN.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Returns the text without ANSI escape codes, the double lines between the columns of the
+    /// tables are replaced by single ones.
+    pub fn plain_text(text: &str) -> String {
+        let mut plain = String::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1B' {
+                // The escape sequence ends with a letter
+                if chars.next() == Some('[') {
+                    for c in chars.by_ref() {
+                        if c.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+            plain.push(c);
+        }
+        plain.replace("||", "|")
+    }
+
+    /// Returns the text as it is printed to the console, see [`set_plain`]
+    pub fn console_text(text: &str) -> String {
+        match is_plain() {
+            true => plain_text(text),
+            false => text.to_string(),
+        }
+    }
+
+    /// Prints the main ui for every player.
+    /// If all players are on the same machine the ui is only printed once.
+    /// The stats and the main ui the players can show at any prompt are updated as well.
+    pub fn print_main_ui_players(
+        current_player_id: u32,
+        players: &mut [Player],
+        board: &Board,
+        settings: &Settings,
+        round: Option<&Round>,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Result<()> {
+        update_stats(players, bank, hotel_chain_manager);
+        let (console_ui, client_uis) = print_main_ui_console_players(
+            current_player_id,
+            players,
+            board,
+            settings,
+            round,
+            bank,
+            hotel_chain_manager,
+        )?;
+        if let Some(console_ui) = console_ui {
+            for player in players
+                .iter_mut()
+                .filter(|player| player.tcp_stream.is_none() && player.bot.is_none())
+            {
+                player.main_ui = console_ui.clone();
+            }
+        }
+        // The whole ui is sent to the client at once
+        for (index, frame, mut rest) in client_uis {
+            let player = &mut players[index];
+            let mut message = main_ui_message(player, &frame);
+            message.append(&mut rest);
+            send_message(player, &message)?;
+            player.main_ui = frame;
+        }
+        Ok(())
+    }
 
-    use super::player::{player_by_name, Player};
+    /// The main ui that has been printed on the console and the main uis of the players that play
+    /// via lan, see [`print_main_ui_console_players`]
+    type MainUis = (Option<String>, Vec<(usize, String, Vec<u8>)>);
 
-    /// Prints the main ui for every player.
-    /// If all players are on the same machine the ui is only printed once.
-    pub fn print_main_ui_players(
-        current_player_name: String,
+    /// Prints the main ui on the console and renders the main ui of the players that play via lan.
+    /// # Returns
+    /// The main ui that has been printed on the console, if any, and the index of each player
+    /// that plays via lan, their main ui and the message with the lines that are shown below it
+    fn print_main_ui_console_players(
+        current_player_id: u32,
         players: &[Player],
         board: &Board,
         settings: &Settings,
         round: Option<&Round>,
         bank: &Bank,
         hotel_chain_manager: &HotelChainManager,
-    ) -> Result<()> {
-        let mut written_to_console = false;
-        for player in players {
+    ) -> Result<MainUis> {
+        let current_player = player_by_id(current_player_id, players).ok_or_else(|| {
+            miette!(
+                "Unable to print main ui: No player with id {} exists",
+                current_player_id
+            )
+        })?;
+        let current_player_name = current_player.colored_name();
+        let mut console_ui = None;
+        // The main ui of every player that plays via lan is rendered into the same buffer
+        let mut frame = String::new();
+        let mut client_uis = Vec::new();
+        for (index, player) in players.iter().enumerate() {
             player.print_text_ln("")?;
             if all_players_local(players) {
-                let current_player = player_by_name(&current_player_name, players).unwrap();
-                print_main_ui_console(
+                console_ui = Some(print_main_ui_console(
                     Some(current_player),
                     Some(&current_player_name),
                     board,
@@ -2301,12 +4747,29 @@ pub mod ui {
                     round,
                     bank,
                     hotel_chain_manager,
-                );
-                written_to_console = true;
+                ));
+                for line in team_mates_ui(current_player, players) {
+                    println!("{}", console_text(&line));
+                }
+                for line in largest_shareholders_ui(current_player, players, bank) {
+                    println!("{}", console_text(&line));
+                }
+                for line in advisor_ui(
+                    current_player,
+                    current_player_id,
+                    players,
+                    board,
+                    settings,
+                    bank,
+                    hotel_chain_manager,
+                )? {
+                    println!("{}", console_text(&line));
+                }
             }
             if player.tcp_stream.is_none() {
-                if !written_to_console {
-                    print_main_ui_console(
+                // The console belongs to the host, the ui is never shown from the view of a bot
+                if console_ui.is_none() && player.bot.is_none() {
+                    console_ui = Some(print_main_ui_console(
                         Some(player),
                         Some(&current_player_name),
                         board,
@@ -2314,11 +4777,28 @@ pub mod ui {
                         round,
                         bank,
                         hotel_chain_manager,
-                    );
-                    written_to_console = true;
+                    ));
+                    for line in team_mates_ui(player, players) {
+                        println!("{}", console_text(&line));
+                    }
+                    for line in largest_shareholders_ui(player, players, bank) {
+                        println!("{}", console_text(&line));
+                    }
+                    for line in advisor_ui(
+                        player,
+                        current_player_id,
+                        players,
+                        board,
+                        settings,
+                        bank,
+                        hotel_chain_manager,
+                    )? {
+                        println!("{}", console_text(&line));
+                    }
                 }
             } else {
-                for line in main_ui(
+                main_ui(
+                    &mut frame,
                     Some(player),
                     Some(&current_player_name),
                     board,
@@ -2326,14 +4806,329 @@ pub mod ui {
                     round,
                     bank,
                     hotel_chain_manager,
-                ) {
-                    player.print_text_ln(&line)?;
+                );
+                let mut message = Vec::new();
+                if let Some(status) = shareholder_status(player, bank, hotel_chain_manager) {
+                    message.extend(client_message(player, &status, "$Shareholders"));
+                }
+                let mut lines = team_mates_ui(player, players);
+                lines.extend(largest_shareholders_ui(player, players, bank));
+                lines.extend(advisor_ui(
+                    player,
+                    current_player_id,
+                    players,
+                    board,
+                    settings,
+                    bank,
+                    hotel_chain_manager,
+                )?);
+                if !lines.is_empty() {
+                    message.extend(client_message(player, &lines.join("\n"), "$Println"));
                 }
+                let frame = frame.strip_suffix('\n').unwrap_or(&frame);
+                client_uis.push((index, String::from(frame), message));
             }
         }
+        Ok((console_ui, client_uis))
+    }
+
+    /// Clears the console and asks the player to take the keyboard.
+    /// The player has to press enter before the game continues.
+    ///
+    /// Used in hot-seat mode so that players do not see each others private information.
+    /// Nothing is done when the player plays via lan or is a bot.
+    pub fn hand_over(player: &Player) -> Result<()> {
+        if player.tcp_stream.is_some() || player.bot.is_some() {
+            return Ok(());
+        }
+        clear_console();
+        player.wait_for_enter(&text(
+            "hot_seat.hand_over",
+            &[&player.colored_name(), &player.name],
+        ))?;
+        clear_console();
         Ok(())
     }
 
+    /// Shows the text to the player and waits until they pressed enter.
+    ///
+    /// In hot-seat mode the console is cleared afterwards so that the next player that takes the
+    /// keyboard does not see the text. Used for information that only the player may learn, like
+    /// the cards they draw.
+    pub fn show_private(player: &Player, text: &str, prompt: &str, hot_seat: bool) -> Result<()> {
+        player.print_text_ln(text)?;
+        if hot_seat && player.tcp_stream.is_none() && player.bot.is_none() {
+            // The text has to be read before it is hidden, even in fast mode
+            player.wait_for_enter(prompt)?;
+            clear_console();
+            return Ok(());
+        }
+        player.get_enter(prompt)
+    }
+
+    /// Clears the console and moves the cursor to the top left.
+    ///
+    /// Dumb terminals do not understand the escape code, when the console shows plain text the
+    /// old text is scrolled out of view instead.
+    pub fn clear_console() {
+        match is_plain() {
+            true => print!("{}", "\n".repeat(100)),
+            false => print!("\x1B[2J\x1B[1;1H"),
+        }
+    }
+
+    /// Returns the stocks of the team mates of the player.
+    /// Empty when the game is not played in teams.
+    fn team_mates_ui(player: &Player, players: &[Player]) -> Vec<String> {
+        let mut ui = Vec::new();
+        for team_mate in players.iter().filter(|p| player.is_team_mate(p)) {
+            ui.push(format!(
+                "{} {}{} {}",
+                "Stocks of your team mate".bright_green(),
+                team_mate.colored_name(),
+                ":".bright_green(),
+                team_mate.stocks_ui()
+            ));
+        }
+        ui
+    }
+
+    /// Returns the names of the largest shareholders of each chain.
+    /// Empty when the privacy level of the player is not [`Privacy::Strict`].
+    fn largest_shareholders_ui(player: &Player, players: &[Player], bank: &Bank) -> Vec<String> {
+        let mut ui = Vec::new();
+        if player.privacy != Privacy::Strict {
+            return ui;
+        }
+        for chain in HotelChain::iterator() {
+            let names: Vec<String> = bank
+                .largest_shareholders
+                .largest(chain)
+                .iter()
+                .filter_map(|id| player_by_id(*id, players))
+                .map(|player| player.colored_name())
+                .collect();
+            if names.is_empty() {
+                continue;
+            }
+            ui.push(format!(
+                "Largest shareholder of {}: {}",
+                chain.name().color(chain.color()),
+                names.join(", ")
+            ));
+        }
+        ui
+    }
+
+    /// Returns how much each card of the player would change their position compared to the
+    /// strongest other player when it is played now. The positions are rated with
+    /// [`crate::evaluation::evaluate`], the money of the other players is not revealed.
+    ///
+    /// Empty when the advisor is disabled, the player is a bot or it is not their turn.
+    fn advisor_ui(
+        player: &Player,
+        current_player_id: u32,
+        players: &[Player],
+        board: &Board,
+        settings: &Settings,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Result<Vec<String>> {
+        let mut ui = Vec::new();
+        if !settings.advisor || player.bot.is_some() || player.id != current_player_id {
+            return Ok(ui);
+        }
+        let observation = Observation::new(player, board, players);
+        let current =
+            Playout::new(player, &observation, bank, hotel_chain_manager, 0).score(player.id);
+        let mut advice = Vec::new();
+        for card in &player.analyzed_cards {
+            if let PlaceHotelCase::Illegal(_) = card.place_hotel_case {
+                advice.push(format!("{} illegal", card.position));
+                continue;
+            }
+            let mut playout = Playout::new(player, &observation, bank, hotel_chain_manager, 0);
+            playout.place_card(card.position)?;
+            advice.push(format!(
+                "{} {:+}€",
+                card.position,
+                playout.score(player.id) - current
+            ));
+        }
+        if !advice.is_empty() {
+            ui.push(format!(
+                "{} {}",
+                "Advisor (change of your lead per card):".color(Rgb(105, 105, 105)),
+                advice.join(", ")
+            ));
+        }
+        if let Some(ending_turn) =
+            solve_ending_turn(player, &observation, bank, hotel_chain_manager)?
+        {
+            let hint = if ending_turn.wins() {
+                format!(
+                    "play {}, buy {} and end the game to lead by {}€",
+                    ending_turn.card,
+                    stocks_to_string(&ending_turn.stocks),
+                    ending_turn.lead
+                )
+            } else {
+                format!(
+                    "ending the game this turn would not make you the richest player (lead: {}€)",
+                    ending_turn.lead
+                )
+            };
+            ui.push(format!(
+                "{} {}",
+                "Advisor (ending the game this turn, the money of the others is assumed to equal yours):"
+                    .color(Rgb(105, 105, 105)),
+                hint
+            ));
+        }
+        Ok(ui)
+    }
+
+    /// Returns the what-if analysis of each card of the player that would fuse chains: who would
+    /// get which bonus and how the net worth of each player would change. The game is not
+    /// changed by the analysis.
+    ///
+    /// The net worth of the other players is only shown when the privacy level of the player is
+    /// not [`Privacy::Strict`]. Empty when the player is a bot or has no card that fuses chains.
+    pub fn fusion_analysis_ui(
+        player: &Player,
+        players: &[Player],
+        board: &Board,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Result<Vec<String>> {
+        let mut ui = Vec::new();
+        if player.bot.is_some() {
+            return Ok(ui);
+        }
+        let state = GameState::new(board, bank, hotel_chain_manager, players);
+        for card in &player.analyzed_cards {
+            if !matches!(card.place_hotel_case, PlaceHotelCase::Fusion(_, _)) {
+                continue;
+            }
+            for analysis in analyze_fusion(&card.position, &state)? {
+                let dead: Vec<String> = analysis
+                    .dead
+                    .iter()
+                    .map(|chain| chain.name().color(chain.color()).to_string())
+                    .collect();
+                ui.push(format!(
+                    "{} {}: {} survives, {} fused",
+                    "Fusion analysis for".color(Rgb(105, 105, 105)),
+                    card.position.color(AnsiColors::Green),
+                    analysis.alive.name().color(analysis.alive.color()),
+                    dead.join(", ")
+                ));
+                for outcome in &analysis.outcomes {
+                    let other = &players[outcome.player_id as usize];
+                    let bonuses: Vec<String> = outcome
+                        .bonuses
+                        .iter()
+                        .map(|(chain, bonus)| {
+                            format!("{}€ for {}", bonus, chain.name().color(chain.color()))
+                        })
+                        .collect();
+                    let mut line = format!("  {}: ", other.colored_name());
+                    if bonuses.is_empty() {
+                        line.push_str("no bonus");
+                    } else {
+                        line.push_str(&format!("bonus {}", bonuses.join(", ")));
+                    }
+                    if other.id == player.id || player.privacy != Privacy::Strict {
+                        line.push_str(&format!(", net worth {:+}€", outcome.net_worth_change));
+                    }
+                    ui.push(line);
+                }
+            }
+        }
+        Ok(ui)
+    }
+
+    /// Returns for each chain that can be founded its price level, the price of a stock and the
+    /// value of the founder bonus stocks when the chain is founded with the number of hotels.
+    pub fn founding_preview_ui(
+        number_of_hotels: u32,
+        chains: &[HotelChain],
+        bank: &Bank,
+    ) -> Vec<String> {
+        let mut ui = vec![text("chain.founding_preview", &[&number_of_hotels])];
+        for chain in chains {
+            let (price_level, price_level_color) = match chain.price_level() {
+                super::hotel_chains::PriceLevel::Low => ("L", AnsiColors::Red),
+                super::hotel_chains::PriceLevel::Medium => ("M", AnsiColors::Yellow),
+                super::hotel_chains::PriceLevel::High => ("H", AnsiColors::Green),
+            };
+            let price = chain.stock_value(number_of_hotels);
+            // The founder bonus is short when the bank has not enough stocks left
+            let bonus_stocks = bank
+                .rules
+                .founder_bonus_stocks
+                .min(*bank.stocks_for_sale.stocks_for_hotel(chain));
+            ui.push(format!(
+                "  {} {:12}[{}] {}",
+                chain.identifier().color(chain.color()),
+                chain.name().color(chain.color()),
+                price_level.color(price_level_color),
+                text("chain.founding_option", &[&price, &(price * bonus_stocks)])
+            ));
+        }
+        ui
+    }
+
+    /// Returns what happens when the player plays the card that fuses the chains: the number of
+    /// hotels of each chain, which chains are fused and whether the player is a majority
+    /// shareholder of the chains that can be fused.
+    pub fn fusion_warning_ui(
+        player: &Player,
+        position: &Position,
+        chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Vec<String> {
+        let mut chains = chains.to_vec();
+        chains.sort_by_key(|chain| std::cmp::Reverse(hotel_chain_manager.chain_length(chain)));
+        let largest = hotel_chain_manager.chain_length(&chains[0]);
+        // When the largest chains have the same size the player decides which one survives
+        let tie = chains
+            .iter()
+            .filter(|chain| hotel_chain_manager.chain_length(chain) == largest)
+            .count()
+            > 1;
+        let mut ui = vec![format!(
+            "{} {} fuses these chains:",
+            "Warning: Playing".color(AnsiColors::Yellow),
+            position.color(AnsiColors::Green)
+        )];
+        for chain in &chains {
+            let length = hotel_chain_manager.chain_length(chain);
+            let fate = match (length == largest, tie) {
+                (true, false) => "survives",
+                (true, true) => "survives or is fused, you decide",
+                (false, _) => "is fused",
+            };
+            let mut line = format!(
+                "  {}: {} hotels, {}",
+                chain.name().color(chain.color()),
+                length,
+                fate
+            );
+            if length < largest || tie {
+                match bank.shareholder_rank(player.id, chain, hotel_chain_manager) {
+                    Some(rank) => {
+                        line.push_str(&format!(", you are the {} shareholder", rank.name()))
+                    }
+                    None => line.push_str(", you are no majority shareholder"),
+                }
+            }
+            ui.push(line);
+        }
+        ui
+    }
+
     /// Checks if all playing players are playing on one pc
     fn all_players_local(players: &[Player]) -> bool {
         for player in players {
@@ -2344,7 +5139,28 @@ pub mod ui {
         true
     }
 
-    /// Prints the main ui to the console
+    /// The header of the table of the chains in the main ui, the line of `=` is left out when the
+    /// text is plain
+    const CHAIN_TABLE_HEADER: [&str; 3] = [
+        "               ||      Hotels       ||        Stocks          ||      Bonuses for the majority shareholders",
+        "               || Number ||  Range  || Bank || Owned || Value || Largest shareholder || Second largest shareholder",
+        "==================================================================================================================",
+    ];
+
+    /// The header of the table of the chains in the main ui when the privacy level is
+    /// [`Privacy::Strict`]
+    const STRICT_CHAIN_TABLE_HEADER: [&str; 2] = [
+        "               || Hotels ||  Range  || Value",
+        "==============================================",
+    ];
+
+    /// The color in which the values of the chains are shown
+    const VALUE_COLOR: DynColors = DynColors::Ansi(AnsiColors::White);
+
+    /// The color in which the chains that are not active are shown
+    const INACTIVE_COLOR: DynColors = DynColors::Rgb(105, 105, 105);
+
+    /// Prints the main ui to the console and returns it without the last line break
     pub fn print_main_ui_console(
         player: Option<&Player>,
         current_player_name: Option<&String>,
@@ -2353,8 +5169,10 @@ pub mod ui {
         round: Option<&Round>,
         bank: &Bank,
         hotel_chain_manager: &HotelChainManager,
-    ) {
-        let main_ui = main_ui(
+    ) -> String {
+        let mut frame = String::new();
+        main_ui(
+            &mut frame,
             player,
             current_player_name,
             board,
@@ -2363,13 +5181,15 @@ pub mod ui {
             bank,
             hotel_chain_manager,
         );
-        for line in main_ui {
-            println!("{}", line);
-        }
+        print!("{}", console_text(&frame));
+        frame.truncate(frame.trim_end_matches('\n').len());
+        frame
     }
 
-    /// Returns the main user interface.
+    /// Renders the main user interface into the buffer, the previous content of the buffer is
+    /// replaced. Every line ends with a line break.
     /// # Arguments
+    /// * `frame` - The buffer that is reused for each player to which the ui is shown
     /// * `player` - The player for which the money, cards and stocks should be displayed
     /// * `current_player_name` - The name of the player whos turn it is
     /// * `board` - The current game board
@@ -2377,9 +5197,9 @@ pub mod ui {
     /// * `round` - The current game round
     /// * `bank` - The bank of the game
     /// * `hotel_chain_manager` - The hotel chain manager of the game
-    /// # Returns
-    /// * `Vec<String>` - This vector contains the contents of the main ui
+    #[allow(clippy::too_many_arguments)]
     pub fn main_ui(
+        frame: &mut String,
         player: Option<&Player>,
         current_player_name: Option<&String>,
         board: &Board,
@@ -2387,143 +5207,523 @@ pub mod ui {
         round: Option<&Round>,
         bank: &Bank,
         hotel_chain_manager: &HotelChainManager,
-    ) -> Vec<String> {
-        let mut main_ui = Vec::new();
-        let small_board = if let Some(player) = player {
-            player.small_board
-        } else {
-            settings.small_board
-        };
-        for line in board.get_board_state(small_board) {
-            main_ui.push(line);
+    ) {
+        frame.clear();
+        write_main_ui(
+            frame,
+            player,
+            current_player_name,
+            board,
+            settings,
+            round,
+            bank,
+            hotel_chain_manager,
+        )
+        .expect("Writing to a string does not fail");
+    }
+
+    /// Writes the lines of the main ui, see [`main_ui`]
+    #[allow(clippy::too_many_arguments)]
+    fn write_main_ui(
+        frame: &mut String,
+        player: Option<&Player>,
+        current_player_name: Option<&String>,
+        board: &Board,
+        settings: &Settings,
+        round: Option<&Round>,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> fmt::Result {
+        let small_board = player.map_or(settings.small_board, |player| player.small_board);
+        let privacy = player.map_or(settings.privacy, |player| player.privacy);
+        let plain = player.map_or(settings.plain, |player| player.plain);
+        for line in board.render_console(BoardStyle::new(small_board, plain)) {
+            writeln!(frame, "{}", line)?;
         }
-        main_ui.push(String::new());
+        writeln!(frame)?;
         match round {
-            None => main_ui.push(String::from("Round 0 - Game has not been started yet")),
+            None => writeln!(frame, "Round 0 - Game has not been started yet")?,
             Some(round) => {
-                main_ui.push(format!("Round {}", round.number));
+                writeln!(frame, "Round {}", round.number)?;
                 match current_player_name {
-                    None => main_ui.push(String::from("Current player: None")),
-                    Some(name) => main_ui.push(format!("Current player: {}", name)),
+                    None => writeln!(frame, "Current player: None")?,
+                    Some(name) => writeln!(frame, "Current player: {}", name)?,
+                }
+                if let Some(remaining_cards) = round.remaining_cards {
+                    writeln!(frame, "Cards left in the draw pile: {}", remaining_cards)?;
                 }
                 match player {
-                    None => main_ui.push(String::from("Player unavailable")),
+                    None => writeln!(frame, "Player unavailable")?,
                     Some(player) => {
-                        main_ui.push(format!("{}, your status:", player.name));
+                        writeln!(frame, "{}, your status:", player.colored_name())?;
                         for line in player.player_ui() {
-                            main_ui.push(line);
+                            writeln!(frame, "{}", line)?;
+                        }
+                        if let Some(profit) = profit_ui(player, hotel_chain_manager) {
+                            writeln!(frame, "{}", profit)?;
                         }
                     }
                 };
             }
         };
-        main_ui.push(String::new());
-        main_ui.push(format!("{}", String::from("Game stats:").bright_green()));
-        main_ui.push(format!("{:15}||      Hotels       ||        Stocks          ||      Bonuses for the majority shareholders", ""));
-        main_ui.push(format!("{:15}|| Number ||  Range  || Bank || Owned || Value || Largest shareholder || Second largest shareholder", ""));
-        main_ui.push("==================================================================================================================".to_string());
-        for chain in HotelChain::iterator() {
-            // Set the color of the values
-            let enable_color = hotel_chain_manager.chain_status(chain);
-            let color = match enable_color {
-                true => DynColors::Ansi(AnsiColors::White),
-                false => DynColors::Rgb(105, 105, 105),
-            };
-            let chain_color = match enable_color {
-                true => chain.color(),
-                false => Rgb(105, 105, 105),
-            };
-            let player_stocks = match player {
-                None => 0_u32,
-                Some(player) => *player.owned_stocks.stocks_for_hotel(chain),
-            };
-            let formatted_string1 = format!(
-                "||   {:2}   || {:7} ||  {:2}  ||   {:2}",
-                hotel_chain_manager.chain_length(chain),
-                hotel_chain_manager.price_range(chain),
-                bank.stocks_available(chain, hotel_chain_manager),
-                player_stocks,
-            );
-            let formatted_string2 = format!(
-                " || {:4}€ ||        {:5}€       ||        {:5}€",
-                Bank::stock_price(hotel_chain_manager, chain),
-                Bank::stock_price(hotel_chain_manager, chain) * 10,
-                Bank::stock_price(hotel_chain_manager, chain) * 5,
-            );
-            let stock_status_symbol = match player {
-                None => String::from(" "),
-                Some(player) => stock_status_symbol(
-                    bank,
-                    hotel_chain_manager,
-                    chain,
-                    player.id,
-                    !settings.hide_extra_info,
-                ),
+        writeln!(frame)?;
+        writeln!(frame, "{}", "Game stats:".bright_green())?;
+        if privacy == Privacy::Strict {
+            // Only information that can be seen on the board is shown
+            let header = match plain {
+                true => &STRICT_CHAIN_TABLE_HEADER[..1],
+                false => &STRICT_CHAIN_TABLE_HEADER[..],
             };
-            let hotel_price_color = if !enable_color {
-                color
-            } else {
-                match chain.price_level() {
-                    super::hotel_chains::PriceLevel::Low => DynColors::Ansi(AnsiColors::Red),
-                    super::hotel_chains::PriceLevel::Medium => DynColors::Ansi(AnsiColors::Yellow),
-                    super::hotel_chains::PriceLevel::High => DynColors::Ansi(AnsiColors::Green),
-                }
+            for line in header {
+                writeln!(frame, "{}", line)?;
+            }
+            for chain in HotelChain::iterator() {
+                let color = match hotel_chain_manager.chain_status(chain) {
+                    true => VALUE_COLOR,
+                    false => INACTIVE_COLOR,
+                };
+                writeln!(
+                    frame,
+                    "{:15}{}",
+                    chain.name().color(chain.color()),
+                    format_args!(
+                        "||   {} || {:7} || {:4}€",
+                        chain_length_cell(hotel_chain_manager, chain),
+                        hotel_chain_manager.price_range(chain),
+                        Bank::stock_price(hotel_chain_manager, chain),
+                    )
+                    .color(color)
+                )?;
+            }
+        } else {
+            let header = match plain {
+                true => &CHAIN_TABLE_HEADER[..2],
+                false => &CHAIN_TABLE_HEADER[..],
             };
-            let hotel_price = match chain.price_level() {
-                super::hotel_chains::PriceLevel::Low => {
-                    if enable_color {
-                        format!("[{}]", "L".color(hotel_price_color))
-                    } else {
-                        format!("[{}]", "L").color(color).to_string()
+            for line in header {
+                writeln!(frame, "{}", line)?;
+            }
+            for chain in HotelChain::iterator() {
+                let active = hotel_chain_manager.chain_status(chain);
+                let (price_level, price_level_color) = match chain.price_level() {
+                    super::hotel_chains::PriceLevel::Low => ("L", AnsiColors::Red),
+                    super::hotel_chains::PriceLevel::Medium => ("M", AnsiColors::Yellow),
+                    super::hotel_chains::PriceLevel::High => ("H", AnsiColors::Green),
+                };
+                // Only the active chains are shown in their colors
+                let color = match active {
+                    true => {
+                        write!(
+                            frame,
+                            "{:12}[{}]",
+                            chain.name().color(chain.color()),
+                            price_level.color(price_level_color)
+                        )?;
+                        VALUE_COLOR
                     }
-                }
-                super::hotel_chains::PriceLevel::Medium => {
-                    if enable_color {
-                        format!("[{}]", "M".color(hotel_price_color))
-                    } else {
-                        format!("[{}]", "M").color(color).to_string()
+                    false => {
+                        write!(
+                            frame,
+                            "{}",
+                            format_args!("{:12}[{}]", chain.name(), price_level)
+                                .color(INACTIVE_COLOR)
+                        )?;
+                        INACTIVE_COLOR
                     }
+                };
+                let stock_status_symbol = match player {
+                    None => String::from(" "),
+                    Some(player) => stock_status_symbol(
+                        bank,
+                        hotel_chain_manager,
+                        chain,
+                        player.id,
+                        privacy == Privacy::Open,
+                        plain,
+                    ),
+                };
+                let price = Bank::stock_price(hotel_chain_manager, chain);
+                writeln!(
+                    frame,
+                    "{}{}{}",
+                    format_args!(
+                        "||   {} || {:7} ||  {:2}  ||   {:2}",
+                        chain_length_cell(hotel_chain_manager, chain),
+                        hotel_chain_manager.price_range(chain),
+                        bank.stocks_available(chain, hotel_chain_manager),
+                        player.map_or(0, |player| *player.owned_stocks.stocks_for_hotel(chain)),
+                    )
+                    .color(color),
+                    stock_status_symbol,
+                    format_args!(
+                        " || {:4}€ ||        {:5}€       ||        {:5}€",
+                        price,
+                        price * 10,
+                        price * 5,
+                    )
+                    .color(color),
+                )?;
+            }
+        }
+        if hotel_chain_manager
+            .active_chains()
+            .iter()
+            .any(|chain| hotel_chain_manager.is_chain_safe(chain))
+        {
+            writeln!(frame, "{}", SAFE_CHAIN_LEGEND)?;
+        }
+        if let Some(trend) = market_trend_ui(bank, hotel_chain_manager) {
+            writeln!(frame, "{}", trend)?;
+        }
+        Ok(())
+    }
+
+    /// Explains the marker that is shown beside the number of hotels of safe chains, see
+    /// [`chain_length_cell`]
+    const SAFE_CHAIN_LEGEND: &str =
+        "S: The chain is safe, it can no longer be fused into another chain";
+
+    /// Returns the number of hotels of the chain followed by `S` when the chain is safe.
+    /// The cell is always four characters wide.
+    fn chain_length_cell(hotel_chain_manager: &HotelChainManager, chain: &HotelChain) -> String {
+        let marker = match hotel_chain_manager.is_chain_safe(chain) {
+            true => "S",
+            false => " ",
+        };
+        format!("{:2} {}", hotel_chain_manager.chain_length(chain), marker)
+    }
+
+    /// Returns how the prices of the active chains have changed since the end of the last round.
+    /// Empty when no round has ended yet or no chain has been active at the end of the last
+    /// round.
+    fn market_trend_ui(bank: &Bank, hotel_chain_manager: &HotelChainManager) -> Option<String> {
+        let trends: Vec<String> = HotelChain::iterator()
+            .filter_map(|chain| {
+                let change = bank.market_history.change(chain, hotel_chain_manager)?;
+                let arrow = match change.cmp(&0) {
+                    Ordering::Greater => "↑",
+                    Ordering::Equal => "→",
+                    Ordering::Less => "↓",
+                };
+                Some(format!(
+                    "{} {}{}€",
+                    chain.name().color(chain.color()),
+                    arrow,
+                    Bank::stock_price(hotel_chain_manager, chain)
+                ))
+            })
+            .collect();
+        if trends.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "{} {}",
+            "Prices since the last round:".color(Rgb(105, 105, 105)),
+            trends.join(", ")
+        ))
+    }
+
+    /// Returns the profit the player has made with the stocks of each chain.
+    /// The stocks the player still owns of active chains are valued with the current price,
+    /// `None` is returned when the player has neither sold nor owns stocks.
+    fn profit_ui(player: &Player, hotel_chain_manager: &HotelChainManager) -> Option<String> {
+        let profits: Vec<String> = HotelChain::iterator()
+            .filter_map(|chain| {
+                let mut parts = Vec::new();
+                if hotel_chain_manager.chain_status(chain)
+                    && *player.owned_stocks.stocks_for_hotel(chain) > 0
+                {
+                    let price = Bank::stock_price(hotel_chain_manager, chain);
+                    parts.push(format!(
+                        "{:+}€ open",
+                        player.cost_basis.unrealized_profit(chain, price)
+                    ));
                 }
-                super::hotel_chains::PriceLevel::High => {
-                    if enable_color {
-                        format!("[{}]", "H".color(hotel_price_color))
-                    } else {
-                        format!("[{}]", "H").color(color).to_string()
-                    }
+                let realized = player.cost_basis.realized_profit(chain);
+                if realized != 0 {
+                    parts.push(format!("{:+}€ sold", realized));
                 }
-            };
-            main_ui.push(format!(
-                "{:12}{}{}{}{}",
-                chain.name().color(chain_color),
-                hotel_price,
-                formatted_string1.color(color),
-                stock_status_symbol,
-                formatted_string2.color(color),
+                if parts.is_empty() {
+                    return None;
+                }
+                Some(format!(
+                    "{}: {}",
+                    chain.name().color(chain.color()),
+                    parts.join(" / ")
+                ))
+            })
+            .collect();
+        if profits.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "{} {}",
+            String::from("Profit:").bright_green(),
+            profits.join(", ")
+        ))
+    }
+
+    /// Returns a table that shows for each player the profit they have made by selling the
+    /// stocks of each chain. Only chains with which a profit or loss has been made are shown.
+    pub fn profit_statistics_ui(players: &[Player]) -> Vec<String> {
+        let chains: Vec<HotelChain> = HotelChain::iterator()
+            .filter(|chain| {
+                players
+                    .iter()
+                    .any(|player| player.cost_basis.realized_profit(chain) != 0)
+            })
+            .copied()
+            .collect();
+        let mut header = format!("{:15}", "");
+        for chain in &chains {
+            header.push_str(&format!(" || {:>11}", chain.name().color(chain.color())));
+        }
+        header.push_str(" ||   Total");
+        let mut ui = vec![String::from("Profit with stocks:"), header];
+        for player in players {
+            let mut line = format!("{:15}", player.name.color(player.color));
+            for chain in &chains {
+                line.push_str(&format!(
+                    " || {:>10}€",
+                    format!("{:+}", player.cost_basis.realized_profit(chain))
+                ));
+            }
+            line.push_str(&format!(
+                " || {:>6}€",
+                format!("{:+}", player.cost_basis.total_realized_profit())
+            ));
+            ui.push(line);
+        }
+        ui
+    }
+
+    /// Updates the summary of the chains that each player can show with
+    /// [`STATS_COMMAND`](super::player::STATS_COMMAND).
+    pub fn update_stats(
+        players: &mut [Player],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) {
+        let stats: Vec<Vec<String>> = players
+            .iter()
+            .map(|player| stats_ui(player.privacy, players, bank, hotel_chain_manager))
+            .collect();
+        for (player, stats) in players.iter_mut().zip(stats) {
+            player.stats = stats;
+        }
+    }
+
+    /// Returns a table of the size, the price and the stocks left in the bank of each active
+    /// chain together with the names of the majority shareholders.
+    ///
+    /// The privacy level decides what is shown like in the main ui: With [`Privacy::Hidden`]
+    /// the shareholders are not shown, with [`Privacy::Strict`] only the largest shareholders
+    /// and no stocks are shown.
+    pub fn stats_ui(
+        privacy: Privacy,
+        players: &[Player],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Vec<String> {
+        let active_chains = hotel_chain_manager.active_chains();
+        if active_chains.is_empty() {
+            return vec![String::from("No chain has been founded yet.")];
+        }
+        // Returns the colored names and how many characters they take up in the console
+        let names = |ids: &[u32]| {
+            let players: Vec<&Player> = ids
+                .iter()
+                .filter_map(|id| player_by_id(*id, players))
+                .collect();
+            let width = players
+                .iter()
+                .map(|player| player.name.chars().count())
+                .sum::<usize>()
+                + players.len().saturating_sub(1) * 2;
+            let names = players
+                .iter()
+                .map(|player| player.colored_name())
+                .collect::<Vec<String>>()
+                .join(", ");
+            (names, width)
+        };
+        let mut ui = vec![format!("{}", String::from("Chain stats:").bright_green())];
+        ui.push(match privacy {
+            Privacy::Open => format!(
+                "{:15}|| Hotels || Value || Bank || Largest shareholder || Second largest shareholder",
+                ""
+            ),
+            Privacy::Hidden => format!("{:15}|| Hotels || Value || Bank", ""),
+            Privacy::Strict => format!("{:15}|| Hotels || Value || Largest shareholder", ""),
+        });
+        for chain in HotelChain::iterator().filter(|chain| active_chains.contains(chain)) {
+            let mut line = format!(
+                "{:15}||   {} || {:4}€",
+                chain.name().color(chain.color()),
+                chain_length_cell(hotel_chain_manager, chain),
+                Bank::stock_price(hotel_chain_manager, chain)
+            );
+            if privacy != Privacy::Strict {
+                line.push_str(&format!(
+                    " ||  {:2} ",
+                    bank.stocks_available(chain, hotel_chain_manager)
+                ));
+            }
+            let (largest, width) = names(bank.largest_shareholders.largest(chain));
+            match privacy {
+                Privacy::Open => line.push_str(&format!(
+                    " || {}{} || {}",
+                    largest,
+                    " ".repeat(19usize.saturating_sub(width)),
+                    names(bank.largest_shareholders.second_largest(chain)).0
+                )),
+                Privacy::Hidden => (),
+                Privacy::Strict => line.push_str(&format!(" || {}", largest)),
+            }
+            ui.push(line);
+        }
+        if active_chains
+            .iter()
+            .any(|chain| hotel_chain_manager.is_chain_safe(chain))
+        {
+            ui.push(String::from(SAFE_CHAIN_LEGEND));
+        }
+        ui
+    }
+
+    /// Returns a table that shows for each player how much money they have spent on stocks and
+    /// how much they have earned with stocks and bonuses.
+    pub fn money_statistics_ui(players: &[Player]) -> Vec<String> {
+        let mut ui = vec![
+            String::from("Where the money came from:"),
+            format!(
+                "{:15} || {:>19} || {:>19} || {:>7} || Founder stocks",
+                "", "Bought stocks", "Sold stocks", "Bonuses"
+            ),
+        ];
+        for player in players {
+            let statistics = &player.statistics;
+            ui.push(format!(
+                "{:15} || {:>19} || {:>19} || {:>6}€ || {}",
+                player.name.color(player.color),
+                format!(
+                    "{} for {}€",
+                    statistics.stocks_bought, statistics.money_spent
+                ),
+                format!(
+                    "{} for {}€",
+                    statistics.stocks_sold, statistics.money_earned
+                ),
+                statistics.bonuses,
+                statistics.founder_stocks
+            ));
+        }
+        ui
+    }
+
+    /// Returns a chart of the stock prices at the end of each round for every chain that has
+    /// been active during the game. Each round is one character, higher bars are higher prices.
+    pub fn price_chart_ui(bank: &Bank) -> Vec<String> {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let mut ui = Vec::new();
+        for chain in HotelChain::iterator() {
+            let prices = bank.market_history.prices(chain);
+            let active: Vec<u32> = prices.iter().flatten().copied().collect();
+            if active.is_empty() {
+                continue;
+            }
+            // Stock prices range from 200€ to 1200€
+            let chart: String = prices
+                .iter()
+                .map(|price| match price {
+                    Some(price) => BARS[(price.clamp(&200, &1200) - 200) as usize * 7 / 1000],
+                    None => ' ',
+                })
+                .collect();
+            ui.push(format!(
+                "{:15} {} {}€ - {}€",
+                chain.name().color(chain.color()),
+                chart,
+                active.iter().min().unwrap(),
+                active.iter().max().unwrap()
             ));
         }
-        main_ui
+        if !ui.is_empty() {
+            ui.insert(0, String::from("Stock prices at the end of each round:"));
+        }
+        ui
     }
 
     /// Used to display a little star that indicates if the player is largest or second largest
-    /// shareholder
+    /// shareholder. Plain text shows the marker of the rank instead.
     fn stock_status_symbol(
         bank: &Bank,
         hotel_manager: &HotelChainManager,
         chain: &HotelChain,
         player_id: u32,
         show_symbol: bool,
+        plain: bool,
     ) -> String {
-        if !hotel_manager.chain_status(chain) || !show_symbol {
-            return String::from(" ");
+        let rank = match show_symbol {
+            true => bank.shareholder_rank(player_id, chain, hotel_manager),
+            false => None,
+        };
+        match (rank, plain) {
+            (Some(rank), true) => format!(" {}", rank.marker()),
+            (Some(rank), false) => "*".color(rank.color()).to_string(),
+            (None, true) => String::from("    "),
+            (None, false) => String::from(" "),
         }
-        if bank.is_largest_shareholder(player_id, chain) {
-            return "*".color(Rgb(225, 215, 0)).to_string();
+    }
+
+    /// Returns the rank of the player among the shareholders of each active chain as json
+    /// object, the keys are the names of the chains. Chains in which the player is no
+    /// shareholder that is paid a bonus are left out.
+    ///
+    /// Sent to clients so that they know their shareholder stars. `None` when the stars are
+    /// hidden by the privacy level of the player.
+    pub fn shareholder_status(
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Option<String> {
+        if player.privacy != Privacy::Open {
+            return None;
+        }
+        let mut status = Map::new();
+        for chain in HotelChain::iterator() {
+            if let Some(rank) = bank.shareholder_rank(player.id, chain, hotel_chain_manager) {
+                status.insert(chain.name().to_string(), Value::from(rank.name()));
+            }
+        }
+        Some(Value::Object(status).to_string())
+    }
+
+    /// Returns the line that the client prints for the shareholder status it has received.
+    /// `None` when the status is invalid.
+    pub fn shareholder_status_ui(status: &str) -> Option<String> {
+        let status: Map<String, Value> = serde_json::from_str(status).ok()?;
+        let mut stars = Vec::new();
+        for chain in HotelChain::iterator() {
+            if let Some(rank) = status.get(chain.name()) {
+                let rank = ShareholderRank::from_name(rank.as_str()?)?;
+                let symbol = match is_plain() {
+                    true => rank.marker().to_string(),
+                    false => "*".color(rank.color()).to_string(),
+                };
+                stars.push(format!(
+                    "{} {} {}",
+                    chain.name().color(chain.color()),
+                    symbol,
+                    rank.name()
+                ));
+            }
         }
-        if bank.is_second_largest_shareholder(player_id, chain) {
-            return "*".color(Rgb(192, 192, 192)).to_string();
+        if stars.is_empty() {
+            return Some(String::from(
+                "Your shareholder status: no bonus for any chain",
+            ));
         }
-        // The star should probably be only displayed when a special terminal flag is set (mayber
-        // --info or something like that)
-        String::from(" ")
+        Some(format!("Your shareholder status: {}", stars.join(", ")))
     }
 }
@@ -0,0 +1,1690 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    slice::Iter,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use miette::Result;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rayon::prelude::*;
+use serde_json::Value;
+
+use crate::{
+    analysis::analyze_fusion,
+    base_game::{
+        bank::Bank,
+        board::{AnalyzedPosition, Board, Position},
+        hotel_chains::HotelChain,
+        player::Player,
+        settings::{Settings, PURCHASE_LIMIT},
+    },
+    evaluation::GameState,
+    game::hotel_chain_manager::HotelChainManager,
+    logic::place_hotel::PlaceHotelCase,
+    playout::{Observation, Playout},
+    solver::{solve_ending_turn, EndingTurn},
+};
+
+/// A decision of a bot and the reason why the bot has made it.
+pub struct Decision<T> {
+    /// What the bot has decided
+    pub choice: T,
+    /// A short explanation that is shown to the players, for example "bought 2 Imperial to take
+    /// the majority"
+    pub reason: String,
+}
+
+impl<T> Decision<T> {
+    pub fn new(choice: T, reason: impl Into<String>) -> Self {
+        Self {
+            choice,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A bot can take the seat of a player. Each time the player has to make a decision the bot is
+/// asked instead of reading the input from the console or from the client.
+///
+/// Each decision is returned together with a reason that is shown to the players.
+///
+/// The bot does not need to validate the game state, it is only asked when a decision can be made.
+///
+/// Bots are shared between the threads that play out moves, state that changes has to be stored
+/// in a [`Mutex`].
+pub trait Bot: Send + Sync {
+    /// Returns the position of the card that should be played.
+    ///
+    /// The player has at least one card that is not illegal, the returned card must not be illegal.
+    fn choose_card(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<Position>;
+
+    /// Returns the chain that should be founded.
+    ///
+    /// # Arguments
+    /// * `available_chains` - The chains that can be founded, contains at least one chain
+    fn choose_chain_to_found(
+        &self,
+        player: &Player,
+        available_chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain>;
+
+    /// Returns the chain that should survive when equally long chains are fused.
+    ///
+    /// # Arguments
+    /// * `chains` - The equally long chains, the returned chain has to be one of them
+    fn choose_surviving_chain(
+        &self,
+        player: &Player,
+        chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain>;
+
+    /// Returns how many stocks of the dead chain should be exchanged and how many should be sold.
+    /// The remaining stocks are kept.
+    ///
+    /// The number of exchanged stocks has to be even and the bank needs to have enough stocks of
+    /// the surviving chain left.
+    fn handle_fusion_stocks(
+        &self,
+        player: &Player,
+        dead: &HotelChain,
+        alive: &HotelChain,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<(u32, u32)>;
+
+    /// Returns the stocks that should be bought.
+    ///
+    /// The player has to be able to pay for the stocks and the purchase limit of the bank rules
+    /// has to be honored.
+    fn buy_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>>;
+
+    /// Returns the stocks that should be sold before new stocks are bought.
+    /// Is only asked when the house rule is active.
+    fn sell_stocks(
+        &self,
+        _player: &Player,
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        Decision::new(HashMap::new(), "kept all stocks")
+    }
+
+    /// Returns true if the game should be ended now. Is only asked when a game ending condition
+    /// is met.
+    fn end_game(
+        &self,
+        player: &Player,
+        players: &[Player],
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<bool>;
+
+    /// Returns true if the hand cards should be redrawn when only cards are left that can not be
+    /// played because the fusion would be illegal.
+    fn redraw_cards(&self, _player: &Player) -> Decision<bool> {
+        Decision::new(true, "redrew the cards because none of them can be played")
+    }
+
+    /// Is called before the bot has to choose a card and before it has to buy stocks. The bot
+    /// can use it to remember the parts of the game that are not passed to the other functions.
+    fn observe(&self, _player: &Player, _board: &Board, _players: &[Player]) {}
+
+    /// Is called before each decision when the game is played as [`Playout`]. The state is the
+    /// game as the player sees it, like it is stored in replays. It is only created when the bot
+    /// calls the function, bots that are controlled from outside of the game use it to show the
+    /// game to their player.
+    fn observe_state(&self, _state: &dyn Fn() -> Value) {}
+}
+
+/// The different strategies a bot can use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BotStrategy {
+    /// The bot chooses a random legal action, see [`RandomBot`]
+    Random,
+    /// The bot does what seems to be the best in the current moment, see [`GreedyBot`]
+    #[default]
+    Greedy,
+    /// The bot fights for the majorities of the chains, see [`HeuristicBot`]
+    Heuristic,
+    /// The bot tries out its options with random playouts, see [`MonteCarloBot`]
+    MonteCarlo,
+}
+
+impl BotStrategy {
+    /// Returns the bot strategy with the name.
+    /// If no strategy with that name exists `None` is returned.
+    pub fn from_name(name: &str) -> Option<BotStrategy> {
+        match name {
+            "random" => Some(BotStrategy::Random),
+            "greedy" => Some(BotStrategy::Greedy),
+            "heuristic" => Some(BotStrategy::Heuristic),
+            "monte-carlo" => Some(BotStrategy::MonteCarlo),
+            _ => None,
+        }
+    }
+
+    /// Returns the bot strategy that belongs to the difficulty.
+    /// If no difficulty with that name exists `None` is returned.
+    ///
+    /// * `easy` - [`BotStrategy::Random`]
+    /// * `normal` - [`BotStrategy::Greedy`]
+    /// * `hard` - [`BotStrategy::MonteCarlo`]
+    pub fn from_difficulty(difficulty: &str) -> Option<BotStrategy> {
+        match difficulty {
+            "easy" => Some(BotStrategy::Random),
+            "normal" => Some(BotStrategy::Greedy),
+            "hard" => Some(BotStrategy::MonteCarlo),
+            _ => None,
+        }
+    }
+
+    /// Returns all strategies
+    pub fn iterator() -> Iter<'static, BotStrategy> {
+        const STRATEGIES: [BotStrategy; 4] = [
+            BotStrategy::Random,
+            BotStrategy::Greedy,
+            BotStrategy::Heuristic,
+            BotStrategy::MonteCarlo,
+        ];
+        STRATEGIES.iter()
+    }
+
+    /// Returns the name of the strategy
+    pub fn name(&self) -> &'static str {
+        match self {
+            BotStrategy::Random => "random",
+            BotStrategy::Greedy => "greedy",
+            BotStrategy::Heuristic => "heuristic",
+            BotStrategy::MonteCarlo => "monte-carlo",
+        }
+    }
+
+    /// Creates a new bot that uses this strategy.
+    /// Bots that make random decisions use the seed, the same seed leads to the same decisions.
+    /// The personality is used by the heuristic and the Monte Carlo bots.
+    pub fn create(
+        &self,
+        personality: BotPersonality,
+        settings: &Settings,
+        seed: u64,
+    ) -> Box<dyn Bot> {
+        match self {
+            BotStrategy::Random => Box::new(RandomBot::new(seed)),
+            BotStrategy::Greedy => Box::new(GreedyBot),
+            BotStrategy::Heuristic => Box::new(HeuristicBot::new(personality)),
+            BotStrategy::MonteCarlo => Box::new(MonteCarloBot::new(
+                personality,
+                settings.bot_playouts,
+                settings
+                    .bot_time
+                    .map(|millis| Duration::from_millis(millis as u64)),
+                seed,
+            )),
+        }
+    }
+}
+
+/// The personality of a bot. It changes how much risk the bot takes, how much money it spends and
+/// how eager it is to fuse chains.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BotPersonality {
+    /// The bot has no special preferences
+    #[default]
+    Balanced,
+    /// The bot avoids risks and keeps some money
+    Cautious,
+    /// The bot founds chains it can not hold and spends all its money
+    Gambler,
+    /// The bot keeps most of its money and only buys stocks to win majorities
+    Hoarder,
+    /// The bot fuses chains whenever it can get a bonus
+    Shark,
+}
+
+impl BotPersonality {
+    /// Returns the personality with the name.
+    /// If no personality with that name exists `None` is returned.
+    pub fn from_name(name: &str) -> Option<BotPersonality> {
+        match name {
+            "balanced" => Some(BotPersonality::Balanced),
+            "cautious" => Some(BotPersonality::Cautious),
+            "gambler" => Some(BotPersonality::Gambler),
+            "hoarder" => Some(BotPersonality::Hoarder),
+            "shark" => Some(BotPersonality::Shark),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the personality
+    pub fn name(&self) -> &'static str {
+        match self {
+            BotPersonality::Balanced => "balanced",
+            BotPersonality::Cautious => "cautious",
+            BotPersonality::Gambler => "gambler",
+            BotPersonality::Hoarder => "hoarder",
+            BotPersonality::Shark => "shark",
+        }
+    }
+
+    /// Returns the traits that belong to this personality
+    pub fn traits(&self) -> PersonalityTraits {
+        let (risk_tolerance, spending, merger_aggressiveness) = match self {
+            BotPersonality::Balanced => (50, 50, 50),
+            BotPersonality::Cautious => (20, 40, 30),
+            BotPersonality::Gambler => (90, 90, 60),
+            BotPersonality::Hoarder => (40, 25, 50),
+            BotPersonality::Shark => (60, 70, 90),
+        };
+        PersonalityTraits {
+            risk_tolerance,
+            spending,
+            merger_aggressiveness,
+        }
+    }
+}
+
+/// The traits of a personality, each trait is a value between 0 and 100. A value of 50 is the
+/// behaviour of a bot without special preferences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PersonalityTraits {
+    /// How willing the bot is to found chains in which it can not hold the majority
+    pub risk_tolerance: u32,
+    /// How much of its money the bot spends on stocks. Below 50 the bot keeps a part of its
+    /// money, above 50 it also buys stocks of safe chains as investment.
+    pub spending: u32,
+    /// How much the bot values the bonuses it gets when it fuses chains
+    pub merger_aggressiveness: u32,
+}
+
+/// A bot that always does what seems to be the best in the current moment.
+///
+/// It founds chains when possible, extends chains in which it is the largest shareholder and
+/// buys the cheapest stocks of chains that can still grow.
+pub struct GreedyBot;
+
+impl Bot for GreedyBot {
+    fn choose_card(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<Position> {
+        let (card, score) = player
+            .analyzed_cards
+            .iter()
+            .filter(|card| !card.is_illegal())
+            .map(|card| {
+                let score = match &card.place_hotel_case {
+                    PlaceHotelCase::NewChain(_) => 3,
+                    PlaceHotelCase::ExtendsChain(chain, _) => {
+                        if bank.is_largest_shareholder(player.id, chain) {
+                            2
+                        } else {
+                            0
+                        }
+                    }
+                    PlaceHotelCase::Fusion(chains, _) => {
+                        // A fusion is good when the player gets a bonus for one of the chains
+                        if chains.iter().any(|chain| {
+                            bank.is_largest_shareholder(player.id, chain)
+                                || bank.is_second_largest_shareholder(player.id, chain)
+                        }) {
+                            2
+                        } else {
+                            0
+                        }
+                    }
+                    _ => 1,
+                };
+                (card, score)
+            })
+            .max_by_key(|(_, score)| *score)
+            .unwrap();
+        let motive = match score {
+            3 => "to get the founder bonus",
+            2 => "to profit as shareholder",
+            _ => "because no other card helps more",
+        };
+        Decision::new(
+            card.position,
+            format!(
+                "{} {}",
+                card_effect(card.position, &card.place_hotel_case),
+                motive
+            ),
+        )
+    }
+
+    fn choose_chain_to_found(
+        &self,
+        player: &Player,
+        available_chains: &[HotelChain],
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        // Prefer chains of which the player already owns stocks, then the most valuable chain
+        let chain = *available_chains
+            .iter()
+            .max_by_key(|chain| {
+                (
+                    *player.owned_stocks.stocks_for_hotel(chain),
+                    chain.stock_value(2),
+                )
+            })
+            .unwrap();
+        if *player.owned_stocks.stocks_for_hotel(&chain) > 0 {
+            Decision::new(
+                chain,
+                format!("founded {} because it already owns stocks of it", chain),
+            )
+        } else {
+            Decision::new(
+                chain,
+                format!("founded {} because it is the most valuable chain", chain),
+            )
+        }
+    }
+
+    fn choose_surviving_chain(
+        &self,
+        player: &Player,
+        chains: &[HotelChain],
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        let chain = *chains
+            .iter()
+            .max_by_key(|chain| *player.owned_stocks.stocks_for_hotel(chain))
+            .unwrap();
+        Decision::new(
+            chain,
+            format!(
+                "let {} survive because it owns the most stocks of it",
+                chain
+            ),
+        )
+    }
+
+    fn handle_fusion_stocks(
+        &self,
+        player: &Player,
+        dead: &HotelChain,
+        alive: &HotelChain,
+        bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<(u32, u32)> {
+        // Exchange as many stocks as possible to strengthen the position in the surviving chain
+        let exchange = max_exchange(player, dead, alive, bank);
+        let sell = player.owned_stocks.stocks_for_hotel(dead) - exchange;
+        Decision::new(
+            (exchange, sell),
+            fusion_stocks_reason(exchange, sell, dead, alive),
+        )
+    }
+
+    fn buy_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        let mut stocks_bought: HashMap<HotelChain, u32> = HashMap::new();
+        let mut money_available = player.money;
+        for _i in 0..PURCHASE_LIMIT {
+            // Prefer chains in which the player already owns stocks, then chains that are not
+            // safe and then the cheapest stock
+            let chain = HotelChain::iterator()
+                .filter(|chain| {
+                    let bought = stocks_bought.get(chain).copied().unwrap_or(0);
+                    *bank.stocks_available(chain, hotel_chain_manager) > bought
+                        && Bank::stock_price(hotel_chain_manager, chain) <= money_available
+                })
+                .min_by_key(|chain| {
+                    (
+                        *player.owned_stocks.stocks_for_hotel(chain) == 0,
+                        hotel_chain_manager.is_chain_safe(chain),
+                        Bank::stock_price(hotel_chain_manager, chain),
+                    )
+                });
+            match chain {
+                Some(chain) => {
+                    money_available -= Bank::stock_price(hotel_chain_manager, chain);
+                    *stocks_bought.entry(*chain).or_insert(0) += 1;
+                }
+                None => break,
+            }
+        }
+        let reason = if stocks_bought.is_empty() {
+            String::from("bought no stocks because it can not afford any")
+        } else {
+            format!(
+                "bought {} because they are the cheapest stocks it is invested in",
+                stocks_to_string(&stocks_bought)
+            )
+        };
+        Decision::new(stocks_bought, reason)
+    }
+
+    fn end_game(
+        &self,
+        player: &Player,
+        players: &[Player],
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<bool> {
+        // The game is ended when the player is currently the richest player
+        let own_worth = net_worth(player, hotel_chain_manager);
+        if players
+            .iter()
+            .all(|other| net_worth(other, hotel_chain_manager) <= own_worth)
+        {
+            Decision::new(true, "ended the game because it is the richest player")
+        } else {
+            Decision::new(false, "continued the game because another player is richer")
+        }
+    }
+}
+
+/// A bot that picks uniformly among the legal actions.
+///
+/// It is not meant to win but to exercise the game engine and to fill seats for stress tests.
+pub struct RandomBot {
+    rng: Mutex<StdRng>,
+}
+
+impl RandomBot {
+    /// Creates a new random bot. The same seed leads to the same decisions.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl Bot for RandomBot {
+    fn choose_card(
+        &self,
+        player: &Player,
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<Position> {
+        let legal_cards: Vec<Position> = player
+            .analyzed_cards
+            .iter()
+            .filter(|card| !card.is_illegal())
+            .map(|card| card.position)
+            .collect();
+        let card = *legal_cards.choose(&mut *self.rng.lock().unwrap()).unwrap();
+        Decision::new(card, format!("played {} at random", card))
+    }
+
+    fn choose_chain_to_found(
+        &self,
+        _player: &Player,
+        available_chains: &[HotelChain],
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        let chain = *available_chains
+            .choose(&mut *self.rng.lock().unwrap())
+            .unwrap();
+        Decision::new(chain, format!("founded {} at random", chain))
+    }
+
+    fn choose_surviving_chain(
+        &self,
+        _player: &Player,
+        chains: &[HotelChain],
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        let chain = *chains.choose(&mut *self.rng.lock().unwrap()).unwrap();
+        Decision::new(chain, format!("let {} survive at random", chain))
+    }
+
+    fn handle_fusion_stocks(
+        &self,
+        player: &Player,
+        dead: &HotelChain,
+        alive: &HotelChain,
+        bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<(u32, u32)> {
+        let mut rng = self.rng.lock().unwrap();
+        let exchange = rng.gen_range(0..=max_exchange(player, dead, alive, bank) / 2) * 2;
+        let sell = rng.gen_range(0..=player.owned_stocks.stocks_for_hotel(dead) - exchange);
+        Decision::new(
+            (exchange, sell),
+            format!(
+                "{} at random",
+                fusion_stocks_reason(exchange, sell, dead, alive)
+            ),
+        )
+    }
+
+    fn buy_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        let mut rng = self.rng.lock().unwrap();
+        let mut stocks_bought: HashMap<HotelChain, u32> = HashMap::new();
+        let mut money_available = player.money;
+        for _i in 0..rng.gen_range(0..=PURCHASE_LIMIT) {
+            let purchasable: Vec<&HotelChain> = HotelChain::iterator()
+                .filter(|chain| {
+                    let bought = stocks_bought.get(chain).copied().unwrap_or(0);
+                    *bank.stocks_available(chain, hotel_chain_manager) > bought
+                        && Bank::stock_price(hotel_chain_manager, chain) <= money_available
+                })
+                .collect();
+            match purchasable.choose(&mut *rng) {
+                Some(chain) => {
+                    money_available -= Bank::stock_price(hotel_chain_manager, chain);
+                    *stocks_bought.entry(**chain).or_insert(0) += 1;
+                }
+                None => break,
+            }
+        }
+        let reason = format!("bought {} at random", stocks_to_string(&stocks_bought));
+        Decision::new(stocks_bought, reason)
+    }
+
+    fn sell_stocks(
+        &self,
+        player: &Player,
+        _bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        let mut rng = self.rng.lock().unwrap();
+        let mut stocks_sold = HashMap::new();
+        for chain in hotel_chain_manager.active_chains() {
+            let sold = rng.gen_range(0..=*player.owned_stocks.stocks_for_hotel(&chain));
+            if sold > 0 {
+                stocks_sold.insert(chain, sold);
+            }
+        }
+        let reason = format!("sold {} at random", stocks_to_string(&stocks_sold));
+        Decision::new(stocks_sold, reason)
+    }
+
+    fn end_game(
+        &self,
+        _player: &Player,
+        _players: &[Player],
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<bool> {
+        if self.rng.lock().unwrap().gen_bool(0.5) {
+            Decision::new(true, "ended the game at random")
+        } else {
+            Decision::new(false, "continued the game at random")
+        }
+    }
+
+    fn redraw_cards(&self, _player: &Player) -> Decision<bool> {
+        if self.rng.lock().unwrap().gen_bool(0.5) {
+            Decision::new(true, "redrew the cards at random")
+        } else {
+            Decision::new(false, "kept the cards at random")
+        }
+    }
+}
+
+/// A bot that keeps track of the shareholder standings.
+///
+/// It buys stocks to defend or steal majorities of chains that can still be fused, plays cards
+/// that lead to fusions in which it gets a bonus and avoids founding chains it can not hold.
+/// How much it weights these goals depends on its [`BotPersonality`].
+pub struct HeuristicBot {
+    traits: PersonalityTraits,
+    /// The game as the bot has last seen it, used to analyze the fusions it can start
+    observation: Mutex<Option<Observation>>,
+}
+
+impl HeuristicBot {
+    pub fn new(personality: BotPersonality) -> Self {
+        Self {
+            traits: personality.traits(),
+            observation: Mutex::new(None),
+        }
+    }
+
+    /// Returns the bonuses the player gets when the card is played. The fusion is analyzed with
+    /// [`analyze_fusion`] when the bot has observed the game, otherwise the bonuses are estimated
+    /// from the current shareholders. When multiple chains can survive the best one for the
+    /// player is assumed.
+    fn fusion_bonus(
+        &self,
+        player: &Player,
+        card: &Position,
+        chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> u32 {
+        if let Some(observation) = self.observation.lock().unwrap().as_ref() {
+            let state = GameState::new(
+                &observation.board,
+                bank,
+                hotel_chain_manager,
+                &observation.players,
+            );
+            if let Ok(analyses) = analyze_fusion(card, &state) {
+                return analyses
+                    .iter()
+                    .map(|analysis| analysis.outcomes[player.id as usize].total_bonus())
+                    .max()
+                    .unwrap_or(0);
+            }
+        }
+        // The longest chain survives, all other chains pay their bonuses
+        let surviving_chain = chains
+            .iter()
+            .max_by_key(|chain| hotel_chain_manager.chain_length(chain))
+            .unwrap();
+        chains
+            .iter()
+            .filter(|chain| *chain != surviving_chain)
+            .map(|chain| expected_bonus(player, chain, bank, hotel_chain_manager))
+            .sum()
+    }
+
+    /// Returns true if the player can become the largest shareholder of the chain when they
+    /// found it now.
+    fn can_hold(&self, player: &Player, chain: &HotelChain, bank: &Bank) -> bool {
+        let price = chain.stock_value(2);
+        let affordable = (player.money / price).min(PURCHASE_LIMIT);
+        let stocks = *player.owned_stocks.stocks_for_hotel(chain)
+            + bank.rules.founder_bonus_stocks
+            + affordable;
+        stocks
+            > bank
+                .rival_stocks(player.id, chain)
+                .first()
+                .copied()
+                .unwrap_or(0)
+    }
+
+    /// Returns how much the card is worth for the player and why. Higher is better.
+    fn card_score(
+        &self,
+        player: &Player,
+        card: &AnalyzedPosition,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> (i64, &'static str) {
+        match &card.place_hotel_case {
+            PlaceHotelCase::NewChain(_) => {
+                let available_chains = hotel_chain_manager.available_chains().unwrap_or_default();
+                if available_chains
+                    .iter()
+                    .any(|chain| self.can_hold(player, chain, bank))
+                {
+                    (50, "because it can hold the majority of the new chain")
+                } else {
+                    (
+                        -10 + (self.traits.risk_tolerance as i64 - 50) / 2,
+                        "although it can not hold the majority of the new chain",
+                    )
+                }
+            }
+            PlaceHotelCase::ExtendsChain(chain, _) => {
+                let lead = majority_lead(player, chain, bank);
+                if *player.owned_stocks.stocks_for_hotel(chain) == 0 {
+                    (-5, "although it owns no stocks of the chain")
+                } else if lead > 0 {
+                    (20, "to raise the value of its majority")
+                } else {
+                    (5, "to raise the value of its stocks")
+                }
+            }
+            PlaceHotelCase::Fusion(chains, _) => {
+                let bonus =
+                    self.fusion_bonus(player, &card.position, chains, bank, hotel_chain_manager);
+                if bonus == 0 {
+                    (-5, "although it gets no shareholder bonus")
+                } else {
+                    (
+                        bonus as i64 * self.traits.merger_aggressiveness as i64 / 5000,
+                        "to collect a shareholder bonus",
+                    )
+                }
+            }
+            _ => (0, "because no other card helps more"),
+        }
+    }
+
+    /// Returns how important it is to buy another stock of the chain and why. Stocks with a
+    /// priority of 0 are not bought.
+    ///
+    /// # Arguments
+    /// * `bought` - The stocks of the chain that are already planned to be bought
+    /// * `stocks_left` - The number of stocks that can still be bought this turn
+    fn buy_priority(
+        &self,
+        player: &Player,
+        chain: &HotelChain,
+        bought: u32,
+        stocks_left: u32,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> (u32, &'static str) {
+        let own = *player.owned_stocks.stocks_for_hotel(chain) + bought;
+        let rival_stocks = bank.rival_stocks(player.id, chain);
+        let largest_rival = rival_stocks.first().copied().unwrap_or(0);
+        let second_rival = rival_stocks.get(1).copied().unwrap_or(0);
+        if hotel_chain_manager.is_chain_safe(chain) {
+            // Safe chains are not fused anymore, stocks are only bought to keep a majority
+            return if own >= largest_rival {
+                (1, "to keep the majority")
+            } else if self.traits.spending > 50 {
+                (1, "as investment")
+            } else {
+                (0, "")
+            };
+        }
+        if own > largest_rival && own - largest_rival <= 1 {
+            (30, "to defend the majority")
+        } else if own <= largest_rival && largest_rival - own < stocks_left {
+            (25, "to take the majority")
+        } else if own <= second_rival && second_rival - own < stocks_left {
+            (15, "to become second largest shareholder")
+        } else if self.traits.spending >= 50 {
+            (2, "as investment")
+        } else {
+            (0, "")
+        }
+    }
+}
+
+impl Bot for HeuristicBot {
+    fn choose_card(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<Position> {
+        let (card, (_, motive)) = player
+            .analyzed_cards
+            .iter()
+            .filter(|card| !card.is_illegal())
+            .map(|card| {
+                (
+                    card,
+                    self.card_score(player, card, bank, hotel_chain_manager),
+                )
+            })
+            .max_by_key(|(_, (score, _))| *score)
+            .unwrap();
+        Decision::new(
+            card.position,
+            format!(
+                "{} {}",
+                card_effect(card.position, &card.place_hotel_case),
+                motive
+            ),
+        )
+    }
+
+    fn choose_chain_to_found(
+        &self,
+        player: &Player,
+        available_chains: &[HotelChain],
+        bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        let chain = *available_chains
+            .iter()
+            .max_by_key(|chain| {
+                (
+                    self.can_hold(player, chain, bank),
+                    majority_lead(player, chain, bank),
+                    chain.stock_value(2),
+                )
+            })
+            .unwrap();
+        if self.can_hold(player, &chain, bank) {
+            Decision::new(
+                chain,
+                format!("founded {} because it can hold the majority", chain),
+            )
+        } else {
+            Decision::new(
+                chain,
+                format!("founded {} because it is the most valuable chain", chain),
+            )
+        }
+    }
+
+    fn choose_surviving_chain(
+        &self,
+        player: &Player,
+        chains: &[HotelChain],
+        bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        let chain = *chains
+            .iter()
+            .max_by_key(|chain| majority_lead(player, chain, bank))
+            .unwrap();
+        Decision::new(
+            chain,
+            format!(
+                "let {} survive because it has the largest lead there",
+                chain
+            ),
+        )
+    }
+
+    fn handle_fusion_stocks(
+        &self,
+        player: &Player,
+        dead: &HotelChain,
+        alive: &HotelChain,
+        bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<(u32, u32)> {
+        // Stocks are only exchanged when they help to hold the majority of the surviving chain,
+        // otherwise the money is more useful for the next majority races
+        let exchange = max_exchange(player, dead, alive, bank);
+        let own = *player.owned_stocks.stocks_for_hotel(alive) + exchange / 2;
+        let largest_rival = bank
+            .rival_stocks(player.id, alive)
+            .first()
+            .copied()
+            .unwrap_or(0);
+        let (exchange, motive) = if own >= largest_rival {
+            (exchange, "to hold the majority of the surviving chain")
+        } else {
+            (0, "to have money for the next majorities")
+        };
+        let sell = player.owned_stocks.stocks_for_hotel(dead) - exchange;
+        Decision::new(
+            (exchange, sell),
+            format!(
+                "{} {}",
+                fusion_stocks_reason(exchange, sell, dead, alive),
+                motive
+            ),
+        )
+    }
+
+    fn buy_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        let mut stocks_bought: HashMap<HotelChain, u32> = HashMap::new();
+        // The reason why the first stock of each chain has been bought
+        let mut motives: Vec<(HotelChain, &str)> = Vec::new();
+        // Bots that hoard their money only spend a part of it
+        let mut money_available = player.money * self.traits.spending.min(50) / 50;
+        for i in 0..PURCHASE_LIMIT {
+            let stocks_left = PURCHASE_LIMIT - i;
+            // Small chains are preferred because they are fused sooner
+            let chain = HotelChain::iterator()
+                .filter(|chain| {
+                    let bought = stocks_bought.get(chain).copied().unwrap_or(0);
+                    *bank.stocks_available(chain, hotel_chain_manager) > bought
+                        && Bank::stock_price(hotel_chain_manager, chain) <= money_available
+                })
+                .map(|chain| {
+                    let bought = stocks_bought.get(chain).copied().unwrap_or(0);
+                    let priority = self.buy_priority(
+                        player,
+                        chain,
+                        bought,
+                        stocks_left,
+                        bank,
+                        hotel_chain_manager,
+                    );
+                    (chain, priority)
+                })
+                .filter(|(_, (priority, _))| *priority > 0)
+                .max_by_key(|(chain, (priority, _))| {
+                    (*priority, Reverse(hotel_chain_manager.chain_length(chain)))
+                });
+            match chain {
+                Some((chain, (_, motive))) => {
+                    money_available -= Bank::stock_price(hotel_chain_manager, chain);
+                    *stocks_bought.entry(*chain).or_insert(0) += 1;
+                    if !motives.iter().any(|(other, _)| other == chain) {
+                        motives.push((*chain, motive));
+                    }
+                }
+                None => break,
+            }
+        }
+        let reason = if motives.is_empty() {
+            String::from("bought no stocks because none of them helps")
+        } else {
+            let motives: Vec<String> = motives
+                .iter()
+                .map(|(chain, motive)| {
+                    format!("{} {} {}", stocks_bought.get(chain).unwrap(), chain, motive)
+                })
+                .collect();
+            format!("bought {}", motives.join(", "))
+        };
+        Decision::new(stocks_bought, reason)
+    }
+
+    fn end_game(
+        &self,
+        player: &Player,
+        players: &[Player],
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<bool> {
+        // The bonuses are not included because the bank is not known here, the stocks are
+        // counted at their current value instead
+        GreedyBot.end_game(player, players, hotel_chain_manager)
+    }
+
+    fn observe(&self, player: &Player, board: &Board, players: &[Player]) {
+        *self.observation.lock().unwrap() = Some(Observation::new(player, board, players));
+    }
+}
+
+/// A bot that tries out its options by playing the game to the end a number of times.
+///
+/// For each legal card and for some stock purchases the game is copied and played on with random
+/// moves for a few turns. The option with the best average result is chosen. The cards of the
+/// other players are not known to the bot, they are dealt randomly in each playout.
+///
+/// All other decisions are made by the [`HeuristicBot`].
+pub struct MonteCarloBot {
+    /// The number of playouts that are played for each decision
+    playouts: u32,
+    /// The time the bot may think for each decision. When set, playouts are played until the
+    /// time is up and the number of playouts is ignored.
+    time_budget: Option<Duration>,
+    /// The number of turns that are played in each playout
+    depth: u32,
+    rng: Mutex<StdRng>,
+    /// The game as the bot has last seen it
+    observation: Mutex<Option<Observation>>,
+    /// The turn that ends the game that the solver has found when the card was chosen, it is used to
+    /// decide whether the game is ended and which stocks are bought
+    ending_turn: Mutex<Option<EndingTurn>>,
+    fallback: HeuristicBot,
+}
+
+impl MonteCarloBot {
+    /// Creates a new bot that plays `playouts` playouts for each decision or as many playouts as
+    /// fit into the `time_budget`. The personality is used for the decisions of the fallback
+    /// bot and for the stocks that are tried out.
+    /// The same seed leads to the same decisions as long as no time budget is set.
+    pub fn new(
+        personality: BotPersonality,
+        playouts: u32,
+        time_budget: Option<Duration>,
+        seed: u64,
+    ) -> Self {
+        Self {
+            playouts,
+            time_budget,
+            depth: 12,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            observation: Mutex::new(None),
+            ending_turn: Mutex::new(None),
+            fallback: HeuristicBot::new(personality),
+        }
+    }
+
+    /// Plays one playout for the option and returns the score.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed with which the cards of the other players are dealt
+    /// * `option` - Is called on the new playout to apply the option
+    fn play<F>(
+        &self,
+        player: &Player,
+        observation: &Observation,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+        seed: u64,
+        option: F,
+    ) -> Result<i64>
+    where
+        F: Fn(&mut Playout) -> Result<()>,
+    {
+        let mut playout = Playout::new(player, observation, bank, hotel_chain_manager, seed);
+        option(&mut playout)?;
+        for _turn in 0..self.depth {
+            if !playout.step()? {
+                break;
+            }
+        }
+        Ok(playout.score(player.id))
+    }
+
+    /// Returns the option with the best average score and a reason that contains the score.
+    /// `None` is returned when the options could not be evaluated.
+    ///
+    /// The playouts are played in parallel on all cores. Without time budget all playouts are
+    /// played at once. With time budget the playouts are played in batches until the time is up,
+    /// each batch plays every option at least once.
+    ///
+    /// The seeds of the playouts are drawn before the playouts are started, so the result does
+    /// not depend on the number of threads.
+    fn best_option<T, F>(
+        &self,
+        player: &Player,
+        options: Vec<T>,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+        apply: F,
+    ) -> Option<(T, String)>
+    where
+        T: Sync,
+        F: Fn(&T, &mut Playout) -> Result<()> + Sync,
+    {
+        let observation = self.observation.lock().unwrap();
+        let observation = observation.as_ref()?;
+        let deadline = self.time_budget.map(|budget| Instant::now() + budget);
+        let rounds_per_batch = match deadline {
+            Some(_) => rayon::current_num_threads().div_ceil(options.len()),
+            None => (self.playouts as usize / options.len()).max(1),
+        };
+        let mut totals = vec![0; options.len()];
+        let mut played = 0;
+        loop {
+            let seeds: Vec<(usize, u64)> = {
+                let mut rng = self.rng.lock().unwrap();
+                (0..rounds_per_batch)
+                    .flat_map(|_| 0..options.len())
+                    .map(|index| (index, rng.gen()))
+                    .collect()
+            };
+            let scores: Vec<(usize, i64)> = seeds
+                .into_par_iter()
+                .map(|(index, seed)| {
+                    self.play(
+                        player,
+                        observation,
+                        bank,
+                        hotel_chain_manager,
+                        seed,
+                        |playout| apply(&options[index], playout),
+                    )
+                    .map(|score| (index, score))
+                })
+                .collect::<Result<_>>()
+                .ok()?;
+            for (index, score) in scores {
+                totals[index] += score;
+            }
+            played += rounds_per_batch;
+            if deadline.is_none_or(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+        }
+        let mut best: Option<(i64, T)> = None;
+        for (option, total) in options.into_iter().zip(totals) {
+            let score = total / played as i64;
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _)| score > *best_score)
+            {
+                best = Some((score, option));
+            }
+        }
+        best.map(|(score, option)| {
+            (
+                option,
+                format!(
+                    "because it had the best result in {} playouts ({:+}€ compared to the strongest other player)",
+                    played, score
+                ),
+            )
+        })
+    }
+
+    /// Returns the purchases that are tried out: no purchase, the purchases of the other bots and
+    /// as many stocks of a single chain as possible.
+    fn purchase_options(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Vec<HashMap<HotelChain, u32>> {
+        let mut options = vec![
+            HashMap::new(),
+            self.fallback
+                .buy_stocks(player, bank, hotel_chain_manager)
+                .choice,
+            GreedyBot
+                .buy_stocks(player, bank, hotel_chain_manager)
+                .choice,
+        ];
+        for chain in HotelChain::iterator() {
+            let price = Bank::stock_price(hotel_chain_manager, chain);
+            if !hotel_chain_manager.chain_status(chain) || price == 0 {
+                continue;
+            }
+            let amount = (player.money / price)
+                .min(*bank.stocks_available(chain, hotel_chain_manager))
+                .min(PURCHASE_LIMIT);
+            if amount > 0 {
+                options.push(HashMap::from([(*chain, amount)]));
+            }
+        }
+        options.dedup();
+        options
+    }
+}
+
+impl Bot for MonteCarloBot {
+    fn choose_card(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<Position> {
+        // When all chains are safe every way to end the game in this turn is searched
+        let ending_turn = self
+            .observation
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|observation| {
+                solve_ending_turn(player, observation, bank, hotel_chain_manager)
+                    .ok()
+                    .flatten()
+            });
+        let winning_card = ending_turn
+            .as_ref()
+            .filter(|ending_turn| ending_turn.wins())
+            .map(|ending_turn| (ending_turn.card, ending_turn.lead));
+        *self.ending_turn.lock().unwrap() = ending_turn;
+        if let Some((card, lead)) = winning_card {
+            return Decision::new(
+                card,
+                format!("played {} to end the game with a lead of {}€", card, lead),
+            );
+        }
+        let legal_cards: Vec<&AnalyzedPosition> = player
+            .analyzed_cards
+            .iter()
+            .filter(|card| !card.is_illegal())
+            .collect();
+        if legal_cards.len() > 1 {
+            if let Some((card, motive)) = self.best_option(
+                player,
+                legal_cards,
+                bank,
+                hotel_chain_manager,
+                |card, playout| {
+                    playout.place_card(card.position)?;
+                    playout.end_turn();
+                    Ok(())
+                },
+            ) {
+                return Decision::new(
+                    card.position,
+                    format!(
+                        "{} {}",
+                        card_effect(card.position, &card.place_hotel_case),
+                        motive
+                    ),
+                );
+            }
+        }
+        self.fallback.choose_card(player, bank, hotel_chain_manager)
+    }
+
+    fn choose_chain_to_found(
+        &self,
+        player: &Player,
+        available_chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        self.fallback
+            .choose_chain_to_found(player, available_chains, bank, hotel_chain_manager)
+    }
+
+    fn choose_surviving_chain(
+        &self,
+        player: &Player,
+        chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        self.fallback
+            .choose_surviving_chain(player, chains, bank, hotel_chain_manager)
+    }
+
+    fn handle_fusion_stocks(
+        &self,
+        player: &Player,
+        dead: &HotelChain,
+        alive: &HotelChain,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<(u32, u32)> {
+        self.fallback
+            .handle_fusion_stocks(player, dead, alive, bank, hotel_chain_manager)
+    }
+
+    fn buy_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        if let Some(ending_turn) = self.ending_turn.lock().unwrap().take() {
+            if ending_turn.wins() {
+                let reason = format!(
+                    "bought {} to maximize its lead at the end of the game",
+                    stocks_to_string(&ending_turn.stocks)
+                );
+                return Decision::new(ending_turn.stocks, reason);
+            }
+        }
+        let options = self.purchase_options(player, bank, hotel_chain_manager);
+        match self.best_option(
+            player,
+            options,
+            bank,
+            hotel_chain_manager,
+            |stocks, playout| {
+                playout.buy_stocks(stocks)?;
+                playout.end_turn();
+                Ok(())
+            },
+        ) {
+            Some((stocks, motive)) => {
+                let reason = format!("bought {} {}", stocks_to_string(&stocks), motive);
+                Decision::new(stocks, reason)
+            }
+            None => self.fallback.buy_stocks(player, bank, hotel_chain_manager),
+        }
+    }
+
+    fn sell_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        self.fallback.sell_stocks(player, bank, hotel_chain_manager)
+    }
+
+    fn end_game(
+        &self,
+        player: &Player,
+        players: &[Player],
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<bool> {
+        match self.ending_turn.lock().unwrap().as_ref() {
+            Some(ending_turn) if ending_turn.wins() => Decision::new(
+                true,
+                format!(
+                    "ended the game because it leads by {}€ after the final account",
+                    ending_turn.lead
+                ),
+            ),
+            Some(_) => Decision::new(
+                false,
+                "continued the game because it would not be the richest player after the final account",
+            ),
+            None => self.fallback.end_game(player, players, hotel_chain_manager),
+        }
+    }
+
+    fn observe(&self, player: &Player, board: &Board, players: &[Player]) {
+        *self.observation.lock().unwrap() = Some(Observation::new(player, board, players));
+        self.fallback.observe(player, board, players);
+    }
+}
+
+/// Returns a short description of what happens when the card is played, for example
+/// "played A2 to found a new chain".
+fn card_effect(position: Position, place_hotel_case: &PlaceHotelCase) -> String {
+    match place_hotel_case {
+        PlaceHotelCase::NewChain(_) => format!("played {} to found a new chain", position),
+        PlaceHotelCase::ExtendsChain(chain, _) => {
+            format!("played {} to extend {}", position, chain)
+        }
+        PlaceHotelCase::Fusion(chains, _) => {
+            let chains: Vec<String> = chains.iter().map(|chain| chain.to_string()).collect();
+            format!("played {} to fuse {}", position, chains.join(" and "))
+        }
+        _ => format!("played {}", position),
+    }
+}
+
+/// Returns a short description of what the player does with their stocks of the dead chain.
+fn fusion_stocks_reason(exchange: u32, sell: u32, dead: &HotelChain, alive: &HotelChain) -> String {
+    match (exchange, sell) {
+        (0, 0) => format!("kept the stocks of {}", dead),
+        (0, sell) => format!("sold {} stocks of {}", sell, dead),
+        (exchange, 0) => format!("exchanged {} stocks of {} into {}", exchange, dead, alive),
+        (exchange, sell) => format!(
+            "exchanged {} stocks of {} into {} and sold {}",
+            exchange, dead, alive, sell
+        ),
+    }
+}
+
+/// Returns the stocks in the form "2 Imperial, 1 Airport" or "no stocks" when the map is empty.
+pub fn stocks_to_string(stocks: &HashMap<HotelChain, u32>) -> String {
+    let stocks: Vec<String> = HotelChain::iterator()
+        .filter_map(|chain| {
+            stocks
+                .get(chain)
+                .filter(|amount| **amount > 0)
+                .map(|amount| format!("{} {}", amount, chain))
+        })
+        .collect();
+    if stocks.is_empty() {
+        String::from("no stocks")
+    } else {
+        stocks.join(", ")
+    }
+}
+
+/// Returns how many stocks the player owns more than the largest other shareholder of the chain.
+/// Is negative when another player owns more stocks.
+pub fn majority_lead(player: &Player, chain: &HotelChain, bank: &Bank) -> i64 {
+    let largest_rival = bank
+        .rival_stocks(player.id, chain)
+        .first()
+        .copied()
+        .unwrap_or(0);
+    *player.owned_stocks.stocks_for_hotel(chain) as i64 - largest_rival as i64
+}
+
+/// Returns the majority shareholder bonus the player would get if the chain was fused now.
+/// Ties are split evenly.
+pub fn expected_bonus(
+    player: &Player,
+    chain: &HotelChain,
+    bank: &Bank,
+    hotel_chain_manager: &HotelChainManager,
+) -> u32 {
+    let own = *player.owned_stocks.stocks_for_hotel(chain);
+    if own == 0 {
+        return 0;
+    }
+    let rival_stocks = bank.rival_stocks(player.id, chain);
+    let price = Bank::stock_price(hotel_chain_manager, chain);
+    let ahead = rival_stocks.iter().filter(|stocks| **stocks > own).count() as u32;
+    let tied = rival_stocks.iter().filter(|stocks| **stocks == own).count() as u32;
+    match ahead {
+        0 if rival_stocks.is_empty() => price * 15,
+        0 if tied == 0 => price * 10,
+        0 => price * 15 / (tied + 1),
+        1 => price * 5 / (tied + 1),
+        _ => 0,
+    }
+}
+
+/// Returns the money of the player plus the value of all their stocks.
+pub fn net_worth(player: &Player, hotel_chain_manager: &HotelChainManager) -> u32 {
+    let mut worth = player.money;
+    for chain in hotel_chain_manager.active_chains() {
+        worth += Bank::stock_price(hotel_chain_manager, &chain)
+            * player.owned_stocks.stocks_for_hotel(&chain);
+    }
+    worth
+}
+
+/// Returns the maximum number of stocks of the dead chain that the player can exchange.
+pub fn max_exchange(player: &Player, dead: &HotelChain, alive: &HotelChain, bank: &Bank) -> u32 {
+    let owned = *player.owned_stocks.stocks_for_hotel(dead);
+    let available = *bank.stocks_for_sale.stocks_for_hotel(alive);
+    (owned / 2).min(available) * 2
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use miette::Result;
+
+    use crate::{
+        base_game::{
+            bank::Bank,
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+        },
+        game::hotel_chain_manager::HotelChainManager,
+    };
+
+    use super::{Bot, BotPersonality, GreedyBot, HeuristicBot, MonteCarloBot};
+
+    #[test]
+    fn greedy_bot_founds_chains() -> Result<()> {
+        let mut board = Board::new();
+        let hotel_chain_manager = HotelChainManager::new();
+        let bank = Bank::new();
+        board.place_hotel(&Position::new('A', 1))?;
+        let mut player = Player::new(
+            vec![Position::new('E', 5), Position::new('A', 2)],
+            0,
+            false,
+            String::from("Bot"),
+        );
+        player.analyze_cards(&board, &hotel_chain_manager);
+        let bot = GreedyBot;
+        let decision = bot.choose_card(&player, &bank, &hotel_chain_manager);
+        assert_eq!(decision.choice, Position::new('A', 2));
+        assert_eq!(
+            decision.reason,
+            "played A2 to found a new chain to get the founder bonus"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn greedy_bot_buys_cheapest_stocks() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut player = Player::new(vec![], 0, false, String::from("Bot"));
+        hotel_chain_manager.start_chain(
+            HotelChain::Imperial,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut player,
+            &mut bank,
+        )?;
+        hotel_chain_manager.start_chain(
+            HotelChain::Airport,
+            vec![Position::new('C', 1), Position::new('C', 2)],
+            &mut board,
+            &mut player,
+            &mut bank,
+        )?;
+        player.owned_stocks.set_stocks(&HotelChain::Imperial, 0);
+        player.owned_stocks.set_stocks(&HotelChain::Airport, 0);
+        let bot = GreedyBot;
+        let bought = bot.buy_stocks(&player, &bank, &hotel_chain_manager).choice;
+        assert_eq!(bought.get(&HotelChain::Airport), Some(&3));
+        assert_eq!(bought.get(&HotelChain::Imperial), None);
+        Ok(())
+    }
+
+    #[test]
+    fn heuristic_bot_defends_majority() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Bot")),
+            Player::new(vec![], 1, false, String::from("Player")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Imperial,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        hotel_chain_manager.start_chain(
+            HotelChain::Airport,
+            vec![Position::new('C', 1), Position::new('C', 2)],
+            &mut board,
+            &mut players[1],
+            &mut bank,
+        )?;
+        players[0].owned_stocks.set_stocks(&HotelChain::Imperial, 3);
+        players[1].owned_stocks.set_stocks(&HotelChain::Imperial, 3);
+        bank.update_largest_shareholders(&players);
+        let bot = HeuristicBot::new(BotPersonality::default());
+        let bought = bot
+            .buy_stocks(&players[0], &bank, &hotel_chain_manager)
+            .choice;
+        // The contested Imperial majority is taken first, then the second place in Airport
+        assert_eq!(bought.get(&HotelChain::Imperial), Some(&2));
+        assert_eq!(bought.get(&HotelChain::Airport), Some(&1));
+        Ok(())
+    }
+
+    #[test]
+    fn hoarder_bot_keeps_money() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Bot")),
+            Player::new(vec![], 1, false, String::from("Player 1")),
+            Player::new(vec![], 2, false, String::from("Player 2")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Airport,
+            vec![Position::new('C', 1), Position::new('C', 2)],
+            &mut board,
+            &mut players[1],
+            &mut bank,
+        )?;
+        players[1].owned_stocks.set_stocks(&HotelChain::Airport, 10);
+        players[2].owned_stocks.set_stocks(&HotelChain::Airport, 5);
+        bank.update_largest_shareholders(&players);
+        // No majority can be reached, the stocks would only be an investment
+        let balanced = HeuristicBot::new(BotPersonality::Balanced)
+            .buy_stocks(&players[0], &bank, &hotel_chain_manager)
+            .choice;
+        let hoarder = HeuristicBot::new(BotPersonality::Hoarder)
+            .buy_stocks(&players[0], &bank, &hotel_chain_manager)
+            .choice;
+        assert_eq!(balanced.get(&HotelChain::Airport), Some(&3));
+        assert!(hoarder.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn monte_carlo_bot_plays_own_card() -> Result<()> {
+        let mut board = Board::new();
+        let hotel_chain_manager = HotelChainManager::new();
+        let bank = Bank::new();
+        board.place_hotel(&Position::new('A', 1))?;
+        let mut players = vec![
+            Player::new(
+                vec![Position::new('E', 5), Position::new('A', 2)],
+                0,
+                false,
+                String::from("Bot"),
+            ),
+            Player::new(vec![], 1, false, String::from("Player")),
+        ];
+        players[0].analyze_cards(&board, &hotel_chain_manager);
+        let bot = MonteCarloBot::new(BotPersonality::default(), 20, None, 42);
+        bot.observe(&players[0], &board, &players);
+        let card = bot
+            .choose_card(&players[0], &bank, &hotel_chain_manager)
+            .choice;
+        assert!(players[0]
+            .analyzed_cards
+            .iter()
+            .any(|analyzed| analyzed.position == card));
+        Ok(())
+    }
+
+    #[test]
+    fn monte_carlo_bot_is_independent_of_threads() -> Result<()> {
+        let mut board = Board::new();
+        let hotel_chain_manager = HotelChainManager::new();
+        let bank = Bank::new();
+        board.place_hotel(&Position::new('A', 1))?;
+        board.place_hotel(&Position::new('C', 3))?;
+        let mut players = vec![
+            Player::new(
+                vec![
+                    Position::new('E', 5),
+                    Position::new('A', 2),
+                    Position::new('C', 4),
+                ],
+                0,
+                false,
+                String::from("Bot"),
+            ),
+            Player::new(vec![], 1, false, String::from("Player")),
+        ];
+        players[0].analyze_cards(&board, &hotel_chain_manager);
+        let mut reasons = Vec::new();
+        for threads in [1, 4] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let bot = MonteCarloBot::new(BotPersonality::default(), 30, None, 42);
+            bot.observe(&players[0], &board, &players);
+            reasons.push(pool.install(|| {
+                bot.choose_card(&players[0], &bank, &hotel_chain_manager)
+                    .reason
+            }));
+        }
+        // The reason contains the score, so the playouts have had the same results
+        assert_eq!(reasons[0], reasons[1]);
+        Ok(())
+    }
+
+    #[test]
+    fn monte_carlo_bot_respects_time_budget() -> Result<()> {
+        let mut board = Board::new();
+        let hotel_chain_manager = HotelChainManager::new();
+        let bank = Bank::new();
+        board.place_hotel(&Position::new('A', 1))?;
+        let mut players = vec![
+            Player::new(
+                vec![Position::new('E', 5), Position::new('A', 2)],
+                0,
+                false,
+                String::from("Bot"),
+            ),
+            Player::new(vec![], 1, false, String::from("Player")),
+        ];
+        players[0].analyze_cards(&board, &hotel_chain_manager);
+        // Without the time budget the bot would play until the end of time
+        let bot = MonteCarloBot::new(
+            BotPersonality::default(),
+            u32::MAX,
+            Some(Duration::from_millis(50)),
+            42,
+        );
+        bot.observe(&players[0], &board, &players);
+        let start = Instant::now();
+        bot.choose_card(&players[0], &bank, &hotel_chain_manager);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use std::{env, fs, path::PathBuf};
+
+use miette::{miette, Result};
+use owo_colors::Rgb;
+use toml::Table;
+
+use crate::{base_game::settings::RulePreset, locale::Language};
+
+/// The defaults that are read from the configuration file.
+///
+/// The file is written in toml and every key is optional:
+/// ```toml
+/// name = "LMH01"
+/// small_board = true
+/// plain = false
+/// color = "#ff8700"
+/// port = 11511
+/// rules = "house"
+/// language = "de"
+/// ```
+/// The options that are given on the command line are used instead of the values of the file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    /// The name with which the player hosts or joins lan games
+    pub name: Option<String>,
+    /// If the board is printed small
+    pub small_board: bool,
+    /// If the text is shown without colors, shareholder stars and box-drawing lines
+    pub plain: bool,
+    /// The color in which the name of the player is printed
+    pub color: Option<Rgb>,
+    /// The port on which lan games are hosted
+    pub port: Option<u16>,
+    /// The rules with which games are played
+    pub rules: Option<RulePreset>,
+    /// The language in which the messages of the game are shown
+    pub language: Option<Language>,
+}
+
+impl Config {
+    /// Reads the configuration from the content of a configuration file.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let table: Table = content
+            .parse()
+            .map_err(|err| miette!("The file is not valid toml: {}", err))?;
+        let mut config = Config::default();
+        for (key, value) in &table {
+            let invalid = || miette!("Invalid value for {}: {}", key, value);
+            match key.as_str() {
+                "name" => config.name = Some(value.as_str().ok_or_else(invalid)?.to_string()),
+                "small_board" => config.small_board = value.as_bool().ok_or_else(invalid)?,
+                "plain" => config.plain = value.as_bool().ok_or_else(invalid)?,
+                "color" => {
+                    config.color = Some(value.as_str().and_then(hex_color).ok_or_else(invalid)?)
+                }
+                "port" => {
+                    config.port = Some(
+                        value
+                            .as_integer()
+                            .and_then(|port| u16::try_from(port).ok())
+                            .ok_or_else(invalid)?,
+                    )
+                }
+                "rules" => {
+                    config.rules = Some(
+                        value
+                            .as_str()
+                            .and_then(RulePreset::from_name)
+                            .ok_or_else(invalid)?,
+                    )
+                }
+                "language" => {
+                    config.language = Some(
+                        value
+                            .as_str()
+                            .and_then(Language::from_name)
+                            .ok_or_else(invalid)?,
+                    )
+                }
+                _ => return Err(miette!("Unknown option: {}", key)),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Reads the configuration file, the defaults are used when there is no file.
+    pub fn load() -> Result<Self> {
+        let path = match config_path() {
+            Some(path) => path,
+            None => return Ok(Config::default()),
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => Config::from_toml(&content).map_err(|err| {
+                miette!(
+                    "Unable to read configuration file {}: {}",
+                    path.display(),
+                    err
+                )
+            }),
+            Err(_) => Ok(Config::default()),
+        }
+    }
+}
+
+/// Returns the path of the configuration file, `~/.config/acquire_rs/config.toml` unless
+/// `XDG_CONFIG_HOME` is set.
+pub fn config_path() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?)
+            .join(".config"),
+    };
+    Some(config_dir.join("acquire_rs").join("config.toml"))
+}
+
+/// Reads a color that is written as hex code like `#ff8700`
+pub fn hex_color(hex: &str) -> Option<Rgb> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some(Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use owo_colors::Rgb;
+
+    use crate::{base_game::settings::RulePreset, locale::Language};
+
+    use super::Config;
+
+    #[test]
+    fn config_is_read_from_toml() -> Result<()> {
+        let config = Config::from_toml(
+            "name = \"LMH01\"\nsmall_board = true\nplain = true\ncolor = \"#ff8700\"\nport = 4000\nrules = \"house\"\nlanguage = \"de\"",
+        )?;
+        assert_eq!(
+            config,
+            Config {
+                name: Some(String::from("LMH01")),
+                small_board: true,
+                plain: true,
+                color: Some(Rgb(255, 135, 0)),
+                port: Some(4000),
+                rules: Some(RulePreset::House),
+                language: Some(Language::German),
+            }
+        );
+        assert_eq!(Config::from_toml("")?, Config::default());
+        assert!(Config::from_toml("port = 70000").is_err());
+        assert!(Config::from_toml("color = \"orange\"").is_err());
+        assert!(Config::from_toml("colour = \"#ff8700\"").is_err());
+        assert!(Config::from_toml("name = ").is_err());
+        Ok(())
+    }
+}
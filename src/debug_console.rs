@@ -0,0 +1,273 @@
+use std::io::{stdin, stdout, Write};
+
+use miette::{miette, IntoDiagnostic, Result};
+use owo_colors::OwoColorize;
+
+use crate::{
+    base_game::{
+        bank::Bank,
+        board::{Board, BoardStyle, Position},
+        hotel_chains::HotelChain,
+        player::Player,
+        ui::console_text,
+    },
+    game::{hotel_chain_manager::HotelChainManager, player_by_id},
+};
+
+/// The commands that the debug console understands
+const HELP: [&str; 9] = [
+    "board                          - Show the board",
+    "chains                         - Show the length, price and safety of each chain",
+    "bank                           - Show the stocks of the bank and the largest shareholders",
+    "players                        - Show the money, cards and stocks of each player",
+    "place <position> [chain]       - Place a hotel, it is added to the chain when one is given",
+    "money <player> <amount>        - Set the money of the player, players are numbered from 1",
+    "stocks <player> <chain> <n>    - Set the stocks of the player, the bank keeps the rest",
+    "help                           - Show this help",
+    "exit                           - Return to the game, an empty line does the same",
+];
+
+/// Opens the debug console in which the state of the game can be inspected and changed.
+///
+/// Commands are read from the terminal until the player returns to the game, mistakes in a
+/// command are printed and do not end the console.
+pub fn open_debug_console(
+    players: &mut [Player],
+    board: &mut Board,
+    bank: &mut Bank,
+    hotel_chain_manager: &mut HotelChainManager,
+) -> Result<()> {
+    println!(
+        "Debug console, enter help to see the commands or an empty line to return to the game."
+    );
+    loop {
+        print!("{} ", console_text(&"debug>".yellow().to_string()));
+        stdout().flush().into_diagnostic()?;
+        let mut buffer = String::new();
+        if stdin().read_line(&mut buffer).into_diagnostic()? == 0 {
+            return Err(miette!("Unable to read input: The input has been closed"));
+        }
+        let command = buffer.trim();
+        if command.is_empty() || command == "exit" {
+            return Ok(());
+        }
+        match execute_debug_command(command, players, board, bank, hotel_chain_manager) {
+            Ok(lines) => {
+                for line in lines {
+                    println!("{}", console_text(&line));
+                }
+            }
+            Err(err) => println!("{}", err),
+        }
+    }
+}
+
+/// Executes the command of the debug console and returns the lines that are shown.
+/// Returns an error when the command is unknown or an argument is invalid, the game is not
+/// changed then.
+pub fn execute_debug_command(
+    command: &str,
+    players: &mut [Player],
+    board: &mut Board,
+    bank: &mut Bank,
+    hotel_chain_manager: &mut HotelChainManager,
+) -> Result<Vec<String>> {
+    let args: Vec<&str> = command.split_whitespace().collect();
+    match args.as_slice() {
+        ["help"] => Ok(HELP.iter().map(|line| line.to_string()).collect()),
+        ["board"] => Ok(board.render_console(BoardStyle::Large)),
+        ["chains"] => Ok(HotelChain::iterator()
+            .map(|chain| {
+                format!(
+                    "{:12} hotels: {:2}, price: {:4}€, bank: {:2}, safe: {}",
+                    chain.name(),
+                    hotel_chain_manager.chain_length(chain),
+                    Bank::stock_price(hotel_chain_manager, chain),
+                    bank.stocks_available(chain, hotel_chain_manager),
+                    hotel_chain_manager.is_chain_safe(chain),
+                )
+            })
+            .collect()),
+        ["bank"] => {
+            let names = |ids: &[u32]| -> String {
+                ids.iter()
+                    .filter_map(|id| player_by_id(*id, players))
+                    .map(|player| player.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            };
+            Ok(HotelChain::iterator()
+                .map(|chain| {
+                    format!(
+                        "{:12} for sale: {:2}, largest: [{}], second largest: [{}]",
+                        chain.name(),
+                        bank.stocks_for_sale.stocks_for_hotel(chain),
+                        names(bank.largest_shareholders.largest(chain)),
+                        names(bank.largest_shareholders.second_largest(chain)),
+                    )
+                })
+                .collect())
+        }
+        ["players"] => {
+            let mut lines = Vec::new();
+            for (index, player) in players.iter().enumerate() {
+                lines.push(format!("{}: {}, {}€", index + 1, player.name, player.money));
+                lines.push(format!("   {}", player.cards_ui()));
+                lines.push(format!("   {}", player.stocks_ui()));
+            }
+            Ok(lines)
+        }
+        ["place", position] => {
+            let position = parse_position(position)?;
+            board.place_hotel(&position)?;
+            Ok(vec![format!("A hotel has been placed on {}", position)])
+        }
+        ["place", position, chain] => {
+            let position = parse_position(position)?;
+            let chain = parse_chain(chain)?;
+            if !hotel_chain_manager.chain_status(&chain) {
+                return Err(miette!("The chain {} has not been founded", chain));
+            }
+            board.place_hotel(&position)?;
+            hotel_chain_manager.add_hotel_to_chain(&chain, position, board)?;
+            Ok(vec![format!(
+                "A hotel of {} has been placed on {}",
+                chain, position
+            )])
+        }
+        ["money", player, amount] => {
+            let index = parse_player(player, players)?;
+            players[index].money = amount
+                .parse()
+                .map_err(|_| miette!("Invalid amount: {}", amount))?;
+            Ok(vec![format!(
+                "{} has {}€ now",
+                players[index].name, players[index].money
+            )])
+        }
+        ["stocks", player, chain, amount] => {
+            let index = parse_player(player, players)?;
+            let chain = parse_chain(chain)?;
+            let amount: u32 = amount
+                .parse()
+                .map_err(|_| miette!("Invalid amount: {}", amount))?;
+            let owned = *players[index].owned_stocks.stocks_for_hotel(&chain);
+            let for_sale = *bank.stocks_for_sale.stocks_for_hotel(&chain);
+            if amount > owned + for_sale {
+                return Err(miette!(
+                    "The bank has only {} stocks of {} left",
+                    for_sale,
+                    chain
+                ));
+            }
+            players[index].owned_stocks.set_stocks(&chain, amount);
+            bank.stocks_for_sale
+                .set_stocks(&chain, owned + for_sale - amount);
+            bank.mark_traded(&chain);
+            bank.update_largest_shareholders(players);
+            Ok(vec![format!(
+                "{} has {} stocks of {} now",
+                players[index].name, amount, chain
+            )])
+        }
+        _ => Err(miette!(
+            "Unknown command: {}, enter help to see the commands",
+            command
+        )),
+    }
+}
+
+/// Reads a position like `B7`
+fn parse_position(position: &str) -> Result<Position> {
+    Position::from_name(&position.to_uppercase())
+        .ok_or_else(|| miette!("Invalid position: {}", position))
+}
+
+/// Reads the name of a chain, the case is ignored
+fn parse_chain(chain: &str) -> Result<HotelChain> {
+    HotelChain::iterator()
+        .find(|hotel_chain| hotel_chain.name().eq_ignore_ascii_case(chain))
+        .copied()
+        .ok_or_else(|| miette!("Invalid chain: {}", chain))
+}
+
+/// Reads the number of a player, the first player has the number 1. Returns the index of the
+/// player.
+fn parse_player(player: &str, players: &[Player]) -> Result<usize> {
+    player
+        .parse::<usize>()
+        .ok()
+        .filter(|number| (1..=players.len()).contains(number))
+        .map(|number| number - 1)
+        .ok_or_else(|| miette!("Invalid player: {}", player))
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::{
+            bank::Bank,
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+        },
+        game::hotel_chain_manager::HotelChainManager,
+    };
+
+    use super::execute_debug_command;
+
+    #[test]
+    fn debug_commands_change_the_game() -> Result<()> {
+        let mut bank = Bank::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut board = Board::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Player 1")),
+            Player::new(vec![], 1, false, String::from("Player 2")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Luxor,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        let mut run = |command: &str| {
+            execute_debug_command(
+                command,
+                &mut players,
+                &mut board,
+                &mut bank,
+                &mut hotel_chain_manager,
+            )
+        };
+        run("place a3 luxor")?;
+        run("place F7")?;
+        assert!(run("place F7").is_err());
+        assert!(run("place B1 festival").is_err());
+        run("money 2 100")?;
+        run("stocks 2 Luxor 5")?;
+        assert!(run("stocks 2 Luxor 30").is_err());
+        assert!(run("money 3 100").is_err());
+        assert!(run("teleport").is_err());
+        let chains = run("chains")?;
+        assert!(chains
+            .iter()
+            .any(|line| line.contains("Luxor") && line.contains("hotels:  3")));
+        let shareholders = run("bank")?;
+        assert!(shareholders
+            .iter()
+            .any(|line| line.contains("Luxor") && line.contains("largest: [Player 2]")));
+        assert_eq!(players[1].money, 100);
+        assert_eq!(hotel_chain_manager.chain_length(&HotelChain::Luxor), 3);
+        assert!(board.is_hotel_placed(&Position::new('F', 7)).is_some());
+        // The founder bonus stock and the five stocks are no longer in the bank
+        assert_eq!(
+            *bank.stocks_for_sale.stocks_for_hotel(&HotelChain::Luxor),
+            19
+        );
+        Ok(())
+    }
+}
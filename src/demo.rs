@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use clap::ArgMatches;
 use miette::Result;
@@ -27,7 +27,7 @@ pub fn test_things(matches: &ArgMatches, settings: Settings) -> Result<()> {
     let round = Round::new(1);
     let mut player_cards = Vec::new();
     for _i in 1..=6 {
-        player_cards.push(draw_card(&mut game_manager.position_cards));
+        player_cards.push(game::draw_card(&mut game_manager.position_cards).unwrap());
     }
     let player = game_manager.players.get_mut(0).unwrap();
     if matches.value_of("demo_type").unwrap() == "0" {
@@ -57,7 +57,7 @@ pub fn test_things(matches: &ArgMatches, settings: Settings) -> Result<()> {
     player.analyze_cards(&game_manager.board, &game_manager.hotel_chain_manager);
     ui::print_main_ui_console(
         Some(player),
-        Some(&player.name),
+        Some(&player.colored_name()),
         &game_manager.board,
         &game_manager.settings,
         Some(&round),
@@ -85,7 +85,7 @@ pub fn test_things(matches: &ArgMatches, settings: Settings) -> Result<()> {
         player.analyze_cards(&game_manager.board, &game_manager.hotel_chain_manager);
         ui::print_main_ui_console(
             Some(player),
-            Some(&player.name),
+            Some(&player.colored_name()),
             &game_manager.board,
             &game_manager.settings,
             Some(&round),
@@ -99,7 +99,7 @@ pub fn test_things(matches: &ArgMatches, settings: Settings) -> Result<()> {
 pub fn set_hotel_chains_random(
     active_chains: &mut Vec<HotelChain>,
     player: &mut Player,
-    position_cards: &mut Vec<Position>,
+    position_cards: &mut VecDeque<Position>,
     board: &mut Board,
     hotel_chain_manager: &mut HotelChainManager,
     bank: &mut Bank,
@@ -113,7 +113,7 @@ pub fn set_hotel_chains_random(
             if rand::thread_rng().gen_bool(0.1) {
                 break;
             }
-            cards.push(game::draw_card(position_cards)?.unwrap());
+            cards.push(game::draw_card(position_cards).unwrap());
         }
         for card in &cards {
             board.place_hotel(card)?;
@@ -130,7 +130,7 @@ pub fn set_hotel_chains_random(
 pub fn set_hotel_chains_clever(
     active_chains: &mut Vec<HotelChain>,
     player: &mut Player,
-    _position_cards: &mut Vec<Position>,
+    _position_cards: &mut VecDeque<Position>,
     board: &mut Board,
     hotel_chain_manager: &mut HotelChainManager,
     bank: &mut Bank,
@@ -229,9 +229,7 @@ fn random_neighbour(
             continue;
         }
         for (index, allowed_position) in allowed_positions.iter_mut().enumerate() {
-            if allowed_position.letter.eq(&position.unwrap().letter)
-                && allowed_position.number == position.unwrap().number
-            {
+            if *allowed_position == position.unwrap() {
                 if !is_neighbour_free(chain, position.unwrap(), placed_hotels) {
                     continue;
                 }
@@ -0,0 +1,918 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use miette::{miette, Result};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde_json::{json, Value};
+
+use crate::{
+    base_game::{
+        bank::Bank,
+        board::{Board, Position},
+        hotel_chains::HotelChain,
+        player::Player,
+        settings::{Privacy, RulePreset, Settings, PURCHASE_LIMIT},
+    },
+    bot::{Bot, BotPersonality, BotStrategy, Decision},
+    game::{hotel_chain_manager::HotelChainManager, GameManager},
+    playout::Playout,
+    simulation::MAX_TURNS,
+};
+
+/// The name of the seats that are taken by players outside of the program in the configuration
+/// of a game, the other seats are taken by bots.
+pub const EXTERNAL_SEAT: &str = "external";
+
+/// A game that is played step by step by players outside of the program, for example by another
+/// program that embeds the game.
+///
+/// Each time one of these players has to decide something, the game waits until the decision is
+/// applied with [`Engine::apply`]. The decisions and the actions use the same json objects as
+/// the replays. The seats that are taken by bots are played automatically.
+///
+/// The game does not need threads, so that it can be used in WebAssembly as well: A turn is
+/// played on a copy of the game until an external player has to decide. When the action is
+/// applied the turn is played again from its start, the decisions that have already been made
+/// in the turn are made again without asking the bots.
+pub struct Engine {
+    /// The names of the players, the index is the id of the player
+    names: Vec<String>,
+    /// The game at the start of the current turn
+    playout: Playout,
+    /// The bots of the seats, `None` for the seats of external players
+    bots: Vec<Option<Arc<dyn Bot>>>,
+    /// The actions of the decisions that have been made in the current turn
+    turn_actions: Vec<Value>,
+    /// What the game is waiting for, a decision or the end of the game
+    pending: Pending,
+    /// Stores if the engine has panicked while it was used through the c interface. The game
+    /// may have been left in any state then, so it is not used anymore, see [`crate::ffi`].
+    poisoned: AtomicBool,
+}
+
+/// What the game is waiting for.
+enum Pending {
+    /// A player has to make a decision
+    Decision {
+        player_id: u32,
+        /// The game as the player sees it
+        state: Value,
+        /// What has to be decided
+        request: Value,
+    },
+    /// The game has ended, contains the money of each player after the final account
+    Ended { money: Vec<u32>, turns: u32 },
+    /// The game has been stopped by an error
+    Failed(String),
+}
+
+impl Engine {
+    /// Creates a new game and plays it until the first decision of an external player.
+    ///
+    /// The configuration looks like this, only `seats` is required:
+    /// ```json
+    /// {"seats": ["external", "heuristic", "random"], "seed": 42, "rules": "classic", "bot_playouts": 200}
+    /// ```
+    /// Each seat is either taken by an external player or by a bot with the strategy. The same
+    /// seed and the same decisions always lead to the same game.
+    ///
+    /// The game can start from a `position` instead of the start of a new game:
+    /// ```json
+    /// {"hotels": [{"position": "A1", "chain": "Luxor"}, {"position": "A2", "chain": "Luxor"}, {"position": "C5", "chain": null}],
+    ///  "players": [{"money": 5000, "stocks": {"Luxor": 3}, "hand": ["A3", "B7"]}, {}],
+    ///  "deck": ["I12"], "current_player": 1}
+    /// ```
+    /// Every key is optional. The players get 6000€ and six cards from the deck when their
+    /// money or hand is missing, the cards in `deck` are drawn first and the other cards that
+    /// are neither on the board nor in a hand are shuffled below them.
+    pub fn new(config: &Value) -> Result<Self> {
+        let seats = config["seats"]
+            .as_array()
+            .ok_or_else(|| miette!("Unable to create game: The seats are missing"))?;
+        if !(2..=6).contains(&seats.len()) {
+            return Err(miette!(
+                "Unable to create game: {} seats are given but 2 to 6 players are needed",
+                seats.len()
+            ));
+        }
+        let mut settings = Settings::new(false, Privacy::default(), true);
+        if let Some(name) = config.get("rules") {
+            settings.rule_preset = name
+                .as_str()
+                .and_then(RulePreset::from_name)
+                .ok_or_else(|| miette!("Unable to create game: Unknown rules {}", name))?;
+            settings.rules = settings.rule_preset.rules();
+        }
+        if let Some(playouts) = config.get("bot_playouts") {
+            settings.bot_playouts = u32_of(playouts)
+                .ok_or_else(|| miette!("Unable to create game: Invalid number of bot playouts"))?;
+        }
+        let seed = match config.get("seed") {
+            Some(seed) => seed
+                .as_u64()
+                .ok_or_else(|| miette!("Unable to create game: Invalid seed {}", seed))?,
+            None => rand::thread_rng().gen(),
+        };
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut players = Vec::new();
+        let mut bots = Vec::new();
+        for (id, seat) in seats.iter().enumerate() {
+            let (name, bot) = match seat.as_str() {
+                Some(EXTERNAL_SEAT) => (format!("Player {}", id + 1), None),
+                Some(name) => {
+                    let strategy = BotStrategy::from_name(name).ok_or_else(|| {
+                        miette!(
+                            "Unable to create game: Seat {} is neither external nor a bot strategy: {}",
+                            id + 1,
+                            name
+                        )
+                    })?;
+                    let bot: Arc<dyn Bot> =
+                        Arc::from(strategy.create(BotPersonality::default(), &settings, rng.gen()));
+                    (format!("Bot {}", id + 1), Some(bot))
+                }
+                None => {
+                    return Err(miette!(
+                        "Unable to create game: Seat {} is not a name",
+                        id + 1
+                    ))
+                }
+            };
+            players.push(Player::new(Vec::new(), id as u32, false, name));
+            bots.push(bot);
+        }
+        let names = players.iter().map(|player| player.name.clone()).collect();
+        let playout = match config.get("position") {
+            Some(position) => set_up_position(position, players, &settings, rng.gen())
+                .map_err(|err| miette!("Unable to create game: {}", err))?,
+            None => Playout::new_game(players, settings.rules, rng.gen())?,
+        };
+        let mut engine = Self {
+            names,
+            playout,
+            bots,
+            turn_actions: Vec::new(),
+            pending: Pending::Failed(String::from("The game has not been started")),
+            poisoned: AtomicBool::new(false),
+        };
+        if let Err(err) = engine.play() {
+            engine.pending = Pending::Failed(err.to_string());
+        }
+        Ok(engine)
+    }
+
+    /// Returns what the game is waiting for as json.
+    ///
+    /// While the game is running it contains the id of the `player` that has to decide, the
+    /// `decision` that has to be made and the `state` of the game as the player sees it. When
+    /// the game has ended it contains the `money` of each player after the final account.
+    pub fn state(&self) -> Value {
+        match &self.pending {
+            Pending::Decision {
+                player_id,
+                state,
+                request,
+            } => json!({
+                "status": "running",
+                "names": self.names,
+                "player": player_id,
+                "decision": request,
+                "state": state,
+            }),
+            Pending::Ended { money, turns } => json!({
+                "status": "ended",
+                "names": self.names,
+                "money": money,
+                "turns": turns,
+            }),
+            Pending::Failed(reason) => json!({
+                "status": "failed",
+                "names": self.names,
+                "error": reason,
+            }),
+        }
+    }
+
+    /// Marks the engine as poisoned after it has panicked.
+    pub(crate) fn poison(&self) {
+        self.poisoned.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true when the engine has panicked before, see [`Engine::poison`].
+    pub(crate) fn is_poisoned(&self) -> bool {
+        self.poisoned.load(Ordering::Relaxed)
+    }
+
+    /// Applies the action of the player that has to decide and plays until the next decision
+    /// of an external player.
+    ///
+    /// The action has the `type` of the decision, for example
+    /// `{"type": "buy_stocks", "stocks": {"Luxor": 2}}`. When the action is not valid an error
+    /// is returned and the game still waits for the same decision.
+    pub fn apply(&mut self, action: &Value) -> Result<()> {
+        let request = match &self.pending {
+            Pending::Decision { request, .. } => request,
+            _ => {
+                return Err(miette!(
+                    "Unable to apply action: The game is no longer running"
+                ))
+            }
+        };
+        if action["type"] != request["type"] {
+            return Err(miette!(
+                "Unable to apply action: The player has to decide {}",
+                request["type"]
+            ));
+        }
+        self.turn_actions.push(action.clone());
+        match self.play() {
+            Ok(()) => Ok(()),
+            Err(err) => match self.pending {
+                // The action has been rejected, the game waits for the same decision
+                Pending::Decision { .. } => {
+                    self.turn_actions.pop();
+                    Err(miette!("Unable to apply action: {}", err))
+                }
+                _ => Err(miette!("Unable to apply action: {}", err)),
+            },
+        }
+    }
+
+    /// Plays the game until an external player has to decide or the game has ended.
+    ///
+    /// Each turn is played on a copy of the game, the actions of the current turn are made
+    /// again before the bots are asked. When an external player has to decide the copy is
+    /// dropped and the game waits for the decision.
+    /// # Returns
+    /// * `Ok(())` - The game waits for a decision or has ended
+    /// * `Err(err)` - The last action of the current turn has been rejected, the pending
+    ///   decision is kept. When the game has been stopped by an error the pending decision is
+    ///   replaced by the error as well.
+    fn play(&mut self) -> Result<()> {
+        loop {
+            if self.playout.turns() >= MAX_TURNS {
+                return self.end();
+            }
+            let context = Arc::new(Mutex::new(TurnContext {
+                actions: self.turn_actions.iter().cloned().collect(),
+                ..TurnContext::default()
+            }));
+            let mut turn = self.playout.headless_copy();
+            for (player, bot) in turn.players.iter_mut().zip(&self.bots) {
+                player.bot = Some(Box::new(Seat {
+                    bot: bot.clone(),
+                    context: Arc::clone(&context),
+                }));
+            }
+            turn.record_decisions();
+            let result = turn.step();
+            let mut context = context.lock().unwrap();
+            if let Some(reason) = context.rejected.take() {
+                return Err(miette!(reason));
+            }
+            if let Some(reason) = context.failed.take() {
+                self.pending = Pending::Failed(reason.clone());
+                return Err(miette!(reason));
+            }
+            if let Some(pending) = context.waiting.take() {
+                self.turn_actions = turn
+                    .take_recorded_decisions()
+                    .into_iter()
+                    .take(context.decisions)
+                    .map(|decision| decision.action)
+                    .collect();
+                self.pending = pending;
+                return Ok(());
+            }
+            let running = match result {
+                Ok(running) => running,
+                Err(err) => {
+                    self.pending = Pending::Failed(err.to_string());
+                    return Err(err);
+                }
+            };
+            self.playout = turn;
+            self.turn_actions.clear();
+            if !running {
+                return self.end();
+            }
+        }
+    }
+
+    /// Pays the final account and ends the game
+    fn end(&mut self) -> Result<()> {
+        if let Err(err) = self.playout.final_account() {
+            self.pending = Pending::Failed(err.to_string());
+            return Err(err);
+        }
+        self.pending = Pending::Ended {
+            money: self
+                .playout
+                .players
+                .iter()
+                .map(|player| player.money)
+                .collect(),
+            turns: self.playout.turns(),
+        };
+        Ok(())
+    }
+}
+
+/// Creates the playout of the position, see [`Engine::new`].
+fn set_up_position(
+    position: &Value,
+    mut players: Vec<Player>,
+    settings: &Settings,
+    seed: u64,
+) -> Result<Playout> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut board = Board::new();
+    for hotel in position["hotels"].as_array().into_iter().flatten() {
+        let card = position_of(&hotel["position"])?;
+        board.place_hotel(&card)?;
+        if !hotel["chain"].is_null() {
+            let chain =
+                chain_of(hotel, HotelChain::iterator().as_slice()).map_err(|err| miette!(err))?;
+            board.update_hotel(chain, &card)?;
+        }
+    }
+    let hotel_chain_manager = HotelChainManager::from_board(&board);
+    let mut bank = Bank::new();
+    bank.rules = settings.rules;
+    let stored_players = position["players"].as_array().cloned().unwrap_or_default();
+    if stored_players.len() > players.len() {
+        return Err(miette!(
+            "The position contains {} players but only {} seats are given",
+            stored_players.len(),
+            players.len()
+        ));
+    }
+    let mut hands = Vec::new();
+    let mut used_cards = Vec::new();
+    for (player, stored) in players.iter_mut().zip(&stored_players) {
+        if let Some(money) = stored.get("money") {
+            player.money = u32_of(money).ok_or_else(|| miette!("Invalid money {}", money))?;
+        }
+        if stored.get("stocks").is_some() {
+            for (chain, amount) in stocks_of(stored).map_err(|err| miette!(err))? {
+                let left = bank
+                    .stocks_for_sale
+                    .stocks_for_hotel(&chain)
+                    .checked_sub(amount)
+                    .ok_or_else(|| miette!("More than 25 stocks of {} are owned", chain))?;
+                player.owned_stocks.set_stocks(&chain, amount);
+                bank.stocks_for_sale.set_stocks(&chain, left);
+                bank.mark_traded(&chain);
+            }
+        }
+        let hand = match stored.get("hand") {
+            Some(hand) => Some(
+                hand.as_array()
+                    .ok_or_else(|| miette!("Invalid hand {}", hand))?
+                    .iter()
+                    .map(position_of)
+                    .collect::<Result<Vec<Position>>>()?,
+            ),
+            None => None,
+        };
+        used_cards.extend(hand.iter().flatten().copied());
+        hands.push(hand);
+    }
+    let deck = position["deck"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(position_of)
+        .collect::<Result<Vec<Position>>>()?;
+    used_cards.extend(&deck);
+    for (index, card) in used_cards.iter().enumerate() {
+        if board.is_hotel_placed(card).is_some() || used_cards[..index].contains(card) {
+            return Err(miette!("The card {} is used twice", card));
+        }
+    }
+    let mut position_cards: Vec<Position> = GameManager::init_position_cards()
+        .into_iter()
+        .filter(|card| board.is_hotel_placed(card).is_none() && !used_cards.contains(card))
+        .collect();
+    position_cards.shuffle(&mut rng);
+    position_cards.extend(deck.iter().rev());
+    hands.resize(players.len(), None);
+    for (player, hand) in players.iter_mut().zip(hands) {
+        let hand = match hand {
+            Some(hand) => hand,
+            None => (0..6).filter_map(|_| position_cards.pop()).collect(),
+        };
+        for card in hand {
+            player.add_card(&card, &board, &hotel_chain_manager);
+        }
+    }
+    bank.update_largest_shareholders(&players);
+    let current_player = match &position["current_player"] {
+        Value::Null => 0,
+        index => index
+            .as_u64()
+            .map(|index| index as usize)
+            .filter(|index| *index < players.len())
+            .ok_or_else(|| miette!("Invalid current player {}", index))?,
+    };
+    Ok(Playout::from_position(
+        board,
+        bank,
+        hotel_chain_manager,
+        players,
+        position_cards,
+        current_player,
+        rng.gen(),
+    ))
+}
+
+/// Reads a position like `"B7"`
+fn position_of(position: &Value) -> Result<Position> {
+    position
+        .as_str()
+        .and_then(Position::from_name)
+        .ok_or_else(|| miette!("Invalid position {}", position))
+}
+
+/// What happens while a turn of the [`Engine`] is played, shared by the seats of the turn.
+#[derive(Default)]
+struct TurnContext {
+    /// The actions that have already been made in the turn and are made again
+    actions: VecDeque<Value>,
+    /// The number of decisions that have been made before an external player has to decide
+    decisions: usize,
+    /// The game as the external player that decides next sees it
+    state: Value,
+    /// The decision of an external player the turn waits for
+    waiting: Option<Pending>,
+    /// Why the action of the external player can not be applied
+    rejected: Option<String>,
+    /// Why the game can not go on
+    failed: Option<String>,
+}
+
+/// Takes a seat while a turn of the [`Engine`] is played.
+///
+/// The decisions that have already been made in the turn are made again. Then the bot of the
+/// seat is asked, or the turn stops when the seat is taken by an external player. Once the turn
+/// has stopped a fallback is returned for every decision, the turn is dropped afterwards.
+struct Seat {
+    /// The bot that takes the seat, `None` for an external player
+    bot: Option<Arc<dyn Bot>>,
+    context: Arc<Mutex<TurnContext>>,
+}
+
+impl Seat {
+    /// Stops the turn and the game because it can not go on
+    fn fail(&self, reason: String) {
+        let mut context = self.context.lock().unwrap();
+        if context.waiting.is_none() && context.rejected.is_none() {
+            context.failed = Some(reason);
+        }
+    }
+
+    /// Makes the decision.
+    /// # Arguments
+    /// * `request` - What has to be decided, shown to the external player
+    /// * `parse` - Reads the choice from the action, the error is returned by [`Engine::apply`]
+    /// * `fallback` - Is returned once the turn has stopped
+    /// * `think` - Asks the bot of the seat
+    fn decide<T>(
+        &self,
+        player: &Player,
+        request: Value,
+        parse: impl Fn(&Value) -> Result<T, String>,
+        fallback: T,
+        think: impl FnOnce(&dyn Bot) -> Decision<T>,
+    ) -> Decision<T> {
+        let mut context = self.context.lock().unwrap();
+        if context.waiting.is_some() || context.rejected.is_some() || context.failed.is_some() {
+            return Decision::new(fallback, "the turn has stopped");
+        }
+        if let Some(action) = context.actions.pop_front() {
+            context.decisions += 1;
+            return match parse(&action) {
+                Ok(choice) => Decision::new(choice, "decided before"),
+                Err(reason) => {
+                    context.rejected = Some(reason);
+                    Decision::new(fallback, "the action has been rejected")
+                }
+            };
+        }
+        match &self.bot {
+            Some(bot) => {
+                context.decisions += 1;
+                // The bots may think on several threads
+                drop(context);
+                think(bot.as_ref())
+            }
+            None => {
+                context.waiting = Some(Pending::Decision {
+                    player_id: player.id,
+                    state: std::mem::take(&mut context.state),
+                    request,
+                });
+                Decision::new(fallback, "waiting for the player")
+            }
+        }
+    }
+}
+
+impl Bot for Seat {
+    fn choose_card(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<Position> {
+        let cards: Vec<Position> = player
+            .analyzed_cards
+            .iter()
+            .filter(|card| !card.is_illegal())
+            .map(|card| card.position)
+            .collect();
+        let fallback = match cards.first() {
+            Some(card) => *card,
+            None => {
+                self.fail(format!("{} has no card that can be played", player.name));
+                // The turn is stopped, the card is never placed
+                return Decision::new(Position::new('A', 1), "no card can be played");
+            }
+        };
+        let names: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+        self.decide(
+            player,
+            json!({"type": "place_card", "cards": names}),
+            |action| {
+                action["card"]
+                    .as_str()
+                    .and_then(Position::from_name)
+                    .filter(|card| cards.contains(card))
+                    .ok_or_else(|| format!("{} is not a card that can be played", action["card"]))
+            },
+            fallback,
+            |bot| bot.choose_card(player, bank, hotel_chain_manager),
+        )
+    }
+
+    fn choose_chain_to_found(
+        &self,
+        player: &Player,
+        available_chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        self.decide(
+            player,
+            json!({"type": "found_chain", "chains": chain_names(available_chains)}),
+            |action| chain_of(action, available_chains),
+            available_chains[0],
+            |bot| bot.choose_chain_to_found(player, available_chains, bank, hotel_chain_manager),
+        )
+    }
+
+    fn choose_surviving_chain(
+        &self,
+        player: &Player,
+        chains: &[HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        self.decide(
+            player,
+            json!({"type": "choose_surviving_chain", "chains": chain_names(chains)}),
+            |action| chain_of(action, chains),
+            chains[0],
+            |bot| bot.choose_surviving_chain(player, chains, bank, hotel_chain_manager),
+        )
+    }
+
+    fn handle_fusion_stocks(
+        &self,
+        player: &Player,
+        dead: &HotelChain,
+        alive: &HotelChain,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<(u32, u32)> {
+        let stocks = *player.owned_stocks.stocks_for_hotel(dead);
+        let exchangeable = *bank.stocks_for_sale.stocks_for_hotel(alive) * 2;
+        self.decide(
+            player,
+            json!({
+                "type": "handle_fusion_stocks",
+                "dead": dead.name(),
+                "alive": alive.name(),
+                "stocks": stocks,
+                "exchangeable": exchangeable.min(stocks - stocks % 2),
+            }),
+            |action| {
+                let number = |key: &str| match &action[key] {
+                    Value::Null => Ok(0),
+                    value => u32_of(value).ok_or_else(|| format!("Invalid number {}", value)),
+                };
+                let (exchange, sell) = (number("exchange")?, number("sell")?);
+                if !exchange.is_multiple_of(2) {
+                    return Err(format!("{} stocks can not be exchanged 2:1", exchange));
+                }
+                if exchange > exchangeable {
+                    return Err(format!(
+                        "Only {} stocks can be exchanged, the bank has not enough stocks of {}",
+                        exchangeable,
+                        alive.name()
+                    ));
+                }
+                if exchange + sell > stocks {
+                    return Err(format!(
+                        "The player only has {} stocks of {}",
+                        stocks,
+                        dead.name()
+                    ));
+                }
+                Ok((exchange, sell))
+            },
+            (0, 0),
+            |bot| bot.handle_fusion_stocks(player, dead, alive, bank, hotel_chain_manager),
+        )
+    }
+
+    fn buy_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        let limit = PURCHASE_LIMIT;
+        self.decide(
+            player,
+            json!({"type": "buy_stocks", "limit": limit}),
+            |action| {
+                let stocks = stocks_of(action)?;
+                if stocks.values().sum::<u32>() > limit {
+                    return Err(format!("At most {} stocks can be bought", limit));
+                }
+                let mut bank = bank.clone();
+                let mut player = player.headless_copy();
+                bank.transaction(&mut player)
+                    .buy_stocks(hotel_chain_manager, &stocks)
+                    .map_err(|err| err.to_string())?;
+                Ok(stocks)
+            },
+            HashMap::new(),
+            |bot| bot.buy_stocks(player, bank, hotel_chain_manager),
+        )
+    }
+
+    fn sell_stocks(
+        &self,
+        player: &Player,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        self.decide(
+            player,
+            json!({"type": "sell_stocks"}),
+            |action| {
+                let stocks = stocks_of(action)?;
+                let mut bank = bank.clone();
+                let mut player = player.headless_copy();
+                let mut transaction = bank.transaction(&mut player);
+                for (chain, amount) in &stocks {
+                    transaction
+                        .sell_stock(*amount, chain, hotel_chain_manager)
+                        .map_err(|err| err.to_string())?;
+                }
+                Ok(stocks)
+            },
+            HashMap::new(),
+            |bot| bot.sell_stocks(player, bank, hotel_chain_manager),
+        )
+    }
+
+    fn end_game(
+        &self,
+        player: &Player,
+        players: &[Player],
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<bool> {
+        self.decide(
+            player,
+            json!({"type": "end_game"}),
+            |action| {
+                action["end"]
+                    .as_bool()
+                    .ok_or_else(|| String::from("end has to be true or false"))
+            },
+            true,
+            |bot| bot.end_game(player, players, hotel_chain_manager),
+        )
+    }
+
+    fn observe(&self, player: &Player, board: &Board, players: &[Player]) {
+        if let Some(bot) = &self.bot {
+            bot.observe(player, board, players);
+        }
+    }
+
+    fn observe_state(&self, state: &dyn Fn() -> Value) {
+        match &self.bot {
+            Some(bot) => bot.observe_state(state),
+            None => {
+                let mut context = self.context.lock().unwrap();
+                // The state is only shown when the external player decides next
+                if context.actions.is_empty() && context.waiting.is_none() {
+                    context.state = state();
+                }
+            }
+        }
+    }
+}
+
+/// Returns the names of the chains
+fn chain_names(chains: &[HotelChain]) -> Vec<&str> {
+    chains.iter().map(|chain| chain.name()).collect()
+}
+
+/// Reads the chain of the action, it has to be one of the chains
+pub(crate) fn chain_of(action: &Value, chains: &[HotelChain]) -> Result<HotelChain, String> {
+    action["chain"]
+        .as_str()
+        .and_then(HotelChain::from_name)
+        .filter(|chain| chains.contains(chain))
+        .ok_or_else(|| {
+            format!(
+                "{} is not one of the chains {}",
+                action["chain"],
+                chain_names(chains).join(", ")
+            )
+        })
+}
+
+/// Reads a number that has to fit into an `u32`, larger numbers are invalid
+fn u32_of(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|number| u32::try_from(number).ok())
+}
+
+/// Reads the stocks of the action like `{"stocks": {"Luxor": 2}}`
+pub(crate) fn stocks_of(action: &Value) -> Result<HashMap<HotelChain, u32>, String> {
+    let mut stocks = HashMap::new();
+    for (name, amount) in action["stocks"]
+        .as_object()
+        .ok_or_else(|| String::from("The stocks are missing"))?
+    {
+        let chain = HotelChain::from_name(name).ok_or_else(|| format!("Unknown chain {}", name))?;
+        let amount =
+            u32_of(amount).ok_or_else(|| format!("Invalid number of stocks of {}", name))?;
+        stocks.insert(chain, amount);
+    }
+    Ok(stocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, VecDeque},
+        sync::{Arc, Mutex},
+    };
+
+    use miette::Result;
+    use serde_json::{json, Value};
+
+    use crate::{
+        base_game::{
+            bank::Bank,
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+        },
+        bot::Bot,
+        game::hotel_chain_manager::HotelChainManager,
+    };
+
+    use super::{stocks_of, Engine, Seat, TurnContext};
+
+    #[test]
+    fn external_players_play_the_game() -> Result<()> {
+        let mut engine = Engine::new(&json!({"seats": ["external", "greedy"], "seed": 7}))?;
+        assert!(engine.apply(&json!({"type": "sell_stocks"})).is_err());
+        let mut decisions = 0;
+        while engine.state()["status"] == "running" {
+            let state = engine.state();
+            assert_eq!(state["player"], 0);
+            let decision = &state["decision"];
+            let action = match decision["type"].as_str().unwrap() {
+                "place_card" => {
+                    assert!(engine
+                        .apply(&json!({"type": "place_card", "card": "Z9"}))
+                        .is_err());
+                    json!({"type": "place_card", "card": decision["cards"][0]})
+                }
+                "found_chain" | "choose_surviving_chain" => {
+                    json!({"type": decision["type"], "chain": decision["chains"][0]})
+                }
+                "handle_fusion_stocks" => {
+                    json!({"type": "handle_fusion_stocks", "exchange": 0, "sell": decision["stocks"]})
+                }
+                "buy_stocks" => {
+                    let chains = state["state"]["chains"].as_array().unwrap();
+                    let money = &state["state"]["players"][0]["money"];
+                    let stocks: serde_json::Map<String, Value> = chains
+                        .iter()
+                        .filter(|chain| {
+                            chain["price"].as_u64() <= money.as_u64()
+                                && chain["stocks_available"] != 0
+                        })
+                        .take(1)
+                        .map(|chain| (chain["chain"].as_str().unwrap().to_string(), json!(1)))
+                        .collect();
+                    assert!(engine
+                        .apply(&json!({"type": "buy_stocks", "stocks": {"Luxor": 9}}))
+                        .is_err());
+                    json!({"type": "buy_stocks", "stocks": stocks})
+                }
+                "end_game" => json!({"type": "end_game", "end": true}),
+                other => panic!("Unexpected decision {}", other),
+            };
+            engine.apply(&action)?;
+            decisions += 1;
+        }
+        let state = engine.state();
+        assert_eq!(state["status"], "ended");
+        assert_eq!(state["names"], json!(["Player 1", "Bot 2"]));
+        assert_eq!(state["money"].as_array().unwrap().len(), 2);
+        assert!(decisions > 10);
+        assert!(engine
+            .apply(&json!({"type": "end_game", "end": true}))
+            .is_err());
+        assert!(Engine::new(&json!({"seats": ["external", "nobody"]})).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_input_does_not_panic() {
+        assert!(Engine::new(
+            &json!({"seats": ["external", "monte-carlo"], "bot_playouts": 4_294_967_296u64})
+        )
+        .is_err());
+        assert!(stocks_of(&json!({"stocks": {"Luxor": 4_294_967_298u64}})).is_err());
+        // A player without a card that can be played stops the game instead of panicking
+        let context = Arc::new(Mutex::new(TurnContext::default()));
+        let seat = Seat {
+            bot: None,
+            context: Arc::clone(&context),
+        };
+        let player = Player::new(vec![], 0, false, String::from("Player 1"));
+        seat.choose_card(&player, &Bank::new(), &HotelChainManager::new());
+        let context = context.lock().unwrap();
+        assert!(context.waiting.is_none());
+        assert_eq!(
+            context.failed.as_deref(),
+            Some("Player 1 has no card that can be played")
+        );
+    }
+
+    #[test]
+    fn stocks_can_be_sold_in_the_buy_phase() -> Result<()> {
+        let mut board = Board::new();
+        let mut bank = Bank::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+        hotel_chain_manager.start_chain(
+            HotelChain::Luxor,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut player,
+            &mut bank,
+        )?;
+        let mut transaction = bank.transaction(&mut player);
+        transaction.buy_stocks(
+            &hotel_chain_manager,
+            &HashMap::from([(HotelChain::Luxor, 3)]),
+        )?;
+        transaction.commit();
+        let money = player.money;
+        let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Luxor);
+        // The player sells one of their four stocks
+        player.bot = Some(Box::new(Seat {
+            bot: None,
+            context: Arc::new(Mutex::new(TurnContext {
+                actions: VecDeque::from([json!({"stocks": {"Luxor": 1}})]),
+                ..TurnContext::default()
+            })),
+        }));
+        let sold = player.sell_stocks(&mut bank, &hotel_chain_manager)?;
+        assert_eq!(sold, Some(HashMap::from([(HotelChain::Luxor, 1)])));
+        assert_eq!(*player.owned_stocks.stocks_for_hotel(&HotelChain::Luxor), 3);
+        assert_eq!(player.money, money + price);
+        assert_eq!(
+            *bank.stocks_available(&HotelChain::Luxor, &hotel_chain_manager),
+            22
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+
+use crate::{
+    base_game::{bank::Bank, board::Board, hotel_chains::HotelChain, player::Player},
+    bot::{expected_bonus, majority_lead},
+    game::hotel_chain_manager::HotelChainManager,
+};
+
+/// The share of the majority shareholder bonus that is counted when the majority can still be
+/// taken over by another player.
+const CONTESTED_BONUS_SHARE: f64 = 0.75;
+
+/// The maximum number of hotels by which a chain is expected to grow until the end of the game.
+const MAX_EXPECTED_GROWTH: u32 = 5;
+
+/// The share of the possible increase in stock value that is counted. The growth of a chain is
+/// not certain, other players can block it or fuse the chain.
+const GROWTH_SHARE: f64 = 0.5;
+
+/// The parts of the game that are needed to evaluate the position of a player.
+pub struct GameState<'a> {
+    pub board: &'a Board,
+    pub bank: &'a Bank,
+    pub hotel_chain_manager: &'a HotelChainManager,
+    /// The players of the game, the index is the id of the player
+    pub players: &'a [Player],
+}
+
+impl<'a> GameState<'a> {
+    pub fn new(
+        board: &'a Board,
+        bank: &'a Bank,
+        hotel_chain_manager: &'a HotelChainManager,
+        players: &'a [Player],
+    ) -> Self {
+        Self {
+            board,
+            bank,
+            hotel_chain_manager,
+            players,
+        }
+    }
+}
+
+/// Estimates how much money the player will have at the end of the game.
+///
+/// The estimate combines:
+/// * the money of the player
+/// * the current value of their stocks
+/// * the majority shareholder bonuses they would get, bonuses that can still be lost are only
+///   partly counted
+/// * the increase in stock value when the chains grow into the free positions around them
+///
+/// The values of different players can be compared to see who is ahead.
+pub fn evaluate(state: &GameState, player_id: u32) -> f64 {
+    let player = &state.players[player_id as usize];
+    let mut value = player.money as f64;
+    for chain in HotelChain::iterator() {
+        if !state.hotel_chain_manager.chain_status(chain) {
+            continue;
+        }
+        let stocks = *player.owned_stocks.stocks_for_hotel(chain);
+        let price = Bank::stock_price(state.hotel_chain_manager, chain);
+        value += (stocks * price) as f64;
+        value += majority_value(player, chain, state);
+        let length = state.hotel_chain_manager.chain_length(chain);
+        let growth = growth_room(chain, state).min(MAX_EXPECTED_GROWTH);
+        let increase = chain.stock_value(length + growth) - price;
+        value += (stocks * increase) as f64 * GROWTH_SHARE;
+    }
+    value
+}
+
+/// Returns the majority shareholder bonus that is expected for the player.
+/// The bonus is fully counted when the other players can no longer buy enough stocks to take
+/// over the majority.
+fn majority_value(player: &Player, chain: &HotelChain, state: &GameState) -> f64 {
+    let bonus = expected_bonus(player, chain, state.bank, state.hotel_chain_manager) as f64;
+    let available = *state
+        .bank
+        .stocks_available(chain, state.hotel_chain_manager) as i64;
+    if majority_lead(player, chain, state.bank) > available {
+        bonus
+    } else {
+        bonus * CONTESTED_BONUS_SHARE
+    }
+}
+
+/// Returns the number of free positions next to the hotels of the chain.
+fn growth_room(chain: &HotelChain, state: &GameState) -> u32 {
+    let mut free = HashSet::new();
+    for position in state.hotel_chain_manager.chain_positions(chain) {
+        for neighbour in position.neighbours() {
+            if state.board.is_hotel_placed(&neighbour).is_none() {
+                free.insert(neighbour);
+            }
+        }
+    }
+    free.len() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::{
+            bank::Bank,
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+        },
+        game::hotel_chain_manager::HotelChainManager,
+    };
+
+    use super::{evaluate, GameState};
+
+    #[test]
+    fn evaluation_counts_stocks_and_majorities() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Player 1")),
+            Player::new(vec![], 1, false, String::from("Player 2")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Imperial,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        players[0].owned_stocks.set_stocks(&HotelChain::Imperial, 4);
+        players[1].owned_stocks.set_stocks(&HotelChain::Imperial, 1);
+        bank.update_largest_shareholders(&players);
+        let state = GameState::new(&board, &bank, &hotel_chain_manager, &players);
+        let majority = evaluate(&state, 0);
+        let minority = evaluate(&state, 1);
+        let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Imperial) as f64;
+        // Both players have the same money, the majority and the stocks make the difference
+        assert!(majority > players[0].money as f64 + 4.0 * price);
+        assert!(minority > players[1].money as f64 + price);
+        assert!(majority > minority);
+        Ok(())
+    }
+}
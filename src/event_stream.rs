@@ -0,0 +1,80 @@
+use std::{fs::File, io::Write};
+
+use miette::{miette, Result};
+use serde_json::{json, Value};
+
+use crate::{
+    base_game::player::Player,
+    game::{player_by_id, round::GameEvent},
+};
+
+/// The file or pipe to which every event of the game is written as a line of json while the game
+/// runs, so that other programs can follow the game.
+///
+/// Each line looks like this, `player_id` and `player` are `null` for the turn of the neutral
+/// player:
+/// ```json
+/// {"player": "LMH01", "player_id": 1, "round": 3, "text": "placed a hotel on B7"}
+/// ```
+pub struct EventStream {
+    file: File,
+}
+
+impl EventStream {
+    /// Creates the file to which the events are written.
+    ///
+    /// When the path is a named pipe this waits until another program opens the pipe for reading.
+    pub fn create(path: &str) -> Result<Self> {
+        let file = File::create(path)
+            .map_err(|err| miette!("Unable to open event stream {}: {}", path, err))?;
+        Ok(Self { file })
+    }
+
+    /// Writes the event as a line of json. A message is printed when the event can not be
+    /// written, the game goes on.
+    pub fn write(&self, event: &GameEvent, players: &[Player]) {
+        let line = format!("{}\n", event_json(event, players));
+        if let Err(err) = (&self.file).write_all(line.as_bytes()) {
+            println!("Unable to write the event stream: {}", err);
+        }
+    }
+}
+
+/// Returns the line of the event stream that contains the event
+fn event_json(event: &GameEvent, players: &[Player]) -> Value {
+    let player = event
+        .player_id
+        .and_then(|id| player_by_id(id, players))
+        .map(|player| player.name.clone());
+    json!({
+        "round": event.round,
+        "player_id": event.player_id,
+        "player": player,
+        "text": event.text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{base_game::player::Player, game::round::GameEvent};
+
+    use super::event_json;
+
+    #[test]
+    fn events_are_written_as_json() {
+        let players = vec![Player::new(vec![], 0, false, String::from("LMH01"))];
+        let mut event = GameEvent {
+            round: 3,
+            player_id: Some(0),
+            text: String::from("placed a hotel on B7"),
+        };
+        assert_eq!(
+            event_json(&event, &players),
+            json!({"round": 3, "player_id": 0, "player": "LMH01", "text": "placed a hotel on B7"})
+        );
+        event.player_id = None;
+        assert_eq!(event_json(&event, &players)["player"], json!(null));
+    }
+}
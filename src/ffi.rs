@@ -0,0 +1,221 @@
+use std::{
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use miette::{miette, Result};
+use serde_json::Value;
+
+use crate::engine::Engine;
+
+/// Creates a new game from the configuration json, see [`Engine::new`].
+/// Returns null when the game can not be created.
+///
+/// # Safety
+/// `config` has to be a valid null terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn acquire_game_new(config: *const c_char) -> *mut Engine {
+    catch_panic(ptr::null_mut, || {
+        match read_json(config).and_then(|config| Engine::new(&config)) {
+            Ok(engine) => Box::into_raw(Box::new(engine)),
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// Returns the state of the game as json, see [`Engine::state`].
+/// Returns null when the game is null, has been poisoned or the state can not be created.
+///
+/// # Safety
+/// `game` has to be a game that has been created with [`acquire_game_new`] and not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn acquire_game_state(game: *const Engine) -> *mut c_char {
+    let poisoned = || {
+        poison(game);
+        ptr::null_mut()
+    };
+    catch_panic(poisoned, || match game.as_ref() {
+        Some(game) if !game.is_poisoned() => into_c_string(game.state().to_string()),
+        _ => ptr::null_mut(),
+    })
+}
+
+/// Applies the action json of the player that has to decide, see [`Engine::apply`].
+/// Returns null when the action has been applied, otherwise the reason why it is not valid or
+/// why the game can not be used anymore.
+///
+/// # Safety
+/// `game` has to be a game that has been created with [`acquire_game_new`] and not been freed,
+/// `action` has to be a valid null terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn acquire_game_apply(
+    game: *mut Engine,
+    action: *const c_char,
+) -> *mut c_char {
+    let stopped = || {
+        into_c_string(String::from(
+            "The engine has stopped with an internal error",
+        ))
+    };
+    let poisoned = || {
+        poison(game);
+        stopped()
+    };
+    catch_panic(poisoned, || {
+        let game = match game.as_mut() {
+            Some(game) if game.is_poisoned() => return stopped(),
+            Some(game) => game,
+            None => return into_c_string(String::from("The game is null")),
+        };
+        match read_json(action).and_then(|action| game.apply(&action)) {
+            Ok(()) => ptr::null_mut(),
+            Err(err) => into_c_string(err.to_string()),
+        }
+    })
+}
+
+/// Frees the game, nothing happens when it is null.
+///
+/// # Safety
+/// `game` has to be created with [`acquire_game_new`] and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn acquire_game_free(game: *mut Engine) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// Frees a string that has been returned by the library, nothing happens when it is null.
+///
+/// # Safety
+/// `string` has to be returned by the library and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn acquire_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Runs the function and returns the fallback when it panics, a panic must not unwind into the
+/// program that calls the library.
+fn catch_panic<T>(fallback: impl FnOnce() -> T, function: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(function)).unwrap_or_else(|_| fallback())
+}
+
+/// Poisons the game after it has panicked, every later call returns an error until the game is
+/// freed. Nothing happens when it is null.
+///
+/// # Safety
+/// `game` has to be null or a game that has been created with [`acquire_game_new`] and not been
+/// freed.
+unsafe fn poison(game: *const Engine) {
+    if let Some(game) = game.as_ref() {
+        game.poison();
+    }
+}
+
+/// Reads the json from the string
+///
+/// # Safety
+/// `string` has to be null or a valid null terminated string.
+unsafe fn read_json(string: *const c_char) -> Result<Value> {
+    if string.is_null() {
+        return Err(miette!("The json is null"));
+    }
+    let string = CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| miette!("The json is not valid utf-8"))?;
+    serde_json::from_str(string).map_err(|err| miette!("The json is not valid: {}", err))
+}
+
+/// Hands the string over to the caller
+fn into_c_string(string: String) -> *mut c_char {
+    // A null character would end the string early, so it is escaped the way json escapes it.
+    // Returning null would read as success in acquire_game_apply.
+    match CString::new(string.replace('\0', "\\u0000")) {
+        Ok(string) => string.into_raw(),
+        Err(_) => CString::from(c"The string contains a null character").into_raw(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString},
+        ptr,
+    };
+
+    use serde_json::Value;
+
+    use super::{
+        acquire_game_apply, acquire_game_free, acquire_game_new, acquire_game_state,
+        acquire_string_free, catch_panic, into_c_string, poison,
+    };
+
+    #[test]
+    fn games_are_played_through_the_c_interface() {
+        let config = CString::new(r#"{"seats": ["external", "random"], "seed": 3}"#).unwrap();
+        unsafe {
+            let game = acquire_game_new(config.as_ptr());
+            assert!(!game.is_null());
+            let state = acquire_game_state(game);
+            let json: Value =
+                serde_json::from_str(CStr::from_ptr(state).to_str().unwrap()).unwrap();
+            acquire_string_free(state);
+            assert_eq!(json["decision"]["type"], "place_card");
+            let card = json["decision"]["cards"][0].as_str().unwrap();
+            let action =
+                CString::new(format!(r#"{{"type": "place_card", "card": "{}"}}"#, card)).unwrap();
+            assert!(acquire_game_apply(game, action.as_ptr()).is_null());
+            let error = acquire_game_apply(game, action.as_ptr());
+            assert!(!error.is_null());
+            acquire_string_free(error);
+            let invalid = CString::new("{").unwrap();
+            assert!(acquire_game_new(invalid.as_ptr()).is_null());
+            assert!(acquire_game_new(ptr::null()).is_null());
+            acquire_game_free(game);
+        }
+    }
+
+    #[test]
+    fn null_characters_are_escaped() {
+        let string = into_c_string(String::from("Player\0 1"));
+        assert!(!string.is_null());
+        unsafe {
+            assert_eq!(CStr::from_ptr(string).to_str().unwrap(), "Player\\u0000 1");
+            acquire_string_free(string);
+        }
+    }
+
+    #[test]
+    fn panics_do_not_unwind_into_the_caller() {
+        assert_eq!(catch_panic(|| 0, || panic!("The engine is broken")), 0);
+        assert_eq!(catch_panic(|| 0, || 1), 1);
+    }
+
+    #[test]
+    fn poisoned_games_can_not_be_used() {
+        let config = CString::new(r#"{"seats": ["external", "random"], "seed": 3}"#).unwrap();
+        unsafe {
+            let game = acquire_game_new(config.as_ptr());
+            let state = acquire_game_state(game);
+            let json: Value =
+                serde_json::from_str(CStr::from_ptr(state).to_str().unwrap()).unwrap();
+            acquire_string_free(state);
+            let card = json["decision"]["cards"][0].as_str().unwrap();
+            let action =
+                CString::new(format!(r#"{{"type": "place_card", "card": "{}"}}"#, card)).unwrap();
+            // The game is poisoned as if the engine had panicked
+            poison(game);
+            assert!(acquire_game_state(game).is_null());
+            let error = acquire_game_apply(game, action.as_ptr());
+            assert_eq!(
+                CStr::from_ptr(error).to_str().unwrap(),
+                "The engine has stopped with an internal error"
+            );
+            acquire_string_free(error);
+            acquire_game_free(game);
+        }
+    }
+}
@@ -1,37 +1,59 @@
 /// Contains all functionalities that are required to play the game.
 use std::{
-    collections::HashMap,
+    collections::VecDeque,
+    fs,
     io::{stdin, stdout, Write},
+    time::Duration,
 };
 
 use miette::{miette, IntoDiagnostic, Result};
 use owo_colors::{AnsiColors, OwoColorize, Rgb};
-use rand::Rng;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 
 use crate::{
     base_game::{
         bank::Bank,
-        board::{letter::LETTERS, Board, Position},
+        board::{Board, Position, COLUMNS, ROWS},
         player::Player,
-        settings::Settings,
+        settings::{Settings, TurnOrder},
         stock::STOCK_BASE_PRICE,
+        ui::{money_statistics_ui, price_chart_ui, profit_statistics_ui, set_plain},
     },
-    network::{broadcast, broadcast_others, ClientPlayer},
+    bot::BotStrategy,
+    history::{append_results, game_results, today},
+    journal::journal_turn,
+    locale::{set_language, text},
+    logic::can_game_continue,
+    network::{broadcast, broadcast_others, send_string, ClientPlayer, SentUi},
+    report::export_report,
+    summary::{export_finances, export_summary},
+    tutorial::TutorialEvent,
+    utils::unique_name,
 };
 
-use self::{hotel_chain_manager::HotelChainManager, round::Round};
+#[cfg(feature = "terminal")]
+use crate::webhook::send_results;
+
+use self::{
+    hotel_chain_manager::HotelChainManager,
+    round::{GameEvent, Round, RoundFinances, TurnProgress},
+};
 
 /// Contains all variables required to play a game.\
 /// This is the main interface to access game functions. Everything that happens in the game
 /// will run through this object.\
 /// A new game can be started this way:
-/// ```
-/// use game::game::GameManager;
+/// ```no_run
+/// use acquire_rs::{
+///     base_game::settings::{Privacy, Settings},
+///     game::GameManager,
+/// };
 ///
-///     let number_of_players = 3;
-///     let large_board = false;
-///     let game_manager = GameManager::new(number_of_players, large_board);
-///     game_manager.start_game();
+/// let number_of_players = 3;
+/// let settings = Settings::new(false, Privacy::default(), false);
+/// let mut game_manager = GameManager::new(number_of_players, settings)?;
+/// game_manager.start_game()?;
+/// # Ok::<(), miette::Report>(())
 /// ```
 pub struct GameManager {
     /// The board that belongs to this game
@@ -40,16 +62,24 @@ pub struct GameManager {
     pub bank: Bank,
     /// The hotel manager for this game
     pub hotel_chain_manager: HotelChainManager,
-    /// The positions that can be drawn
-    pub position_cards: Vec<Position>,
-    /// A vector that contains all players that participate in the game
+    /// The positions that can be drawn, they are shuffled when the game is created and drawn
+    /// from the front
+    pub position_cards: VecDeque<Position>,
+    /// A vector that contains all players that participate in the game.
+    /// Once the game has started the index of each player is their id.
     pub players: Vec<Player>,
     /// Stores if the game has been started
     game_started: bool,
     /// Stores the settings
     pub settings: Settings,
-    /// Stores if the game is ran as server
-    pub server: bool,
+    /// The money and the value of the stocks of each player at the end of each round
+    pub finances: Vec<RoundFinances>,
+    /// What has happened in each turn of the game
+    pub events: Vec<GameEvent>,
+    /// The board at the end of each round
+    pub boards: Vec<Board>,
+    /// Shuffles the cards, draws the turn order and seeds the bots, see [`Settings::seed`]
+    rng: StdRng,
 }
 
 impl GameManager {
@@ -59,22 +89,37 @@ impl GameManager {
         if !(2..=6).contains(&number_of_players) {
             return Err(miette!("Unable to create new game: The amount of players is invalid. Valid: 2-6, entered: {}", number_of_players));
         }
+        if settings.neutral_player && number_of_players != 2 {
+            return Err(miette!("Unable to create new game: The neutral player can only be used in games with 2 players, entered: {}", number_of_players));
+        }
+        if settings.teams && number_of_players != 4 && number_of_players != 6 {
+            return Err(miette!("Unable to create new game: Teams can only be used in games with 4 or 6 players, entered: {}", number_of_players));
+        }
+        if settings.bots > number_of_players {
+            return Err(miette!("Unable to create new game: There can not be more bots than players. Players: {}, bots: {}", number_of_players, settings.bots));
+        }
 
-        let mut position_cards = GameManager::init_position_cards();
-        let players = GameManager::init_players(
-            number_of_players,
-            &mut position_cards,
-            settings.small_board,
-        )?;
+        let mut bank = Bank::new();
+        bank.rules = settings.rules;
+        let hotel_chain_manager = HotelChainManager::new();
+        let mut rng = game_rng(&settings);
+        let mut position_cards = GameManager::shuffled_position_cards(&mut rng);
+        let mut players =
+            GameManager::init_players(number_of_players, &mut position_cards, &settings, &mut rng)?;
+        set_starting_money(&mut players, &settings);
+        set_first_player_color(&mut players, settings.player_color);
         Ok(Self {
             board: Board::new(),
             position_cards,
-            bank: Bank::new(),
-            hotel_chain_manager: HotelChainManager::new(),
+            bank,
+            hotel_chain_manager,
             players,
             game_started: false,
             settings,
-            server: false,
+            finances: Vec::new(),
+            events: Vec::new(),
+            boards: Vec::new(),
+            rng,
         })
     }
 
@@ -85,29 +130,148 @@ impl GameManager {
         settings: Settings,
         host_name: String,
     ) -> Result<Self> {
-        let mut position_cards = GameManager::init_position_cards();
-        let players = GameManager::init_players_lan(
+        // The host, the client players and the bots
+        let number_of_players = client_players.len() as u32 + 1 + settings.bots;
+        if number_of_players > 6 {
+            return Err(miette!("Unable to create new game: The amount of players is invalid. Valid: 2-6, entered: {}", number_of_players));
+        }
+        if settings.neutral_player && number_of_players != 2 {
+            return Err(miette!("Unable to create new game: The neutral player can only be used in games with 2 players, entered: {}", number_of_players));
+        }
+        if settings.hot_seat {
+            return Err(miette!(
+                "Unable to create new game: The hot-seat mode can not be used in lan games"
+            ));
+        }
+        if settings.teams && number_of_players != 4 && number_of_players != 6 {
+            return Err(miette!("Unable to create new game: Teams can only be used in games with 4 or 6 players, entered: {}", number_of_players));
+        }
+        let mut bank = Bank::new();
+        bank.rules = settings.rules;
+        let hotel_chain_manager = HotelChainManager::new();
+        let mut rng = game_rng(&settings);
+        let mut position_cards = GameManager::shuffled_position_cards(&mut rng);
+        let mut players = GameManager::init_players_lan(
             &mut client_players,
             &mut position_cards,
             &settings,
             host_name,
+            &mut rng,
         )?;
+        set_starting_money(&mut players, &settings);
+        set_first_player_color(&mut players, settings.player_color);
         Ok(Self {
             board: Board::new(),
             position_cards,
-            bank: Bank::new(),
-            hotel_chain_manager: HotelChainManager::new(),
+            bank,
+            hotel_chain_manager,
             players,
             game_started: false,
             settings,
-            server: true,
+            finances: Vec::new(),
+            events: Vec::new(),
+            boards: Vec::new(),
+            rng,
         })
     }
 
+    /// Creates a new server game with the players and the settings of this game after it has
+    /// ended. The clients stay connected and every player keeps their seat.
+    pub fn rematch(mut self) -> Result<Self> {
+        // The rematch is dealt differently but can be played again with the seed of this game
+        self.settings.seed = Some(self.rng.gen());
+        let host_name = self.players[0].name.clone();
+        let mut client_players: Vec<ClientPlayer> = self
+            .players
+            .into_iter()
+            .filter_map(ClientPlayer::from_player)
+            .collect();
+        // The clients are seated from the end of the list
+        client_players.reverse();
+        GameManager::new_server(client_players, self.settings, host_name)
+    }
+
+    /// Creates a game that continues from a position of another game, like a position of a
+    /// replay. The game is continued with [`GameManager::continue_game`].
+    /// # Arguments
+    /// * `position_cards` - The cards that can be drawn, the first card is drawn next
+    /// * `players` - The players in the order in which they play, the index has to be the id of
+    ///   the player. The first player has the next turn.
+    pub fn from_position(
+        board: Board,
+        bank: Bank,
+        hotel_chain_manager: HotelChainManager,
+        position_cards: VecDeque<Position>,
+        players: Vec<Player>,
+        settings: Settings,
+    ) -> Self {
+        let rng = game_rng(&settings);
+        Self {
+            board,
+            position_cards,
+            bank,
+            hotel_chain_manager,
+            players,
+            game_started: false,
+            settings,
+            finances: Vec::new(),
+            events: Vec::new(),
+            boards: Vec::new(),
+            rng,
+        }
+    }
+
+    /// Continues the game that has been created with [`GameManager::from_position`], the first
+    /// player has the next turn.
+    /// Returns an Error when the game has already been started.
+    pub fn continue_game(&mut self) -> Result<()> {
+        self.resume_game(1, 0, TurnProgress::default())
+    }
+
+    /// Continues the game that has been created with [`GameManager::from_position`] in the
+    /// middle of a round.
+    /// Returns an Error when the game has already been started.
+    /// # Arguments
+    /// * `round` - The number of the round that is continued
+    /// * `first_player` - The index of the player that has the next turn, the players before
+    ///   have already played in this round
+    /// * `progress` - How far the turn of the first player has already been played
+    pub fn resume_game(
+        &mut self,
+        round: u32,
+        first_player: usize,
+        progress: TurnProgress,
+    ) -> Result<()> {
+        if self.game_started {
+            return Err(miette!(
+                "Unable to continue game: Game has already been started!"
+            ));
+        }
+        self.game_started = true;
+        set_language(self.settings.language);
+        set_plain(self.settings.plain);
+        broadcast(
+            &text(
+                "game.continuing",
+                &[
+                    &self.settings.rule_preset.name(),
+                    &self.settings.rules.summary().join("\n"),
+                ],
+            ),
+            &self.players,
+        )?;
+        for player in &mut self.players {
+            player.analyze_cards(&self.board, &self.hotel_chain_manager);
+        }
+        self.start_rounds(round, first_player, progress)
+    }
+
     /// Starts the game that has been created previously.
     /// Returns an Error when the game has already been started.
     pub fn start_game(&mut self) -> Result<()> {
-        broadcast("Starting game!", &self.players)?;
+        set_language(self.settings.language);
+        set_plain(self.settings.plain);
+        broadcast(&text("game.starting", &[]), &self.players)?;
         if self.game_started {
             return Err(miette!(
                 "Unable to start game: Game has already been started!"
@@ -115,63 +279,182 @@ impl GameManager {
         } else {
             self.game_started = true;
         }
+        if let Some(journal) = &self.settings.journal {
+            // The journal of the game that has been played before is not recovered anymore
+            if let Err(err) = journal.clear() {
+                println!("Unable to clear the journal {}: {}", journal.path, err);
+            }
+        }
         broadcast(
-            "Each player draws a card now, the player with the lowest card starts.",
+            &text(
+                "game.rules",
+                &[
+                    &self.settings.rule_preset.name(),
+                    &self.settings.rules.summary().join("\n"),
+                ],
+            ),
             &self.players,
         )?;
-        let mut cards_with_players = HashMap::new();
+        if let Some(tutorial) = &self.settings.tutorial {
+            tutorial.explain(TutorialEvent::GameStart, &self.players)?;
+        }
+        if matches!(
+            self.settings.bot_strategy,
+            BotStrategy::Heuristic | BotStrategy::MonteCarlo
+        ) {
+            for bot_number in 1..=self.settings.bots {
+                broadcast(
+                    &text(
+                        "game.bot_personality",
+                        &[
+                            &bot_number,
+                            &self.settings.bot_personality(bot_number).name(),
+                        ],
+                    ),
+                    &self.players,
+                )?;
+            }
+        }
+        match self.settings.turn_order {
+            TurnOrder::LowestTile => {
+                broadcast(&text("game.draw_lowest_starts", &[]), &self.players)?
+            }
+            _ => broadcast(&text("game.draw", &[]), &self.players)?,
+        }
         let mut cards = Vec::new();
-        for (index, player) in self.players.iter().enumerate() {
-            let card = draw_card(&mut self.position_cards)?.unwrap();
-            player.get_enter("Press enter to draw your card")?;
+        for player in &self.players {
+            let card = draw_card(&mut self.position_cards).unwrap();
+            player.get_enter(&text("game.draw_prompt", &[]))?;
             broadcast(
-                &format!(
-                    "{} drew card {}",
-                    player.name,
-                    &card.color(AnsiColors::Green)
+                &text(
+                    "game.drew_card",
+                    &[&player.colored_name(), &card.color(AnsiColors::Green)],
                 ),
                 &self.players,
             )?;
             self.board.place_hotel(&card)?;
-            cards_with_players.insert(card, index);
             cards.push(card);
         }
-        cards.sort();
         // Determine turn order
-        for (index, card) in cards.iter().enumerate() {
-            let player_index = cards_with_players.get(card).unwrap();
-            let player_name = self.players.get(*player_index).unwrap().name.clone();
-            self.players.get_mut(*player_index).unwrap().id = index as u32;
+        let turn_order = match self.settings.turn_order {
+            TurnOrder::LowestTile => turn_order_by_cards(&cards),
+            TurnOrder::Random => {
+                let mut turn_order: Vec<usize> = (0..self.players.len()).collect();
+                turn_order.shuffle(&mut self.rng);
+                turn_order
+            }
+            TurnOrder::HostChosen => self.read_turn_order()?,
+        };
+        for (index, player_index) in turn_order.iter().enumerate() {
+            let player = &mut self.players[*player_index];
+            player.id = index as u32;
+            let player_name = player.colored_name();
             broadcast(
-                &format!("{} is the {}. player", player_name, index + 1),
+                &text("game.turn_position", &[&player_name, &(index + 1)]),
                 &self.players,
             )?;
         }
+        self.players.sort();
         broadcast_others(
-            &format!(
-                "Waiting for {} to start the first round...",
-                &self.players[0].name
+            &text(
+                "game.waiting_for_first_round",
+                &[&self.players[0].colored_name()],
             ),
-            &self.players[0].name,
+            self.players[0].id,
             &self.players,
         )?;
-        self.players[0].get_enter("Press enter to start the first round!")?;
-        self.players.sort();
+        if self.settings.teams {
+            self.assign_teams()?;
+        }
+        self.players[0].get_enter(&text("game.start_first_round", &[]))?;
         // Analyze the initial player cards
         for player in &mut self.players {
             player.analyze_cards(&self.board, &self.hotel_chain_manager);
         }
-        self.start_rounds()?;
+        self.start_rounds(1, 0, TurnProgress::default())?;
+        Ok(())
+    }
+
+    /// Asks the host in which order the players should play.
+    ///
+    /// Returns the indices of the players in the order in which they play.
+    fn read_turn_order(&self) -> Result<Vec<usize>> {
+        let host = &self.players[0];
+        let mut remaining: Vec<usize> = (0..self.players.len()).collect();
+        if host.bot.is_some() {
+            // Only bots are playing, the order is not changed
+            return Ok(remaining);
+        }
+        let mut turn_order = Vec::new();
+        while remaining.len() > 1 {
+            for (number, player_index) in remaining.iter().enumerate() {
+                host.print_text_ln(&format!(
+                    "{}: {}",
+                    number + 1,
+                    self.players[*player_index].colored_name()
+                ))?;
+            }
+            let number = host.read_input(
+                format!("Who should be the {}. player?: ", turn_order.len() + 1),
+                (1..=remaining.len()).collect(),
+            )?;
+            turn_order.push(remaining.remove(number - 1));
+        }
+        turn_order.append(&mut remaining);
+        Ok(turn_order)
+    }
+
+    /// Pairs the players into teams of two.
+    /// The players of one team do not play directly after each other.
+    fn assign_teams(&mut self) -> Result<()> {
+        let number_of_teams = self.players.len() as u32 / 2;
+        for player in &mut self.players {
+            player.team = Some(player.id % number_of_teams);
+        }
+        for team in 0..number_of_teams {
+            let members: Vec<String> = self
+                .players
+                .iter()
+                .filter(|player| player.team == Some(team))
+                .map(|player| player.colored_name())
+                .collect();
+            broadcast(
+                &format!("Team {}: {}", team + 1, members.join(" and ")),
+                &self.players,
+            )?;
+        }
         Ok(())
     }
 
     /// Starts game rounds.
     /// If one round returns true no new round is started.
-    fn start_rounds(&mut self) -> Result<()> {
+    /// # Arguments
+    /// * `first_round` - The number of the first round that is played
+    /// * `first_player` - The index of the player that plays first in the first round
+    /// * `progress` - How far the turn of the first player has already been played
+    fn start_rounds(
+        &mut self,
+        first_round: u32,
+        first_player: usize,
+        progress: TurnProgress,
+    ) -> Result<()> {
+        journal_turn(
+            &self.settings,
+            first_round,
+            first_player,
+            progress,
+            &self.players,
+            &self.board,
+            &self.position_cards,
+        );
         let mut game_running = true;
-        let mut round_number = 1;
+        let mut round_number = first_round;
         while game_running {
             let mut round = Round::new(round_number);
+            if round_number == first_round {
+                round.first_player = first_player;
+                round.first_progress = progress;
+            }
             let round_status = round.start_round(
                 &mut self.players,
                 &mut self.board,
@@ -180,37 +463,107 @@ impl GameManager {
                 &mut self.hotel_chain_manager,
                 &mut self.position_cards,
             )?;
+            self.finances.append(&mut round.finances);
+            self.events.append(&mut round.events);
+            self.boards.push(self.board.clone());
+            self.bank
+                .market_history
+                .record_round(&self.hotel_chain_manager);
             if round_status {
                 game_running = false;
+            } else if !can_game_continue(
+                &self.players,
+                &self.board,
+                &self.hotel_chain_manager,
+                !self.position_cards.is_empty(),
+            ) {
+                broadcast(&text("game.no_moves_left", &[]), &self.players)?;
+                game_running = false;
             }
             round_number += 1;
         }
+        let price_chart = price_chart_ui(&self.bank);
+        if !price_chart.is_empty() {
+            broadcast(&price_chart.join("\n"), &self.players)?;
+        }
         final_account(&mut self.players, &mut self.bank, &self.hotel_chain_manager)?;
+        if let Some(journal) = &self.settings.journal {
+            // The game has ended, there is nothing left to recover
+            let _ = fs::remove_file(&journal.path);
+        }
+        #[cfg(feature = "terminal")]
+        if let Some(url) = &self.settings.webhook_url {
+            let results = game_results(&self.players, &self.settings, &today());
+            match send_results(url, &results) {
+                Ok(()) => println!("The results have been sent to the webhook"),
+                Err(err) => println!("Unable to send the results to the webhook: {}", err),
+            }
+        }
+        if let Some(path) = &self.settings.history_file {
+            let results = game_results(&self.players, &self.settings, &today());
+            match append_results(path, &results) {
+                Ok(()) => println!("The results have been added to {}", path),
+                Err(err) => println!("Unable to add the results to {}: {}", path, err),
+            }
+        }
+        if let Some(path) = &self.settings.summary_file {
+            match export_summary(
+                path,
+                &self.players,
+                &self.bank,
+                &self.hotel_chain_manager,
+                &self.settings,
+            ) {
+                Ok(()) => println!("The summary of the game has been written to {}", path),
+                Err(err) => println!("Unable to write the summary to {}: {}", path, err),
+            }
+        }
+        if let Some(path) = &self.settings.finances_file {
+            match export_finances(path, &self.finances, &self.players) {
+                Ok(()) => println!("The finances of each round have been written to {}", path),
+                Err(err) => println!("Unable to write the finances to {}: {}", path, err),
+            }
+        }
+        if let Some(path) = &self.settings.report_file {
+            match export_report(path, self) {
+                Ok(()) => println!("The report of the game has been written to {}", path),
+                Err(err) => println!("Unable to write the report to {}: {}", path, err),
+            }
+        }
         Ok(())
     }
 
     /// Initializes all position cards and puts them in the vector
-    fn init_position_cards() -> Vec<Position> {
+    pub fn init_position_cards() -> Vec<Position> {
         let mut cards: Vec<Position> = Vec::new();
-        for c in LETTERS {
-            for i in 1..=12 {
-                cards.push(Position::new(c, i));
+        for row in 0..ROWS {
+            for column in 0..COLUMNS {
+                cards.push(Position::from_indices(row, column));
             }
         }
         cards
     }
 
+    /// Returns all position cards in a random order, shuffled with the rng of the game.
+    pub fn shuffled_position_cards<R: Rng>(rng: &mut R) -> VecDeque<Position> {
+        let mut cards = GameManager::init_position_cards();
+        cards.shuffle(rng);
+        cards.into()
+    }
+
     /// Initializes all players and puts them in the vector
     fn init_players(
         number_of_players: u32,
-        position_cards: &mut Vec<Position>,
-        small_board: bool,
+        position_cards: &mut VecDeque<Position>,
+        settings: &Settings,
+        rng: &mut StdRng,
     ) -> Result<Vec<Player>> {
         let mut players: Vec<Player> = Vec::new();
         // Initialize new players and put them in the list
         let mut player_id = 0;
         let mut player_cards = GameManager::init_player_cards(number_of_players, position_cards)?;
-        while !player_cards.is_empty() {
+        // The last players are played by bots
+        while player_cards.len() > settings.bots as usize {
             // Runs until player entered a name that is not yet taken
             // If nothing is entered the player name will be `Player i`
             'inner: loop {
@@ -220,7 +573,7 @@ impl GameManager {
                 stdin().read_line(&mut buffer).into_diagnostic()?;
                 buffer = buffer.trim().to_string();
                 let player_name = if buffer.is_empty() {
-                    format!("Player {}", player_id + 1)
+                    unique_name(&format!("Player {}", player_id + 1), &Self::names(&players))
                 } else {
                     for player in &players {
                         if player.name == buffer {
@@ -230,63 +583,142 @@ impl GameManager {
                     }
                     buffer.clone()
                 };
-                players.push(Player::new(
+                let mut player = Player::new(
                     player_cards.pop().unwrap(),
                     player_id,
-                    small_board,
+                    settings.small_board,
                     player_name,
-                ));
+                );
+                player.privacy = settings.privacy;
+                player.auto_sort = settings.auto_sort;
+                player.plain = settings.plain;
+                player.skip_dialogues = settings.skip_dialogues;
+                players.push(player);
                 player_id += 1;
                 break;
             }
         }
+        for bot_number in 1..=settings.bots {
+            let mut player = Player::new(
+                player_cards.pop().unwrap(),
+                player_id,
+                settings.small_board,
+                unique_name(&format!("Bot {}", bot_number), &Self::names(&players)),
+            );
+            player.bot = Some(settings.bot_strategy.create(
+                settings.bot_personality(bot_number),
+                settings,
+                rng.gen(),
+            ));
+            players.push(player);
+            player_id += 1;
+        }
         Ok(players)
     }
 
-    /// Initializes the client players and one local player.
+    /// Initializes the client players, one local player and the bots.
+    /// The bots take the last seats.
     fn init_players_lan(
         client_players: &mut Vec<ClientPlayer>,
-        position_cards: &mut Vec<Position>,
+        position_cards: &mut VecDeque<Position>,
         settings: &Settings,
         host_name: String,
+        rng: &mut StdRng,
     ) -> Result<Vec<Player>> {
         let mut players: Vec<Player> = Vec::new();
         // Initialize new players and put them in the list
         let mut player_id = 0;
-        let mut player_cards =
-            GameManager::init_player_cards(client_players.len() as u32 + 1, position_cards)?;
+        let mut player_cards = GameManager::init_player_cards(
+            client_players.len() as u32 + 1 + settings.bots,
+            position_cards,
+        )?;
         let mut first_player = true;
-        while !player_cards.is_empty() {
+        while player_cards.len() > settings.bots as usize {
             if first_player {
                 // Initialize local player
-                players.push(Player::new(
+                let name = match host_name.is_empty() {
+                    true => String::from("Host"),
+                    false => host_name.clone(),
+                };
+                let mut player = Player::new(
                     player_cards.pop().unwrap(),
                     player_id,
                     settings.small_board,
-                    host_name.clone(),
-                ));
+                    name,
+                );
+                player.privacy = settings.privacy;
+                player.auto_sort = settings.auto_sort;
+                player.plain = settings.plain;
+                player.skip_dialogues = settings.skip_dialogues;
+                players.push(player);
                 first_player = false;
             } else {
                 // Initialize client players
                 let client_player = client_players.pop().unwrap();
-                players.push(Player::new_client(
+                let requested_name = match client_player.name.is_empty() {
+                    true => format!("Player {}", player_id + 1),
+                    false => client_player.name,
+                };
+                let name = unique_name(&requested_name, &Self::names(&players));
+                let mut player = Player::new_client(
                     player_cards.pop().unwrap(),
                     player_id,
-                    client_player.name,
+                    name.clone(),
                     client_player.tcp_stream,
                     client_player.small_board,
-                ));
+                    // The client can not see more than the host allows
+                    client_player.privacy.max(settings.privacy),
+                );
+                player.auto_sort = settings.auto_sort;
+                player.plain = client_player.plain;
+                player.skip_dialogues = settings.skip_dialogues;
+                player.binary = client_player.binary;
+                player.sent_ui = client_player.delta.then(SentUi::default);
+                player.set_input_timeout(settings.input_timeout)?;
+                player.remind_after = settings.remind_after.map(Duration::from_secs);
+                player.cancellation = settings.cancellation.clone();
+                if name != requested_name {
+                    println!(
+                        "{} has been renamed to {} because the name is already taken.",
+                        requested_name, name
+                    );
+                    player.print_text_ln(&format!(
+                        "The name {} is already taken, you play as {}.",
+                        requested_name, name
+                    ))?;
+                }
+                players.push(player);
             }
             player_id += 1;
         }
+        for bot_number in 1..=settings.bots {
+            let mut player = Player::new(
+                player_cards.pop().unwrap(),
+                player_id,
+                settings.small_board,
+                unique_name(&format!("Bot {}", bot_number), &Self::names(&players)),
+            );
+            player.bot = Some(settings.bot_strategy.create(
+                settings.bot_personality(bot_number),
+                settings,
+                rng.gen(),
+            ));
+            players.push(player);
+            player_id += 1;
+        }
         Ok(players)
     }
 
+    /// Returns the names of the players
+    fn names(players: &[Player]) -> Vec<String> {
+        players.iter().map(|player| player.name.clone()).collect()
+    }
+
     /// Initializes player cards for each player. The players cards are then put into the
     /// vector. A position vector is returned for each player.
     fn init_player_cards(
         number_of_players: u32,
-        position_cards: &mut Vec<Position>,
+        position_cards: &mut VecDeque<Position>,
     ) -> Result<Vec<Vec<Position>>> {
         // Contains the position cards for each player.
         let mut player_cards: Vec<Vec<Position>> = Vec::new();
@@ -296,17 +728,14 @@ impl GameManager {
         }
         // Get the starting cards for the player
         for _i in 1..=6 {
-            for player in 0..=number_of_players - 1 {
-                let random_number = rand::thread_rng().gen_range(0..=position_cards.len() - 1);
-                if let Some(position) = position_cards.get(random_number) {
-                    player_cards
-                        .get_mut(usize::try_from(player).unwrap())
-                        .unwrap()
-                        .push(*position);
-                    position_cards.remove(random_number);
-                } else {
-                    println!("position_cards length: {}", position_cards.len());
-                    return Err(miette!("Unable to add position to list. The index {} does not exist in the position_cards vector!", random_number));
+            for cards in player_cards.iter_mut() {
+                match draw_card(position_cards) {
+                    Some(position) => cards.push(position),
+                    None => {
+                        return Err(miette!(
+                            "Unable to deal the starting cards: No position cards are left"
+                        ))
+                    }
                 }
             }
         }
@@ -314,45 +743,68 @@ impl GameManager {
     }
 }
 
-/// Tries to draw a card from the position_cards deck.
+/// Draws the card at the front of the shuffled position cards.
 /// # Returns
-/// * `Ok(None)` - No card is left that could be drawn
-/// * `Ok(Some(position))` - Card has been drawn successfully
-/// * `Err(err)` - The random card does not exist in the positon cards vector
-pub fn draw_card(position_cards: &mut Vec<Position>) -> Result<Option<Position>> {
-    // No cards are left
-    if position_cards.is_empty() {
-        return Ok(None);
-    }
-    let random_number = rand::thread_rng().gen_range(0..=position_cards.len() - 1);
-    if position_cards.get(random_number).is_none() {
-        println!("position_cards length: {}", position_cards.len());
-        return Err(miette!("Unable to add position to list. The index {} does not exist in the position_cards vector!", random_number));
+/// * `None` - No card is left that could be drawn
+/// * `Some(position)` - Card has been drawn successfully
+pub fn draw_card(position_cards: &mut VecDeque<Position>) -> Option<Position> {
+    position_cards.pop_front()
+}
+
+/// Returns the indices of the players in the order in which they play.
+/// The player that drew the lowest card starts.
+///
+/// # Arguments
+/// * `cards` - The cards that the players drew, the index of the card is the index of the player
+pub fn turn_order_by_cards(cards: &[Position]) -> Vec<usize> {
+    let mut turn_order: Vec<usize> = (0..cards.len()).collect();
+    turn_order.sort_by_key(|index| cards[*index]);
+    turn_order
+}
+
+/// Gives each player the starting money of the rules.
+pub fn set_starting_money(players: &mut [Player], settings: &Settings) {
+    for player in players {
+        player.money = settings.rules.starting_money;
     }
-    let position = position_cards.get(random_number).cloned();
-    match position {
-        Some(pos) => {
-            position_cards.remove(random_number);
-            Ok(Some(pos))
+}
+
+/// Creates the rng of the game from [`Settings::seed`], a random seed is used when none is set.
+fn game_rng(settings: &Settings) -> StdRng {
+    StdRng::seed_from_u64(settings.seed.unwrap_or_else(|| rand::thread_rng().gen()))
+}
+
+/// Gives the first player the color. A player that already has the color gets the previous color
+/// of the first player, so that the names of two players are never printed in the same color.
+fn set_first_player_color(players: &mut [Player], color: Option<Rgb>) {
+    let (color, previous) = match (color, players.first()) {
+        (Some(color), Some(first)) => (color, first.color),
+        _ => return,
+    };
+    for player in players.iter_mut() {
+        if player.color == color {
+            player.color = previous;
         }
-        None => Ok(None),
     }
+    players[0].color = color;
 }
 
 /// Returns a reference to the player with the entered id
+///
+/// Once the game has started the players are stored in the order of their ids, so the player is
+/// found at the index of its id. The players are only searched when they are stored in another
+/// order.
 pub fn player_by_id(id: u32, players: &[Player]) -> Option<&Player> {
-    for player in players {
-        if player.id == id {
-            return Some(player);
-        }
+    match players.get(id as usize) {
+        Some(player) if player.id == id => Some(player),
+        _ => players.iter().find(|player| player.id == id),
     }
-    None
 }
 
 /// Sells all stocks back to the bank, gives majority shareholder bonuses and determines
 /// which player won the game
 pub fn final_account(
-    players: &mut Vec<Player>,
+    players: &mut [Player],
     bank: &mut Bank,
     hotel_chain_manager: &HotelChainManager,
 ) -> Result<()> {
@@ -369,67 +821,138 @@ pub fn final_account(
             )?;
         }
     }
-    let mut player_money_map = HashMap::new();
-    let mut player_money = Vec::new();
-    for player in players.iter() {
-        player_money_map.insert(player.money, player.id);
-        player_money.push(player.money);
-    }
-    player_money.sort_unstable();
-    player_money.reverse();
+    broadcast(&money_statistics_ui(players).join("\n"), players)?;
+    broadcast(&profit_statistics_ui(players).join("\n"), players)?;
+    let ranking = ranking(players);
     let mut leader_board = String::new();
-    for (index, money) in player_money.iter().enumerate() {
-        let player = &players[*player_money_map.get(money).unwrap() as usize];
-        match index {
-            0 => leader_board.push_str(
-                &format!("1. {} - {}€\n", player.name, money)
-                    .color(Rgb(225, 215, 0))
-                    .to_string(),
-            ),
-            1 => leader_board.push_str(
-                &format!("2. {} - {}€\n", player.name, money)
-                    .color(Rgb(192, 192, 192))
-                    .to_string(),
-            ),
-            2 => leader_board.push_str(
-                &format!("3. {} - {}€\n", player.name, money)
-                    .color(Rgb(191, 137, 112))
-                    .to_string(),
-            ),
-            _ => leader_board.push_str(
-                &format!("{}. {} - {}€\n", player.id + 1, player.name, money)
-                    .color(Rgb(105, 105, 105))
-                    .to_string(),
-            ),
-        }
+    for (rank, player) in &ranking {
+        let color = match rank {
+            1 => Rgb(225, 215, 0),
+            2 => Rgb(192, 192, 192),
+            3 => Rgb(191, 137, 112),
+            _ => Rgb(105, 105, 105),
+        };
+        // The rank and the money are printed in the color of the rank, the name in the color of
+        // the player
+        leader_board.push_str(&format!(
+            "{} {} {}\n",
+            format!("{}.", rank).color(color),
+            player.colored_name(),
+            format!("- {}€", player.money).color(color)
+        ));
     }
     broadcast(&leader_board, players)?;
-    for i in 0..=players.len() - 1 {
-        let money = player_money.get(i).unwrap();
-        let player_id = player_money_map.get(money).unwrap();
-        let player = players.get(*player_id as usize).unwrap();
+    for (rank, player) in &ranking {
+        let shared = ranking.iter().filter(|(other, _)| other == rank).count() > 1;
         // Should be sent do every player
-        match i {
-            0 => player.print_text_ln(&format!(
-                "{}, congratulations, you are the winner!",
-                player.name
-            ))?,
-            1 => player.print_text_ln(&format!("{}, you are second place!", player.name))?,
-            2 => player.print_text_ln(&format!("{}, you are third place!", player.name))?,
-            _ => player.print_text_ln(&format!("{}, you have lost!", player.name))?,
+        match (rank, shared) {
+            (1, false) => player.print_text_ln(&text("result.winner", &[&player.name]))?,
+            (1, true) => player.print_text_ln(&text("result.shared_win", &[&player.name]))?,
+            (2, _) => player.print_text_ln(&text("result.second", &[&player.name]))?,
+            (3, _) => player.print_text_ln(&text("result.third", &[&player.name]))?,
+            _ => player.print_text_ln(&text("result.lost", &[&player.name]))?,
         }
         if player.tcp_stream.is_some() {
-            player
-                .tcp_stream
-                .as_ref()
-                .unwrap()
-                .write_all("€GameEnded\n".as_bytes())
-                .into_diagnostic()?;
+            send_string(player, "", "€GameEnded")?;
         }
     }
+    if players.iter().any(|player| player.team.is_some()) {
+        team_final_account(players)?;
+    }
     Ok(())
 }
 
+/// Prints the leader board of the teams. The money of the team members is summed up.
+fn team_final_account(players: &[Player]) -> Result<()> {
+    let team_ranking = team_ranking(players);
+    let mut leader_board = format!("{}\n", text("result.teams", &[]));
+    for (rank, team, money) in &team_ranking {
+        let members: Vec<String> = players
+            .iter()
+            .filter(|player| player.team == Some(*team))
+            .map(|player| player.colored_name())
+            .collect();
+        leader_board.push_str(&text(
+            "result.team",
+            &[
+                rank,
+                &(team + 1),
+                &members.join(&text("result.team_members", &[])),
+                money,
+            ],
+        ));
+        leader_board.push('\n');
+    }
+    broadcast(&leader_board, players)?;
+    let winning_teams: Vec<u32> = team_ranking
+        .iter()
+        .filter(|(rank, _, _)| *rank == 1)
+        .map(|(_, team, _)| *team)
+        .collect();
+    for player in players {
+        let won = player
+            .team
+            .is_some_and(|team| winning_teams.contains(&team));
+        match (won, winning_teams.len() > 1) {
+            (true, false) => player.print_text_ln(&text("result.team_won", &[&player.name]))?,
+            (true, true) => {
+                player.print_text_ln(&text("result.team_shared_win", &[&player.name]))?
+            }
+            (false, _) => player.print_text_ln(&text("result.team_lost", &[&player.name]))?,
+        }
+    }
+    Ok(())
+}
+
+/// Returns the players together with their rank, sorted by money in descending order.
+///
+/// Players with the same money share a rank, the ranks after a shared rank are skipped so that
+/// the rank of each player is one more than the number of richer players (e.g. 1, 1, 3).
+pub fn ranking(players: &[Player]) -> Vec<(u32, &Player)> {
+    let mut sorted: Vec<&Player> = players.iter().collect();
+    sorted.sort_by(|a, b| b.money.cmp(&a.money).then(a.id.cmp(&b.id)));
+    sorted
+        .iter()
+        .map(|player| {
+            let richer = sorted
+                .iter()
+                .filter(|other| other.money > player.money)
+                .count();
+            (richer as u32 + 1, *player)
+        })
+        .collect()
+}
+
+/// Returns the teams together with their rank and their summed up money, sorted by money in
+/// descending order.
+///
+/// Teams with the same money share a rank, like the players in [`ranking`].
+fn team_ranking(players: &[Player]) -> Vec<(u32, u32, u32)> {
+    let team_money = team_money(players);
+    team_money
+        .iter()
+        .map(|(team, money)| {
+            let richer = team_money.iter().filter(|(_, other)| other > money).count();
+            (richer as u32 + 1, *team, *money)
+        })
+        .collect()
+}
+
+/// Returns the summed up money of each team, sorted by money in descending order.
+fn team_money(players: &[Player]) -> Vec<(u32, u32)> {
+    let mut team_money: Vec<(u32, u32)> = Vec::new();
+    for player in players {
+        if let Some(team) = player.team {
+            match team_money.iter_mut().find(|(t, _money)| *t == team) {
+                Some((_team, money)) => *money += player.money,
+                None => team_money.push((team, player.money)),
+            }
+        }
+    }
+    team_money.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    team_money
+}
+
 /// Manages the currently active hotel chains
 pub mod hotel_chain_manager {
     use std::collections::HashMap;
@@ -439,17 +962,79 @@ pub mod hotel_chain_manager {
     use crate::{
         base_game::{
             bank::Bank,
-            board::{AnalyzedPosition, Board, Position},
+            board::{AnalyzedPosition, Board, Position, COLUMNS, ROWS},
             hotel_chains::HotelChain,
             player::Player,
+            settings::SAFE_CHAIN_SIZE,
         },
         logic::place_hotel::PlaceHotelCase,
     };
 
     /// Store the currently active hotel chains
+    #[derive(Clone)]
     pub struct HotelChainManager {
-        /// Stores the active hotel chains and the buildings that belong to the chain
-        active_chains: HashMap<HotelChain, Vec<Position>>,
+        /// Stores the active hotel chains and the index of the hotel that represents the set of
+        /// the hotels that belong to the chain in `hotels`
+        active_chains: HashMap<HotelChain, usize>,
+        /// The sets of hotels that belong to the same chain, see [`Position::index`]
+        hotels: HotelSets,
+    }
+
+    /// Disjoint sets of the positions of the board, the hotels of each chain form one set.
+    ///
+    /// Adding a hotel to a chain and fusing two chains joins two sets, which takes nearly
+    /// constant time because the smaller set is always attached to the larger one.
+    #[derive(Clone)]
+    struct HotelSets {
+        /// The index of the parent of each position, a position that is its own parent
+        /// represents its set
+        parents: Vec<usize>,
+        /// The number of positions in the set of each representing position
+        sizes: Vec<u32>,
+    }
+
+    impl HotelSets {
+        fn new() -> Self {
+            Self {
+                parents: (0..ROWS * COLUMNS).collect(),
+                sizes: vec![1; ROWS * COLUMNS],
+            }
+        }
+
+        /// Returns the index of the position that represents the set of the index
+        fn find(&self, mut index: usize) -> usize {
+            while self.parents[index] != index {
+                index = self.parents[index];
+            }
+            index
+        }
+
+        /// Joins the sets of the two indices and returns the index that represents the joined
+        /// set
+        fn union(&mut self, a: usize, b: usize) -> usize {
+            let (a, b) = (self.find(a), self.find(b));
+            if a == b {
+                return a;
+            }
+            let (root, child) = match self.sizes[a] >= self.sizes[b] {
+                true => (a, b),
+                false => (b, a),
+            };
+            self.parents[child] = root;
+            self.sizes[root] += self.sizes[child];
+            root
+        }
+
+        /// Returns the number of positions in the set of the index
+        fn size(&self, index: usize) -> u32 {
+            self.sizes[self.find(index)]
+        }
+    }
+
+    impl Default for HotelChainManager {
+        fn default() -> Self {
+            Self::new()
+        }
     }
 
     impl HotelChainManager {
@@ -457,21 +1042,33 @@ pub mod hotel_chain_manager {
         pub fn new() -> Self {
             Self {
                 active_chains: HashMap::new(),
+                hotels: HotelSets::new(),
             }
         }
 
+        /// Creates a hotel manager for the chains of the hotels on the board.
+        pub fn from_board(board: &Board) -> Self {
+            let mut hotel_chain_manager = Self::new();
+            for piece in &board.pieces {
+                if let (true, Some(chain)) = (piece.piece_set, piece.chain) {
+                    let index = piece.position.index();
+                    let root = match hotel_chain_manager.active_chains.get(&chain) {
+                        Some(root) => hotel_chain_manager.hotels.union(*root, index),
+                        None => index,
+                    };
+                    hotel_chain_manager.active_chains.insert(chain, root);
+                }
+            }
+            hotel_chain_manager
+        }
+
         /// Returns the number of hotels currently built for the specified chain.
         /// If the chain is not active 0 is returned
         pub fn chain_length(&self, hotel: &HotelChain) -> u32 {
-            if !self.active_chains.contains_key(hotel) {
-                return 0;
+            match self.active_chains.get(hotel) {
+                Some(root) => self.hotels.size(*root),
+                None => 0,
             }
-            self.active_chains
-                .get(hotel)
-                .unwrap()
-                .len()
-                .try_into()
-                .unwrap()
         }
 
         /// Returns a vector of currently active chains
@@ -483,6 +1080,20 @@ pub mod hotel_chain_manager {
             chains
         }
 
+        /// Returns the positions of the hotels that belong to the chain in the order of
+        /// [`Position::index`].
+        /// If the chain is not active an empty vector is returned
+        pub fn chain_positions(&self, hotel: &HotelChain) -> Vec<Position> {
+            let root = match self.active_chains.get(hotel) {
+                Some(root) => self.hotels.find(*root),
+                None => return Vec::new(),
+            };
+            (0..ROWS * COLUMNS)
+                .filter(|index| self.hotels.find(*index) == root)
+                .map(Position::from_index)
+                .collect()
+        }
+
         /// Returns true if the chain is currently active
         pub fn chain_status(&self, hotel: &HotelChain) -> bool {
             self.active_chains.contains_key(hotel)
@@ -490,17 +1101,7 @@ pub mod hotel_chain_manager {
 
         /// Returns the range in which the current price level of the chain is
         pub fn price_range(&self, hotel: &HotelChain) -> String {
-            let chains = match self.active_chains.contains_key(hotel) {
-                true => self
-                    .active_chains
-                    .get(hotel)
-                    .unwrap()
-                    .len()
-                    .try_into()
-                    .unwrap(),
-                false => 0,
-            };
-            let range = match chains {
+            let range = match self.chain_length(hotel) {
                 0 => "",
                 2 => "    [2]",
                 3 => "    [3]",
@@ -518,7 +1119,7 @@ pub mod hotel_chain_manager {
 
         /// Start a new chain.
         /// The hotels on the board are updated to show the chain.
-        /// The player will be given one stock as start-up bonus.
+        /// The player will be given the number of stocks set in the bank rules as start-up bonus.
         /// When the hotel pieces have not been set on the board they will be placed but a
         /// warning will be shown.
         /// # Arguments
@@ -529,7 +1130,9 @@ pub mod hotel_chain_manager {
         /// * `bank` - The bank that manages the available stocks
         ///
         /// # Returns
-        /// A result containing 'Ok()' when the chain has been founded successfully
+        /// * `Ok(stocks)` - The chain has been founded, `stocks` is the number of bonus stocks the
+        ///   player has been given. It is lower than set in the rules when not enough stocks are
+        ///   left.
         pub fn start_chain(
             &mut self,
             hotel_chain: HotelChain,
@@ -537,7 +1140,7 @@ pub mod hotel_chain_manager {
             board: &mut Board,
             player: &mut Player,
             bank: &mut Bank,
-        ) -> Result<()> {
+        ) -> Result<u32> {
             if positions.len() < 2 {
                 return Err(miette!(
                     "Unable to start new chain of hotel {}: Not enough buildings!",
@@ -551,12 +1154,17 @@ pub mod hotel_chain_manager {
                     &hotel_chain
                 ));
             }
-            self.active_chains.insert(hotel_chain, positions.clone());
+            let root = positions
+                .iter()
+                .map(|position| position.index())
+                .reduce(|root, index| self.hotels.union(root, index))
+                .unwrap();
+            self.active_chains.insert(hotel_chain, root);
             // Update hotels on board
             for position in positions {
                 if board.is_hotel_placed(&position).is_none() {
                     board.place_hotel(&position)?;
-                    eprintln!("Warning: Hotel at {} was not placed but has been placed to start the chain {}. Please place the hotel before the chain is stared!", &position, &hotel_chain);
+                    eprintln!("Warning: Hotel at {} was not placed but has been placed to start the chain {}. Please place the hotel before the chain is started!", &position, &hotel_chain);
                 }
                 // Update single hotels that surround the placed hotel
                 let analyzed_position = AnalyzedPosition::new(position, board, self);
@@ -569,8 +1177,13 @@ pub mod hotel_chain_manager {
                 board.update_hotel(hotel_chain, &position)?;
             }
             // Update player stocks
-            bank.give_bonus_stock(&hotel_chain, player)?;
-            Ok(())
+            let mut bonus_stocks = 0;
+            for _i in 0..bank.rules.founder_bonus_stocks {
+                if bank.give_bonus_stock(&hotel_chain, player)? {
+                    bonus_stocks += 1;
+                }
+            }
+            Ok(bonus_stocks)
         }
 
         /// Adds a hotel to an existing chain.
@@ -589,13 +1202,12 @@ pub mod hotel_chain_manager {
             position: Position,
             board: &mut Board,
         ) -> Result<()> {
-            if !self.active_chains.contains_key(hotel_chain) {
-                return Err(miette!("Unable to add hotel at position {} to chain {}: The chain has not been founded yet!", &position, &hotel_chain));
-            }
-            self.active_chains
-                .get_mut(hotel_chain)
-                .unwrap()
-                .push(position);
+            let root = match self.active_chains.get(hotel_chain) {
+                Some(root) => *root,
+                None => return Err(miette!("Unable to add hotel at position {} to chain {}: The chain has not been founded yet!", &position, &hotel_chain)),
+            };
+            let root = self.hotels.union(root, position.index());
+            self.active_chains.insert(*hotel_chain, root);
             // Update hotel on board
             board.update_hotel(*hotel_chain, &position)?;
             Ok(())
@@ -619,15 +1231,22 @@ pub mod hotel_chain_manager {
             board: &mut Board,
         ) -> Result<()> {
             // Check if the two chains exist
-            if !(self.active_chains.contains_key(alive) && self.active_chains.contains_key(dead)) {
-                return Err(miette!("Unable to fuse chain {} into {}: At least one of the two chains does not exist!", &dead, &alive));
-            }
-            // Transfer positions and uptate board
-            for position in self.active_chains.get(dead).unwrap().clone() {
-                self.active_chains.get_mut(alive).unwrap().push(position);
-                board.update_hotel(*alive, &position)?;
+            let (alive_root, dead_root) =
+                match (self.active_chains.get(alive), self.active_chains.get(dead)) {
+                    (Some(alive_root), Some(dead_root)) => (*alive_root, *dead_root),
+                    _ => return Err(miette!("Unable to fuse chain {} into {}: At least one of the two chains does not exist!", &dead, &alive)),
+                };
+            // Update board
+            for piece in board
+                .pieces
+                .iter_mut()
+                .filter(|piece| piece.piece_set && piece.chain == Some(*dead))
+            {
+                piece.chain = Some(*alive);
             }
-            // Remove old chain
+            // Join the hotels and remove old chain
+            let root = self.hotels.union(alive_root, dead_root);
+            self.active_chains.insert(*alive, root);
             self.active_chains.remove(dead);
             Ok(())
         }
@@ -649,13 +1268,16 @@ pub mod hotel_chain_manager {
 
         /// Returns true if the chain is safe. This means that it can no longer be fused into another chain.
         pub fn is_chain_safe(&self, chain: &HotelChain) -> bool {
-            self.chain_length(chain) >= 11
+            self.chain_length(chain) >= SAFE_CHAIN_SIZE
         }
     }
 
     #[cfg(test)]
     mod tests {
+        use std::collections::VecDeque;
+
         use miette::Result;
+        use rand::{rngs::StdRng, SeedableRng};
 
         use crate::{
             base_game::{
@@ -663,10 +1285,14 @@ pub mod hotel_chain_manager {
                 board::{Board, Position},
                 hotel_chains::HotelChain,
                 player::Player,
-                settings::Settings,
+                settings::{Privacy, Settings},
                 ui,
             },
-            game::{draw_card, final_account, round::Round, GameManager},
+            bot::BotStrategy,
+            game::{
+                draw_card, final_account, player_by_id, ranking, round::Round, team_money,
+                team_ranking, turn_order_by_cards, GameManager,
+            },
         };
 
         use super::HotelChainManager;
@@ -691,13 +1317,193 @@ pub mod hotel_chain_manager {
             bank.buy_stock(&hotel_chain_manager, &chain, players.get_mut(0).unwrap())?;
             bank.update_largest_shareholders(&players);
             final_account(&mut players, &mut bank, &hotel_chain_manager)?;
-            assert_eq!(players.get(0).unwrap().money, 12400);
+            assert_eq!(players.first().unwrap().money, 12400);
             Ok(())
         }
 
+        #[test]
+        fn position_cards_are_drawn_from_the_front() {
+            let mut rng = StdRng::seed_from_u64(1);
+            let mut position_cards = GameManager::shuffled_position_cards(&mut rng);
+            assert_eq!(position_cards.len(), 108);
+            assert_ne!(
+                position_cards,
+                VecDeque::from(GameManager::init_position_cards())
+            );
+            // The same seed leads to the same order
+            let same = GameManager::shuffled_position_cards(&mut StdRng::seed_from_u64(1));
+            assert_eq!(position_cards, same);
+            assert_eq!(draw_card(&mut position_cards), same.front().copied());
+            assert_eq!(position_cards.len(), 107);
+            position_cards.clear();
+            assert_eq!(draw_card(&mut position_cards), None);
+        }
+
+        #[test]
+        fn games_with_the_same_seed_are_dealt_the_same() -> Result<()> {
+            let game = |seed| {
+                let mut settings = Settings::new(false, Privacy::default(), true);
+                settings.seed = Some(seed);
+                settings.bots = 3;
+                GameManager::new(3, settings)
+            };
+            let hands = |game: &GameManager| -> Vec<Vec<Position>> {
+                game.players
+                    .iter()
+                    .map(|player| {
+                        player
+                            .analyzed_cards
+                            .iter()
+                            .map(|card| card.position)
+                            .collect()
+                    })
+                    .collect()
+            };
+            let (first, same, other) = (game(7)?, game(7)?, game(8)?);
+            assert_eq!(first.position_cards, same.position_cards);
+            assert_eq!(hands(&first), hands(&same));
+            assert_ne!(first.position_cards, other.position_cards);
+            Ok(())
+        }
+
+        #[test]
+        fn players_are_found_by_id() {
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+            ];
+            assert_eq!(player_by_id(1, &players).unwrap().name, "Player 2");
+            // Players that are not stored in the order of their ids are found as well
+            players.swap(0, 1);
+            assert_eq!(player_by_id(1, &players).unwrap().name, "Player 2");
+            assert_eq!(player_by_id(0, &players).unwrap().name, "Player 1");
+            assert!(player_by_id(2, &players).is_none());
+        }
+
+        #[test]
+        fn founder_bonus_stocks_correct() -> Result<()> {
+            for founder_bonus_stocks in 0..=2 {
+                let mut bank = Bank::new();
+                bank.rules.founder_bonus_stocks = founder_bonus_stocks;
+                let mut board = Board::new();
+                let mut hotel_chain_manager = HotelChainManager::new();
+                let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+                let chain = HotelChain::Festival;
+                let bonus_stocks = hotel_chain_manager.start_chain(
+                    chain,
+                    vec![Position::new('A', 1), Position::new('B', 1)],
+                    &mut board,
+                    &mut player,
+                    &mut bank,
+                )?;
+                assert_eq!(bonus_stocks, founder_bonus_stocks);
+                assert_eq!(
+                    *player.owned_stocks.stocks_for_hotel(&chain),
+                    founder_bonus_stocks
+                );
+                assert_eq!(
+                    *bank.stocks_for_sale.stocks_for_hotel(&chain),
+                    25 - founder_bonus_stocks
+                );
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn founder_bonus_stock_skipped_when_bank_is_empty() -> Result<()> {
+            let mut bank = Bank::new();
+            let mut board = Board::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            let chain = HotelChain::Festival;
+            bank.stocks_for_sale.set_stocks(&chain, 0);
+            let bonus_stocks = hotel_chain_manager.start_chain(
+                chain,
+                vec![Position::new('A', 1), Position::new('B', 1)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            assert_eq!(bonus_stocks, 0);
+            assert_eq!(*player.owned_stocks.stocks_for_hotel(&chain), 0);
+            assert_eq!(*bank.stocks_for_sale.stocks_for_hotel(&chain), 0);
+            assert!(hotel_chain_manager.chain_status(&chain));
+            Ok(())
+        }
+
+        #[test]
+        fn ranking_shares_ranks_on_ties() {
+            let mut players = Vec::new();
+            for (id, money) in [3000, 5000, 3000, 1000, 5000].iter().enumerate() {
+                let mut player = Player::new(vec![], id as u32, false, format!("Player {}", id));
+                player.money = *money;
+                players.push(player);
+            }
+            let ranking: Vec<(u32, u32, u32)> = ranking(&players)
+                .iter()
+                .map(|(rank, player)| (*rank, player.id, player.money))
+                .collect();
+            assert_eq!(
+                ranking,
+                vec![
+                    (1, 1, 5000),
+                    (1, 4, 5000),
+                    (3, 0, 3000),
+                    (3, 2, 3000),
+                    (5, 3, 1000)
+                ]
+            );
+        }
+
+        #[test]
+        fn team_money_summed_up() {
+            let mut players = Vec::new();
+            for (id, money) in [1000, 4000, 3000, 500].iter().enumerate() {
+                let mut player = Player::new(vec![], id as u32, false, format!("Player {}", id));
+                player.money = *money;
+                player.team = Some(id as u32 % 2);
+                players.push(player);
+            }
+            assert_eq!(team_money(&players), vec![(1, 4500), (0, 4000)]);
+        }
+
+        #[test]
+        fn tied_teams_share_the_win() {
+            let mut players = Vec::new();
+            for (id, money) in [1000, 4000, 3500, 4000, 1000].iter().enumerate() {
+                let mut player = Player::new(vec![], id as u32, false, format!("Player {}", id));
+                player.money = *money;
+                player.team = Some(id as u32 % 3);
+                players.push(player);
+            }
+            assert_eq!(
+                team_ranking(&players),
+                vec![(1, 0, 5000), (1, 1, 5000), (3, 2, 3500)]
+            );
+        }
+
+        #[test]
+        fn random_bots_finish_game() -> Result<()> {
+            let mut settings = Settings::new(false, Privacy::default(), true);
+            settings.bots = 4;
+            settings.bot_strategy = BotStrategy::Random;
+            let mut game_manager = GameManager::new(4, settings)?;
+            game_manager.start_game()
+        }
+
+        #[test]
+        fn turn_order_by_cards_correct() {
+            let cards = vec![
+                Position::new('C', 4),
+                Position::new('A', 7),
+                Position::new('C', 2),
+            ];
+            assert_eq!(turn_order_by_cards(&cards), vec![2, 0, 1]);
+        }
+
         #[test]
         fn chain_status_and_length_correct() -> Result<()> {
-            let mut position_cards = GameManager::init_position_cards();
+            let mut position_cards = GameManager::shuffled_position_cards(&mut rand::thread_rng());
             let mut board = Board::new();
             let mut bank = Bank::new();
             let mut hotel_chain_manager = HotelChainManager::new();
@@ -716,7 +1522,7 @@ pub mod hotel_chain_manager {
                     &mut player,
                     hotel_chain,
                 )?;
-                assert_eq!(hotel_chain_manager.chain_status(hotel_chain), true);
+                assert!(hotel_chain_manager.chain_status(hotel_chain));
                 assert_eq!(hotel_chain_manager.chain_length(hotel_chain), 13);
             }
             Ok(())
@@ -724,8 +1530,8 @@ pub mod hotel_chain_manager {
 
         #[test]
         fn fusion_correct() -> Result<()> {
-            let mut position_cards = GameManager::init_position_cards();
-            let settings = Settings::new(false, true, false);
+            let mut position_cards = GameManager::shuffled_position_cards(&mut rand::thread_rng());
+            let settings = Settings::new(false, Privacy::Hidden, false);
             let mut board = Board::new();
             let mut bank = Bank::new();
             let mut hotel_chain_manager = HotelChainManager::new();
@@ -768,15 +1574,24 @@ pub mod hotel_chain_manager {
                 &HotelChain::Airport,
                 &mut board,
             )?;
-            assert_eq!(hotel_chain_manager.chain_status(hotel_chain_1), false);
-            assert_eq!(hotel_chain_manager.chain_status(hotel_chain_2), true);
+            assert!(!hotel_chain_manager.chain_status(hotel_chain_1));
+            assert!(hotel_chain_manager.chain_status(hotel_chain_2));
             assert_eq!(hotel_chain_manager.chain_length(hotel_chain_1), 0);
             assert_eq!(hotel_chain_manager.chain_length(hotel_chain_2), 26);
+            let positions = hotel_chain_manager.chain_positions(hotel_chain_2);
+            assert_eq!(positions.len(), 26);
+            assert!(positions
+                .iter()
+                .all(|position| board.is_hotel_placed(position) == Some(Some(*hotel_chain_2))));
+            // The chains are found again from the board
+            let restored = HotelChainManager::from_board(&board);
+            assert_eq!(restored.chain_length(hotel_chain_2), 26);
+            assert_eq!(restored.chain_positions(hotel_chain_2), positions);
             Ok(())
         }
 
         fn setup_hotel(
-            position_cards: &mut Vec<Position>,
+            position_cards: &mut VecDeque<Position>,
             board: &mut Board,
             hotel_chain_manager: &mut HotelChainManager,
             bank: &mut Bank,
@@ -785,10 +1600,10 @@ pub mod hotel_chain_manager {
         ) -> Result<()> {
             let mut cards: Vec<Position> = Vec::new();
             for _i in 1..=13 {
-                cards.push(draw_card(position_cards)?.unwrap());
+                cards.push(draw_card(position_cards).unwrap());
             }
             for card in &cards {
-                board.place_hotel(&card)?;
+                board.place_hotel(card)?;
             }
             hotel_chain_manager.start_chain(*hotel_chain, cards, board, player, bank)?;
             Ok(())
@@ -836,6 +1651,7 @@ pub fn print_info_card() {
 
 /// Manages a single round. A round consists of each player doing a move.
 pub mod round {
+    use std::{collections::VecDeque, slice};
 
     use miette::{miette, Result};
     use owo_colors::{AnsiColors, OwoColorize};
@@ -844,22 +1660,310 @@ pub mod round {
         base_game::{
             bank::Bank,
             board::{AnalyzedPosition, Board, Position},
+            hotel_chains::HotelChain,
             player::Player,
-            settings::Settings,
+            settings::{Privacy, Settings},
             ui,
         },
+        journal::journal_turn,
+        locale::text,
         logic::{
             check_end_condition,
-            place_hotel::{place_hotel, IllegalPlacement, PlaceHotelCase},
+            place_hotel::{
+                analyze_position, extend_chain, place_hotel, IllegalPlacement, PlaceHotelCase,
+            },
+            remaining_players,
         },
-        network::broadcast_others,
+        network::{broadcast, broadcast_bot_reasons, broadcast_each, broadcast_others},
+        tutorial::TutorialEvent,
     };
 
-    use super::hotel_chain_manager::HotelChainManager;
+    use super::{hotel_chain_manager::HotelChainManager, player_by_id, GameManager};
 
     pub struct Round {
         pub started: bool,
         pub number: u32,
+        /// The finances of each player at the end of the round, recorded by
+        /// [`Round::start_round`]
+        pub finances: Vec<RoundFinances>,
+        /// What has happened in the turns of the round, recorded by [`Round::start_round`]
+        pub events: Vec<GameEvent>,
+        /// The index of the player that plays first in this round, the players before have
+        /// already played. Used when a game is recovered in the middle of a round.
+        pub first_player: usize,
+        /// How far the turn of the first player has already been played. Used when a game is
+        /// recovered in the middle of a turn.
+        pub first_progress: TurnProgress,
+        /// The number of cards that are left in the draw pile at the start of the current turn,
+        /// `None` when it is not known
+        pub remaining_cards: Option<usize>,
+    }
+
+    /// The parts of a turn in the order in which they are played.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum TurnPhase {
+        /// The player places a hotel and decides if the game ends when it can be ended
+        #[default]
+        PlaceHotel,
+        /// The player sells stocks when the rules allow it
+        SellStocks,
+        /// The player buys stocks
+        BuyStocks,
+        /// The player draws a new card
+        DrawCard,
+    }
+
+    impl TurnPhase {
+        /// Returns the name with which the phase is stored in the journal
+        pub fn name(&self) -> &'static str {
+            match self {
+                TurnPhase::PlaceHotel => "place_hotel",
+                TurnPhase::SellStocks => "sell_stocks",
+                TurnPhase::BuyStocks => "buy_stocks",
+                TurnPhase::DrawCard => "draw_card",
+            }
+        }
+
+        /// Returns the phase with the name that is stored in the journal.
+        /// If no phase with that name exists `None` is returned.
+        pub fn from_name(name: &str) -> Option<TurnPhase> {
+            match name {
+                "place_hotel" => Some(TurnPhase::PlaceHotel),
+                "sell_stocks" => Some(TurnPhase::SellStocks),
+                "buy_stocks" => Some(TurnPhase::BuyStocks),
+                "draw_card" => Some(TurnPhase::DrawCard),
+                _ => None,
+            }
+        }
+    }
+
+    /// How far a turn has been played. The progress is stored in the journal after each action,
+    /// so that a game can be recovered in the middle of a turn.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct TurnProgress {
+        /// The part of the turn that is played next
+        pub phase: TurnPhase,
+        /// If the player has placed a hotel in this turn
+        pub hotel_placed: bool,
+        /// If the player has ended the game in this turn
+        pub game_ended: bool,
+    }
+
+    /// Something that has happened during a turn.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct GameEvent {
+        pub round: u32,
+        /// The id of the player whose turn it was, `None` for the turn of the neutral player
+        pub player_id: Option<u32>,
+        /// What has happened, without colors
+        pub text: String,
+    }
+
+    /// Something that has changed in a turn, see [`TurnSnapshot::changes`].
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct TurnChange {
+        /// What has changed
+        text: String,
+        /// The id of the player whose stocks and money are revealed by the change, `None` when
+        /// every player may see the change
+        holder: Option<u32>,
+        /// What is shown instead of the text to players that may not see the stocks and money
+        /// of the holder, the change is left out when `None`
+        coarse: Option<String>,
+    }
+
+    impl TurnChange {
+        /// Creates a change that every player may see
+        fn public(text: String) -> Self {
+            Self {
+                text,
+                holder: None,
+                coarse: None,
+            }
+        }
+    }
+
+    /// Returns if the stocks and the money of the player with the id `holder` may be shown to
+    /// the recipient of the game log. The recipient is `None` for the console that is shared by
+    /// several local players, it only shows them when the game is played with
+    /// [`Privacy::Open`] and not in hot-seat mode.
+    fn holdings_visible(holder: u32, recipient: Option<&Player>, settings: &Settings) -> bool {
+        match recipient {
+            Some(player) => {
+                player.id == holder || player.privacy.max(settings.privacy) == Privacy::Open
+            }
+            None => settings.privacy == Privacy::Open && !settings.hot_seat,
+        }
+    }
+
+    /// The parts of the game that are compared before and after a turn to find out what has
+    /// happened in the turn.
+    struct TurnSnapshot {
+        /// The placed hotels and the chain they belong to
+        hotels: Vec<(Position, Option<HotelChain>)>,
+        chains: Vec<HotelChain>,
+        /// The stocks of each player in the order of [`HotelChain::iterator`]
+        stocks: Vec<Vec<u32>>,
+        money: Vec<u32>,
+        bonus_payouts: usize,
+    }
+
+    impl TurnSnapshot {
+        fn new(
+            players: &[Player],
+            board: &Board,
+            bank: &Bank,
+            hotel_chain_manager: &HotelChainManager,
+        ) -> Self {
+            Self {
+                hotels: GameManager::init_position_cards()
+                    .into_iter()
+                    .filter_map(|position| {
+                        board
+                            .is_hotel_placed(&position)
+                            .map(|chain| (position, chain))
+                    })
+                    .collect(),
+                chains: hotel_chain_manager.active_chains(),
+                stocks: players
+                    .iter()
+                    .map(|player| {
+                        HotelChain::iterator()
+                            .map(|chain| *player.owned_stocks.stocks_for_hotel(chain))
+                            .collect()
+                    })
+                    .collect(),
+                money: players.iter().map(|player| player.money).collect(),
+                bonus_payouts: bank.bonus_payouts.len(),
+            }
+        }
+
+        /// Describes what has changed between this snapshot and the snapshot that has been
+        /// taken after the turn of the player. The name of the player is left out in the
+        /// descriptions of their own actions.
+        fn changes(
+            &self,
+            after: &TurnSnapshot,
+            player_id: Option<u32>,
+            players: &[Player],
+            bank: &Bank,
+        ) -> Vec<TurnChange> {
+            let mut changes = Vec::new();
+            for (hotel, chain) in after
+                .hotels
+                .iter()
+                .filter(|(hotel, _)| !self.hotels.iter().any(|(placed, _)| placed == hotel))
+            {
+                changes.push(TurnChange::public(format!("placed a hotel on {}", hotel)));
+                if let Some(chain) = chain.filter(|chain| self.chains.contains(chain)) {
+                    changes.push(TurnChange::public(format!("extended {}", chain)));
+                }
+            }
+            for chain in after
+                .chains
+                .iter()
+                .filter(|chain| !self.chains.contains(chain))
+            {
+                changes.push(TurnChange::public(format!("founded {}", chain)));
+            }
+            for chain in self
+                .chains
+                .iter()
+                .filter(|chain| !after.chains.contains(chain))
+            {
+                changes.push(TurnChange::public(format!("{} has been taken over", chain)));
+            }
+            let name = |player: &Player| match player_id == Some(player.id) {
+                true => String::new(),
+                false => format!("{}: ", player.name),
+            };
+            // The bonuses are announced to every player when they are paid
+            for payout in &bank.bonus_payouts[self.bonus_payouts..] {
+                if let Some(player) = player_by_id(payout.player_id, players) {
+                    changes.push(TurnChange::public(format!(
+                        "{}was paid {}€ as {} shareholder of {}",
+                        name(player),
+                        payout.bonus,
+                        payout.rank.name(),
+                        payout.chain
+                    )));
+                }
+            }
+            for (index, player) in players.iter().enumerate() {
+                let mut trades: Vec<String> = HotelChain::iterator()
+                    .enumerate()
+                    .filter_map(|(chain_index, chain)| {
+                        let change = after.stocks[index][chain_index] as i64
+                            - self.stocks[index][chain_index] as i64;
+                        (change != 0).then(|| format!("{:+} {}", change, chain))
+                    })
+                    .collect();
+                let traded_stocks = !trades.is_empty();
+                let money = after.money[index] as i64 - self.money[index] as i64;
+                if money != 0 {
+                    trades.push(format!("{:+}€", money));
+                }
+                if !trades.is_empty() {
+                    changes.push(TurnChange {
+                        text: format!("{}{}", name(player), trades.join(", ")),
+                        holder: Some(player.id),
+                        coarse: traded_stocks.then(|| format!("{}traded stocks", name(player))),
+                    });
+                }
+            }
+            changes
+        }
+    }
+
+    /// Returns the line of the game log that sums up what has happened in a turn, like
+    /// `Anna: placed a hotel on D6 | founded Festival | +1 Festival | 57 cards left`. The turn
+    /// of the neutral player is shown as turn of the `Neutral player`.
+    ///
+    /// The stocks and the money of players that the recipient may not see are left out, see
+    /// [`holdings_visible`].
+    fn log_line(
+        player_id: Option<u32>,
+        changes: &[TurnChange],
+        remaining_cards: usize,
+        players: &[Player],
+        recipient: Option<&Player>,
+        settings: &Settings,
+    ) -> String {
+        let name = match player_id.and_then(|id| player_by_id(id, players)) {
+            Some(player) => player.colored_name(),
+            None => String::from("Neutral player"),
+        };
+        let mut parts: Vec<String> = changes
+            .iter()
+            .filter_map(|change| match change.holder {
+                Some(holder) if !holdings_visible(holder, recipient, settings) => {
+                    change.coarse.clone()
+                }
+                _ => Some(change.text.clone()),
+            })
+            .collect();
+        if parts.is_empty() {
+            parts.push(String::from("nothing has changed"));
+        }
+        parts.push(format!("{} cards left", remaining_cards));
+        format!("{}: {}", name, parts.join(" | "))
+    }
+
+    /// The money and the value of the stocks of one player at the end of a round.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct RoundFinances {
+        pub round: u32,
+        pub player_id: u32,
+        pub money: u32,
+        /// The value of the stocks of the player at the prices at the end of the round
+        pub stock_value: u32,
+    }
+
+    impl RoundFinances {
+        /// Returns the money of the player plus the value of their stocks
+        pub fn net_worth(&self) -> u32 {
+            self.money + self.stock_value
+        }
     }
 
     impl Round {
@@ -868,30 +1972,121 @@ pub mod round {
             Self {
                 started: false,
                 number,
+                finances: Vec::new(),
+                events: Vec::new(),
+                first_player: 0,
+                first_progress: TurnProgress::default(),
+                remaining_cards: None,
             }
         }
 
+        /// Adds what has changed since the snapshot has been taken to the events of the round.
+        /// The events are written to the event stream of the settings and are summed up in one
+        /// line of the game log together with the number of cards that are left in the draw
+        /// pile. Each player is sent the line that leaves out what they may not see, see
+        /// [`log_line`].
+        #[allow(clippy::too_many_arguments)]
+        fn record_events(
+            &mut self,
+            settings: &Settings,
+            player_id: Option<u32>,
+            before: &TurnSnapshot,
+            players: &[Player],
+            board: &Board,
+            bank: &Bank,
+            hotel_chain_manager: &HotelChainManager,
+            remaining_cards: usize,
+        ) -> Result<()> {
+            let after = TurnSnapshot::new(players, board, bank, hotel_chain_manager);
+            let changes = before.changes(&after, player_id, players, bank);
+            // The turn of a player is always summed up, so that the others know it has ended
+            if !changes.is_empty() || player_id.is_some() {
+                broadcast_each(
+                    |recipient| {
+                        log_line(
+                            player_id,
+                            &changes,
+                            remaining_cards,
+                            players,
+                            recipient,
+                            settings,
+                        )
+                    },
+                    players,
+                )?;
+            }
+            for change in changes {
+                let event = GameEvent {
+                    round: self.number,
+                    player_id,
+                    text: change.text,
+                };
+                if let Some(event_stream) = &settings.event_stream {
+                    event_stream.write(&event, players);
+                }
+                self.events.push(event);
+            }
+            Ok(())
+        }
+
+        /// Stores the money and the value of the stocks of each player as the finances at the
+        /// end of this round.
+        pub fn record_finances(
+            &mut self,
+            players: &[Player],
+            hotel_chain_manager: &HotelChainManager,
+        ) {
+            self.finances = players
+                .iter()
+                .map(|player| RoundFinances {
+                    round: self.number,
+                    player_id: player.id,
+                    money: player.money,
+                    stock_value: hotel_chain_manager
+                        .active_chains()
+                        .iter()
+                        .map(|chain| {
+                            Bank::stock_price(hotel_chain_manager, chain)
+                                * player.owned_stocks.stocks_for_hotel(chain)
+                        })
+                        .sum(),
+                })
+                .collect();
+        }
+
         /// Starts a new round consisting of each player doing a single turn.
         /// Does not automatically start a new round when the game is not over yet.
         /// When the game finishes in this round `true` is returned.
         /// The final account is not calculated in this function.
+        /// The finances of the players are recorded when the round ends, see
+        /// [`Round::record_finances`].
         pub fn start_round(
             &mut self,
-            players: &mut Vec<Player>,
+            players: &mut [Player],
             board: &mut Board,
             settings: &Settings,
             bank: &mut Bank,
             hotel_chain_manager: &mut HotelChainManager,
-            position_cards: &mut Vec<Position>,
+            position_cards: &mut VecDeque<Position>,
         ) -> Result<bool> {
             if self.started {
                 return Err(miette!("Round was already started!"));
             }
             self.started = true;
             // Make a turn for each player
-            for i in 0..=players.len() - 1 {
+            for i in self.first_player..players.len() {
+                if players[i].resigned {
+                    continue;
+                }
+                self.remaining_cards = Some(position_cards.len());
+                let before = TurnSnapshot::new(players, board, bank, hotel_chain_manager);
+                let progress = match i == self.first_player {
+                    true => self.first_progress,
+                    false => TurnProgress::default(),
+                };
                 let status = self.player_turn(
                     i,
+                    progress,
                     players,
                     board,
                     settings,
@@ -899,33 +2094,126 @@ pub mod round {
                     hotel_chain_manager,
                     position_cards,
                 )?;
+                self.record_events(
+                    settings,
+                    Some(players[i].id),
+                    &before,
+                    players,
+                    board,
+                    bank,
+                    hotel_chain_manager,
+                    position_cards.len(),
+                )?;
                 if status {
+                    self.record_finances(players, hotel_chain_manager);
                     return Ok(true);
                 }
+                journal_turn(
+                    settings,
+                    self.number,
+                    i + 1,
+                    TurnProgress::default(),
+                    players,
+                    board,
+                    position_cards,
+                );
+            }
+            if settings.neutral_player {
+                let before = TurnSnapshot::new(players, board, bank, hotel_chain_manager);
+                self.neutral_turn(players, board, hotel_chain_manager, position_cards)?;
+                self.record_events(
+                    settings,
+                    None,
+                    &before,
+                    players,
+                    board,
+                    bank,
+                    hotel_chain_manager,
+                    position_cards.len(),
+                )?;
+                journal_turn(
+                    settings,
+                    self.number + 1,
+                    0,
+                    TurnProgress::default(),
+                    players,
+                    board,
+                    position_cards,
+                );
             }
+            self.record_finances(players, hotel_chain_manager);
             Ok(false)
         }
 
+        /// Plays the turn of the neutral player.
+        ///
+        /// The neutral player draws cards until one is drawn that places a single hotel or
+        /// extends a chain. That hotel is placed on the board. Cards that would start a new chain,
+        /// fuse chains or that are illegal are discarded.
+        /// Nothing is done when no cards are left.
+        pub fn neutral_turn(
+            &self,
+            players: &[Player],
+            board: &mut Board,
+            hotel_chain_manager: &mut HotelChainManager,
+            position_cards: &mut VecDeque<Position>,
+        ) -> Result<()> {
+            while let Some(card) = super::draw_card(position_cards) {
+                match analyze_position(&card, board, hotel_chain_manager) {
+                    // What the neutral player has placed is shown in the game log
+                    PlaceHotelCase::SingleHotel => {
+                        board.place_hotel(&card)?;
+                        return Ok(());
+                    }
+                    PlaceHotelCase::ExtendsChain(chain, positions) => {
+                        board.place_hotel(&card)?;
+                        extend_chain(chain, positions, hotel_chain_manager, board)?;
+                        return Ok(());
+                    }
+                    _ => broadcast(
+                        &format!(
+                            "The neutral player has discarded the card {}",
+                            card.color(AnsiColors::Green)
+                        ),
+                        players,
+                    )?,
+                }
+            }
+            Ok(())
+        }
+
         /// Plays a single player turn
         /// When this player finishes the game this round `true` is returned
+        /// # Arguments
+        /// * `progress` - How far the turn has already been played, the parts before are skipped.
+        ///   Used when a game is recovered in the middle of a turn.
         #[allow(clippy::too_many_arguments)]
         fn player_turn(
             &self,
             player_index: usize,
-            players: &mut Vec<Player>,
+            progress: TurnProgress,
+            players: &mut [Player],
             board: &mut Board,
             settings: &Settings,
             bank: &mut Bank,
             hotel_chain_manager: &mut HotelChainManager,
-            position_cards: &mut Vec<Position>,
+            position_cards: &mut VecDeque<Position>,
         ) -> Result<bool> {
-            let player = players.get_mut(player_index).unwrap();
-            let current_player_name = player.name.clone();
+            let player = &mut players[player_index];
+            let current_player_id = player.id;
+            if settings.hot_seat {
+                ui::hand_over(player)?;
+            }
             // Update the players cards to new game state
             player.analyze_cards(board, hotel_chain_manager);
             player.sort_cards();
+            let player = &players[player_index];
+            let is_bot = player.bot.is_some();
+            if let Some(bot) = &player.bot {
+                bot.observe(player, board, players);
+            }
             ui::print_main_ui_players(
-                current_player_name.clone(),
+                current_player_id,
                 players,
                 board,
                 settings,
@@ -933,52 +2221,108 @@ pub mod round {
                 bank,
                 hotel_chain_manager,
             )?;
-            let mut game_ended = false;
-            //1. Place piece
-            let hotel_placed = place_hotel(
-                player_index,
-                players,
-                board,
-                settings,
-                self,
-                bank,
-                hotel_chain_manager,
-            )?;
-            //2. Check if end game condition is met
-            //      If yes ask give user the option to end the game here
-            let player = players.get_mut(player_index).unwrap();
-            if let Some(condition) = check_end_condition(board, hotel_chain_manager) {
-                ui::print_main_ui_players(
-                    player.name.clone(),
+            let TurnProgress {
+                phase,
+                mut hotel_placed,
+                mut game_ended,
+            } = progress;
+            if let (Some(tutorial), false) = (&settings.tutorial, is_bot) {
+                tutorial.explain(TutorialEvent::PlayerTurn, players)?;
+            }
+            if phase == TurnPhase::PlaceHotel {
+                let chains_before = hotel_chain_manager.active_chains();
+                //1. Place piece
+                hotel_placed = place_hotel(
+                    player_index,
                     players,
                     board,
                     settings,
-                    Some(self),
+                    self,
                     bank,
                     hotel_chain_manager,
                 )?;
-                let player = players.get_mut(player_index).unwrap();
-                player.print_text_ln(&format!(
-                    "The following game ending condition is met: {}",
-                    condition.description().color(AnsiColors::Green)
-                ))?;
-                let input = player.read_input(
-                    "Would you like to end the game (you will still be able to by stocks)? [Y/n]: "
-                        .to_string(),
-                    vec!['Y', 'y', 'N', 'n'],
-                )?;
-                match input {
-                    'Y' => game_ended = true,
-                    'y' => game_ended = true,
-                    _ => (),
+                broadcast_bot_reasons(players)?;
+                if players[player_index].resigned {
+                    // The game ends when less than two players are left
+                    if remaining_players(players) < 2 {
+                        broadcast(&text("game.not_enough_players", &[]), players)?;
+                        return Ok(true);
+                    }
+                    return Ok(false);
                 }
+                if let Some(tutorial) = &settings.tutorial {
+                    for chain in hotel_chain_manager.active_chains() {
+                        if !chains_before.contains(&chain) {
+                            tutorial.explain(TutorialEvent::ChainFounded(chain), players)?;
+                        }
+                        if hotel_chain_manager.is_chain_safe(&chain) {
+                            tutorial.explain(TutorialEvent::SafeChain(chain), players)?;
+                        }
+                    }
+                    tutorial.pause_for_bot(&players[player_index]);
+                }
+                //2. Check if end game condition is met
+                //      If yes ask give user the option to end the game here
+                if let Some(condition) = check_end_condition(board, hotel_chain_manager) {
+                    ui::print_main_ui_players(
+                        current_player_id,
+                        players,
+                        board,
+                        settings,
+                        Some(self),
+                        bank,
+                        hotel_chain_manager,
+                    )?;
+                    players[player_index].print_text_ln(&text(
+                        "game.end_condition",
+                        &[&condition.description().color(AnsiColors::Green)],
+                    ))?;
+                    if let Some(tutorial) = &settings.tutorial {
+                        tutorial.explain(TutorialEvent::EndCondition, players)?;
+                    }
+                    let player = &players[player_index];
+                    if let Some(bot) = &player.bot {
+                        let decision = bot.end_game(player, players, hotel_chain_manager);
+                        game_ended = decision.choice;
+                        players[player_index].bot_reasons.push(decision.reason);
+                        broadcast_bot_reasons(players)?;
+                    } else {
+                        // A client that does not answer does not end the game
+                        game_ended = player.answer_or_default(
+                            player.read_yes_no(text("game.end_prompt", &[])),
+                            || false,
+                        )?;
+                    }
+                    if game_ended {
+                        broadcast_others(
+                            &format!(
+                                "{} has ended the game, the final account follows after their turn.",
+                                players[player_index].colored_name()
+                            ),
+                            current_player_id,
+                            players,
+                        )?;
+                    }
+                }
+                journal_turn(
+                    settings,
+                    self.number,
+                    player_index,
+                    TurnProgress {
+                        phase: TurnPhase::SellStocks,
+                        hotel_placed,
+                        game_ended,
+                    },
+                    players,
+                    board,
+                    position_cards,
+                );
             }
             //3. Buy stocks
             bank.update_largest_shareholders(players);
-            let player = players.get_mut(player_index).unwrap();
-            if !hotel_chain_manager.active_chains().is_empty() {
+            if phase <= TurnPhase::BuyStocks && !hotel_chain_manager.active_chains().is_empty() {
                 ui::print_main_ui_players(
-                    player.name.clone(),
+                    current_player_id,
                     players,
                     board,
                     settings,
@@ -986,30 +2330,66 @@ pub mod round {
                     bank,
                     hotel_chain_manager,
                 )?;
-                let player = players.get_mut(player_index).unwrap();
-                match player.buy_stocks(bank, hotel_chain_manager)? {
-                    None => broadcast_others(
-                        &format!("{} bought no stocks.", player.name),
-                        &current_player_name,
+                if let (Some(tutorial), false) = (&settings.tutorial, is_bot) {
+                    tutorial.explain(TutorialEvent::BuyStocks, players)?;
+                }
+                let player = &mut players[player_index];
+                // The traded stocks are shown to the other players in the game log. A client that
+                // does not answer neither sells nor buys any stocks.
+                if settings.rules.sell_during_buy_phase && phase <= TurnPhase::SellStocks {
+                    let answer = player.sell_stocks(bank, hotel_chain_manager);
+                    player.answer_or_default(answer, || None)?;
+                    journal_turn(
+                        settings,
+                        self.number,
+                        player_index,
+                        TurnProgress {
+                            phase: TurnPhase::BuyStocks,
+                            hotel_placed,
+                            game_ended,
+                        },
                         players,
-                    )?,
-                    Some(map) => {
-                        let mut out = String::new();
-                        out.push_str(&format!("{} bought the following stocks:\n", player.name));
-                        for (k, v) in map {
-                            out.push_str(&format!("{}: {}\n", k.name().color(k.color()), v));
-                        }
-                        broadcast_others(&out, &current_player_name, players)?;
-                    }
+                        board,
+                        position_cards,
+                    );
+                }
+                let player = &players[player_index];
+                if let Some(bot) = &player.bot {
+                    bot.observe(player, board, players);
+                }
+                let player = &mut players[player_index];
+                let answer = player.buy_stocks(bank, hotel_chain_manager);
+                player.answer_or_default(answer, || None)?;
+                broadcast_bot_reasons(players)?;
+                if let Some(tutorial) = &settings.tutorial {
+                    tutorial.pause_for_bot(&players[player_index]);
                 }
             }
             bank.update_largest_shareholders(players);
+            if phase <= TurnPhase::BuyStocks {
+                journal_turn(
+                    settings,
+                    self.number,
+                    player_index,
+                    TurnProgress {
+                        phase: TurnPhase::DrawCard,
+                        hotel_placed,
+                        game_ended,
+                    },
+                    players,
+                    board,
+                    position_cards,
+                );
+            }
             // If game has ended no new card is drawn
             if game_ended {
                 return Ok(true);
             }
             //4. Draw new card if the hotel has been placed
-            let player = players.get_mut(player_index).unwrap();
+            let player = &mut players[player_index];
+            if settings.rules.replace_dead_tiles {
+                replace_dead_cards(player, board, hotel_chain_manager, position_cards)?;
+            }
             if !hotel_placed {
                 // Hotel was not placed
                 // Check if player has only illegal fusion cards
@@ -1022,26 +2402,37 @@ pub mod round {
                     }
                 }
                 if only_illegal_fusion {
-                    player.print_text_ln("You have only cards left that can not be played because the fusion would be illegal.")?;
-                    let redraw = match player.read_input(
-                        String::from("Would you like to redraw your hand cards? [Y/n]: "),
-                        vec!['Y', 'y', 'N', 'n'],
-                    )? {
-                        'Y' => true,
-                        'y' => true,
-                        'N' => false,
-                        'n' => false,
-                        _ => false,
+                    player.print_text_ln(&text("card.only_illegal", &[]))?;
+                    if let (Some(tutorial), false) = (&settings.tutorial, is_bot) {
+                        tutorial.explain(TutorialEvent::IllegalCards, slice::from_ref(player))?;
+                    }
+                    let redraw = match &player.bot {
+                        Some(bot) => {
+                            let decision = bot.redraw_cards(player);
+                            player.bot_reasons.push(decision.reason);
+                            decision.choice
+                        }
+                        None => player.answer_or_default(
+                            player.read_yes_no(text("card.redraw_prompt", &[])),
+                            || false,
+                        )?,
                     };
                     if redraw {
-                        let drawn_position = super::draw_card(position_cards)?;
+                        broadcast_others(
+                            &format!(
+                                "{} has redrawn their hand cards because none of them could be played.",
+                                player.colored_name()
+                            ),
+                            current_player_id,
+                            players,
+                        )?;
+                        let player = &mut players[player_index];
+                        let drawn_position = super::draw_card(position_cards);
                         // Cards have been reset
                         player.analyzed_cards = Vec::new();
                         match drawn_position {
                             None => {
-                                player.print_text_ln(
-                                    "No card can be drawn because no cards are left.",
-                                )?;
+                                player.print_text_ln(&text("turn.no_cards_left", &[]))?;
                             }
                             Some(card) => {
                                 let new_card =
@@ -1049,29 +2440,207 @@ pub mod round {
                                 player.analyzed_cards.push(new_card);
                             }
                         }
-                        for card in &player.analyzed_cards {
-                            player.print_text_ln(&format!("New card: {}", &card))?;
-                        }
-                        player.get_enter(&format!(
-                            "You have gotten {} new cards. Press enter to finish your turn.",
-                            player.analyzed_cards.len()
-                        ))?;
+                        let new_cards: Vec<String> = player
+                            .analyzed_cards
+                            .iter()
+                            .map(|card| text("turn.new_card", &[card]))
+                            .collect();
+                        ui::show_private(
+                            player,
+                            &new_cards.join("\n"),
+                            &text("turn.new_cards", &[&player.analyzed_cards.len()]),
+                            settings.hot_seat,
+                        )?;
                     }
                 }
-                player.get_enter("Press enter to finish your turn")?;
+                players[player_index].get_enter(&text("turn.finish", &[]))?;
+                broadcast_bot_reasons(players)?;
                 return Ok(false);
             }
-            let drawn_position = super::draw_card(position_cards)?;
+            let drawn_position = super::draw_card(position_cards);
             match drawn_position {
                 None => {
-                    player.print_text_ln("No card can be drawn because no cards are left.")?;
-                    player.get_enter("Press enter to finish your turn")?;
-                }
-                Some(card) => {
-                    player.draw_card(card, settings.skip_dialogues, board, hotel_chain_manager)?
+                    player.print_text_ln(&text("turn.no_cards_left", &[]))?;
+                    player.get_enter(&text("turn.finish", &[]))?;
                 }
+                Some(card) => player.draw_card(card, settings, board, hotel_chain_manager)?,
             }
             Ok(false)
         }
     }
+
+    /// Replaces the cards of the player that can never be played because they would fuse safe
+    /// chains. The new cards are drawn from the position cards.
+    fn replace_dead_cards(
+        player: &mut Player,
+        board: &Board,
+        hotel_chain_manager: &HotelChainManager,
+        position_cards: &mut VecDeque<Position>,
+    ) -> Result<()> {
+        let dead_cards: Vec<Position> = player
+            .analyzed_cards
+            .iter()
+            .filter(|card| {
+                card.place_hotel_case == PlaceHotelCase::Illegal(IllegalPlacement::FusionIllegal)
+            })
+            .map(|card| card.position)
+            .collect();
+        for dead_card in dead_cards {
+            let drawn_position = match super::draw_card(position_cards) {
+                Some(position) => position,
+                None => break,
+            };
+            player.remove_card(&dead_card)?;
+            player.add_card(&drawn_position, board, hotel_chain_manager);
+            player.print_text_ln(&format!(
+                "The card {} can never be played and has been replaced by {}",
+                dead_card.color(AnsiColors::Red),
+                drawn_position.color(AnsiColors::Green)
+            ))?;
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::VecDeque;
+
+        use miette::Result;
+
+        use crate::{
+            base_game::{
+                bank::Bank,
+                board::{Board, Position},
+                hotel_chains::HotelChain,
+                player::Player,
+                settings::{Privacy, Settings},
+            },
+            game::hotel_chain_manager::HotelChainManager,
+        };
+
+        use super::{log_line, Round, TurnSnapshot};
+
+        #[test]
+        fn neutral_turn_discards_chain_starting_cards() -> Result<()> {
+            let mut board = Board::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let players = vec![
+                Player::new(vec![], 0, false, String::from("Player 1")),
+                Player::new(vec![], 1, false, String::from("Player 2")),
+            ];
+            board.place_hotel(&Position::new('A', 1))?;
+            // Only card left would start a new chain and is discarded
+            let mut position_cards = VecDeque::from([Position::new('A', 2)]);
+            let round = Round::new(1);
+            round.neutral_turn(
+                &players,
+                &mut board,
+                &mut hotel_chain_manager,
+                &mut position_cards,
+            )?;
+            assert!(position_cards.is_empty());
+            assert!(board.is_hotel_placed(&Position::new('A', 2)).is_none());
+            // Card that extends a chain is placed
+            let mut bank = Bank::new();
+            let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+            board.place_hotel(&Position::new('B', 1))?;
+            hotel_chain_manager.start_chain(
+                HotelChain::Luxor,
+                vec![Position::new('A', 1), Position::new('B', 1)],
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+            let mut position_cards = VecDeque::from([Position::new('C', 1)]);
+            round.neutral_turn(
+                &players,
+                &mut board,
+                &mut hotel_chain_manager,
+                &mut position_cards,
+            )?;
+            assert_eq!(hotel_chain_manager.chain_length(&HotelChain::Luxor), 3);
+            Ok(())
+        }
+
+        #[test]
+        fn turn_is_summed_up_in_one_log_line() -> Result<()> {
+            let mut board = Board::new();
+            let mut bank = Bank::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Anna")),
+                Player::new(vec![], 1, false, String::from("Ben")),
+            ];
+            board.place_hotel(&Position::new('A', 1))?;
+            board.place_hotel(&Position::new('B', 1))?;
+            hotel_chain_manager.start_chain(
+                HotelChain::Luxor,
+                vec![Position::new('A', 1), Position::new('B', 1)],
+                &mut board,
+                &mut players[1],
+                &mut bank,
+            )?;
+            let before = TurnSnapshot::new(&players, &board, &bank, &hotel_chain_manager);
+            board.place_hotel(&Position::new('C', 1))?;
+            hotel_chain_manager.add_hotel_to_chain(
+                &HotelChain::Luxor,
+                Position::new('C', 1),
+                &mut board,
+            )?;
+            let after = TurnSnapshot::new(&players, &board, &bank, &hotel_chain_manager);
+            let changes = before.changes(&after, Some(0), &players, &bank);
+            let texts: Vec<&str> = changes.iter().map(|change| change.text.as_str()).collect();
+            assert_eq!(texts, ["placed a hotel on C1", "extended Luxor"]);
+            let settings = Settings::new(false, Privacy::Open, false);
+            let line = log_line(Some(0), &changes, 57, &players, None, &settings);
+            assert!(line.contains("Anna"));
+            assert!(line.ends_with(": placed a hotel on C1 | extended Luxor | 57 cards left"));
+            assert!(log_line(None, &changes, 57, &players, None, &settings)
+                .starts_with("Neutral player: "));
+            assert!(log_line(Some(1), &[], 0, &players, None, &settings)
+                .ends_with(": nothing has changed | 0 cards left"));
+            Ok(())
+        }
+
+        #[test]
+        fn log_line_hides_the_holdings_of_others() -> Result<()> {
+            let board = Board::new();
+            let bank = Bank::new();
+            let hotel_chain_manager = HotelChainManager::new();
+            let mut players = vec![
+                Player::new(vec![], 0, false, String::from("Anna")),
+                Player::new(vec![], 1, false, String::from("Ben")),
+            ];
+            let before = TurnSnapshot::new(&players, &board, &bank, &hotel_chain_manager);
+            players[0].owned_stocks.set_stocks(&HotelChain::Festival, 2);
+            players[0].money -= 600;
+            let after = TurnSnapshot::new(&players, &board, &bank, &hotel_chain_manager);
+            let changes = before.changes(&after, Some(0), &players, &bank);
+            let settings = Settings::new(false, Privacy::Strict, false);
+            let line = log_line(
+                Some(0),
+                &changes,
+                57,
+                &players,
+                Some(&players[1]),
+                &settings,
+            );
+            assert!(line.ends_with(": traded stocks | 57 cards left"));
+            assert!(!line.contains("Festival") && !line.contains('€'));
+            // The player sees their own trades
+            let line = log_line(
+                Some(0),
+                &changes,
+                57,
+                &players,
+                Some(&players[0]),
+                &settings,
+            );
+            assert!(line.ends_with(": +2 Festival, -600€ | 57 cards left"));
+            // The console that is shared by the local players does not show them
+            assert!(log_line(Some(0), &changes, 57, &players, None, &settings)
+                .ends_with(": traded stocks | 57 cards left"));
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,282 @@
+use std::{
+    cmp::Ordering,
+    fs::OpenOptions,
+    io::Write,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use miette::{miette, IntoDiagnostic, Result};
+use serde_json::{json, Value};
+
+use crate::{
+    base_game::{player::Player, settings::Settings},
+    game::ranking,
+};
+
+/// The file to which the results of the games are appended when no other file is set.
+pub const DEFAULT_HISTORY_FILE: &str = "acquire_rs_history.jsonl";
+
+/// The result of one player in one game.
+///
+/// Each result is stored as one json object per line in the history file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameResult {
+    pub name: String,
+    /// The rank of the player after the final account, players with the same money share a rank
+    pub placement: u32,
+    /// The money of the player after the final account
+    pub money: u32,
+    /// The number of players that took part in the game, including the bots
+    pub players: u32,
+    /// The number of players that have been played by bots
+    pub bots: u32,
+    /// The name of the rule preset with which the game has been played
+    pub rules: String,
+    /// The day on which the game has ended as `YYYY-MM-DD`
+    pub date: String,
+}
+
+impl GameResult {
+    pub(crate) fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "placement": self.placement,
+            "money": self.money,
+            "players": self.players,
+            "bots": self.bots,
+            "rules": self.rules,
+            "date": self.date,
+        })
+    }
+
+    fn from_json(result: &Value) -> Option<Self> {
+        Some(Self {
+            name: result["name"].as_str()?.to_string(),
+            placement: result["placement"].as_u64()? as u32,
+            money: result["money"].as_u64()? as u32,
+            players: result["players"].as_u64()? as u32,
+            bots: result["bots"].as_u64()? as u32,
+            rules: result["rules"].as_str()?.to_string(),
+            date: result["date"].as_str()?.to_string(),
+        })
+    }
+}
+
+/// Returns the result of each player after the final account.
+pub fn game_results(players: &[Player], settings: &Settings, date: &str) -> Vec<GameResult> {
+    ranking(players)
+        .iter()
+        .map(|(placement, player)| GameResult {
+            name: player.name.clone(),
+            placement: *placement,
+            money: player.money,
+            players: players.len() as u32,
+            bots: players.iter().filter(|player| player.bot.is_some()).count() as u32,
+            rules: settings.rule_preset.name().to_string(),
+            date: date.to_string(),
+        })
+        .collect()
+}
+
+/// Appends the results to the history file, the file is created when it does not exist.
+pub fn append_results(path: &str, results: &[GameResult]) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .into_diagnostic()?;
+    let mut lines = String::new();
+    for result in results {
+        lines.push_str(&result.to_json().to_string());
+        lines.push('\n');
+    }
+    file.write_all(lines.as_bytes()).into_diagnostic()
+}
+
+/// Reads the results from the content of a history file. Empty lines are skipped.
+pub fn read_results(history: &str) -> Result<Vec<GameResult>> {
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .ok()
+                .and_then(|result| GameResult::from_json(&result))
+                .ok_or_else(|| {
+                    miette!(
+                        "Unable to read history: Line {} is not a valid result",
+                        index + 1
+                    )
+                })
+        })
+        .collect()
+}
+
+/// The statistics of all games a player has played under one name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LifetimeStatistics {
+    pub name: String,
+    pub games: u32,
+    /// The number of games in which the player has been placed first, shared wins included
+    pub wins: u32,
+    pub average_placement: f64,
+    pub average_money: f64,
+}
+
+impl LifetimeStatistics {
+    /// Returns the share of games the player has won
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+}
+
+/// Sums up the results per player name.
+///
+/// The players are sorted by their win rate, the best first. Players with the same win rate are
+/// sorted by name.
+pub fn lifetime_statistics(results: &[GameResult]) -> Vec<LifetimeStatistics> {
+    let mut names: Vec<&str> = results.iter().map(|result| result.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+    let mut statistics: Vec<LifetimeStatistics> = names
+        .iter()
+        .map(|name| {
+            let results: Vec<&GameResult> = results
+                .iter()
+                .filter(|result| result.name == *name)
+                .collect();
+            let games = results.len() as u32;
+            LifetimeStatistics {
+                name: name.to_string(),
+                games,
+                wins: results
+                    .iter()
+                    .filter(|result| result.placement == 1)
+                    .count() as u32,
+                average_placement: results
+                    .iter()
+                    .map(|result| result.placement as f64)
+                    .sum::<f64>()
+                    / games as f64,
+                average_money: results
+                    .iter()
+                    .map(|result| result.money as f64)
+                    .sum::<f64>()
+                    / games as f64,
+            }
+        })
+        .collect();
+    statistics.sort_by(|a, b| match b.win_rate().total_cmp(&a.win_rate()) {
+        Ordering::Equal => a.name.cmp(&b.name),
+        ordering => ordering,
+    });
+    statistics
+}
+
+/// Prints the lifetime statistics of each player.
+pub fn print_lifetime_statistics(statistics: &[LifetimeStatistics], path: &str) {
+    if statistics.is_empty() {
+        println!("No games have been recorded in {} yet.", path);
+        return;
+    }
+    println!("Lifetime statistics from {}:", path);
+    println!(
+        "{:15} || {:>5} || {:>4} || {:>8} || {:>13} || {:>13}",
+        "Player", "Games", "Wins", "Win rate", "Avg placement", "Avg money"
+    );
+    for statistics in statistics {
+        println!(
+            "{:15} || {:>5} || {:>4} || {:>7.1}% || {:>13.2} || {:>12.0}€",
+            statistics.name,
+            statistics.games,
+            statistics.wins,
+            statistics.win_rate() * 100.0,
+            statistics.average_placement,
+            statistics.average_money
+        );
+    }
+}
+
+/// Returns the current day as `YYYY-MM-DD` in UTC.
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    date_from_days((seconds / 86_400) as i64)
+}
+
+/// Converts the number of days since 1970-01-01 into a date of the gregorian calendar.
+fn date_from_days(days: i64) -> String {
+    // Shifts the epoch to 0000-03-01 so that the leap day is the last day of the year
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::base_game::{
+        player::Player,
+        settings::{Privacy, Settings},
+    };
+
+    use super::{date_from_days, game_results, lifetime_statistics, read_results};
+
+    #[test]
+    fn dates_are_computed() {
+        assert_eq!(date_from_days(0), "1970-01-01");
+        assert_eq!(date_from_days(11_016), "2000-02-29");
+        assert_eq!(date_from_days(20_742), "2026-10-16");
+    }
+
+    #[test]
+    fn lifetime_statistics_are_summed_up_per_name() -> Result<()> {
+        let settings = Settings::new(false, Privacy::default(), false);
+        let mut history = String::new();
+        for money in [[8000, 5000], [4000, 4000], [3000, 9000]] {
+            let players: Vec<Player> = ["Anna", "Ben"]
+                .iter()
+                .zip(money)
+                .enumerate()
+                .map(|(id, (name, money))| {
+                    let mut player = Player::new(vec![], id as u32, false, name.to_string());
+                    player.money = money;
+                    player
+                })
+                .collect();
+            for result in game_results(&players, &settings, "2026-10-16") {
+                history.push_str(&format!("{}\n", result.to_json()));
+            }
+        }
+        history.push('\n');
+        let results = read_results(&history)?;
+        assert_eq!(results.len(), 6);
+        assert_eq!(results[0].rules, "classic");
+        let statistics = lifetime_statistics(&results);
+        // Both have won two games when the shared win is counted, so they are sorted by name
+        assert_eq!(statistics[0].name, "Anna");
+        assert_eq!(statistics[0].games, 3);
+        assert_eq!(statistics[0].wins, 2);
+        assert_eq!(statistics[0].average_money, 5000.0);
+        assert_eq!(statistics[1].wins, 2);
+        assert!((statistics[1].average_placement - 4.0 / 3.0).abs() < 1e-9);
+        assert!(read_results("{\"name\": \"Anna\"}").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,647 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{BufRead, BufReader, Read, Write},
+    net::{IpAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use miette::{miette, IntoDiagnostic, Result};
+use rand::Rng;
+use serde_json::{json, Value};
+
+use crate::engine::{Engine, EXTERNAL_SEAT};
+
+/// The port on which the http server listens when no other port is set.
+pub const DEFAULT_HTTP_PORT: u16 = 11512;
+
+/// The largest body of a request that is read, larger requests are rejected
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+
+/// How long a client counts as connected after its last request
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the server waits for a client to send its request or to read the answer
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How many connections are answered at the same time, further connections are closed
+const MAX_CONNECTIONS: usize = 64;
+
+/// How long a game is kept after its last request
+const GAME_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// The most playouts the bots of a game may use for each decision
+const MAX_BOT_PLAYOUTS: u64 = 1000;
+
+/// How many games are hosted at the same time, further games are rejected
+const MAX_GAMES: usize = 100;
+
+/// The games that are played through the http server, the key is the id of the game.
+///
+/// The requests are answered like this:
+/// * `POST /games` - Creates a game from the configuration in the body, see [`Engine::new`].
+///   Returns the `id` of the game, its state and the `tokens` of the seats.
+/// * `GET /games/<id>` - Returns the state of the game, see [`Engine::state`]. The hand and the
+///   decision of the player that has to decide are only sent to their own seat, the other
+///   seats get the `type` of the decision.
+/// * `POST /games/<id>/actions` - Applies the action in the body, see [`Engine::apply`].
+///   Returns the new state of the game.
+/// * `DELETE /games/<id>` - Stops the game.
+/// * `GET /metrics` - Returns the metrics of the server in the text format of Prometheus.
+///
+/// Each external seat gets its own token, the bot seats get `null`. A game is only read or
+/// stopped with the token of one of its seats and an action is only applied when it is sent
+/// with the token of the player that has to decide. The token is sent as
+/// `Authorization: Bearer <token>`.
+///
+/// Errors are returned as `{"error": "..."}` with a matching status code.
+///
+/// At most 100 games are hosted at the same time. A game is removed when it has ended or when
+/// it has not been requested for 30 minutes.
+#[derive(Default)]
+pub struct HttpGames {
+    games: HashMap<u32, HttpGame>,
+    next_id: u32,
+    metrics: Metrics,
+}
+
+/// A game of the http server.
+struct HttpGame {
+    engine: Engine,
+    /// The tokens of the seats, `None` for the seats of the bots
+    tokens: Vec<Option<String>>,
+    /// The number of turns that have been played
+    turns: u64,
+    /// The time at which the current turn has started
+    turn_started: Instant,
+    /// The time at which the game has been requested the last time
+    last_request: Instant,
+}
+
+impl HttpGame {
+    fn new(engine: Engine, tokens: Vec<Option<String>>) -> Self {
+        Self {
+            engine,
+            tokens,
+            turns: 0,
+            turn_started: Instant::now(),
+            last_request: Instant::now(),
+        }
+    }
+
+    /// Returns if the game still waits for a decision
+    fn is_running(&self) -> bool {
+        self.engine.state()["status"] == "running"
+    }
+
+    /// Returns the seat to which the token belongs
+    fn seat(&self, token: Option<&str>) -> Option<u64> {
+        let token = token?;
+        self.tokens
+            .iter()
+            .position(|seat_token| seat_token.as_deref() == Some(token))
+            .map(|seat| seat as u64)
+    }
+
+    /// Returns the state of the game as the seat sees it.
+    ///
+    /// The hand and the decision of the player that has to decide are private, the other seats
+    /// only see what has to be decided.
+    fn state_for(&self, seat: u64) -> Value {
+        let mut state = self.engine.state();
+        if state["player"].as_u64() != Some(seat) && state["status"] == "running" {
+            state["decision"] = json!({ "type": state["decision"]["type"] });
+            if let Some(state) = state["state"].as_object_mut() {
+                state.remove("hand");
+            }
+        }
+        state
+    }
+}
+
+/// What has happened since the server has been started.
+#[derive(Default)]
+struct Metrics {
+    /// The time at which each client has sent its last request
+    clients: HashMap<IpAddr, Instant>,
+    /// The number of turns that have been finished in all games
+    turns: u64,
+    /// The time that the finished turns have taken together
+    turn_duration: Duration,
+    /// The number of requests that have been answered with an error, by status code
+    errors: BTreeMap<u16, u64>,
+}
+
+impl Metrics {
+    /// Adds the turns that have been finished in the game since the last time
+    fn update_turns(&mut self, game: &mut HttpGame) {
+        let state = game.engine.state();
+        let turns = state["state"]["turn"]
+            .as_u64()
+            .or_else(|| state["turns"].as_u64())
+            .unwrap_or(game.turns);
+        if turns > game.turns {
+            self.turns += turns - game.turns;
+            self.turn_duration += game.turn_started.elapsed();
+            game.turns = turns;
+            game.turn_started = Instant::now();
+        }
+    }
+}
+
+impl HttpGames {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers the request, returns the status code and the json that is sent back.
+    ///
+    /// `token` is the token that has been sent with the request, see [`HttpGames`].
+    pub fn handle(
+        &mut self,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: &str,
+    ) -> (u16, Value) {
+        self.remove_idle_games(GAME_TIMEOUT);
+        let (status, answer) = self.route(method, path, token, body);
+        if status >= 400 {
+            *self.metrics.errors.entry(status).or_default() += 1;
+        }
+        (status, answer)
+    }
+
+    fn route(&mut self, method: &str, path: &str, token: Option<&str>, body: &str) -> (u16, Value) {
+        let segments: Vec<&str> = path
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        match (method, segments.as_slice()) {
+            ("POST", ["games"]) => {
+                if self.games.len() >= MAX_GAMES {
+                    return error(503, format!("The server already hosts {} games", MAX_GAMES));
+                }
+                let config =
+                    read_body(body).and_then(|config| check_config(&config).map(|_| config));
+                let (game, config) = match config
+                    .and_then(|config| Engine::new(&config).map(|game| (game, config)))
+                {
+                    Ok(game) => game,
+                    Err(err) => return error(400, err.to_string()),
+                };
+                let tokens: Vec<Option<String>> = config["seats"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|seat| (seat == EXTERNAL_SEAT).then(new_token))
+                    .collect();
+                self.next_id += 1;
+                let mut state = game.state();
+                state["id"] = json!(self.next_id);
+                state["tokens"] = json!(tokens);
+                let mut game = HttpGame::new(game, tokens);
+                // The bots may have played turns before the first external player decides
+                self.metrics.update_turns(&mut game);
+                if game.is_running() {
+                    self.games.insert(self.next_id, game);
+                }
+                (201, state)
+            }
+            ("GET", ["games", id]) => match self.game(id) {
+                Some(game) => match game.seat(token) {
+                    Some(seat) => {
+                        game.last_request = Instant::now();
+                        (200, game.state_for(seat))
+                    }
+                    None => error(
+                        403,
+                        String::from("The token does not belong to a seat of the game"),
+                    ),
+                },
+                None => error(404, format!("There is no game with the id {}", id)),
+            },
+            ("POST", ["games", id, "actions"]) => {
+                let (game_id, mut game) =
+                    match id.parse().ok().and_then(|id| self.games.remove_entry(&id)) {
+                        Some(entry) => entry,
+                        None => return error(404, format!("There is no game with the id {}", id)),
+                    };
+                game.last_request = Instant::now();
+                let player = game.engine.state()["player"].as_u64();
+                let seat = match game.seat(token) {
+                    Some(seat) if Some(seat) == player => seat,
+                    _ => {
+                        self.games.insert(game_id, game);
+                        return error(
+                            403,
+                            String::from(
+                                "The token does not belong to the player that has to decide",
+                            ),
+                        );
+                    }
+                };
+                let answer = match read_body(body).and_then(|action| game.engine.apply(&action)) {
+                    Ok(()) => {
+                        self.metrics.update_turns(&mut game);
+                        // The next decision may belong to another seat
+                        (200, game.state_for(seat))
+                    }
+                    Err(err) => error(400, err.to_string()),
+                };
+                // The game is removed once it has ended, the final state is sent with this answer
+                if game.is_running() {
+                    self.games.insert(game_id, game);
+                }
+                answer
+            }
+            ("DELETE", ["games", id]) => {
+                let (game_id, game) =
+                    match id.parse().ok().and_then(|id| self.games.remove_entry(&id)) {
+                        Some(entry) => entry,
+                        None => return error(404, format!("There is no game with the id {}", id)),
+                    };
+                if game.seat(token).is_none() {
+                    self.games.insert(game_id, game);
+                    return error(
+                        403,
+                        String::from("The token does not belong to a seat of the game"),
+                    );
+                }
+                (200, json!({}))
+            }
+            (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "actions"]) => {
+                error(405, format!("{} is not allowed on {}", method, path))
+            }
+            _ => error(404, format!("Unknown path {}", path)),
+        }
+    }
+
+    /// Returns the game with the id
+    fn game(&mut self, id: &str) -> Option<&mut HttpGame> {
+        id.parse().ok().and_then(|id| self.games.get_mut(&id))
+    }
+
+    /// Removes the games that have not been requested within the timeout, their players have
+    /// left them
+    fn remove_idle_games(&mut self, timeout: Duration) {
+        self.games
+            .retain(|_, game| game.last_request.elapsed() < timeout);
+    }
+
+    /// Remembers that the client has sent a request
+    fn client_seen(&mut self, client: IpAddr) {
+        self.metrics.clients.insert(client, Instant::now());
+    }
+
+    /// Returns the metrics of the server in the text format of Prometheus.
+    ///
+    /// A client counts as connected when it has sent a request in the last minute.
+    pub fn metrics(&mut self) -> String {
+        self.metrics
+            .clients
+            .retain(|_, last_request| last_request.elapsed() < CLIENT_TIMEOUT);
+        let running = self
+            .games
+            .values()
+            .filter(|game| game.engine.state()["status"] == "running")
+            .count();
+        let average_turn = match self.metrics.turns {
+            0 => 0.0,
+            turns => self.metrics.turn_duration.as_secs_f64() / turns as f64,
+        };
+        let mut metrics = vec![
+            String::from("# HELP acquire_games_in_progress The games that are waiting for a decision"),
+            String::from("# TYPE acquire_games_in_progress gauge"),
+            format!("acquire_games_in_progress {}", running),
+            String::from("# HELP acquire_connected_clients The clients that have sent a request in the last minute"),
+            String::from("# TYPE acquire_connected_clients gauge"),
+            format!("acquire_connected_clients {}", self.metrics.clients.len()),
+            String::from("# HELP acquire_turns_total The turns that have been finished in all games"),
+            String::from("# TYPE acquire_turns_total counter"),
+            format!("acquire_turns_total {}", self.metrics.turns),
+            String::from("# HELP acquire_average_turn_duration_seconds The average time a turn has taken"),
+            String::from("# TYPE acquire_average_turn_duration_seconds gauge"),
+            format!("acquire_average_turn_duration_seconds {:.3}", average_turn),
+            String::from("# HELP acquire_errors_total The requests that have been answered with an error"),
+            String::from("# TYPE acquire_errors_total counter"),
+        ];
+        for (status, errors) in &self.metrics.errors {
+            metrics.push(format!(
+                "acquire_errors_total{{status=\"{}\"}} {}",
+                status, errors
+            ));
+        }
+        metrics.push(String::new());
+        metrics.join("\n")
+    }
+}
+
+/// Checks the limits that the server sets on the configuration of a game, see [`Engine::new`]
+/// for the configuration.
+///
+/// Games that are only played by bots are not hosted and the bots may not use more than
+/// [`MAX_BOT_PLAYOUTS`] playouts, because the server plays their turns while it answers the
+/// request.
+fn check_config(config: &Value) -> Result<()> {
+    if let Some(seats) = config["seats"].as_array() {
+        if !seats.iter().any(|seat| seat == EXTERNAL_SEAT) {
+            return Err(miette!(
+                "Unable to create game: At least one seat has to be {}",
+                EXTERNAL_SEAT
+            ));
+        }
+    }
+    if let Some(playouts) = config.get("bot_playouts") {
+        if playouts
+            .as_u64()
+            .is_some_and(|playouts| playouts > MAX_BOT_PLAYOUTS)
+        {
+            return Err(miette!(
+                "Unable to create game: The bots may use at most {} playouts",
+                MAX_BOT_PLAYOUTS
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Plays games with clients that connect via http, for example web or mobile front-ends.
+///
+/// Each connection is answered on its own thread, see [`HttpGames`] for the endpoints. Pages in
+/// the browser of every origin may read the metrics, the games are only answered to clients of
+/// the same origin and to clients outside of the browser.
+pub fn start_http_server(address: IpAddr, port: u16) -> Result<()> {
+    let listener = TcpListener::bind((address, port)).into_diagnostic()?;
+    println!(
+        "Http server is listening on {}",
+        listener.local_addr().into_diagnostic()?
+    );
+    let games = Arc::new(Mutex::new(HttpGames::new()));
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        // A client that does not send its request should not keep the connection open
+        if stream.set_read_timeout(Some(REQUEST_TIMEOUT)).is_err()
+            || stream.set_write_timeout(Some(REQUEST_TIMEOUT)).is_err()
+            || connections.load(Ordering::SeqCst) >= MAX_CONNECTIONS
+        {
+            continue;
+        }
+        connections.fetch_add(1, Ordering::SeqCst);
+        let games = Arc::clone(&games);
+        let connections = Arc::clone(&connections);
+        thread::spawn(move || {
+            // A client that sends an invalid request should not stop the server
+            if let Err(err) = answer_request(stream, &games) {
+                println!("Unable to answer request: {}", err);
+            }
+            connections.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
+
+/// Reads one request from the stream and writes the answer, the connection is closed afterwards.
+///
+/// The games are only locked once the request has been read, so that a slow client does not
+/// hold up the others.
+fn answer_request(stream: TcpStream, games: &Mutex<HttpGames>) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone().into_diagnostic()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).into_diagnostic()?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(miette!("Invalid request line: {}", request_line.trim())),
+    };
+    let mut content_length = 0;
+    let mut token = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).into_diagnostic()? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().into_diagnostic()?;
+            } else if name.trim().eq_ignore_ascii_case("authorization") {
+                token = value.trim().strip_prefix("Bearer ").map(str::to_string);
+            }
+        }
+    }
+    let mut body = Vec::new();
+    if content_length <= MAX_BODY_LENGTH {
+        body.resize(content_length, 0);
+        reader.read_exact(&mut body).into_diagnostic()?;
+    }
+    // The games stay usable when a request has panicked while they were locked
+    let mut games = games.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Ok(address) = stream.peer_addr() {
+        games.client_seen(address.ip());
+    }
+    if method == "GET" && path == "/metrics" {
+        let metrics = games.metrics();
+        return write_response(stream, 200, "text/plain; version=0.0.4", true, &metrics);
+    }
+    let (status, answer) = if content_length > MAX_BODY_LENGTH {
+        *games.metrics.errors.entry(413).or_default() += 1;
+        error(413, String::from("The body of the request is too large"))
+    } else {
+        let body = String::from_utf8_lossy(&body);
+        games.handle(&method, &path, token.as_deref(), &body)
+    };
+    drop(games);
+    // Pages of other origins may not read the hands of the players
+    write_response(
+        stream,
+        status,
+        "application/json",
+        false,
+        &answer.to_string(),
+    )
+}
+
+/// Writes the body with the status code as http response, `any_origin` allows pages of every
+/// origin to read the response
+fn write_response(
+    mut stream: TcpStream,
+    status: u16,
+    content_type: &str,
+    any_origin: bool,
+    body: &str,
+) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let origin = if any_origin {
+        "Access-Control-Allow-Origin: *\r\n"
+    } else {
+        ""
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        origin,
+        body
+    );
+    stream.write_all(response.as_bytes()).into_diagnostic()
+}
+
+/// Returns a new token for a seat that can not be guessed
+fn new_token() -> String {
+    format!("{:032x}", rand::thread_rng().gen::<u128>())
+}
+
+/// Reads the json of the body
+fn read_body(body: &str) -> Result<Value> {
+    serde_json::from_str(body).map_err(|err| miette!("The body is not valid json: {}", err))
+}
+
+/// Returns the error with the status code
+fn error(status: u16, reason: String) -> (u16, Value) {
+    (status, json!({ "error": reason }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::Mutex,
+        thread,
+        time::Duration,
+    };
+
+    use serde_json::json;
+
+    use super::{answer_request, HttpGames, MAX_GAMES};
+
+    #[test]
+    fn games_are_played_through_http() {
+        let mut games = HttpGames::new();
+        let (status, game) = games.handle(
+            "POST",
+            "/games",
+            None,
+            r#"{"seats": ["external", "random"], "seed": 5}"#,
+        );
+        assert_eq!((status, &game["status"]), (201, &json!("running")));
+        assert_eq!(game["tokens"][1], json!(null));
+        let token = game["tokens"][0].as_str();
+        let path = format!("/games/{}", game["id"]);
+        let actions = format!("{}/actions", path);
+        let card = game["decision"]["cards"][0].as_str().unwrap();
+        let action = json!({"type": "place_card", "card": card}).to_string();
+        assert_eq!(games.handle("POST", &actions, None, &action).0, 403);
+        assert_eq!(
+            games.handle("POST", &actions, Some("guess"), &action).0,
+            403
+        );
+        let (status, state) = games.handle("POST", &actions, token, &action);
+        assert_eq!(status, 200);
+        assert_eq!(games.handle("GET", &path, None, "").0, 403);
+        assert_eq!(games.handle("GET", &path, token, "").1, state);
+        assert_eq!(games.handle("POST", &actions, token, &action).0, 400);
+        assert_eq!(games.handle("POST", "/games", None, "{").0, 400);
+        assert_eq!(games.handle("PUT", &path, None, "").0, 405);
+        assert_eq!(games.handle("DELETE", &path, None, "").0, 403);
+        assert_eq!(games.handle("DELETE", &path, token, "").0, 200);
+        assert_eq!(games.handle("GET", &path, None, "").0, 404);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let body = r#"{"seats": ["external", "external"]}"#;
+            write!(
+                stream,
+                "POST /games HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let games = Mutex::new(games);
+        answer_request(stream, &games).unwrap();
+        let mut games = games.into_inner().unwrap();
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+        assert!(response.contains("\"decision\""));
+        assert!(!response.contains("Access-Control-Allow-Origin"));
+        let metrics = games.metrics();
+        assert!(metrics.contains("acquire_games_in_progress 1\n"));
+        assert!(metrics.contains("acquire_connected_clients 1\n"));
+        assert!(metrics.contains("acquire_turns_total 2\n"));
+        assert!(metrics.contains("acquire_errors_total{status=\"400\"} 2\n"));
+        assert!(metrics.contains("acquire_errors_total{status=\"403\"} 4\n"));
+        assert!(metrics.contains("acquire_errors_total{status=\"404\"} 1\n"));
+    }
+
+    #[test]
+    fn games_are_limited() {
+        let mut games = HttpGames::new();
+        let (status, answer) =
+            games.handle("POST", "/games", None, r#"{"seats": ["random", "greedy"]}"#);
+        assert_eq!(status, 400);
+        assert!(answer["error"].as_str().unwrap().contains("external"));
+        let config = r#"{"seats": ["external", "monte-carlo"], "bot_playouts": 1000000}"#;
+        assert_eq!(games.handle("POST", "/games", None, config).0, 400);
+
+        let config = r#"{"seats": ["external", "external"]}"#;
+        let (status, game) = games.handle("POST", "/games", None, config);
+        assert_eq!(status, 201);
+        let path = format!("/games/{}", game["id"]);
+        let token = game["tokens"][0].as_str();
+        assert_eq!(games.handle("GET", &path, token, "").0, 200);
+        games.remove_idle_games(Duration::ZERO);
+        assert_eq!(games.handle("GET", &path, token, "").0, 404);
+
+        for _ in 0..MAX_GAMES {
+            assert_eq!(games.handle("POST", "/games", None, config).0, 201);
+        }
+        assert_eq!(games.handle("POST", "/games", None, config).0, 503);
+    }
+
+    #[test]
+    fn hands_are_only_sent_to_their_seat() {
+        let mut games = HttpGames::new();
+        let config = r#"{"seats": ["external", "external"], "seed": 3}"#;
+        let (_, game) = games.handle("POST", "/games", None, config);
+        let path = format!("/games/{}", game["id"]);
+        let player = game["player"].as_u64().unwrap() as usize;
+        let seat = |seat: usize| game["tokens"][seat].as_str();
+        let (_, own) = games.handle("GET", &path, seat(player), "");
+        assert!(own["state"]["hand"].is_array());
+        assert!(own["decision"]["cards"].is_array());
+        let (_, other) = games.handle("GET", &path, seat(1 - player), "");
+        assert_eq!(other["decision"], json!({"type": "place_card"}));
+        assert!(other["state"].get("hand").is_none());
+        assert_eq!(other["state"]["players"], own["state"]["players"]);
+    }
+}
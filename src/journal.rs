@@ -0,0 +1,738 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{self, File, OpenOptions},
+    io::Write,
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+
+use miette::{miette, IntoDiagnostic, Result};
+use owo_colors::Rgb;
+use rand::Rng;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    base_game::{
+        bank::Bank,
+        board::{Board, Position},
+        hotel_chains::HotelChain,
+        player::Player,
+        settings::Settings,
+    },
+    game::{
+        hotel_chain_manager::HotelChainManager,
+        round::{TurnPhase, TurnProgress},
+        GameManager,
+    },
+    network::{ClientPlayer, SentUi},
+    replay::KEYFRAME_INTERVAL,
+};
+
+/// The file to which the journal of a lan game is written when no other file is set.
+pub const DEFAULT_JOURNAL_FILE: &str = "acquire_rs_journal.jsonl";
+
+/// The journal of a lan game, the position of the game is appended to it after each action so
+/// that the game can be recovered when the server stops.
+///
+/// Like a replay the journal stores keyframes and deltas. A keyframe contains the whole position,
+/// see [`JournalEntry`], and is marked with `"keyframe": true`. The other lines only contain what
+/// has changed since the line before: the fields that have changed, the hotels that have been
+/// placed or joined a chain, the number of cards that have been `drawn` and the changed fields
+/// of the players with their `index`. A keyframe is written for the first position, every
+/// [`KEYFRAME_INTERVAL`]th position and when a change can not be stored as delta.
+pub struct Journal {
+    /// The file to which the journal is written
+    pub path: String,
+    /// The position that has been written last and the number of deltas that have been written
+    /// since the last keyframe, `None` when nothing has been written yet
+    written: Mutex<Option<(Value, usize)>>,
+}
+
+impl Journal {
+    /// Creates the journal, nothing is written until the first position is appended.
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            written: Mutex::new(None),
+        }
+    }
+
+    /// Empties the journal when a new game starts, so that the game that has been played before
+    /// is not recovered.
+    pub fn clear(&self) -> Result<()> {
+        let mut written = self.written.lock().unwrap_or_else(PoisonError::into_inner);
+        File::create(&self.path).into_diagnostic()?;
+        *written = None;
+        Ok(())
+    }
+
+    /// Appends the position to the journal, as delta when possible.
+    fn append(&self, position: Value) -> Result<()> {
+        let mut written = self.written.lock().unwrap_or_else(PoisonError::into_inner);
+        let (line, deltas) = journal_line(&written, &position);
+        // A line that has not been written completely when the server has stopped is ended, so
+        // that the first keyframe after the recovery can be read
+        let unfinished = written.is_none()
+            && fs::read(&self.path)
+                .ok()
+                .and_then(|journal| journal.last().copied())
+                .is_some_and(|last| last != b'\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .into_diagnostic()?;
+        let line = match unfinished {
+            true => format!("\n{}\n", line),
+            false => format!("{}\n", line),
+        };
+        file.write_all(line.as_bytes()).into_diagnostic()?;
+        *written = Some((position, deltas));
+        Ok(())
+    }
+}
+
+/// Returns the line that is appended to the journal for the position and the number of deltas
+/// since the last keyframe after it.
+/// # Arguments
+/// * `written` - The position that has been written last and the number of deltas since the last
+///   keyframe
+fn journal_line(written: &Option<(Value, usize)>, position: &Value) -> (Value, usize) {
+    let delta = match written {
+        Some((last, deltas)) if deltas + 1 < KEYFRAME_INTERVAL => {
+            delta_json(last, position).map(|delta| (delta, deltas + 1))
+        }
+        _ => None,
+    };
+    delta.unwrap_or_else(|| {
+        let mut keyframe = position.clone();
+        keyframe["keyframe"] = json!(true);
+        (keyframe, 0)
+    })
+}
+
+/// Returns what has changed from the position `last` to `position`, see [`Journal`].
+/// # Returns
+/// * `None` - The change can not be stored as delta, because hotels have been removed, cards have
+///   been put back or the number of players has changed
+fn delta_json(last: &Value, position: &Value) -> Option<Value> {
+    let mut delta = Map::new();
+    for (key, value) in position.as_object()? {
+        match key.as_str() {
+            "hotels" => {
+                let last_hotels: HashMap<&str, &Value> = last["hotels"]
+                    .as_array()?
+                    .iter()
+                    .map(|hotel| Some((hotel["position"].as_str()?, &hotel["chain"])))
+                    .collect::<Option<_>>()?;
+                let hotels = value.as_array()?;
+                if hotels.len() < last_hotels.len() {
+                    return None;
+                }
+                let changed: Vec<Value> = hotels
+                    .iter()
+                    .filter(|hotel| {
+                        hotel["position"]
+                            .as_str()
+                            .and_then(|position| last_hotels.get(position))
+                            != Some(&&hotel["chain"])
+                    })
+                    .cloned()
+                    .collect();
+                if !changed.is_empty() {
+                    delta.insert(key.clone(), Value::Array(changed));
+                }
+            }
+            "position_cards" => {
+                let last_cards = last["position_cards"].as_array()?;
+                let cards = value.as_array()?;
+                let drawn = last_cards.len().checked_sub(cards.len())?;
+                if last_cards[drawn..] != cards[..] {
+                    return None;
+                }
+                if drawn > 0 {
+                    delta.insert(String::from("drawn"), json!(drawn));
+                }
+            }
+            "players" => {
+                let last_players = last["players"].as_array()?;
+                let players = value.as_array()?;
+                if last_players.len() != players.len() {
+                    return None;
+                }
+                let mut changed = Vec::new();
+                for (index, (last_player, player)) in last_players.iter().zip(players).enumerate() {
+                    let mut changes: Map<String, Value> = player
+                        .as_object()?
+                        .iter()
+                        .filter(|(field, value)| last_player[field.as_str()] != **value)
+                        .map(|(field, value)| (field.clone(), value.clone()))
+                        .collect();
+                    if !changes.is_empty() {
+                        changes.insert(String::from("index"), json!(index));
+                        changed.push(Value::Object(changes));
+                    }
+                }
+                if !changed.is_empty() {
+                    delta.insert(key.clone(), Value::Array(changed));
+                }
+            }
+            _ => {
+                if last[key.as_str()] != *value {
+                    delta.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    Some(Value::Object(delta))
+}
+
+/// Applies the delta of the journal to the position, see [`Journal`].
+/// # Returns
+/// * `None` - The delta does not fit to the position, the journal is corrupt
+fn apply_delta(position: &mut Value, delta: &Value) -> Option<()> {
+    for (key, value) in delta.as_object()? {
+        match key.as_str() {
+            "hotels" => {
+                let hotels = position["hotels"].as_array_mut()?;
+                for hotel in value.as_array()? {
+                    match hotels
+                        .iter_mut()
+                        .find(|placed| placed["position"] == hotel["position"])
+                    {
+                        Some(placed) => *placed = hotel.clone(),
+                        None => hotels.push(hotel.clone()),
+                    }
+                }
+            }
+            "drawn" => {
+                let cards = position["position_cards"].as_array_mut()?;
+                let drawn = value.as_u64()? as usize;
+                if drawn > cards.len() {
+                    return None;
+                }
+                cards.drain(..drawn);
+            }
+            "players" => {
+                for changes in value.as_array()? {
+                    let player = position["players"]
+                        .get_mut(changes["index"].as_u64()? as usize)?
+                        .as_object_mut()?;
+                    for (field, value) in changes.as_object()? {
+                        if field != "index" {
+                            player.insert(field.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+            _ => {
+                position.as_object_mut()?.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Some(())
+}
+
+/// A player like they have been stored in the journal.
+pub struct JournalPlayer {
+    pub name: String,
+    pub money: u32,
+    /// The stocks of the player in the order of [`HotelChain::iterator`]
+    pub stocks: Vec<u32>,
+    pub cards: Vec<Position>,
+    pub resigned: bool,
+    pub team: Option<u32>,
+    pub color: Rgb,
+    /// If the player is played by a bot
+    pub bot: bool,
+    /// If the player has played on a client
+    pub client: bool,
+}
+
+/// The position of the game after an action.
+///
+/// A position is appended to the [`Journal`] after each action that has been completed: after
+/// the hotel has been placed, after the stocks have been sold and bought and after the card has
+/// been drawn. A fusion is completed together with the placed hotel. The last position is used
+/// to recover the game. The hotel chains and the stocks of the bank are not stored, they are
+/// restored from the board and the stocks of the players.
+pub struct JournalEntry {
+    /// The round that is continued
+    pub round: u32,
+    /// The index of the player that has the next turn in the round or whose turn is continued.
+    /// It is equal to the number of players when only the turn of the neutral player is left.
+    pub next_player: usize,
+    /// How far the turn of the next player has already been played
+    pub progress: TurnProgress,
+    /// The placed hotels and the chain they belong to
+    pub hotels: Vec<(Position, Option<HotelChain>)>,
+    /// The cards that can still be drawn, the first card is drawn next
+    pub position_cards: Vec<Position>,
+    /// The players in the order in which they play
+    pub players: Vec<JournalPlayer>,
+}
+
+impl JournalEntry {
+    fn to_json(
+        round: u32,
+        next_player: usize,
+        progress: TurnProgress,
+        players: &[Player],
+        board: &Board,
+        position_cards: &VecDeque<Position>,
+    ) -> Value {
+        let hotels: Vec<Value> = board
+            .pieces
+            .iter()
+            .filter(|piece| piece.piece_set)
+            .map(|piece| {
+                json!({
+                    "position": piece.position.to_string(),
+                    "chain": piece.chain.map(|chain| chain.name().to_string()),
+                })
+            })
+            .collect();
+        let players: Vec<Value> = players
+            .iter()
+            .map(|player| {
+                let stocks: Vec<u32> = player.owned_stocks.iter().map(|(_, n)| n).collect();
+                let cards: Vec<String> = player
+                    .analyzed_cards
+                    .iter()
+                    .map(|card| card.position.to_string())
+                    .collect();
+                json!({
+                    "name": player.name,
+                    "money": player.money,
+                    "stocks": stocks,
+                    "cards": cards,
+                    "resigned": player.resigned,
+                    "team": player.team,
+                    "color": [player.color.0, player.color.1, player.color.2],
+                    "bot": player.bot.is_some(),
+                    "client": player.tcp_stream.is_some(),
+                })
+            })
+            .collect();
+        let position_cards: Vec<String> = position_cards
+            .iter()
+            .map(|position| position.to_string())
+            .collect();
+        json!({
+            "round": round,
+            "next_player": next_player,
+            "phase": progress.phase.name(),
+            "hotel_placed": progress.hotel_placed,
+            "game_ended": progress.game_ended,
+            "hotels": hotels,
+            "position_cards": position_cards,
+            "players": players,
+        })
+    }
+
+    fn from_json(entry: &Value) -> Option<Self> {
+        let positions = |value: &Value| -> Option<Vec<Position>> {
+            value
+                .as_array()?
+                .iter()
+                .map(|position| position.as_str().and_then(Position::from_name))
+                .collect()
+        };
+        let hotels = entry["hotels"]
+            .as_array()?
+            .iter()
+            .map(|hotel| {
+                let position = Position::from_name(hotel["position"].as_str()?)?;
+                let chain = match &hotel["chain"] {
+                    Value::Null => None,
+                    chain => Some(HotelChain::from_name(chain.as_str()?)?),
+                };
+                Some((position, chain))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let players = entry["players"]
+            .as_array()?
+            .iter()
+            .map(|player| {
+                let color: Vec<u8> = player["color"]
+                    .as_array()?
+                    .iter()
+                    .map(|value| value.as_u64().map(|value| value as u8))
+                    .collect::<Option<_>>()?;
+                Some(JournalPlayer {
+                    name: player["name"].as_str()?.to_string(),
+                    money: player["money"].as_u64()? as u32,
+                    stocks: player["stocks"]
+                        .as_array()?
+                        .iter()
+                        .map(|stocks| stocks.as_u64().map(|stocks| stocks as u32))
+                        .collect::<Option<_>>()?,
+                    cards: positions(&player["cards"])?,
+                    resigned: player["resigned"].as_bool()?,
+                    team: player["team"].as_u64().map(|team| team as u32),
+                    color: Rgb(*color.first()?, *color.get(1)?, *color.get(2)?),
+                    bot: player["bot"].as_bool()?,
+                    client: player["client"].as_bool()?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            round: entry["round"].as_u64()? as u32,
+            next_player: entry["next_player"].as_u64()? as usize,
+            progress: TurnProgress {
+                phase: TurnPhase::from_name(entry["phase"].as_str()?)?,
+                hotel_placed: entry["hotel_placed"].as_bool()?,
+                game_ended: entry["game_ended"].as_bool()?,
+            },
+            hotels,
+            position_cards: positions(&entry["position_cards"])?,
+            players,
+        })
+    }
+
+    /// Returns the names of the players that have played on a client
+    pub fn client_names(&self) -> Vec<&str> {
+        self.players
+            .iter()
+            .filter(|player| player.client)
+            .map(|player| player.name.as_str())
+            .collect()
+    }
+}
+
+/// Appends the position of the game after an action to the journal when a journal is written,
+/// see [`JournalEntry`]. A message is printed when the journal can not be written, the game goes
+/// on.
+pub fn journal_turn(
+    settings: &Settings,
+    round: u32,
+    next_player: usize,
+    progress: TurnProgress,
+    players: &[Player],
+    board: &Board,
+    position_cards: &VecDeque<Position>,
+) {
+    if let Some(journal) = &settings.journal {
+        let entry =
+            JournalEntry::to_json(round, next_player, progress, players, board, position_cards);
+        if let Err(err) = journal.append(entry) {
+            println!("Unable to write the journal to {}: {}", journal.path, err);
+        }
+    }
+}
+
+/// Returns the last complete position of the journal, it is restored from the last keyframe and
+/// the deltas after it, see [`Journal`].
+///
+/// A line that can not be read is written when the server stops in the middle of writing a line.
+/// The deltas after it are not applied until the next keyframe.
+pub fn last_entry(journal: &str) -> Result<JournalEntry> {
+    let mut position: Option<Value> = None;
+    let mut complete = false;
+    for line in journal.lines() {
+        let line: Value = match serde_json::from_str(line) {
+            Ok(line) => line,
+            Err(_) => {
+                complete = false;
+                continue;
+            }
+        };
+        if line["keyframe"] == json!(true) {
+            position = Some(line);
+            complete = true;
+        } else if let (Some(last), true) = (&position, complete) {
+            let mut next = last.clone();
+            match apply_delta(&mut next, &line) {
+                Some(()) => position = Some(next),
+                None => complete = false,
+            }
+        }
+    }
+    position
+        .and_then(|position| JournalEntry::from_json(&position))
+        .ok_or_else(|| miette!("Unable to recover game: The journal does not contain a turn"))
+}
+
+/// Creates the game of the journal entry again.
+///
+/// The players that have played on a client are given the client with the same name. The bots
+/// are created again with the settings, so the server has to be started with the same bot
+/// settings. The statistics of the players are not stored in the journal and start again.
+/// # Arguments
+/// * `clients` - The clients that have connected again, one for each client player of the entry
+pub fn recover_game(
+    entry: &JournalEntry,
+    mut clients: Vec<ClientPlayer>,
+    settings: Settings,
+) -> Result<GameManager> {
+    let mut board = Board::new();
+    for (position, chain) in &entry.hotels {
+        board.place_hotel(position)?;
+        if let Some(chain) = chain {
+            board.update_hotel(*chain, position)?;
+        }
+    }
+    // The order in which the hotels have been placed is not stored
+    board.last_placed = None;
+    let hotel_chain_manager = HotelChainManager::from_board(&board);
+    let mut bank = Bank::new();
+    bank.rules = settings.rules;
+    let mut players = Vec::new();
+    let mut bot_number = 0;
+    for (id, stored) in entry.players.iter().enumerate() {
+        let mut player = match stored.client {
+            true => {
+                let index = clients
+                    .iter()
+                    .position(|client| client.name == stored.name)
+                    .ok_or_else(|| {
+                        miette!(
+                            "Unable to recover game: {} has not connected again",
+                            stored.name
+                        )
+                    })?;
+                let client = clients.remove(index);
+                let mut player = Player::new_client(
+                    stored.cards.clone(),
+                    id as u32,
+                    stored.name.clone(),
+                    client.tcp_stream,
+                    client.small_board,
+                    client.privacy.max(settings.privacy),
+                );
+                player.plain = client.plain;
+                player.binary = client.binary;
+                player.sent_ui = client.delta.then(SentUi::default);
+                player.set_input_timeout(settings.input_timeout)?;
+                player.remind_after = settings.remind_after.map(Duration::from_secs);
+                player.cancellation = settings.cancellation.clone();
+                player
+            }
+            false => {
+                let mut player = Player::new(
+                    stored.cards.clone(),
+                    id as u32,
+                    settings.small_board,
+                    stored.name.clone(),
+                );
+                player.privacy = settings.privacy;
+                player.plain = settings.plain;
+                player
+            }
+        };
+        if stored.bot {
+            bot_number += 1;
+            player.bot = Some(settings.bot_strategy.create(
+                settings.bot_personality(bot_number),
+                &settings,
+                rand::thread_rng().gen(),
+            ));
+        }
+        player.money = stored.money;
+        for (chain, stocks) in HotelChain::iterator().zip(&stored.stocks) {
+            player.owned_stocks.set_stocks(chain, *stocks);
+            let left = bank
+                .stocks_for_sale
+                .stocks_for_hotel(chain)
+                .checked_sub(*stocks)
+                .ok_or_else(|| {
+                    miette!(
+                        "Unable to recover game: The journal is corrupt, the players own more than all stocks of {}",
+                        chain
+                    )
+                })?;
+            bank.stocks_for_sale.set_stocks(chain, left);
+            bank.mark_traded(chain);
+        }
+        player.resigned = stored.resigned;
+        player.team = stored.team;
+        player.color = stored.color;
+        player.auto_sort = settings.auto_sort;
+        player.skip_dialogues = settings.skip_dialogues;
+        players.push(player);
+    }
+    bank.update_largest_shareholders(&players);
+    Ok(GameManager::from_position(
+        board,
+        bank,
+        hotel_chain_manager,
+        entry.position_cards.iter().copied().collect(),
+        players,
+        settings,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use miette::Result;
+    use serde_json::Value;
+
+    use crate::{
+        base_game::{
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+            settings::{Privacy, Settings},
+        },
+        game::{
+            round::{TurnPhase, TurnProgress},
+            GameManager,
+        },
+        replay::KEYFRAME_INTERVAL,
+    };
+
+    use super::{journal_line, last_entry, recover_game, JournalEntry};
+
+    /// Returns the journal that is written for the positions
+    fn journal(positions: &[Value]) -> String {
+        let mut written = None;
+        let mut journal = String::new();
+        for position in positions {
+            let (line, deltas) = journal_line(&written, position);
+            journal.push_str(&format!("{}\n", line));
+            written = Some((position.clone(), deltas));
+        }
+        journal
+    }
+
+    #[test]
+    fn game_is_recovered_from_the_last_entry() -> Result<()> {
+        let settings = Settings::new(false, Privacy::default(), true);
+        let mut board = Board::new();
+        board.place_hotel(&Position::new('A', 1))?;
+        board.place_hotel(&Position::new('A', 2))?;
+        board.update_hotel(HotelChain::Luxor, &Position::new('A', 1))?;
+        board.update_hotel(HotelChain::Luxor, &Position::new('A', 2))?;
+        board.place_hotel(&Position::new('E', 5))?;
+        let mut players = vec![
+            Player::new(vec![Position::new('B', 1)], 0, false, String::from("Anna")),
+            Player::new(vec![Position::new('C', 7)], 1, false, String::from("Ben")),
+        ];
+        players[1].money = 4200;
+        players[1].owned_stocks.set_stocks(&HotelChain::Luxor, 3);
+        players[1].resigned = true;
+        let mut position_cards: VecDeque<Position> = GameManager::init_position_cards()
+            .into_iter()
+            .take(10)
+            .collect();
+        let first = JournalEntry::to_json(
+            1,
+            0,
+            TurnProgress::default(),
+            &players,
+            &Board::new(),
+            &position_cards,
+        );
+        position_cards.pop_front();
+        // The hotel has been placed, the stocks are bought next
+        let progress = TurnProgress {
+            phase: TurnPhase::BuyStocks,
+            hotel_placed: true,
+            game_ended: false,
+        };
+        let last = JournalEntry::to_json(2, 1, progress, &players, &board, &position_cards);
+        let journal = journal(&[first, last]);
+        // Only the changes are stored after the keyframe
+        let delta: Value = serde_json::from_str(journal.lines().nth(1).unwrap()).unwrap();
+        assert_eq!(delta["drawn"], 1);
+        assert!(delta["players"].is_null());
+        // The last line has not been written completely
+        let journal = format!("{}{{\"round\":", journal);
+        let entry = last_entry(&journal)?;
+        assert_eq!((entry.round, entry.next_player), (2, 1));
+        assert_eq!(entry.progress, progress);
+        assert!(entry.client_names().is_empty());
+        let game = recover_game(&entry, Vec::new(), settings)?;
+        assert_eq!(game.position_cards, position_cards);
+        assert_eq!(
+            game.board.is_hotel_placed(&Position::new('A', 2)),
+            Some(Some(HotelChain::Luxor))
+        );
+        assert_eq!(
+            game.board.is_hotel_placed(&Position::new('E', 5)),
+            Some(None)
+        );
+        assert_eq!(game.hotel_chain_manager.chain_length(&HotelChain::Luxor), 2);
+        assert_eq!(
+            *game
+                .bank
+                .stocks_for_sale
+                .stocks_for_hotel(&HotelChain::Luxor),
+            22
+        );
+        assert_eq!(
+            game.bank.largest_shareholders.largest(&HotelChain::Luxor),
+            [1]
+        );
+        let ben = &game.players[1];
+        assert_eq!(
+            (ben.money, ben.resigned, ben.color),
+            (4200, true, players[1].color)
+        );
+        assert_eq!(ben.analyzed_cards[0].position, Position::new('C', 7));
+        assert!(last_entry("").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt_journal_is_not_recovered() -> Result<()> {
+        let settings = Settings::new(false, Privacy::default(), true);
+        let mut players = vec![
+            Player::new(Vec::new(), 0, false, String::from("Anna")),
+            Player::new(Vec::new(), 1, false, String::from("Ben")),
+        ];
+        players[0].owned_stocks.set_stocks(&HotelChain::Luxor, 20);
+        players[1].owned_stocks.set_stocks(&HotelChain::Luxor, 6);
+        let journal = journal(&[JournalEntry::to_json(
+            1,
+            0,
+            TurnProgress::default(),
+            &players,
+            &Board::new(),
+            &VecDeque::new(),
+        )]);
+        let entry = last_entry(&journal)?;
+        let err = recover_game(&entry, Vec::new(), settings).err().unwrap();
+        assert!(err.to_string().contains("corrupt"));
+        Ok(())
+    }
+
+    #[test]
+    fn keyframes_are_written_regularly() -> Result<()> {
+        let mut players = vec![
+            Player::new(Vec::new(), 0, false, String::from("Anna")),
+            Player::new(Vec::new(), 1, false, String::from("Ben")),
+        ];
+        let positions: Vec<Value> = (0..=KEYFRAME_INTERVAL as u32)
+            .map(|turn| {
+                players[1].money = 6000 + turn;
+                JournalEntry::to_json(
+                    turn + 1,
+                    1,
+                    TurnProgress::default(),
+                    &players,
+                    &Board::new(),
+                    &VecDeque::new(),
+                )
+            })
+            .collect();
+        let journal = journal(&positions);
+        let keyframes: Vec<usize> = journal
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.contains("\"keyframe\":true"))
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(keyframes, [0, KEYFRAME_INTERVAL]);
+        let entry = last_entry(&journal)?;
+        assert_eq!((entry.round, entry.players[1].money), (51, 6050));
+        // The deltas after a line that can not be read are not applied
+        let mut lines: Vec<&str> = journal.lines().take(KEYFRAME_INTERVAL).collect();
+        lines[10] = "{\"round\":";
+        let entry = last_entry(&lines.join("\n"))?;
+        assert_eq!((entry.round, entry.players[1].money), (10, 6009));
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+
+use miette::{miette, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    base_game::settings::Settings,
+    bot::BotStrategy,
+    playout::Playout,
+    simulation::{bot_players, MAX_TURNS},
+};
+
+/// The rating every strategy starts with.
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// How much the rating of a strategy can change with one game.
+const K_FACTOR: f64 = 16.0;
+
+/// The results of a round-robin between bot strategies.
+pub struct Ladder {
+    pub strategies: Vec<BotStrategy>,
+    /// The number of seeds each pairing has been played with
+    pub seeds: u32,
+    /// `scores[a][b]` contains the points strategy `a` has scored against strategy `b`. A win
+    /// counts one point and a draw half a point.
+    pub scores: Vec<Vec<f64>>,
+    /// `games[a][b]` contains the number of games strategy `a` has played against strategy `b`
+    pub games: Vec<Vec<u32>>,
+    /// The Elo-like rating of each strategy
+    pub ratings: Vec<f64>,
+}
+
+impl Ladder {
+    /// Returns the share of points strategy `a` has scored against strategy `b`.
+    /// `None` is returned when they have not played against each other.
+    pub fn score_rate(&self, a: usize, b: usize) -> Option<f64> {
+        match self.games[a][b] {
+            0 => None,
+            games => Some(self.scores[a][b] / games as f64),
+        }
+    }
+
+    /// Returns the indices of the strategies ordered by their rating, the best first.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ranking: Vec<usize> = (0..self.strategies.len()).collect();
+        ranking.sort_by(|a, b| self.ratings[*b].total_cmp(&self.ratings[*a]));
+        ranking
+    }
+
+    /// Updates the scores and ratings with the result of one game.
+    /// `score` is the result of strategy `a`: 1 for a win, 0.5 for a draw and 0 for a loss.
+    fn add_game(&mut self, a: usize, b: usize, score: f64) {
+        self.scores[a][b] += score;
+        self.scores[b][a] += 1.0 - score;
+        self.games[a][b] += 1;
+        self.games[b][a] += 1;
+        let expected = 1.0 / (1.0 + 10f64.powf((self.ratings[b] - self.ratings[a]) / 400.0));
+        let change = K_FACTOR * (score - expected);
+        self.ratings[a] += change;
+        self.ratings[b] -= change;
+    }
+}
+
+/// Lets every strategy play against every other strategy in two-player games.
+///
+/// Each pairing plays one game per seed from each seat, both games are dealt with the same seed.
+/// The seeds are `seed`, `seed + 1`, ... The same strategies and seeds lead to the same ladder.
+/// The ratings are updated after each game like Elo ratings.
+/// # Arguments
+/// * `strategies` - The strategies that take part, at least 2
+/// * `seeds` - The number of seeds each pairing is played with
+/// * `seed` - The first seed
+/// * `settings` - The rules and bot settings that are used in the games
+pub fn run_ladder(
+    strategies: &[BotStrategy],
+    seeds: u32,
+    seed: u64,
+    settings: &Settings,
+) -> Result<Ladder> {
+    if strategies.len() < 2 {
+        return Err(miette!(
+            "Unable to run ladder: At least 2 strategies are needed, entered: {}",
+            strategies.len()
+        ));
+    }
+    let size = strategies.len();
+    let mut ladder = Ladder {
+        strategies: strategies.to_vec(),
+        seeds,
+        scores: vec![vec![0.0; size]; size],
+        games: vec![vec![0; size]; size],
+        ratings: vec![INITIAL_RATING; size],
+    };
+    for game_seed in (0..seeds as u64).map(|offset| seed.wrapping_add(offset)) {
+        for a in 0..size {
+            for b in a + 1..size {
+                for (first, second) in [(a, b), (b, a)] {
+                    let money = play_game(
+                        &[strategies[first], strategies[second]],
+                        game_seed,
+                        settings,
+                    )?;
+                    let score = match money[0].cmp(&money[1]) {
+                        Ordering::Greater => 1.0,
+                        Ordering::Equal => 0.5,
+                        Ordering::Less => 0.0,
+                    };
+                    ladder.add_game(first, second, score);
+                }
+            }
+        }
+    }
+    Ok(ladder)
+}
+
+/// Plays one game between the strategies and returns the money of each seat after the final
+/// account.
+fn play_game(strategies: &[BotStrategy], seed: u64, settings: &Settings) -> Result<Vec<u32>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let players = bot_players(strategies, settings, &mut rng);
+    let mut playout = Playout::new_game(players, settings.rules, rng.gen())?;
+    while playout.turns() < MAX_TURNS && playout.step()? {}
+    playout.final_account()?;
+    Ok(playout.players.iter().map(|player| player.money).collect())
+}
+
+/// Prints the cross-table and the ratings of the ladder.
+///
+/// Each cell contains the share of points the strategy of the row has scored against the
+/// strategy of the column.
+pub fn print_ladder(ladder: &Ladder, seed: u64) {
+    println!(
+        "Ladder with {} seeds starting at seed {}:",
+        ladder.seeds, seed
+    );
+    let ranking = ladder.ranking();
+    let mut header = format!("{:<4} {:<12} {:>6}", "Rank", "Strategy", "Rating");
+    for column in &ranking {
+        header.push_str(&format!(" {:>12}", ladder.strategies[*column].name()));
+    }
+    println!("{}", header);
+    for (rank, row) in ranking.iter().enumerate() {
+        let mut line = format!(
+            "{:<4} {:<12} {:>6.0}",
+            rank + 1,
+            ladder.strategies[*row].name(),
+            ladder.ratings[*row]
+        );
+        for column in &ranking {
+            match ladder.score_rate(*row, *column) {
+                Some(rate) => line.push_str(&format!(" {:>11.1}%", rate * 100.0)),
+                None => line.push_str(&format!(" {:>12}", "-")),
+            }
+        }
+        println!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::settings::{Privacy, Settings},
+        bot::BotStrategy,
+    };
+
+    use super::{run_ladder, INITIAL_RATING};
+
+    #[test]
+    fn ladder_plays_every_pairing_from_both_seats() -> Result<()> {
+        let settings = Settings::new(false, Privacy::default(), true);
+        let strategies = [
+            BotStrategy::Greedy,
+            BotStrategy::Heuristic,
+            BotStrategy::Random,
+        ];
+        let ladder = run_ladder(&strategies, 2, 1, &settings)?;
+        for a in 0..strategies.len() {
+            assert_eq!(ladder.games[a][a], 0);
+            for b in (0..strategies.len()).filter(|b| *b != a) {
+                assert_eq!(ladder.games[a][b], 4);
+                assert_eq!(ladder.scores[a][b] + ladder.scores[b][a], 4.0);
+            }
+        }
+        // Ratings are only moved between the strategies
+        let total: f64 = ladder.ratings.iter().sum();
+        assert!((total - INITIAL_RATING * 3.0).abs() < 1e-6);
+        // The same seeds lead to the same ladder
+        let again = run_ladder(&strategies, 2, 1, &settings)?;
+        assert_eq!(ladder.scores, again.scores);
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+//! The board game Acquire.
+//!
+//! The rules engine does not need a terminal. Built without the default features it compiles to
+//! `wasm32-unknown-unknown`, so that other front-ends like a browser can reuse it:
+//! * `terminal` - The command line interface that reads the input of the players from the
+//!   terminal, required by the `acquire_rs` binary
+//! * `lan` - Hosting and joining games via lan
+
+/// Contains the what-if analysis of fusions that does not change the game.
+pub mod analysis;
+/// Contains all base functionalities that the game needs to work.
+/// This includes all basic data types and the playfield, some game logic and more.
+pub mod base_game;
+/// Contains the bots that can take the seat of a player.
+pub mod bot;
+/// Contains the configuration file from which the defaults of the command line are read.
+#[cfg(feature = "terminal")]
+pub mod config;
+/// Contains functions that help to read and parse the user input
+#[cfg(feature = "terminal")]
+pub mod data_stream;
+/// Contains the debug console in which the state of a local game can be inspected and changed.
+pub mod debug_console;
+/// Contains some code to print the board without that the game has to be started
+#[cfg(feature = "terminal")]
+pub mod demo;
+/// Contains the engine that lets programs outside of the game play it step by step.
+pub mod engine;
+/// Contains the evaluation of game states that tells how good the position of a player is.
+pub mod evaluation;
+/// Contains the stream to which the events of a game are written as json while it runs.
+pub mod event_stream;
+/// Contains the C interface of the engine, so that the game can be embedded in other programs.
+/// Strings are passed as null terminated utf-8, the declarations are in `include/acquire_rs.h`.
+pub mod ffi;
+/// Contains all functionalities that are required to play the game. This includes the setting up
+/// of new games, round, turn and player managemnt and more.
+pub mod game;
+/// Contains the results of past games and the lifetime statistics of the players.
+pub mod history;
+/// Contains the http server through which web and mobile clients play the game. It is the
+/// dedicated server of the game, the lan host stops with its game.
+#[cfg(feature = "lan")]
+pub mod http_server;
+/// Contains the journal from which a lan game is recovered when the server has stopped.
+pub mod journal;
+/// Contains the ladder in which all bot strategies play against each other.
+pub mod ladder;
+/// Contains the lobby in which the host changes the settings of a lan game before it starts.
+#[cfg(feature = "lan")]
+pub mod lobby;
+/// Contains the catalog of the messages that are shown to the players in each language.
+pub mod locale;
+/// Contains the most part of the game logic.
+/// Does not contain the logic of the different managers. Their logic is implemented in their main impl block.
+pub mod logic;
+/// Contains all functionalities required to play the game via lan.
+pub mod network;
+/// Contains headless copies of the game that bots use to play out possible moves.
+pub mod playout;
+/// Contains the recording and checking of replays of games between bots.
+pub mod replay;
+/// Contains the html report of a finished game.
+pub mod report;
+/// Contains the scenarios that play a game from a position and check how it turns out.
+pub mod scenario;
+/// Contains the computation of the largest shareholders of each chain.
+pub mod shareholders;
+/// Contains the simulation of games in which bots play against each other.
+pub mod simulation;
+/// Contains the exact solver for the last turn of the game.
+pub mod solver;
+/// Contains the summary and the finances of a finished game that can be exported as json and csv.
+pub mod summary;
+/// Contains the tutorial that explains the game while it is played.
+pub mod tutorial;
+/// Contains some functions that dont fit in another module.
+pub mod utils;
+/// Contains the viewer that steps through the decisions of a replay.
+pub mod viewer;
+/// Contains the webhook to which the results of a game are posted.
+#[cfg(feature = "terminal")]
+pub mod webhook;
@@ -0,0 +1,153 @@
+use std::io::{stdin, stdout, Write};
+
+use miette::{IntoDiagnostic, Result};
+
+use crate::{base_game::settings::RulePreset, game::GameManager, locale::text, network::broadcast};
+
+/// A change of the settings that the host enters in the lobby.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LobbyCommand {
+    /// Replaces the rules with the rules of the preset, the starting money is kept
+    Rules(RulePreset),
+    /// Sets the money each player has when the game starts
+    Money(u32),
+    /// Sets the number of seconds in which a client has to answer, `None` for unlimited time
+    Timer(Option<u64>),
+}
+
+impl LobbyCommand {
+    /// Returns the command that has been entered, like `rules house`, `money 8000`, `timer 60`
+    /// or `timer off`.
+    /// If the input is no valid command `None` is returned.
+    pub fn parse(input: &str) -> Option<LobbyCommand> {
+        let mut words = input.split_whitespace();
+        let command = match (words.next()?, words.next()?) {
+            ("rules", preset) => LobbyCommand::Rules(RulePreset::from_name(preset)?),
+            ("money", money) => LobbyCommand::Money(money.parse().ok()?),
+            ("timer", "off") => LobbyCommand::Timer(None),
+            // A read timeout of zero seconds is not allowed
+            ("timer", seconds) => match seconds.parse() {
+                Ok(seconds) if seconds > 0 => LobbyCommand::Timer(Some(seconds)),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        match words.next() {
+            None => Some(command),
+            Some(_) => None,
+        }
+    }
+
+    /// Changes the settings of the game that has not been started yet.
+    pub fn apply(&self, game_manager: &mut GameManager) -> Result<()> {
+        let settings = &mut game_manager.settings;
+        match *self {
+            LobbyCommand::Rules(preset) => {
+                let starting_money = settings.rules.starting_money;
+                settings.rule_preset = preset;
+                settings.rules = preset.rules();
+                settings.rules.starting_money = starting_money;
+            }
+            LobbyCommand::Money(money) => settings.rules.starting_money = money,
+            LobbyCommand::Timer(seconds) => {
+                settings.input_timeout = seconds;
+                for player in &game_manager.players {
+                    player.set_input_timeout(seconds)?;
+                }
+            }
+        }
+        // The bank and the players use their own copy of the rules
+        game_manager.bank.rules = game_manager.settings.rules;
+        for player in &mut game_manager.players {
+            player.money = game_manager.settings.rules.starting_money;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the settings of the game that are shown to all players in the lobby.
+pub fn lobby_settings_ui(game_manager: &GameManager) -> Vec<String> {
+    let settings = &game_manager.settings;
+    let mut ui = vec![text("lobby.rules", &[&settings.rule_preset.name()])];
+    ui.extend(settings.rules.summary());
+    ui.push(match settings.input_timeout {
+        Some(seconds) => text("lobby.timer", &[&seconds]),
+        None => text("lobby.no_timer", &[]),
+    });
+    ui
+}
+
+/// Lets the host change the settings while the clients wait in the lobby, see
+/// [`LobbyCommand`]. The settings are shown to every player when the lobby is opened and after
+/// each change. The game can be started when the host enters an empty line.
+pub fn run_lobby(game_manager: &mut GameManager) -> Result<()> {
+    broadcast(
+        &lobby_settings_ui(game_manager).join("\n"),
+        &game_manager.players,
+    )?;
+    loop {
+        println!("{}", text("lobby.help", &[]));
+        print!("> ");
+        stdout().flush().into_diagnostic()?;
+        let mut buffer = String::new();
+        // The game starts when the input has been closed
+        if stdin().read_line(&mut buffer).into_diagnostic()? == 0 || buffer.trim().is_empty() {
+            return Ok(());
+        }
+        match LobbyCommand::parse(buffer.trim()) {
+            Some(command) => {
+                command.apply(game_manager)?;
+                broadcast(
+                    &lobby_settings_ui(game_manager).join("\n"),
+                    &game_manager.players,
+                )?;
+            }
+            None => println!("{}", text("lobby.unknown", &[&buffer.trim()])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::settings::{Privacy, RulePreset, Settings},
+        game::GameManager,
+    };
+
+    use super::{lobby_settings_ui, LobbyCommand};
+
+    #[test]
+    fn settings_are_changed_in_the_lobby() -> Result<()> {
+        assert_eq!(
+            LobbyCommand::parse("rules house"),
+            Some(LobbyCommand::Rules(RulePreset::House))
+        );
+        assert_eq!(
+            LobbyCommand::parse("timer off"),
+            Some(LobbyCommand::Timer(None))
+        );
+        assert_eq!(LobbyCommand::parse("timer 0"), None);
+        assert_eq!(LobbyCommand::parse("money 8000 now"), None);
+        assert_eq!(LobbyCommand::parse("rules chess"), None);
+        let mut settings = Settings::new(false, Privacy::default(), false);
+        settings.bots = 2;
+        let mut game_manager = GameManager::new(2, settings)?;
+        LobbyCommand::Money(8000).apply(&mut game_manager)?;
+        LobbyCommand::Rules(RulePreset::House).apply(&mut game_manager)?;
+        LobbyCommand::Timer(Some(60)).apply(&mut game_manager)?;
+        assert_eq!(game_manager.settings.rule_preset, RulePreset::House);
+        assert!(game_manager.bank.rules.third_shareholder_bonus);
+        assert!(game_manager.settings.rules.replace_dead_tiles);
+        assert!(game_manager
+            .players
+            .iter()
+            .all(|player| player.money == 8000));
+        let ui = lobby_settings_ui(&game_manager);
+        assert!(ui[0].contains("house"));
+        assert!(ui.contains(&String::from("Starting money: 8000€")));
+        assert!(ui.last().unwrap().contains("60 seconds"));
+        Ok(())
+    }
+}
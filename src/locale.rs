@@ -0,0 +1,731 @@
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// The language in which the messages are currently shown
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+/// The languages in which the messages of the game can be shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    /// Returns the language with the name or language code.
+    /// If no language with that name exists `None` is returned.
+    pub fn from_name(name: &str) -> Option<Language> {
+        match name {
+            "en" | "english" => Some(Language::English),
+            "de" | "german" | "deutsch" => Some(Language::German),
+            _ => None,
+        }
+    }
+
+    /// Returns the language code of the language
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+}
+
+/// Sets the language in which the messages are shown from now on
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// Returns the language in which the messages are currently shown
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::German,
+        _ => Language::English,
+    }
+}
+
+/// Returns the message with the id in the current language.
+///
+/// The placeholders `{0}`, `{1}`, ... of the message are replaced by the arguments.
+/// When the id is not in the catalog the id itself is returned.
+pub fn text(id: &str, args: &[&dyn Display]) -> String {
+    let template = match MESSAGES.iter().find(|message| message.0 == id) {
+        Some((_, english, german)) => match language() {
+            Language::English => english,
+            Language::German => german,
+        },
+        None => return id.to_string(),
+    };
+    let mut text = template.to_string();
+    for (index, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", index), &arg.to_string());
+    }
+    text
+}
+
+/// Returns the keys with which a yes or no question can be answered. German players answer
+/// yes with `J`, `Y` is accepted as well.
+pub fn yes_no_keys() -> Vec<char> {
+    let mut keys = vec!['Y', 'y', 'N', 'n'];
+    if language() == Language::German {
+        keys.extend(['J', 'j']);
+    }
+    keys
+}
+
+/// Returns if the key answers a yes or no question with yes, see [`yes_no_keys`]
+pub fn is_yes(key: char) -> bool {
+    matches!(key, 'Y' | 'y' | 'J' | 'j')
+}
+
+/// The catalog of the messages, each entry contains the id, the English and the German text.
+const MESSAGES: &[(&str, &str, &str)] = &[
+    (
+        "welcome",
+        "Welcome to the Game Acquire!",
+        "Willkommen bei Acquire!",
+    ),
+    ("game.starting", "Starting game!", "Das Spiel beginnt!"),
+    (
+        "game.rules",
+        "The game is played with the {0} rules:\n{1}",
+        "Das Spiel wird mit den Regeln {0} gespielt:\n{1}",
+    ),
+    (
+        "lobby.rules",
+        "Settings of the game, it is played with the {0} rules:",
+        "Einstellungen des Spiels, es wird mit den Regeln {0} gespielt:",
+    ),
+    (
+        "lobby.timer",
+        "Time to answer: {0} seconds",
+        "Zeit für eine Antwort: {0} Sekunden",
+    ),
+    (
+        "lobby.no_timer",
+        "Time to answer: unlimited",
+        "Zeit für eine Antwort: unbegrenzt",
+    ),
+    (
+        "lobby.help",
+        "Change the settings with rules <classic|1999|house>, money <amount> or timer <seconds|off>, press enter to start the game.",
+        "Ändere die Einstellungen mit rules <classic|1999|house>, money <Betrag> oder timer <Sekunden|off>, drücke Enter, um das Spiel zu starten.",
+    ),
+    (
+        "input.still_waiting",
+        "(still waiting)",
+        "(wir warten noch)",
+    ),
+    (
+        "lobby.unknown",
+        "{0} is no setting that can be changed.",
+        "{0} ist keine Einstellung, die geändert werden kann.",
+    ),
+    (
+        "game.continuing",
+        "Continuing the game with the {0} rules:\n{1}",
+        "Das Spiel wird mit den Regeln {0} fortgesetzt:\n{1}",
+    ),
+    (
+        "game.bot_personality",
+        "Bot {0} plays with the {1} personality",
+        "Bot {0} spielt mit der Persönlichkeit {1}",
+    ),
+    (
+        "game.draw_lowest_starts",
+        "Each player draws a card now, the player with the lowest card starts.",
+        "Jeder Spieler zieht jetzt eine Karte, der Spieler mit der niedrigsten Karte beginnt.",
+    ),
+    (
+        "game.draw",
+        "Each player draws a card now and places it on the board.",
+        "Jeder Spieler zieht jetzt eine Karte und legt sie auf das Spielfeld.",
+    ),
+    (
+        "game.draw_prompt",
+        "Press enter to draw your card",
+        "Drücke Enter, um deine Karte zu ziehen",
+    ),
+    (
+        "game.drew_card",
+        "{0} drew card {1}",
+        "{0} hat die Karte {1} gezogen",
+    ),
+    (
+        "game.turn_position",
+        "{0} is the {1}. player",
+        "{0} ist der {1}. Spieler",
+    ),
+    (
+        "game.waiting_for_first_round",
+        "Waiting for {0} to start the first round...",
+        "Warte darauf, dass {0} die erste Runde startet...",
+    ),
+    (
+        "game.start_first_round",
+        "Press enter to start the first round!",
+        "Drücke Enter, um die erste Runde zu starten!",
+    ),
+    (
+        "game.no_moves_left",
+        "No player can place a hotel and no new cards can be drawn, the game ends now!",
+        "Kein Spieler kann ein Hotel platzieren und es können keine Karten mehr gezogen werden, das Spiel endet jetzt!",
+    ),
+    (
+        "game.not_enough_players",
+        "Not enough players are left to continue, the game ends now!",
+        "Es sind nicht mehr genug Spieler übrig, das Spiel endet jetzt!",
+    ),
+    (
+        "rematch.prompt",
+        "Would you like to play a rematch with the same players? [y/n]: ",
+        "Möchtest du eine Revanche mit denselben Spielern spielen? [j/n]: ",
+    ),
+    (
+        "rematch.waiting",
+        "{0} is deciding if they would like to play a rematch...",
+        "{0} entscheidet, ob eine Revanche gespielt werden soll...",
+    ),
+    (
+        "rematch.starting",
+        "Everybody wants a rematch, a new game is starting!",
+        "Alle wollen eine Revanche, ein neues Spiel beginnt!",
+    ),
+    (
+        "rematch.declined",
+        "There is no rematch because {0} declined.",
+        "Es gibt keine Revanche, weil {0} abgelehnt hat.",
+    ),
+    (
+        "game.end_condition",
+        "The following game ending condition is met: {0}",
+        "Die folgende Bedingung für das Spielende ist erfüllt: {0}",
+    ),
+    (
+        "game.end_prompt",
+        "Would you like to end the game (you will still be able to buy stocks)? [Y/n]: ",
+        "Möchtest du das Spiel beenden (du kannst danach noch Aktien kaufen)? [J/n]: ",
+    ),
+    (
+        "turn.choose_card",
+        "Please choose what hotel card you would like to play.",
+        "Bitte wähle, welche Hotelkarte du spielen möchtest.",
+    ),
+    (
+        "turn.no_playable_card",
+        "You have no card that could be played. (Press enter to continue)",
+        "Du hast keine Karte, die gespielt werden kann. (Drücke Enter, um fortzufahren)",
+    ),
+    (
+        "turn.no_cards_left",
+        "No card can be drawn because no cards are left.",
+        "Es kann keine Karte gezogen werden, weil keine Karten mehr übrig sind.",
+    ),
+    ("turn.new_card", "New card: {0}", "Neue Karte: {0}"),
+    (
+        "turn.new_cards",
+        "You have gotten {0} new cards. Press enter to finish your turn.",
+        "Du hast {0} neue Karten bekommen. Drücke Enter, um deinen Zug zu beenden.",
+    ),
+    (
+        "turn.finish",
+        "Press enter to finish your turn",
+        "Drücke Enter, um deinen Zug zu beenden",
+    ),
+    (
+        "chain.start_prompt",
+        "What chain would you like to start? [{0}]: ",
+        "Welche Kette möchtest du gründen? [{0}]: ",
+    ),
+    (
+        "chain.founding_preview",
+        "With {0} hotels the chains are worth:",
+        "Mit {0} Hotels sind die Ketten so viel wert:",
+    ),
+    (
+        "chain.founding_option",
+        "{0}€ per stock, your founder bonus is worth {1}€",
+        "{0}€ pro Aktie, deine Gründeraktien sind {1}€ wert",
+    ),
+    (
+        "chain.founder_bonus_short",
+        "You received {0} of {1} founder bonus stocks because no more stocks are left!",
+        "Du hast {0} von {1} Gründeraktien erhalten, weil keine Aktien mehr übrig sind!",
+    ),
+    (
+        "fusion.start",
+        "Fusion between {0} chains at {1}!",
+        "Fusion von {0} Ketten bei {1}!",
+    ),
+    (
+        "fusion.announce",
+        "Chain {0} is being fused into {1}",
+        "Die Kette {0} wird in {1} fusioniert",
+    ),
+    (
+        "fusion.prompt",
+        "Press enter to fuse {0} into {1} ",
+        "Drücke Enter, um {0} in {1} zu fusionieren ",
+    ),
+    (
+        "fusion.preview",
+        "These bonuses will be paid for {0} at {1}€ per stock:",
+        "Für {0} werden bei {1}€ pro Aktie diese Prämien ausgezahlt:",
+    ),
+    (
+        "fusion.preview.none",
+        "  No bonuses will be paid.",
+        "  Es werden keine Prämien ausgezahlt.",
+    ),
+    (
+        "fusion.preview.largest",
+        "  {0} will receive {1}€ as largest shareholder",
+        "  {0} erhält {1}€ als größter Aktionär",
+    ),
+    (
+        "fusion.preview.second_largest",
+        "  {0} will receive {1}€ as second largest shareholder",
+        "  {0} erhält {1}€ als zweitgrößter Aktionär",
+    ),
+    (
+        "fusion.preview.third_largest",
+        "  {0} will receive {1}€ as third largest shareholder",
+        "  {0} erhält {1}€ als drittgrößter Aktionär",
+    ),
+    (
+        "fusion.summary",
+        "Summary of the fusion of {0} into {1}:",
+        "Zusammenfassung der Fusion von {0} in {1}:",
+    ),
+    (
+        "fusion.no_bonuses",
+        "  No bonuses have been paid.",
+        "  Es wurden keine Prämien ausgezahlt.",
+    ),
+    (
+        "fusion.bonus.largest",
+        "  {0} received {1}€ as largest shareholder",
+        "  {0} hat {1}€ als größter Aktionär erhalten",
+    ),
+    (
+        "fusion.bonus.second_largest",
+        "  {0} received {1}€ as second largest shareholder",
+        "  {0} hat {1}€ als zweitgrößter Aktionär erhalten",
+    ),
+    (
+        "fusion.bonus.third_largest",
+        "  {0} received {1}€ as third largest shareholder",
+        "  {0} hat {1}€ als drittgrößter Aktionär erhalten",
+    ),
+    (
+        "fusion.stocks",
+        "  {0} exchanged {1}, sold {2} and kept {3} stocks",
+        "  {0} hat {1} Aktien getauscht, {2} verkauft und {3} behalten",
+    ),
+    (
+        "bonus.largest",
+        "{0} received {1}€ because they were the largest shareholder.",
+        "{0} hat als größter Aktionär {1}€ erhalten.",
+    ),
+    (
+        "bonus.largest.you",
+        "{0}, you received {1}€ because you were the largest shareholder. (press enter to continue)",
+        "{0}, du hast als größter Aktionär {1}€ erhalten. (Drücke Enter, um fortzufahren)",
+    ),
+    (
+        "bonus.largest_tied.you",
+        "{0}, you received {1}€ because you were one of the largest shareholders. (press enter to continue)",
+        "{0}, du hast als einer der größten Aktionäre {1}€ erhalten. (Drücke Enter, um fortzufahren)",
+    ),
+    (
+        "bonus.second_largest",
+        "{0} received {1}€ because they were the second largest shareholder.",
+        "{0} hat als zweitgrößter Aktionär {1}€ erhalten.",
+    ),
+    (
+        "bonus.second_largest.you",
+        "{0}, you received {1}€ because you were the second largest shareholder. (press enter to continue)",
+        "{0}, du hast als zweitgrößter Aktionär {1}€ erhalten. (Drücke Enter, um fortzufahren)",
+    ),
+    (
+        "bonus.second_largest_tied",
+        "{0} received {1}€ because they were one of the second largest shareholders.",
+        "{0} hat als einer der zweitgrößten Aktionäre {1}€ erhalten.",
+    ),
+    (
+        "bonus.second_largest_tied.you",
+        "{0}, you received {1}€ because you were one of the second largest shareholders. (press enter to continue)",
+        "{0}, du hast als einer der zweitgrößten Aktionäre {1}€ erhalten. (Drücke Enter, um fortzufahren)",
+    ),
+    (
+        "bonus.third_largest_tied",
+        "{0} received {1}€ because they were one of the third largest shareholders.",
+        "{0} hat als einer der drittgrößten Aktionäre {1}€ erhalten.",
+    ),
+    (
+        "bonus.third_largest_tied.you",
+        "{0}, you received {1}€ because you were one of the third largest shareholders. (press enter to continue)",
+        "{0}, du hast als einer der drittgrößten Aktionäre {1}€ erhalten. (Drücke Enter, um fortzufahren)",
+    ),
+    (
+        "input.invalid",
+        "That value did not pass, please try again!",
+        "Dieser Wert ist nicht gültig, bitte versuche es erneut!",
+    ),
+    (
+        "input.confirm",
+        "Is this correct? [Y/n]: ",
+        "Ist das richtig? [J/n]: ",
+    ),
+    (
+        "hot_seat.hand_over",
+        "Please hand the keyboard to {0}. {1}, press enter to continue",
+        "Bitte gib die Tastatur an {0} weiter. {1}, drücke Enter, um fortzufahren",
+    ),
+    (
+        "card.draw_prompt",
+        "Press enter to draw a new card",
+        "Drücke Enter, um eine neue Karte zu ziehen",
+    ),
+    (
+        "card.drawn",
+        "Card drawn: {0}",
+        "Gezogene Karte: {0}",
+    ),
+    (
+        "card.debug_console",
+        "Enter {0} to open the debug console.",
+        "Gib {0} ein, um die Debug-Konsole zu öffnen.",
+    ),
+    (
+        "card.commands",
+        "Enter {0} <card> <slot> to reorder your cards or {1} to turn sorting them automatically on or off.",
+        "Gib {0} <Karte> <Platz> ein, um deine Karten umzusortieren, oder {1}, um das automatische Sortieren ein- oder auszuschalten.",
+    ),
+    (
+        "card.prompt",
+        "Enter a number 1-{0} or {1} to leave the game: ",
+        "Gib eine Zahl von 1-{0} ein oder {1}, um das Spiel zu verlassen: ",
+    ),
+    (
+        "card.prompt_fusion",
+        "Enter a number 1-{0}, 0 to analyze your fusion cards or {1} to leave the game: ",
+        "Gib eine Zahl von 1-{0} ein, 0, um deine Fusionskarten zu analysieren, oder {1}, um das Spiel zu verlassen: ",
+    ),
+    (
+        "card.sorted",
+        "Your cards are sorted automatically now.",
+        "Deine Karten werden jetzt automatisch sortiert.",
+    ),
+    (
+        "card.unsorted",
+        "Your cards are no longer sorted automatically.",
+        "Deine Karten werden nicht mehr automatisch sortiert.",
+    ),
+    (
+        "card.unsorted_hint",
+        "Your cards are no longer sorted automatically, enter {0} to turn it on again.",
+        "Deine Karten werden nicht mehr automatisch sortiert, gib {0} ein, um es wieder einzuschalten.",
+    ),
+    (
+        "card.resign_prompt",
+        "Do you really want to leave the game? Your stocks will be sold to the bank. [y/N]: ",
+        "Möchtest du das Spiel wirklich verlassen? Deine Aktien werden an die Bank verkauft. [j/N]: ",
+    ),
+    (
+        "card.illegal",
+        "This position is illegal [{0}]: {1}",
+        "Diese Position ist nicht erlaubt [{0}]: {1}",
+    ),
+    (
+        "card.choose_another",
+        "Please select another card!",
+        "Bitte wähle eine andere Karte!",
+    ),
+    (
+        "card.only_illegal",
+        "You have only cards left that can not be played because the fusion would be illegal.",
+        "Du hast nur noch Karten, die nicht gespielt werden können, weil die Fusion nicht erlaubt wäre.",
+    ),
+    (
+        "card.redraw_prompt",
+        "Would you like to redraw your hand cards? [Y/n]: ",
+        "Möchtest du deine Handkarten neu ziehen? [J/n]: ",
+    ),
+    (
+        "summary.stocks_added",
+        "Total {0} stocks: {1} + {2} = {3}",
+        "{0}-Aktien insgesamt: {1} + {2} = {3}",
+    ),
+    (
+        "summary.stocks_removed",
+        "Total {0} stocks: {1} - {2} = {3}",
+        "{0}-Aktien insgesamt: {1} - {2} = {3}",
+    ),
+    (
+        "summary.money_added",
+        "Money: {0}€ + {1}€ = {2}€",
+        "Geld: {0}€ + {1}€ = {2}€",
+    ),
+    (
+        "summary.money_removed",
+        "Money: {0}€ - {1}€ = {2}€",
+        "Geld: {0}€ - {1}€ = {2}€",
+    ),
+    (
+        "fusion_stocks.turn",
+        "{0}, it's your turn to decide what you would like to do with your {1} stock(s):",
+        "{0}, du entscheidest jetzt, was du mit deinen {1} Aktie(n) machen möchtest:",
+    ),
+    (
+        "fusion_stocks.exchange",
+        "Please enter how many stocks you would like to exchange [{0}]: ",
+        "Bitte gib ein, wie viele Aktien du tauschen möchtest [{0}]: ",
+    ),
+    (
+        "fusion_stocks.bank_empty",
+        "- the bank does not have any stocks left that could be exchanged to you",
+        "- die Bank hat keine Aktien mehr, die sie dir eintauschen könnte",
+    ),
+    (
+        "fusion_stocks.too_few",
+        "- you don't have enough stocks to exchange them",
+        "- du hast nicht genug Aktien, um sie zu tauschen",
+    ),
+    (
+        "fusion_stocks.sell",
+        "Please enter how many stocks you would like to sell [0-{0}]: ",
+        "Bitte gib ein, wie viele Aktien du verkaufen möchtest [0-{0}]: ",
+    ),
+    (
+        "fusion_stocks.none_left",
+        "- no stocks left to sell",
+        "- keine Aktien mehr zum Verkaufen",
+    ),
+    (
+        "buy.maximum",
+        "{0}, you can buy a maximum of {1} stocks now:",
+        "{0}, du kannst jetzt höchstens {1} Aktien kaufen:",
+    ),
+    (
+        "buy.prompt",
+        "How many stocks would you like to buy of {0}?",
+        "Wie viele Aktien von {0} möchtest du kaufen?",
+    ),
+    (
+        "buy.limit_reached",
+        "- already bought {0} stocks",
+        "- bereits {0} Aktien gekauft",
+    ),
+    (
+        "buy.none_left",
+        "- no stocks left",
+        "- keine Aktien mehr übrig",
+    ),
+    (
+        "buy.no_money",
+        "- not enough money",
+        "- nicht genug Geld",
+    ),
+    (
+        "buy.nothing",
+        "You did not buy any stocks.",
+        "Du hast keine Aktien gekauft.",
+    ),
+    (
+        "sell.prompt",
+        "Would you like to sell stocks before buying new ones? [Y/n]: ",
+        "Möchtest du Aktien verkaufen, bevor du neue kaufst? [J/n]: ",
+    ),
+    (
+        "sell.amount",
+        "How many stocks would you like to sell of {0}? [0-{1}]: ",
+        "Wie viele Aktien von {0} möchtest du verkaufen? [0-{1}]: ",
+    ),
+    (
+        "sell.nothing",
+        "You did not sell any stocks.",
+        "Du hast keine Aktien verkauft.",
+    ),
+    (
+        "fusion_order.deciding",
+        "{0} is deciding the fusion order between {1}",
+        "{0} entscheidet über die Reihenfolge der Fusion von {1}",
+    ),
+    (
+        "fusion_order.cases",
+        "[1] = Fuse {0} in {1}\n[2] = Fuse {1} in {0}",
+        "[1] = {0} in {1} fusionieren\n[2] = {1} in {0} fusionieren",
+    ),
+    (
+        "fusion_order.choose_case",
+        "Choose a case: ",
+        "Wähle einen Fall: ",
+    ),
+    (
+        "fusion_order.three_equal",
+        "All three chains are equally long.",
+        "Alle drei Ketten sind gleich lang.",
+    ),
+    (
+        "fusion_order.three_note",
+        "Note: The chain that you pick first will be fused into the second and the second will be fused into the third.",
+        "Hinweis: Die Kette, die du zuerst wählst, wird in die zweite fusioniert und die zweite in die dritte.",
+    ),
+    (
+        "fusion_order.four",
+        "Congratulations, you are fusing 4 chains into one.",
+        "Herzlichen Glückwunsch, du fusionierst 4 Ketten zu einer.",
+    ),
+    (
+        "fusion_order.four_manual",
+        "The fusion order of four chains is not detected automatically.",
+        "Die Reihenfolge der Fusion von vier Ketten wird nicht automatisch bestimmt.",
+    ),
+    (
+        "fusion_order.four_rules",
+        "You will have to do that manually and according to the rules:",
+        "Du musst das selbst nach den Regeln tun:",
+    ),
+    (
+        "fusion_order.rule_largest",
+        "1. The chain with the most hotels absorbs all smaller chains",
+        "1. Die Kette mit den meisten Hotels übernimmt alle kleineren Ketten",
+    ),
+    (
+        "fusion_order.rule_size",
+        "2. The order in which the smaller chains are fused is determined by their size.\n   The smallest chain fuses into the second smallest chain and so on.",
+        "2. Die Reihenfolge, in der die kleineren Ketten fusioniert werden, hängt von ihrer Größe ab.\n   Die kleinste Kette wird in die zweitkleinste fusioniert und so weiter.",
+    ),
+    (
+        "fusion_order.rule_tie",
+        "3. The player that started the fusion can decide the fusion order, if all chains are the same size",
+        "3. Wenn alle Ketten gleich groß sind, entscheidet der Spieler, der die Fusion ausgelöst hat, über die Reihenfolge",
+    ),
+    (
+        "fusion_order.four_note",
+        "Note: The chain that you pick first will be fused into the second, second will be fused into the third and the third will be fused into the fourth.",
+        "Hinweis: Die Kette, die du zuerst wählst, wird in die zweite fusioniert, die zweite in die dritte und die dritte in die vierte.",
+    ),
+    (
+        "fusion_order.survivor",
+        "Which chain should survive the fusion? [{0}]: ",
+        "Welche Kette soll die Fusion überstehen? [{0}]: ",
+    ),
+    (
+        "fusion_order.order",
+        "Please choose the order in which the hotels should be fused into {0}:",
+        "Bitte wähle die Reihenfolge, in der die Hotels in {0} fusioniert werden sollen:",
+    ),
+    (
+        "fusion_order.position",
+        "At which position should {0} be? [{1}]: ",
+        "An welcher Position soll {0} sein? [{1}]: ",
+    ),
+    (
+        "fusion_order.summary",
+        "The fusion will take place as follows:",
+        "Die Fusion läuft so ab:",
+    ),
+    (
+        "result.winner",
+        "{0}, congratulations, you are the winner!",
+        "{0}, herzlichen Glückwunsch, du hast gewonnen!",
+    ),
+    (
+        "result.shared_win",
+        "{0}, congratulations, you share the win!",
+        "{0}, herzlichen Glückwunsch, du teilst dir den Sieg!",
+    ),
+    (
+        "result.second",
+        "{0}, you are second place!",
+        "{0}, du bist auf dem zweiten Platz!",
+    ),
+    (
+        "result.third",
+        "{0}, you are third place!",
+        "{0}, du bist auf dem dritten Platz!",
+    ),
+    (
+        "result.lost",
+        "{0}, you have lost!",
+        "{0}, du hast verloren!",
+    ),
+    (
+        "result.teams",
+        "Team results:",
+        "Ergebnisse der Teams:",
+    ),
+    (
+        "result.team",
+        "{0}. Team {1} ({2}) - {3}€",
+        "{0}. Team {1} ({2}) - {3}€",
+    ),
+    (
+        "result.team_members",
+        " and ",
+        " und ",
+    ),
+    (
+        "result.team_won",
+        "{0}, congratulations, your team has won!",
+        "{0}, herzlichen Glückwunsch, dein Team hat gewonnen!",
+    ),
+    (
+        "result.team_shared_win",
+        "{0}, congratulations, your team shares the win!",
+        "{0}, herzlichen Glückwunsch, dein Team teilt sich den Sieg!",
+    ),
+    (
+        "result.team_lost",
+        "{0}, your team has lost!",
+        "{0}, dein Team hat verloren!",
+    ),
+    (
+        "webhook.ended",
+        "The game of Acquire has ended:",
+        "Die Partie Acquire ist zu Ende:",
+    ),
+    (
+        "webhook.result",
+        "{0}. {1} with {2}€",
+        "{0}. {1} mit {2}€",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{is_yes, text, Language, MESSAGES};
+
+    #[test]
+    fn messages_are_translated() {
+        let mut ids = HashSet::new();
+        for (id, english, german) in MESSAGES {
+            assert!(ids.insert(id), "The id {} is used twice", id);
+            for index in 0..4 {
+                let placeholder = format!("{{{}}}", index);
+                assert_eq!(
+                    english.contains(&placeholder),
+                    german.contains(&placeholder),
+                    "The translations of {} do not use the same placeholders",
+                    id
+                );
+            }
+        }
+        // The tests run in parallel, so the language is not switched here
+        assert_eq!(
+            text("game.drew_card", &[&"Player 1", &"A3"]),
+            "Player 1 drew card A3"
+        );
+        assert_eq!(text("unknown", &[]), "unknown");
+        assert_eq!(Language::from_name("de"), Some(Language::German));
+        assert_eq!(Language::from_name("fr"), None);
+        assert!(is_yes('J') && is_yes('y') && !is_yes('n'));
+    }
+}
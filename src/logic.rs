@@ -1,28 +1,36 @@
 use crate::{
-    base_game::{board::Board, hotel_chains::HotelChain},
+    base_game::{
+        bank::Bank,
+        board::Board,
+        hotel_chains::HotelChain,
+        player::Player,
+        settings::{END_CHAIN_SIZE, SAFE_CHAIN_SIZE},
+    },
     game::hotel_chain_manager::HotelChainManager,
-    logic::place_hotel::{analyze_position, PlaceHotelCase},
+    logic::place_hotel::{analyze_position, IllegalPlacement, PlaceHotelCase},
+    network::broadcast,
 };
+use miette::Result;
 use std::slice::Iter;
 
 /// The different ways the game can end.
 #[derive(Clone, Copy)]
 pub enum EndCondition {
-    /// The game can be finished when all chains on the board have at least 10 hotels and
+    /// The game can be finished when all chains on the board are safe and
     /// when there is no space to found a new chain
-    AllChainsMoreThan10HotelsAndNoSpaceForNewChain,
-    /// The game can be finished when at least one chain has 41 or more hotels
-    OneChain41OrMoreHotels,
+    AllChainsSafeAndNoSpaceForNewChain,
+    /// The game can be finished when at least one chain has reached the end chain size
+    OneChainReachedEndSize,
 }
 
 impl EndCondition {
     fn is_condition_met(&self, board: &Board, hotel_chain_manager: &HotelChainManager) -> bool {
         match self {
-            Self::AllChainsMoreThan10HotelsAndNoSpaceForNewChain => {
+            Self::AllChainsSafeAndNoSpaceForNewChain => {
                 let mut all_chains_safe = true;
                 for chain in HotelChain::iterator() {
                     if hotel_chain_manager.chain_status(chain)
-                        && hotel_chain_manager.chain_length(chain) <= 10
+                        && !hotel_chain_manager.is_chain_safe(chain)
                     {
                         all_chains_safe = false;
                     }
@@ -30,31 +38,29 @@ impl EndCondition {
                 if !all_chains_safe {
                     return false;
                 }
-                for line in &board.pieces {
-                    for piece in line {
-                        match analyze_position(&piece.position, board, hotel_chain_manager) {
-                            PlaceHotelCase::NewChain(_positions) => return false,
-                            PlaceHotelCase::SingleHotel => {
-                                let neighbours = piece.position.neighbours();
-                                // Check if one of the neighbours is free for a single hotel.
-                                // If yes two single hotels stand next to each other and could
-                                // found a new chain.
-                                for neighbour in neighbours {
-                                    match analyze_position(&neighbour, board, hotel_chain_manager) {
-                                        PlaceHotelCase::SingleHotel => return false,
-                                        _ => continue,
-                                    }
+                for piece in &board.pieces {
+                    match analyze_position(&piece.position, board, hotel_chain_manager) {
+                        PlaceHotelCase::NewChain(_positions) => return false,
+                        PlaceHotelCase::SingleHotel => {
+                            let neighbours = piece.position.neighbours();
+                            // Check if one of the neighbours is free for a single hotel.
+                            // If yes two single hotels stand next to each other and could
+                            // found a new chain.
+                            for neighbour in neighbours {
+                                match analyze_position(&neighbour, board, hotel_chain_manager) {
+                                    PlaceHotelCase::SingleHotel => return false,
+                                    _ => continue,
                                 }
                             }
-                            _ => continue,
                         }
+                        _ => continue,
                     }
                 }
                 true
             }
-            Self::OneChain41OrMoreHotels => {
+            Self::OneChainReachedEndSize => {
                 for chain in HotelChain::iterator() {
-                    if hotel_chain_manager.chain_length(chain) >= 41 {
+                    if hotel_chain_manager.chain_length(chain) >= END_CHAIN_SIZE {
                         return true;
                     }
                 }
@@ -66,17 +72,20 @@ impl EndCondition {
     /// Returns a description on the end condition
     pub fn description(&self) -> String {
         match self {
-            Self::AllChainsMoreThan10HotelsAndNoSpaceForNewChain => {
-                String::from("All chains have at least 10 hotels and no new chains can be founded")
+            Self::AllChainsSafeAndNoSpaceForNewChain => format!(
+                "All chains have at least {} hotels and no new chains can be founded",
+                SAFE_CHAIN_SIZE
+            ),
+            Self::OneChainReachedEndSize => {
+                format!("One chain has {} or more hotels", END_CHAIN_SIZE)
             }
-            Self::OneChain41OrMoreHotels => String::from("One chain has 41 or more hotels"),
         }
     }
 
     fn iterator() -> Iter<'static, EndCondition> {
         const END_CONDITION: [EndCondition; 2] = [
-            EndCondition::AllChainsMoreThan10HotelsAndNoSpaceForNewChain,
-            EndCondition::OneChain41OrMoreHotels,
+            EndCondition::AllChainsSafeAndNoSpaceForNewChain,
+            EndCondition::OneChainReachedEndSize,
         ];
         END_CONDITION.iter()
     }
@@ -100,6 +109,72 @@ pub fn check_end_condition(
     None
 }
 
+/// Checks if the game can continue.
+///
+/// The game can not continue when no player has a card left that can be played and when no
+/// player can get new cards. New cards can only be drawn when cards are left and when a player
+/// has only cards that would start an illegal fusion.
+/// # Arguments
+/// * `cards_left` - If position cards are left that can be drawn
+pub fn can_game_continue(
+    players: &[Player],
+    board: &Board,
+    hotel_chain_manager: &HotelChainManager,
+    cards_left: bool,
+) -> bool {
+    let mut redraw_possible = false;
+    for player in players.iter().filter(|player| !player.resigned) {
+        let mut only_illegal_fusion = true;
+        for card in &player.analyzed_cards {
+            match analyze_position(&card.position, board, hotel_chain_manager) {
+                PlaceHotelCase::Illegal(IllegalPlacement::FusionIllegal) => (),
+                PlaceHotelCase::Illegal(IllegalPlacement::ChainStartIllegal) => {
+                    only_illegal_fusion = false
+                }
+                _ => return true,
+            }
+        }
+        if only_illegal_fusion {
+            redraw_possible = true;
+        }
+    }
+    redraw_possible && cards_left
+}
+
+/// The player leaves the game.
+///
+/// Their stocks are sold to the bank for the current prices, their cards are discarded and
+/// their turns are skipped for the rest of the game. All players are informed.
+pub fn resign(
+    player_index: usize,
+    players: &mut [Player],
+    bank: &mut Bank,
+    hotel_chain_manager: &HotelChainManager,
+) -> Result<()> {
+    let player = &mut players[player_index];
+    let money_before = player.money;
+    for chain in HotelChain::iterator() {
+        let stocks = *player.owned_stocks.stocks_for_hotel(chain);
+        if stocks > 0 {
+            bank.sell_stock(player, stocks, chain, hotel_chain_manager)?;
+        }
+    }
+    player.analyzed_cards.clear();
+    player.resigned = true;
+    let message = format!(
+        "{} has left the game, their stocks have been sold for {}€.",
+        player.colored_name(),
+        player.money - money_before
+    );
+    bank.update_largest_shareholders(players);
+    broadcast(&message, players)
+}
+
+/// Returns how many players have not left the game.
+pub fn remaining_players(players: &[Player]) -> usize {
+    players.iter().filter(|player| !player.resigned).count()
+}
+
 /// All functions related to placing a hotel
 pub mod place_hotel {
     use std::{cmp::Ordering, collections::HashMap};
@@ -108,16 +183,20 @@ pub mod place_hotel {
     use owo_colors::{AnsiColors, OwoColorize};
 
     use crate::{
+        analysis::preview_bonuses,
         base_game::{
-            bank::Bank,
+            bank::{Bank, ShareholderRank},
             board::{AnalyzedPosition, Board, Position},
             hotel_chains::HotelChain,
-            player::Player,
+            player::{CardChoice, Player},
             settings::Settings,
             ui,
         },
+        debug_console::open_debug_console,
         game::{hotel_chain_manager::HotelChainManager, round::Round},
+        locale::text,
         network::{broadcast, broadcast_others},
+        tutorial::TutorialEvent,
         utils::{chains_to_print, remove_content_from_vec},
     };
 
@@ -129,26 +208,72 @@ pub mod place_hotel {
     /// * `Ok(false)` - No hotel has been placed
     pub fn place_hotel(
         player_index: usize,
-        players: &mut Vec<Player>,
+        players: &mut [Player],
         board: &mut Board,
         settings: &Settings,
         round: &Round,
         bank: &mut Bank,
         hotel_chain_manager: &mut HotelChainManager,
     ) -> Result<bool> {
-        let player = players.get_mut(player_index).unwrap();
-        player.print_text_ln("Please choose what hotel card you would like to play.")?;
+        let fusion_analysis = ui::fusion_analysis_ui(
+            &players[player_index],
+            players,
+            board,
+            bank,
+            hotel_chain_manager,
+        )?;
+        let player = &mut players[player_index];
+        player.print_text_ln(&text("turn.choose_card", &[]))?;
         // Check if player has at least one card that can be played
         if player.only_illegal_cards() {
-            player.get_enter("You have no card that could be played. (Press enter to continue)")?;
+            player.get_enter(&text("turn.no_playable_card", &[]))?;
             return Ok(false);
         }
-        let played_position = player.read_card()?;
+        let mut fusion_analysis = fusion_analysis;
+        let played_position = loop {
+            let player = &mut players[player_index];
+            let choice = match player.read_card(
+                bank,
+                hotel_chain_manager,
+                &fusion_analysis,
+                settings.debug_console,
+            ) {
+                Ok(choice) => choice,
+                // A client that has left or does not answer leaves the game, so that the others
+                // can go on playing
+                Err(err) => match player.missed_input(&err) {
+                    Some(input_error) => {
+                        broadcast_others(&input_error.to_string(), player.id, players)?;
+                        CardChoice::Resign
+                    }
+                    None => return Err(err),
+                },
+            };
+            match choice {
+                CardChoice::Card(played_position) => break played_position,
+                CardChoice::Resign => {
+                    super::resign(player_index, players, bank, hotel_chain_manager)?;
+                    return Ok(false);
+                }
+                CardChoice::DebugConsole => {
+                    open_debug_console(players, board, bank, hotel_chain_manager)?;
+                    // The cards have to be analyzed again when the board has been changed
+                    players[player_index].analyze_cards(board, hotel_chain_manager);
+                    fusion_analysis = ui::fusion_analysis_ui(
+                        &players[player_index],
+                        players,
+                        board,
+                        bank,
+                        hotel_chain_manager,
+                    )?;
+                }
+            }
+        };
         // Place hotel
         board.place_hotel(&played_position.position)?;
-        let player_name = player.name.clone();
+        let player_id = players[player_index].id;
         ui::print_main_ui_players(
-            player.name.clone(),
+            player_id,
             players,
             board,
             settings,
@@ -156,16 +281,14 @@ pub mod place_hotel {
             bank,
             hotel_chain_manager,
         )?;
+        let tutorial_event = match &played_position.place_hotel_case {
+            PlaceHotelCase::SingleHotel => Some(TutorialEvent::SingleHotel),
+            PlaceHotelCase::ExtendsChain(chain, _) => Some(TutorialEvent::ChainExtended(*chain)),
+            _ => None,
+        };
+        // The other players see the placed hotel and the changed chains in the game log
         match played_position.place_hotel_case {
-            PlaceHotelCase::SingleHotel => broadcast_others(
-                &format!(
-                    "{} has placed a hotel on {}",
-                    player_name,
-                    played_position.position.color(AnsiColors::Green)
-                ),
-                &player_name,
-                players,
-            )?,
+            PlaceHotelCase::SingleHotel => (),
             PlaceHotelCase::NewChain(positions) => start_chain(
                 positions,
                 player_index,
@@ -175,17 +298,7 @@ pub mod place_hotel {
                 bank,
             )?,
             PlaceHotelCase::ExtendsChain(chain, positions) => {
-                let len = positions.len();
-                extend_chain(chain, positions, hotel_chain_manager, board)?;
-                broadcast(
-                    &format!(
-                        "{} has extended the chain {} by {} hotel(s)",
-                        player_name,
-                        chain.name().color(chain.color()),
-                        len,
-                    ),
-                    players,
-                )?;
+                extend_chain(chain, positions, hotel_chain_manager, board)?
             }
             PlaceHotelCase::Fusion(chains, origin) => fuse_chains(
                 chains,
@@ -200,6 +313,9 @@ pub mod place_hotel {
             )?,
             _ => (),
         }
+        if let (Some(tutorial), Some(event)) = (&settings.tutorial, tutorial_event) {
+            tutorial.explain(event, players)?;
+        }
         Ok(true)
     }
 
@@ -210,12 +326,12 @@ pub mod place_hotel {
     pub fn start_chain(
         positions: Vec<Position>,
         player_index: usize,
-        players: &mut Vec<Player>,
+        players: &mut [Player],
         hotel_chain_manager: &mut HotelChainManager,
         board: &mut Board,
         bank: &mut Bank,
     ) -> Result<()> {
-        let player = players.get_mut(player_index).unwrap();
+        let player = &mut players[player_index];
         let mut available_chains = HashMap::new();
         let mut available_chains_identifier = Vec::new();
         for chain in HotelChain::iterator() {
@@ -238,26 +354,45 @@ pub mod place_hotel {
             }
             available_chains_help.push_str(&k.color(v.color()).to_string());
         }
-        let input = player.read_input(
-            format!(
-                "What chain would you like to start? [{}]: ",
-                available_chains_help
-            ),
-            available_chains_identifier,
-        )?;
-
-        let chain = available_chains.get(&input).unwrap();
-        hotel_chain_manager.start_chain(*chain, positions, board, player, bank)?;
-        let player_name = player.name.clone();
+        let chain = match &player.bot {
+            Some(bot) => {
+                let decision = bot.choose_chain_to_found(
+                    player,
+                    &hotel_chain_manager.available_chains().unwrap(),
+                    bank,
+                    hotel_chain_manager,
+                );
+                player.bot_reasons.push(decision.reason);
+                decision.choice
+            }
+            None => {
+                let chains: Vec<HotelChain> = available_chains_identifier
+                    .iter()
+                    .map(|identifier| available_chains[identifier])
+                    .collect();
+                for line in ui::founding_preview_ui(positions.len() as u32, &chains, bank) {
+                    player.print_text_ln(&line)?;
+                }
+                let first_chain = available_chains_identifier[0];
+                let input = player.answer_or_default(
+                    player.read_input(
+                        text("chain.start_prompt", &[&available_chains_help]),
+                        available_chains_identifier,
+                    ),
+                    || first_chain,
+                )?;
+                *available_chains.get(&input).unwrap()
+            }
+        };
+        let bonus_stocks =
+            hotel_chain_manager.start_chain(chain, positions, board, player, bank)?;
+        if bonus_stocks < bank.rules.founder_bonus_stocks {
+            player.print_text_ln(&text(
+                "chain.founder_bonus_short",
+                &[&bonus_stocks, &bank.rules.founder_bonus_stocks],
+            ))?;
+        }
         bank.update_largest_shareholders(players);
-        broadcast(
-            &format!(
-                "{} has stared the new chain {}",
-                player_name,
-                chain.name().color(chain.color())
-            ),
-            players,
-        )?;
         Ok(())
     }
 
@@ -284,7 +419,7 @@ pub mod place_hotel {
         chains: Vec<HotelChain>,
         origin: Position,
         player_index: usize,
-        players: &mut Vec<Player>,
+        players: &mut [Player],
         board: &mut Board,
         bank: &mut Bank,
         hotel_chain_manager: &mut HotelChainManager,
@@ -295,27 +430,26 @@ pub mod place_hotel {
         let mut fuse_order = Vec::new();
         let surviving_chain;
         broadcast(
-            &format!(
-                "Fusion between {} chains at {}!",
-                chains.len(),
-                &origin.color(AnsiColors::Green)
+            &text(
+                "fusion.start",
+                &[&chains.len(), &origin.color(AnsiColors::Green)],
             ),
             players,
         )?;
         // Determine the order in which the hotels are fused
-        let player = players.get_mut(player_index).unwrap();
-        let player_name = player.name.clone();
+        let player_id = players[player_index].id;
+        let player = &mut players[player_index];
         match chains.len() {
             2 => {
-                let chain1 = chains.get(0).unwrap();
+                let chain1 = chains.first().unwrap();
                 let chain2 = chains.get(1).unwrap();
                 let resolved_order =
-                    resolve_fusion_order(player, chain1, chain2, hotel_chain_manager)?;
-                fuse_order.push(*resolved_order.get(0).unwrap());
+                    resolve_fusion_order(player, chain1, chain2, bank, hotel_chain_manager)?;
+                fuse_order.push(*resolved_order.first().unwrap());
                 surviving_chain = *resolved_order.get(1).unwrap();
             }
             3 => {
-                let chain1 = chains.get(0).unwrap();
+                let chain1 = chains.first().unwrap();
                 let chain2 = chains.get(1).unwrap();
                 let chain3 = chains.get(2).unwrap();
                 match longest_chain(chain1, chain2, Some(chain3), None, hotel_chain_manager) {
@@ -323,49 +457,73 @@ pub mod place_hotel {
                         let mut resolved_order = Vec::new();
                         if chain == chain1 {
                             resolved_order =
-                                resolve_fusion_order(player, chain2, chain3, hotel_chain_manager)?;
+                                resolve_fusion_order(player, chain2, chain3, bank, hotel_chain_manager)?;
                         }
                         if chain == chain2 {
                             resolved_order =
-                                resolve_fusion_order(player, chain1, chain3, hotel_chain_manager)?;
+                                resolve_fusion_order(player, chain1, chain3, bank, hotel_chain_manager)?;
                         }
                         if chain == chain3 {
                             resolved_order =
-                                resolve_fusion_order(player, chain1, chain2, hotel_chain_manager)?;
+                                resolve_fusion_order(player, chain1, chain2, bank, hotel_chain_manager)?;
                         }
-                        fuse_order.push(resolved_order.get(0).unwrap());
+                        fuse_order.push(resolved_order.first().unwrap());
                         fuse_order.push(resolved_order.get(1).unwrap());
                         surviving_chain = chain;
                     }
                     None => {
                         // All three chains are equally long
-                        broadcast_others(&format!("{} is deciding the fusion order between {}", &player_name, chains_to_print(&chains)), &player_name, players)?;
-                        let player = players.get_mut(player_index).unwrap();
-                        player.print_text_ln("All three chains are equally long.")?;
-                        player.print_text_ln("Note: The chain that you pic first will be fused into the second and the second will be fused into the third.")?;
-                        let resolved_order =
-                            resolve_fusion_order_three_and_four_chains(player, &chains)?;
-                        fuse_order.push(resolved_order.get(0).unwrap());
+                        broadcast_others(
+                            &text(
+                                "fusion_order.deciding",
+                                &[&players[player_index].colored_name(), &chains_to_print(&chains)],
+                            ),
+                            player_id,
+                            players,
+                        )?;
+                        let player = &mut players[player_index];
+                        player.print_text_ln(&text("fusion_order.three_equal", &[]))?;
+                        player.print_text_ln(&text("fusion_order.three_note", &[]))?;
+                        let resolved_order = resolve_fusion_order_three_and_four_chains(
+                            player,
+                            &chains,
+                            bank,
+                            hotel_chain_manager,
+                        )?;
+                        fuse_order.push(resolved_order.first().unwrap());
                         fuse_order.push(resolved_order.get(1).unwrap());
                         surviving_chain = resolved_order.get(2).unwrap();
                     }
                 }
             }
             4 => {
-                broadcast_others(&format!("{} is deciding the fusion order between {}", &player_name, chains_to_print(&chains)), &player_name, players)?;
-                let player = players.get_mut(player_index).unwrap();
-                player.print_text_ln("Concratulations, you are fusing 4 chains into one.")?;
-                player.print_text_ln("Because this scenario is so unlikely i did not code a way to automatically detect the fusion order.")?;
-                player
-                    .print_text_ln("You will have to do that manually and acording to the rules:")?;
-                player
-                    .print_text_ln("1. The chain with the most hotels absorbs all smaller chains")?;
-                player
-                    .print_text_ln("2. The order in which the smaller chains are fused is determined by their size.\n   The smallest chain fuses into the second smallest chain and so on.")?;
-                player.print_text_ln("3. The player that stared the fusion can decide the fusion order, if all chains are the same size")?;
-                player.print_text_ln("Note: The chain that you pic first will be fused into the second, second will be fused into the third and the third will be fused into the fourth.")?;
-                let resolved_order = resolve_fusion_order_three_and_four_chains(player, &chains)?;
-                fuse_order.push(resolved_order.get(0).unwrap());
+                broadcast_others(
+                            &text(
+                                "fusion_order.deciding",
+                                &[&players[player_index].colored_name(), &chains_to_print(&chains)],
+                            ),
+                            player_id,
+                            players,
+                        )?;
+                let player = &mut players[player_index];
+                for id in [
+                    "fusion_order.four",
+                    "fusion_order.four_manual",
+                    "fusion_order.four_rules",
+                    "fusion_order.rule_largest",
+                    "fusion_order.rule_size",
+                    "fusion_order.rule_tie",
+                    "fusion_order.four_note",
+                ] {
+                    player.print_text_ln(&text(id, &[]))?;
+                }
+                let resolved_order = resolve_fusion_order_three_and_four_chains(
+                    player,
+                    &chains,
+                    bank,
+                    hotel_chain_manager,
+                )?;
+                fuse_order.push(resolved_order.first().unwrap());
                 fuse_order.push(resolved_order.get(1).unwrap());
                 fuse_order.push(resolved_order.get(2).unwrap());
                 surviving_chain = resolved_order.get(3).unwrap();
@@ -373,7 +531,7 @@ pub mod place_hotel {
             _ => return Err(miette!("Unable to fuse chains: The amount of input chains is invalid. Should be 1-4, was {}", chains.len())),
         };
         // Fuse oder has been determined
-        let chain1 = *fuse_order.get(0).unwrap();
+        let chain1 = *fuse_order.first().unwrap();
         fuse_two_chains(
             surviving_chain,
             chain1,
@@ -382,11 +540,11 @@ pub mod place_hotel {
             board,
             hotel_chain_manager,
             bank,
+            settings,
         )?;
         if fuse_order.len() > 1 {
-            let player = players.get_mut(player_index).unwrap();
             ui::print_main_ui_players(
-                player.name.clone(),
+                player_id,
                 players,
                 board,
                 settings,
@@ -403,11 +561,11 @@ pub mod place_hotel {
                 board,
                 hotel_chain_manager,
                 bank,
+                settings,
             )?;
             if fuse_order.len() > 2 {
-                let player = players.get_mut(player_index).unwrap();
                 ui::print_main_ui_players(
-                    player.name.clone(),
+                    player_id,
                     players,
                     board,
                     settings,
@@ -424,6 +582,7 @@ pub mod place_hotel {
                     board,
                     hotel_chain_manager,
                     bank,
+                    settings,
                 )?;
             }
         }
@@ -436,15 +595,16 @@ pub mod place_hotel {
         Ok(())
     }
 
-    /// Determines which chain will surivive the fusion.
+    /// Determines which chain will survive the fusion.
     /// If the two chains are equal in size the player that started the fusion is asked which chain
     /// should survive.
     /// # Returns
     /// A vector: The first element will be fused into the second element
     fn resolve_fusion_order<'a>(
-        player: &Player,
+        player: &mut Player,
         chain1: &'a HotelChain,
         chain2: &'a HotelChain,
+        bank: &Bank,
         hotel_chain_manager: &HotelChainManager,
     ) -> Result<Vec<&'a HotelChain>> {
         let mut fuse_order = Vec::new();
@@ -460,64 +620,142 @@ pub mod place_hotel {
                 fuse_order.push(chain1);
                 fuse_order.push(chain2);
             }
+            Ordering::Equal if player.bot.is_some() => {
+                let bot = player.bot.as_ref().unwrap();
+                let decision = bot.choose_surviving_chain(
+                    player,
+                    &[*chain1, *chain2],
+                    bank,
+                    hotel_chain_manager,
+                );
+                player.bot_reasons.push(decision.reason);
+                let surviving_chain = decision.choice;
+                if surviving_chain == *chain1 {
+                    fuse_order.push(chain2);
+                    fuse_order.push(chain1);
+                } else {
+                    fuse_order.push(chain1);
+                    fuse_order.push(chain2);
+                }
+            }
             Ordering::Equal => {
-                // Player decides which chain should fuse into which
-                loop {
-                    let message = format!(
-                        "[1] = Fuse {} in {}\n[2] = Fuse {} in {}",
-                        chain1.name().color(chain1.color()),
-                        chain2.name().color(chain2.color()),
-                        chain2.name().color(chain2.color()),
-                        chain1.name().color(chain1.color())
-                    );
-                    player.print_text_ln(&message)?;
-                    let fusion_case =
-                        player.read_input(String::from("Choose a case: "), vec![1, 2])?;
-                    let mut confirm_message = String::new();
-                    match fusion_case {
-                        1 => confirm_message.push_str(&format!(
-                            "{} in {}",
-                            chain1.name().color(chain1.color()),
-                            chain2.name().color(chain2.color())
-                        )),
-                        2 => confirm_message.push_str(&format!(
-                            "{} in {}",
-                            chain2.name().color(chain2.color()),
-                            chain1.name().color(chain1.color())
-                        )),
-                        _ => (),
-                    }
-                    if !player.get_correct()? {
-                        continue;
-                    }
-                    match fusion_case {
-                        1 => {
-                            fuse_order.push(chain1);
-                            fuse_order.push(chain2);
-                        }
-                        2 => {
-                            fuse_order.push(chain2);
-                            fuse_order.push(chain1);
-                        }
-                        _ => (),
-                    }
-                    break;
+                // Player decides which chain should fuse into which, the first chain survives
+                // when they do not answer
+                let answer = read_fusion_order(player, chain1, chain2);
+                fuse_order = player.answer_or_default(answer, || vec![chain2, chain1])?;
+            }
+        }
+        Ok(fuse_order)
+    }
+
+    /// Asks the player which of the two equally long chains should fuse into the other one.
+    /// # Returns
+    /// A vector: The first element will be fused into the second element
+    fn read_fusion_order<'a>(
+        player: &Player,
+        chain1: &'a HotelChain,
+        chain2: &'a HotelChain,
+    ) -> Result<Vec<&'a HotelChain>> {
+        let mut fuse_order = Vec::new();
+        loop {
+            let message = text(
+                "fusion_order.cases",
+                &[
+                    &chain1.name().color(chain1.color()),
+                    &chain2.name().color(chain2.color()),
+                ],
+            );
+            player.print_text_ln(&message)?;
+            let fusion_case =
+                player.read_input(text("fusion_order.choose_case", &[]), vec![1, 2])?;
+            let mut confirm_message = String::new();
+            match fusion_case {
+                1 => confirm_message.push_str(&format!(
+                    "{} in {}",
+                    chain1.name().color(chain1.color()),
+                    chain2.name().color(chain2.color())
+                )),
+                2 => confirm_message.push_str(&format!(
+                    "{} in {}",
+                    chain2.name().color(chain2.color()),
+                    chain1.name().color(chain1.color())
+                )),
+                _ => (),
+            }
+            if !player.get_correct()? {
+                continue;
+            }
+            match fusion_case {
+                1 => {
+                    fuse_order.push(chain1);
+                    fuse_order.push(chain2);
+                }
+                2 => {
+                    fuse_order.push(chain2);
+                    fuse_order.push(chain1);
                 }
+                _ => (),
             }
+            break;
         }
         Ok(fuse_order)
     }
 
     /// Asks the player the order in which order the three or four chains should be fused.
     fn resolve_fusion_order_three_and_four_chains<'a>(
-        player: &Player,
+        player: &mut Player,
         chains: &'a [HotelChain],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
     ) -> Result<Vec<&'a HotelChain>> {
         if chains.len() <= 2 || chains.len() > 4 {
             return Err(miette!(
                 "Unable to resolve fusion order: Not enough/too many chains where provided!"
             ));
         }
+        if let Some(bot) = &player.bot {
+            let decision = bot.choose_surviving_chain(player, chains, bank, hotel_chain_manager);
+            player.bot_reasons.push(decision.reason);
+            return Ok(fuse_into(chains, decision.choice));
+        }
+        // The first of the longest chains survives when the player does not answer
+        let answer = read_fusion_order_three_and_four_chains(player, chains);
+        player.answer_or_default(answer, || {
+            let longest = chains
+                .iter()
+                .map(|chain| hotel_chain_manager.chain_length(chain))
+                .max()
+                .unwrap_or_default();
+            let surviving_chain = chains
+                .iter()
+                .find(|chain| hotel_chain_manager.chain_length(chain) == longest)
+                .unwrap();
+            fuse_into(chains, *surviving_chain)
+        })
+    }
+
+    /// Returns the order in which the chains are fused when the other chains are fused into the
+    /// surviving chain in the order in which they are stored. The surviving chain is the last
+    /// element.
+    fn fuse_into(chains: &[HotelChain], surviving_chain: HotelChain) -> Vec<&HotelChain> {
+        let mut fuse_order: Vec<&HotelChain> = chains
+            .iter()
+            .filter(|chain| **chain != surviving_chain)
+            .collect();
+        fuse_order.push(
+            chains
+                .iter()
+                .find(|chain| **chain == surviving_chain)
+                .unwrap(),
+        );
+        fuse_order
+    }
+
+    /// Asks the player the order in which the three or four chains should be fused.
+    fn read_fusion_order_three_and_four_chains<'a>(
+        player: &Player,
+        chains: &'a [HotelChain],
+    ) -> Result<Vec<&'a HotelChain>> {
         let mut fuse_order = Vec::new();
         loop {
             // Setup variables for user input
@@ -539,19 +777,16 @@ pub mod place_hotel {
                 available_chains_help.push_str(&k.color(v.color()).to_string());
             }
             let surviving_chain = player.read_input(
-                format!(
-                    "Which chain should surivive the fusion? [{}]: ",
-                    available_chains_help
-                ),
+                text("fusion_order.survivor", &[&available_chains_help]),
                 available_chains_identifier,
             )?;
             // Contains the chain that the player decided should survive.
             let surviving_chain_temp = *available_chains.get(&surviving_chain).unwrap();
-            player.print_text_ln(&format!(
-                "Please choose the order in which the hotels should be fused into {}:",
-                surviving_chain_temp
+            player.print_text_ln(&text(
+                "fusion_order.order",
+                &[&surviving_chain_temp
                     .name()
-                    .color(surviving_chain_temp.color())
+                    .color(surviving_chain_temp.color())],
             ))?;
             let mut available_positions: Vec<u32> = if chains.len() == 3 {
                 vec![1, 2]
@@ -577,10 +812,9 @@ pub mod place_hotel {
                     allowed_values_string.push_str(&p.to_string());
                 }
                 let pos = player.read_input(
-                    format!(
-                        "At which position should {} be? [{}]: ",
-                        chain.name().color(chain.color()),
-                        allowed_values_string,
+                    text(
+                        "fusion_order.position",
+                        &[&chain.name().color(chain.color()), &allowed_values_string],
                     ),
                     available_positions.clone(),
                 )?;
@@ -589,7 +823,7 @@ pub mod place_hotel {
             }
             let surviving_chain = surviving_chain.unwrap();
             // Show summary
-            player.print_text_ln("The fusion will take place as followed:")?;
+            player.print_text_ln(&text("fusion_order.summary", &[]))?;
             let chain1 = *determined_positions.get(&1).unwrap();
             let chain2 = *determined_positions.get(&2).unwrap();
             if chains.len() == 3 {
@@ -708,69 +942,189 @@ pub mod place_hotel {
     /// This function uses [`crate::game::game::hotel_chain_manager::HotelChainManager::fuse_chains`] to update
     /// the active chains and the board.
     /// The currently playing player is asked to press enter do start the fusion.
+    /// A summary of the bonuses and the stock trades is sent to every player afterwards.
+    #[allow(clippy::too_many_arguments)]
     fn fuse_two_chains(
         alive: &HotelChain,
         dead: &HotelChain,
         player_index: usize,
-        players: &mut Vec<Player>,
+        players: &mut [Player],
         board: &mut Board,
         hotel_chain_manager: &mut HotelChainManager,
         bank: &mut Bank,
-    ) -> Result<()> {
-        let player = players.get_mut(player_index).unwrap();
-        let player_name = player.name.clone();
+        settings: &Settings,
+    ) -> Result<FusionSummary> {
+        let player_id = players[player_index].id;
         broadcast_others(
-            &format!(
-                "Chain {} is being fused into {}",
-                dead.name().color(dead.color()),
-                alive.name().color(alive.color())
+            &text(
+                "fusion.announce",
+                &[
+                    &dead.name().color(dead.color()),
+                    &alive.name().color(alive.color()),
+                ],
             ),
-            &player_name,
+            player_id,
             players,
         )?;
-        let player = players.get_mut(player_index).unwrap();
-        player.get_enter(&format!(
-            "Press enter to fuse {} into {} ",
-            dead.name().color(dead.color()),
-            alive.name().color(alive.color())
+        broadcast(
+            &bonus_preview_lines(dead, players, bank, hotel_chain_manager)?.join("\n"),
+            players,
+        )?;
+        let player = &mut players[player_index];
+        player.get_enter(&text(
+            "fusion.prompt",
+            &[
+                &dead.name().color(dead.color()),
+                &alive.name().color(alive.color()),
+            ],
         ))?;
+        if let Some(tutorial) = &settings.tutorial {
+            tutorial.explain(
+                TutorialEvent::Fusion {
+                    alive: *alive,
+                    dead: *dead,
+                },
+                players,
+            )?;
+        }
+        let mut summary = FusionSummary {
+            alive: *alive,
+            dead: *dead,
+            bonuses: Vec::new(),
+            stocks: Vec::new(),
+        };
         // 1. Payout the majority shareholder bonuses
         bank.update_largest_shareholders(players);
+        let money_before: Vec<u32> = players.iter().map(|player| player.money).collect();
         bank.give_majority_shareholder_bonuses(players, dead, hotel_chain_manager, true)?;
+        for (player, money_before) in players.iter().zip(money_before) {
+            if let Some(rank) = bank.shareholder_rank(player.id, dead, hotel_chain_manager) {
+                if player.money > money_before {
+                    summary.bonuses.push((
+                        player.colored_name(),
+                        rank,
+                        player.money - money_before,
+                    ));
+                }
+            }
+        }
         // 2. Trade stocks
         let mut index = player_index;
+        // Stores if the keyboard has been handed to another player in hot-seat mode
+        let mut handed_over = false;
         for _i in 0..=players.len() - 1 {
             if index > players.len() - 1 {
                 index = 0;
             }
-            let player = players.get_mut(index).unwrap();
-            let player_name = player.name.clone();
+            let player = &mut players[index];
+            let player_id = player.id;
             // check if player has stocks. If yes let them decide what they would like to do with them
-            println!(
-                "Player {} has {} stocks of hotel {}",
-                player.name,
-                player.owned_stocks.stocks_for_hotel(dead),
-                dead
-            );
             if *player.owned_stocks.stocks_for_hotel(dead) > 0 {
+                if settings.hot_seat && index != player_index {
+                    ui::hand_over(player)?;
+                    handed_over = true;
+                }
                 broadcast_others(
                     &format!(
                         "{} is deciding what they are going to do with their stocks...",
-                        player_name
+                        player.colored_name()
                     ),
-                    &player_name,
+                    player_id,
                     players,
                 )?;
-                let player = players.get_mut(index).unwrap();
-                let stocks_status =
-                    player.handle_fusion_stocks(dead, alive, bank, hotel_chain_manager)?;
-                broadcast_others(&format!("{} did the following with their stocks:\nExchanged: {}\nSold: {}\nKeept: {}", player_name, stocks_status.0, stocks_status.1, stocks_status.2), &player_name, players)?;
+                let player = &mut players[index];
+                // A client that does not answer keeps their stocks
+                let stocks = *player.owned_stocks.stocks_for_hotel(dead);
+                let answer = player.handle_fusion_stocks(dead, alive, bank, hotel_chain_manager);
+                let (exchanged, sold, kept) =
+                    player.answer_or_default(answer, || (0, 0, stocks))?;
+                summary
+                    .stocks
+                    .push((player.colored_name(), exchanged, sold, kept));
             }
             index += 1;
         }
+        if handed_over {
+            ui::hand_over(&players[player_index])?;
+        }
         // 3. Fuse chains on board
         hotel_chain_manager.fuse_chains(alive, dead, board)?;
-        Ok(())
+        broadcast(&summary.lines().join("\n"), players)?;
+        Ok(summary)
+    }
+
+    /// Returns which majority shareholder bonuses will be paid for the chain when it is fused
+    /// at the current prices, see [`preview_bonuses`].
+    fn bonus_preview_lines(
+        dead: &HotelChain,
+        players: &[Player],
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+    ) -> Result<Vec<String>> {
+        let mut lines = vec![text(
+            "fusion.preview",
+            &[
+                &dead.name().color(dead.color()),
+                &Bank::stock_price(hotel_chain_manager, dead),
+            ],
+        )];
+        let bonuses = preview_bonuses(dead, players, bank, hotel_chain_manager)?;
+        if bonuses.is_empty() {
+            lines.push(text("fusion.preview.none", &[]));
+        }
+        for (player_id, rank, bonus) in bonuses {
+            let id = match rank {
+                ShareholderRank::Largest => "fusion.preview.largest",
+                ShareholderRank::SecondLargest => "fusion.preview.second_largest",
+                ShareholderRank::ThirdLargest => "fusion.preview.third_largest",
+            };
+            lines.push(text(
+                id,
+                &[&players[player_id as usize].colored_name(), &bonus],
+            ));
+        }
+        Ok(lines)
+    }
+
+    /// What happened during the fusion of one chain into another.
+    /// Sent to every player after the fusion.
+    pub struct FusionSummary {
+        pub alive: HotelChain,
+        pub dead: HotelChain,
+        /// The colored name of each player that has been paid a bonus, the rank for which the
+        /// bonus has been paid and the bonus
+        pub bonuses: Vec<(String, ShareholderRank, u32)>,
+        /// The colored name of each player that owned stocks of the dead chain and how many of
+        /// them they have exchanged, sold and kept
+        pub stocks: Vec<(String, u32, u32, u32)>,
+    }
+
+    impl FusionSummary {
+        /// Returns the summary as text
+        pub fn lines(&self) -> Vec<String> {
+            let mut lines = vec![text(
+                "fusion.summary",
+                &[
+                    &self.dead.name().color(self.dead.color()),
+                    &self.alive.name().color(self.alive.color()),
+                ],
+            )];
+            if self.bonuses.is_empty() {
+                lines.push(text("fusion.no_bonuses", &[]));
+            }
+            for (name, rank, bonus) in &self.bonuses {
+                let id = match rank {
+                    ShareholderRank::Largest => "fusion.bonus.largest",
+                    ShareholderRank::SecondLargest => "fusion.bonus.second_largest",
+                    ShareholderRank::ThirdLargest => "fusion.bonus.third_largest",
+                };
+                lines.push(text(id, &[name, bonus]));
+            }
+            for (name, exchanged, sold, kept) in &self.stocks {
+                lines.push(text("fusion.stocks", &[name, exchanged, sold, kept]));
+            }
+            lines
+        }
     }
 
     /// The different cases that can hapen when a hotel is placed
@@ -836,18 +1190,27 @@ pub mod place_hotel {
         let surrounding_positions: Vec<Position> = surrounding_positions(origin);
         // Stores the surrounding chains
         let mut surrounding_chains: Vec<HotelChain> = Vec::new();
-        // Stores the surrounding hotels that do not belong to any chain
-        let mut surrounding_hotels: Vec<Position> = Vec::new();
         for position in surrounding_positions {
-            if let Some(value) = board.is_hotel_placed(&position) {
-                match value {
-                    None => surrounding_hotels.push(position),
-                    Some(chain) => {
-                        // Add each chain only once
-                        if !surrounding_chains.contains(&chain) {
-                            surrounding_chains.push(chain);
-                        }
-                    }
+            if let Some(Some(chain)) = board.is_hotel_placed(&position) {
+                // Add each chain only once
+                if !surrounding_chains.contains(&chain) {
+                    surrounding_chains.push(chain);
+                }
+            }
+        }
+        // Stores the hotels that do not belong to any chain and are connected to the origin.
+        // Single hotels can be next to each other because the cards that determine the turn
+        // order are placed without checking their surroundings.
+        let mut surrounding_hotels: Vec<Position> = Vec::new();
+        let mut positions_to_check = vec![*origin];
+        while let Some(position) = positions_to_check.pop() {
+            for neighbour in self::surrounding_positions(&position) {
+                if neighbour == *origin || surrounding_hotels.contains(&neighbour) {
+                    continue;
+                }
+                if let Some(None) = board.is_hotel_placed(&neighbour) {
+                    surrounding_hotels.push(neighbour);
+                    positions_to_check.push(neighbour);
                 }
             }
         }
@@ -874,7 +1237,7 @@ pub mod place_hotel {
                 new_members.push(hotel);
             }
             new_members.push(*origin);
-            return PlaceHotelCase::ExtendsChain(*surrounding_chains.get(0).unwrap(), new_members);
+            return PlaceHotelCase::ExtendsChain(*surrounding_chains.first().unwrap(), new_members);
         }
         // Case 4: Fusion
         let mut cant_fuse = 0;
@@ -913,18 +1276,100 @@ pub mod place_hotel {
     mod tests {
         use miette::Result;
 
+        use rand::{rngs::StdRng, SeedableRng};
+
         use crate::{
             base_game::{
-                bank::Bank,
+                bank::{Bank, ShareholderRank},
                 board::{Board, Position},
                 hotel_chains::HotelChain,
                 player::Player,
+                settings::{Privacy, Settings},
             },
+            bot::BotStrategy,
             game::hotel_chain_manager::HotelChainManager,
             logic::place_hotel::{analyze_position, IllegalPlacement, PlaceHotelCase},
+            simulation::bot_players,
         };
 
-        use super::{longest_chain, surrounding_positions};
+        use super::{fuse_two_chains, longest_chain, surrounding_positions};
+
+        #[test]
+        fn fusion_summary_lists_bonuses_and_stock_trades() -> Result<()> {
+            let settings = Settings::new(false, Privacy::default(), true);
+            let mut rng = StdRng::seed_from_u64(1);
+            let mut players = bot_players(
+                &[
+                    BotStrategy::Greedy,
+                    BotStrategy::Greedy,
+                    BotStrategy::Greedy,
+                ],
+                &settings,
+                &mut rng,
+            );
+            let mut board = Board::new();
+            let mut hotel_chain_manager = HotelChainManager::new();
+            let mut bank = Bank::new();
+            hotel_chain_manager.start_chain(
+                HotelChain::Imperial,
+                vec![Position::new('A', 1), Position::new('A', 2)],
+                &mut board,
+                &mut players[0],
+                &mut bank,
+            )?;
+            hotel_chain_manager.start_chain(
+                HotelChain::Luxor,
+                vec![
+                    Position::new('A', 4),
+                    Position::new('A', 5),
+                    Position::new('A', 6),
+                ],
+                &mut board,
+                &mut players[1],
+                &mut bank,
+            )?;
+            players[0].owned_stocks.set_stocks(&HotelChain::Imperial, 4);
+            players[1].owned_stocks.set_stocks(&HotelChain::Imperial, 2);
+            let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Imperial);
+            let summary = fuse_two_chains(
+                &HotelChain::Luxor,
+                &HotelChain::Imperial,
+                0,
+                &mut players,
+                &mut board,
+                &mut hotel_chain_manager,
+                &mut bank,
+                &settings,
+            )?;
+            assert_eq!(
+                summary.bonuses,
+                vec![
+                    (
+                        players[0].colored_name(),
+                        ShareholderRank::Largest,
+                        price * 10
+                    ),
+                    (
+                        players[1].colored_name(),
+                        ShareholderRank::SecondLargest,
+                        price * 5
+                    ),
+                ]
+            );
+            // Only the players that owned stocks of the dead chain decide about them
+            assert_eq!(summary.stocks.len(), 2);
+            for (name, exchanged, sold, kept) in &summary.stocks {
+                let stocks = if *name == players[0].colored_name() {
+                    4
+                } else {
+                    2
+                };
+                assert_eq!(exchanged + sold + kept, stocks);
+            }
+            assert_eq!(summary.lines().len(), 5);
+            assert!(!hotel_chain_manager.chain_status(&HotelChain::Imperial));
+            Ok(())
+        }
         #[test]
         fn surrounding_positions_correct() -> Result<()> {
             let origin = Position::new('B', 2);
@@ -946,7 +1391,7 @@ pub mod place_hotel {
             let mut board = Board::new();
             let mut bank = Bank::new();
             let mut hotel_chain_manager = HotelChainManager::new();
-            let mut players = vec![
+            let mut players = [
                 Player::new(vec![], 0, false, String::from("Player 1")),
                 Player::new(vec![], 1, false, String::from("Player 2")),
             ];
@@ -1018,10 +1463,10 @@ pub mod place_hotel {
             let chain1 = vec![Position::new('H', 3), Position::new('H', 4)];
             let chain2 = vec![Position::new('G', 6), Position::new('H', 6)];
             for chain in &chain1 {
-                board.place_hotel(&chain)?;
+                board.place_hotel(chain)?;
             }
             for chain in &chain2 {
-                board.place_hotel(&chain)?;
+                board.place_hotel(chain)?;
             }
             hotel_chain_manager.start_chain(
                 HotelChain::Airport,
@@ -1076,6 +1521,23 @@ pub mod place_hotel {
             Ok(())
         }
 
+        #[test]
+        fn new_chain_includes_connected_hotels() -> Result<()> {
+            let mut board = Board::new();
+            let hotel_chain_manager = HotelChainManager::new();
+            // Single hotels that are next to each other, like the cards drawn at the start
+            board.place_hotel(&Position::new('A', 1))?;
+            board.place_hotel(&Position::new('A', 2))?;
+            match analyze_position(&Position::new('A', 3), &board, &hotel_chain_manager) {
+                PlaceHotelCase::NewChain(positions) => {
+                    assert_eq!(positions.len(), 3);
+                    assert!(positions.contains(&Position::new('A', 1)));
+                }
+                _ => panic!("Placing the hotel should start a new chain"),
+            }
+            Ok(())
+        }
+
         fn type_name(place_hotel_case: &PlaceHotelCase) -> String {
             match place_hotel_case {
                 PlaceHotelCase::SingleHotel => String::from("SingleHotel"),
@@ -1189,13 +1651,111 @@ mod tests {
             board::{Board, Position},
             hotel_chains::HotelChain,
             player::Player,
-            settings::Settings,
+            settings::{Privacy, Settings},
             ui,
         },
         game::hotel_chain_manager::HotelChainManager,
-        logic::check_end_condition,
+        logic::{can_game_continue, check_end_condition, remaining_players, resign},
     };
 
+    #[test]
+    fn resigned_player_leaves_the_game() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(
+                vec![Position::new('E', 1), Position::new('F', 3)],
+                0,
+                false,
+                String::from("Player 1"),
+            ),
+            Player::new(vec![], 1, false, String::from("Player 2")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Luxor,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        bank.buy_stock(&hotel_chain_manager, &HotelChain::Luxor, &mut players[0])?;
+        bank.update_largest_shareholders(&players);
+        let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Luxor);
+        let money = players[0].money;
+        resign(0, &mut players, &mut bank, &hotel_chain_manager)?;
+        assert!(players[0].resigned);
+        assert!(players[0].analyzed_cards.is_empty());
+        assert_eq!(players[0].owned_stocks.total(), 0);
+        assert_eq!(players[0].money, money + price * 2);
+        assert_eq!(
+            *bank.stocks_available(&HotelChain::Luxor, &hotel_chain_manager),
+            25
+        );
+        assert!(bank
+            .largest_shareholders
+            .largest(&HotelChain::Luxor)
+            .is_empty());
+        assert_eq!(remaining_players(&players), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn can_game_continue_works() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+        // Two safe chains
+        for (c, chain) in [('A', HotelChain::Luxor), ('C', HotelChain::Airport)] {
+            let mut positions = Vec::new();
+            for i in 1..=12 {
+                positions.push(Position::new(c, i));
+            }
+            hotel_chain_manager.start_chain(
+                chain,
+                positions,
+                &mut board,
+                &mut player,
+                &mut bank,
+            )?;
+        }
+        // Player only has a card that would fuse two safe chains
+        let players = vec![Player::new(
+            vec![Position::new('B', 1)],
+            0,
+            false,
+            String::from("Player 1"),
+        )];
+        assert!(!can_game_continue(
+            &players,
+            &board,
+            &hotel_chain_manager,
+            false
+        ));
+        // Player can redraw
+        assert!(can_game_continue(
+            &players,
+            &board,
+            &hotel_chain_manager,
+            true
+        ));
+        // Player has a card that can be played
+        let players = vec![Player::new(
+            vec![Position::new('B', 1), Position::new('G', 5)],
+            0,
+            false,
+            String::from("Player 1"),
+        )];
+        assert!(can_game_continue(
+            &players,
+            &board,
+            &hotel_chain_manager,
+            false
+        ));
+        Ok(())
+    }
+
     #[test]
     fn is_end_game_condition_met_working() -> Result<()> {
         let mut board = Board::new();
@@ -1205,7 +1765,7 @@ mod tests {
         let mut positions = Vec::new();
         // Check no end condition is met
         assert!(check_end_condition(&board, &hotel_chain_manager).is_none());
-        for c in vec!['A', 'B', 'C', 'D'] {
+        for c in ['A', 'B', 'C', 'D'] {
             for i in 1..=12 {
                 positions.push(Position::new(c, i));
             }
@@ -1221,7 +1781,7 @@ mod tests {
         assert!(check_end_condition(&board, &hotel_chain_manager).is_some());
         let mut board = Board::new();
         let mut hotel_chain_manager = HotelChainManager::new();
-        for c in vec!['A', 'C', 'E', 'G', 'I'] {
+        for c in ['A', 'C', 'E', 'G', 'I'] {
             let mut positions = Vec::new();
             for i in 1..=12 {
                 positions.push(Position::new(c, i));
@@ -1244,9 +1804,9 @@ mod tests {
         }
         ui::print_main_ui_console(
             Some(&player),
-            Some(&player.name),
+            Some(&player.colored_name()),
             &board,
-            &Settings::new(false, false, false),
+            &Settings::new(false, Privacy::Open, false),
             None,
             &bank,
             &hotel_chain_manager,
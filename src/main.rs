@@ -1,125 +1,820 @@
-/// Contains all base functionalities that the game needs to work.
-/// This includes all basic data types and the playfield, some game logic and more.
-mod base_game;
-/// Contains functions that help to read and parse the user input
-mod data_stream;
-/// Contains some code to print the board without that the game has to be started
-mod demo;
-/// Contains all functionalities that are required to play the game. This includes the setting up
-/// of new games, round, turn and player managemnt and more.
-mod game;
-/// Contains the most part of the game logic.
-/// Does not contain the logic of the different managers. Their logic is implemented in their main impl block.
-mod logic;
-/// Contains all functionalities required to play the game fia lan.
-mod network;
-/// Contains some functions that dont fit in another module.
-mod utils;
-
-use base_game::settings::Settings;
-use clap::{App, Arg};
-use demo::test_things;
-use game::{print_info_card, GameManager};
-use network::{start_client, start_server};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    net::IpAddr,
+    num::NonZeroU64,
+};
+
+use acquire_rs::{
+    base_game::settings::{Privacy, RulePreset, Settings, TieHandling, TurnOrder},
+    bot::{BotPersonality, BotStrategy},
+    config::{hex_color, Config},
+    demo::test_things,
+    event_stream::EventStream,
+    game::{print_info_card, GameManager},
+    history::{lifetime_statistics, print_lifetime_statistics, read_results, DEFAULT_HISTORY_FILE},
+    http_server::{start_http_server, DEFAULT_HTTP_PORT},
+    journal::{Journal, DEFAULT_JOURNAL_FILE},
+    ladder::{print_ladder, run_ladder},
+    locale::{set_language, text, Language},
+    network::{start_client, start_server},
+    replay::{branch_replay, check_replay, record_replay, ReplayHeader},
+    scenario::{run_scenario, scenario_files, SCENARIO_DIR},
+    simulation::{print_statistics, simulate_bots},
+    tutorial::Tutorial,
+    viewer::ReplayViewer,
+};
+use clap::{App, AppSettings, Arg, ArgMatches};
+use miette::{miette, IntoDiagnostic};
+use rand::Rng;
 
 fn main() -> miette::Result<()> {
+    let config = Config::load()?;
+    let http_port = DEFAULT_HTTP_PORT.to_string();
+    let lan_port = config.port.unwrap_or(11511).to_string();
+    let rules = config.rules.unwrap_or_default().name();
+    let language = config.language.unwrap_or_default().code();
+    let config_color = config
+        .color
+        .map(|color| format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2));
+    let defaults = Defaults {
+        name: config.name.as_deref(),
+        color: config_color.as_deref(),
+        rules,
+        language,
+    };
     let matches = App::new("Acquire_rs")
         .version("1.0.0")
         .author("LMH01")
-        .about("The board game Acquire fia command line in Rust")
-        .arg(Arg::new("players")
-            .short('p')
-            .long("players")
-            .help("The number of players")
-            .value_name("NUMBER")
-            .possible_values(["2", "3", "4", "5", "6"])
-            .required_unless_present_any(&["lan_client", "demo", "demo_type", "info_card"])
-            .default_value_if("demo", None, Some("2")))
-        .arg(Arg::new("hide_extra_info")
-            .short('h')
-            .long("hide-extra-info")
-            .help("Use to hide additional information to the player")
-            .long_help("Use to hide additional information to the player. This will hide information that the player would normally have to give the game more variation. The following is hidden:\n - Who the largest or second largest shareholders are\nWhen this flag is not set a little star next to your bought stocks displayes if you are the largest (golden star) or second largest shareholder for that chain (silver star)."))
-        .arg(Arg::new("lan_client")
-            .long("lan-client")
-            .help("Use to play the game on multiplayer per lan and join a server")
-            .conflicts_with_all(&["hide_extra_info", "players", "skip_dialogues", "lan_server"]))
-        .arg(Arg::new("lan_server")
-            .long("lan-server")
-            .help("Start the game as server")
-            .conflicts_with_all(&["lan_client"]))
-        .arg(Arg::new("name")
-            .short('n')
-            .long("name")
-            .help("The name of the player")
-            .long_help("The name of the player. This can also be used to set the player name of the player that hosts the game.")
-            .takes_value(true)
-            .requires("lan_server"))
-        .arg(Arg::new("ip")
-            .long("ip")
-            .help("The ip and port to which to connect")
-            .long_help("The ip and port to wich to connect. Example: 192.168.178.10:11511")
-            .requires("lan_client")
-            .takes_value(true)
-            .value_name("IP")
-            .conflicts_with("lan_server"))
-        .arg(Arg::new("port")
-            .long("port")
-            .help("Overwrite the port at wich the game should be hosted")
-            .long_help("Overwrite the port at wich the game should be hosted\nDefault is 11511")
-            .default_value_if("lan_server", None, Some("11511"))
-            .requires("lan_server")
-            )
-        .arg(Arg::new("info_card")
-            .long("info-card")
-            .help("Print the stock info card")
-            .long_help("Print the stocks info card. This card displayes information on how much a stock is worth depending on the length of the hotel chain")
-            .exclusive(true))
-        .arg(Arg::new("small_board")
-            .short('s')
-            .long("small-board")
-            .help("Use to make the board smaller"))
-        .arg(Arg::new("skip_dialogues")
-            .long("skip-dialogues")
-            .help("Use to always skip some dialogues")
-            .long_help("Use to always skip some dialogues. Dialogues that are skipped include: The confirmation what card the player drew."))
-        .arg(Arg::new("demo")
-            .long("demo")
-            .help("Use to run some demo on how the game looks like instead of the main game")
-            .conflicts_with_all(&["lan_client", "lan_server"]))
-        .arg(Arg::new("demo_type")
-            .long("demo-type")
-            .help("Set what demo type to run")
-            .default_value_if("demo", None, Some("0"))
-            .requires("demo"))
+        .about("The board game Acquire via command line in Rust")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(game_args(App::new("play")
+            .about("Play a game on this pc, alone against bots or with others on the same keyboard")
+            .arg(players_arg()
+                .required_unless_present("tutorial")
+                .default_value_if("tutorial", None, Some("2")))
+            .arg(Arg::new("hot_seat")
+                .long("hot-seat")
+                .help("Use to hide the private information of the players when playing on one pc")
+                .long_help("Use to hide the private information of the players when playing on one pc. The console is cleared between turns and the next player has to confirm that they have taken the keyboard before their money, cards and stocks are shown. The cards a player draws are hidden again as soon as they have confirmed that they have seen them."))
+            .arg(Arg::new("tutorial")
+                .long("tutorial")
+                .help("Use to play a guided first game against a bot")
+                .long_help("Use to play a guided first game against a bot. The bot plays slowly and the game explains what happens the first time something new happens, for example when a chain is founded or when two chains are fused. When no number of players is set you play alone against one bot."))
+            .arg(Arg::new("debug_console")
+                .long("debug-console")
+                .help("Use to be able to open a debug console at the card prompt")
+                .long_help("Use to be able to open a debug console by entering : instead of a card. The console shows the board, the chains, the bank and the players and can place hotels and change the money and stocks of the players without abiding by the rules, so that the situation of a rule bug can be reproduced. Enter an empty line to return to the game.")), &defaults)
+            .mut_arg("bots", |arg| arg.default_value_if("tutorial", None, Some("1"))))
+        .subcommand(game_args(App::new("host")
+            .about("Host a game that other players join via lan")
+            .arg(players_arg()
+                .required_unless_present("recover"))
+            .arg(name_arg(&defaults)
+                .long_help("The name of the player that hosts the game"))
+            .arg(Arg::new("port")
+                .long("port")
+                .help("Overwrite the port at which the game should be hosted")
+                .long_help("Overwrite the port at which the game should be hosted\nDefault is 11511 or the port of the configuration file")
+                .takes_value(true)
+                .value_name("PORT")
+                .default_value(&lan_port)
+                .validator(|value| value.parse::<u16>()))
+            .arg(Arg::new("journal")
+                .long("journal")
+                .help("Set the file to which the server writes the journal of the game")
+                .long_help("Set the file to which the server writes the journal of the game. After each action the position of the game is appended to the file, so that the game can be recovered with --recover when the server has stopped. The journal is emptied when a new game starts and deleted when the game has ended.")
+                .takes_value(true)
+                .value_name("FILE")
+                .default_value(DEFAULT_JOURNAL_FILE))
+            .arg(Arg::new("recover")
+                .long("recover")
+                .help("Recover the game of the journal after the server has stopped")
+                .long_help("Recover the game of the journal after the server has stopped. The game continues after the last completed action in the journal once all players that have played on a client have connected again with the same name. The server has to be started with the same rules and bot settings as before.")
+                .conflicts_with_all(&["players", "name"]))
+            .arg(Arg::new("input_timeout")
+                .long("input-timeout")
+                .help("Set in how many seconds the clients have to answer")
+                .long_help("Set in how many seconds the clients have to answer when they are asked for input. A client that does not answer in time while they have to choose a card leaves the game and their stocks are sold to the bank, so that the others can go on playing. A client that should only press enter continues. At the other prompts the safe answer is taken for them, they buy and sell nothing, keep their stocks and take the first chain that they are offered. Clients that lose the connection while they choose a card leave the game as well.")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .validator(|value| value.parse::<NonZeroU64>()))
+            .arg(Arg::new("remind_after")
+                .long("remind-after")
+                .help("Set after how many seconds the prompt is sent again to clients that have not answered")
+                .long_help("Set after how many seconds the prompt is sent again to clients that have not answered. The prompt is shown again with the note (still waiting) each time the interval has passed, so that players that have missed the prompt, because it has scrolled out of view for example, notice that the game waits for them.")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .validator(|value| value.parse::<NonZeroU64>())), &defaults))
+        .subcommand(App::new("join")
+            .about("Join a game that is hosted via lan")
+            .arg(Arg::new("ip")
+                .long("ip")
+                .help("The ip and port to which to connect")
+                .long_help("The ip and port to which to connect. Example: 192.168.178.10:11511")
+                .takes_value(true)
+                .value_name("IP"))
+            .arg(name_arg(&defaults)
+                .long_help("The name of the player. When the name is already taken by another player a number is appended to it."))
+            .arg(privacy_arg()
+                .long_help("Set how much information is shown to you. The host may have chosen a stricter level, then the level of the host is used.\n - open: A little star next to your bought stocks displays if you are the largest (golden star) or second largest shareholder for that chain (silver star).\n - hidden: The stars are hidden.\n - strict: The stats table and the stars are hidden. Only the names of the largest shareholders are shown."))
+            .arg(small_board_arg())
+            .arg(plain_arg())
+            .arg(Arg::new("notify_after")
+                .long("notify-after")
+                .help("Set after how many seconds of waiting the bell is rung when it is your turn")
+                .long_help("Set after how many seconds of waiting the terminal bell is rung when it is your turn, so that you notice it when you do something else in the meantime. Use 0 to ring the bell each time you have to enter something.")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .validator(|value| value.parse::<u64>())
+                .default_value("20"))
+            .arg(Arg::new("banner")
+                .long("banner")
+                .help("Use to print a banner as well when the bell is rung"))
+            .arg(Arg::new("binary")
+                .long("binary")
+                .help("Use to receive the messages of the host as binary frames")
+                .long_help("Use to receive the messages of the host as binary frames instead of lines of text. Each frame is prefixed with its length, so that messages can contain line breaks, and it needs less bandwidth. Hosts of older versions do not understand the option and keep sending lines.")))
+        .subcommand(App::new("simulate")
+            .about("Let bots play against each other and show how well each strategy performs")
+            .arg(strategies_arg()
+                .long_help("The strategies of the bots separated by commas, each strategy takes one seat. Between 2 and 6 strategies can be used, a strategy can be used multiple times. Example: greedy,heuristic,random"))
+            .arg(Arg::new("games")
+                .long("games")
+                .help("The number of games that are simulated")
+                .takes_value(true)
+                .value_name("NUMBER")
+                .default_value("100")
+                .validator(|value| value.parse::<u32>()))
+            .arg(Arg::new("seed")
+                .long("seed")
+                .help("The seed that is used to deal the cards and for the random decisions of the bots")
+                .long_help("The seed that is used to deal the cards and for the random decisions of the bots. The same seed leads to the same results. When no seed is set a random seed is used.")
+                .takes_value(true)
+                .value_name("SEED")
+                .validator(|value| value.parse::<u64>()))
+            .arg(Arg::new("export")
+                .long("export")
+                .help("Write the decisions of the bots to a file")
+                .long_help("Write the decisions of the bots to a file. Each line of the file is a json object that contains the game state before the decision, the decision and the final result of the bot. The file can be used to train new bots.")
+                .takes_value(true)
+                .value_name("FILE"))
+            .arg(bot_playouts_arg()))
+        .subcommand(App::new("ladder")
+            .about("Let all bot strategies play against each other and rate them")
+            .long_about("Let all bot strategies play against each other and rate them. Each pair of strategies plays one two-player game per seed from each seat. The results are shown in a cross-table together with Elo-like ratings, so improvements of a strategy can be measured over time.")
+            .arg(Arg::new("strategies")
+                .long("strategies")
+                .help("The strategies that take part, by default all strategies take part")
+                .takes_value(true)
+                .value_name("STRATEGIES")
+                .use_delimiter(true)
+                .multiple_values(true)
+                .possible_values(["greedy", "heuristic", "monte-carlo", "random"]))
+            .arg(Arg::new("seeds")
+                .long("seeds")
+                .help("The number of seeds each pair of strategies plays")
+                .takes_value(true)
+                .value_name("NUMBER")
+                .default_value("10")
+                .validator(|value| value.parse::<u32>()))
+            .arg(Arg::new("seed")
+                .long("seed")
+                .help("The first seed, the following seeds are counted up from it")
+                .takes_value(true)
+                .value_name("SEED")
+                .default_value("1")
+                .validator(|value| value.parse::<u64>()))
+            .arg(bot_playouts_arg()))
+        .subcommand(App::new("replay")
+            .about("Record, check, view and continue replays of games between bots")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(App::new("record")
+                .about("Let bots play a game and record every decision in a replay file")
+                .long_about("Let bots play a game and record every decision in a replay file. The same seed and bots always lead to the same game, so the replay can be checked with `replay check` to find out if a change of the code has changed the game.")
+                .arg(Arg::new("file")
+                    .help("The file to which the replay is written")
+                    .value_name("FILE")
+                    .required(true))
+                .arg(strategies_arg())
+                .arg(Arg::new("seed")
+                    .long("seed")
+                    .help("The seed that is used to deal the cards and for the random decisions of the bots")
+                    .takes_value(true)
+                    .value_name("SEED")
+                    .default_value("1")
+                    .validator(|value| value.parse::<u64>()))
+                .arg(Arg::new("rules")
+                    .long("rules")
+                    .help("Set the rules with which the game is played")
+                    .takes_value(true)
+                    .value_name("PRESET")
+                    .possible_values(["classic", "1999", "house"])
+                    .default_value("classic"))
+                .arg(bot_playouts_arg()))
+            .subcommand(App::new("check")
+                .about("Play the games of replay files again and fail when a game diverges from its replay")
+                .arg(Arg::new("files")
+                    .help("The replay files that are checked")
+                    .value_name("FILES")
+                    .multiple_values(true)
+                    .required(true)))
+            .subcommand(App::new("view")
+                .about("Step through the decisions of a replay and see the board, market and money after each of them")
+                .arg(Arg::new("file")
+                    .help("The replay file that is shown")
+                    .value_name("FILE")
+                    .required(true)))
+            .subcommand(result_args(player_args(App::new("branch")
+                .about("Continue the game of a replay from a turn with humans or bots")
+                .long_about("Continue the game of a replay from a turn with humans or bots. The game is played like it has been recorded until the turn, then the seats that are set with --humans are taken by humans and the game continues as a normal game. This can be used to find out what would have happened if another decision had been made.")
+                .arg(Arg::new("file")
+                    .help("The replay file from which the game is continued")
+                    .value_name("FILE")
+                    .required(true))
+                .arg(Arg::new("turn")
+                    .long("turn")
+                    .help("The turn with which the game continues, like it is shown by `replay view`")
+                    .takes_value(true)
+                    .value_name("TURN")
+                    .required(true)
+                    .validator(|value| value.parse::<u32>()))
+                .arg(Arg::new("humans")
+                    .long("humans")
+                    .help("The seats that are taken by humans, the first seat is 1. The other seats are kept by their bot")
+                    .takes_value(true)
+                    .value_name("SEATS")
+                    .use_delimiter(true)
+                    .multiple_values(true)
+                    .validator(|value| value.parse::<u32>())), &defaults))))
+        .subcommand(App::new("scenario")
+            .about("Play scenarios from their position and check that each game turns out like it is expected")
+            .long_about("Play scenarios from their position and check that each game turns out like it is expected. A scenario is a json file that describes the position of a game, the actions that are applied one after another and what is expected of the game after each of them. Directories are searched for .json files, all scenarios are played even when one of them fails.")
+            .arg(Arg::new("paths")
+                .help("The scenario files and directories that are played")
+                .value_name("PATHS")
+                .multiple_values(true)
+                .default_value(SCENARIO_DIR)))
+        .subcommand(App::new("stats")
+            .about("Show the win rates and averages of all players that have played on this pc")
+            .arg(Arg::new("file")
+                .long("file")
+                .help("The file from which the results are read")
+                .takes_value(true)
+                .value_name("FILE")
+                .default_value(DEFAULT_HISTORY_FILE)))
+        .subcommand(App::new("http-server")
+            .about("Run a dedicated server for games that web or mobile clients play through http and json")
+            .long_about("Host games that web or mobile clients play through http and json. A game is created with POST /games, its state is read with GET /games/<id> and the actions of the players are sent with POST /games/<id>/actions together with the token of their seat. The decisions and actions use the same json objects as the replays. The metrics of the server can be scraped by Prometheus from GET /metrics.")
+            .arg(Arg::new("port")
+                .long("port")
+                .help("The port on which the server listens")
+                .takes_value(true)
+                .value_name("PORT")
+                .default_value(&http_port)
+                .validator(|value| value.parse::<u16>()))
+            .arg(Arg::new("address")
+                .long("address")
+                .help("The address on which the server listens")
+                .long_help("The address on which the server listens. Use 0.0.0.0 to let clients on other devices connect.")
+                .takes_value(true)
+                .value_name("ADDRESS")
+                .default_value("127.0.0.1")
+                .validator(|value| value.parse::<IpAddr>())))
+        .subcommand(App::new("info-card")
+            .about("Print the stock info card")
+            .long_about("Print the stocks info card. This card displays information on how much a stock is worth depending on the length of the hotel chain"))
+        .subcommand(App::new("demo")
+            .about("Run some demo on how the game looks like instead of the main game")
+            .arg(players_arg()
+                .default_value("2"))
+            .arg(Arg::new("demo_type")
+                .long("type")
+                .help("Set what demo type to run")
+                .takes_value(true)
+                .default_value("0")))
         .get_matches();
     set_terminal_output();
+    set_language(config.language.unwrap_or_default());
     print_welcome();
-    let settings = Settings::new(
-        matches.is_present("small_board"),
-        matches.is_present("hide_extra_info"),
+    match matches.subcommand() {
+        Some(("play", matches)) => {
+            let mut settings = game_settings(matches, &config)?;
+            settings.hot_seat = matches.is_present("hot_seat");
+            settings.debug_console = matches.is_present("debug_console");
+            if matches.is_present("tutorial") {
+                settings.tutorial = Some(Tutorial::new());
+            }
+            let mut game_manager = GameManager::new(
+                matches.value_of("players").unwrap().parse().unwrap(),
+                settings,
+            )?;
+            game_manager.start_game()?;
+        }
+        Some(("host", matches)) => {
+            let mut settings = game_settings(matches, &config)?;
+            settings.journal = matches
+                .value_of("journal")
+                .map(|path| Journal::new(path.to_string()));
+            settings.input_timeout = matches
+                .value_of("input_timeout")
+                .map(|seconds| seconds.parse().unwrap());
+            settings.remind_after = matches
+                .value_of("remind_after")
+                .map(|seconds| seconds.parse().unwrap());
+            start_server(matches, settings)?
+        }
+        Some(("join", matches)) => start_client(
+            matches,
+            matches.is_present("small_board") || config.small_board,
+            matches.is_present("plain") || config.plain,
+        )?,
+        Some(("simulate", matches)) => {
+            let strategies = strategies_of(matches);
+            let games = matches.value_of("games").unwrap().parse().unwrap();
+            let seed = match matches.value_of("seed") {
+                Some(seed) => seed.parse().unwrap(),
+                None => rand::thread_rng().gen(),
+            };
+            let settings = bot_settings(matches);
+            let mut export = match matches.value_of("export") {
+                Some(path) => Some(BufWriter::new(File::create(path).into_diagnostic()?)),
+                None => None,
+            };
+            let statistics = simulate_bots(
+                &strategies,
+                games,
+                seed,
+                &settings,
+                export.as_mut().map(|export| export as &mut dyn Write),
+            )?;
+            if let Some(mut export) = export {
+                export.flush().into_diagnostic()?;
+            }
+            print_statistics(&statistics, games, seed);
+        }
+        Some(("ladder", matches)) => {
+            let strategies: Vec<BotStrategy> = match matches.values_of("strategies") {
+                Some(_) => strategies_of(matches),
+                None => BotStrategy::iterator().copied().collect(),
+            };
+            let seeds = matches.value_of("seeds").unwrap().parse().unwrap();
+            let seed = matches.value_of("seed").unwrap().parse().unwrap();
+            let ladder = run_ladder(&strategies, seeds, seed, &bot_settings(matches))?;
+            print_ladder(&ladder, seed);
+        }
+        Some(("replay", matches)) => match matches.subcommand() {
+            Some(("record", matches)) => {
+                let header = ReplayHeader {
+                    strategies: strategies_of(matches),
+                    seed: matches.value_of("seed").unwrap().parse().unwrap(),
+                    rule_preset: RulePreset::from_name(matches.value_of("rules").unwrap()).unwrap(),
+                    bot_playouts: matches.value_of("bot_playouts").unwrap().parse().unwrap(),
+                };
+                let path = matches.value_of("file").unwrap();
+                let mut replay = record_replay(&header)?.join("\n");
+                replay.push('\n');
+                fs::write(path, replay).into_diagnostic()?;
+                println!("The replay has been written to {}", path);
+            }
+            Some(("check", matches)) => {
+                for path in matches.values_of("files").unwrap() {
+                    let replay = fs::read_to_string(path).into_diagnostic()?;
+                    check_replay(&replay).map_err(|error| error.wrap_err(path.to_string()))?;
+                    println!("{} has been reproduced", path);
+                }
+            }
+            Some(("view", matches)) => {
+                let path = matches.value_of("file").unwrap();
+                let replay = fs::read_to_string(path).into_diagnostic()?;
+                ReplayViewer::new(&replay)
+                    .map_err(|error| error.wrap_err(path.to_string()))?
+                    .run()?;
+            }
+            Some(("branch", matches)) => {
+                let path = matches.value_of("file").unwrap();
+                let replay = fs::read_to_string(path).into_diagnostic()?;
+                let humans: Vec<u32> = matches
+                    .values_of("humans")
+                    .map(|seats| seats.map(|seat| seat.parse().unwrap()).collect())
+                    .unwrap_or_default();
+                let mut settings = player_settings(matches, &config);
+                set_result_settings(&mut settings, matches)?;
+                let mut game_manager = branch_replay(
+                    &replay,
+                    matches.value_of("turn").unwrap().parse().unwrap(),
+                    &humans,
+                    settings,
+                )
+                .map_err(|error| error.wrap_err(path.to_string()))?;
+                game_manager.continue_game()?;
+            }
+            _ => unreachable!("A replay subcommand is required"),
+        },
+        Some(("scenario", matches)) => {
+            let paths: Vec<&str> = matches.values_of("paths").unwrap().collect();
+            let files = scenario_files(&paths)?;
+            let mut failed = 0;
+            for file in &files {
+                let result = fs::read_to_string(file)
+                    .into_diagnostic()
+                    .and_then(|scenario| run_scenario(&scenario));
+                match result {
+                    Ok(()) => println!("{} has turned out as expected", file.display()),
+                    Err(err) => {
+                        failed += 1;
+                        println!("{:?}", err.wrap_err(file.display().to_string()));
+                    }
+                }
+            }
+            if failed > 0 {
+                return Err(miette!(
+                    "{} of {} scenarios have not turned out as expected",
+                    failed,
+                    files.len()
+                ));
+            }
+        }
+        Some(("stats", matches)) => {
+            let path = matches.value_of("file").unwrap();
+            let results = match fs::read_to_string(path) {
+                Ok(history) => read_results(&history)?,
+                Err(_) => Vec::new(),
+            };
+            print_lifetime_statistics(&lifetime_statistics(&results), path);
+        }
+        Some(("http-server", matches)) => start_http_server(
+            matches.value_of("address").unwrap().parse().unwrap(),
+            matches.value_of("port").unwrap().parse().unwrap(),
+        )?,
+        Some(("info-card", _)) => print_info_card(),
+        Some(("demo", matches)) => test_things(
+            matches,
+            Settings::new(config.small_board, Privacy::default(), false),
+        )?,
+        _ => unreachable!("A subcommand is required"),
+    }
+    Ok(())
+}
+
+/// The defaults of the options that are read from the configuration file
+struct Defaults<'a> {
+    name: Option<&'a str>,
+    color: Option<&'a str>,
+    rules: &'a str,
+    language: &'a str,
+}
+
+fn players_arg<'a>() -> Arg<'a> {
+    Arg::new("players")
+        .short('p')
+        .long("players")
+        .help("The number of players")
+        .takes_value(true)
+        .value_name("NUMBER")
+        .possible_values(["2", "3", "4", "5", "6"])
+}
+
+fn name_arg<'a>(defaults: &Defaults<'a>) -> Arg<'a> {
+    let arg = Arg::new("name")
+        .short('n')
+        .long("name")
+        .help("The name of the player")
+        .takes_value(true);
+    match defaults.name {
+        Some(name) => arg.default_value(name),
+        None => arg,
+    }
+}
+
+fn privacy_arg<'a>() -> Arg<'a> {
+    Arg::new("privacy")
+        .long("privacy")
+        .help("Set how much information is shown to the players")
+        .takes_value(true)
+        .value_name("LEVEL")
+        .possible_values(["open", "hidden", "strict"])
+        .default_value("open")
+}
+
+fn small_board_arg<'a>() -> Arg<'a> {
+    Arg::new("small_board")
+        .short('s')
+        .long("small-board")
+        .help("Use to make the board smaller")
+}
+
+fn plain_arg<'a>() -> Arg<'a> {
+    Arg::new("plain")
+        .long("plain")
+        .help("Use to show the text without colors, stars and box-drawing lines")
+        .long_help("Use to show the text without colors, stars and box-drawing lines, so that screen readers and dumb terminals can be used. The shareholder stars are replaced by (L) for the largest, (S) for the second largest and (T) for the third largest shareholder and empty positions of the board are shown as dots.")
+}
+
+fn strategies_arg<'a>() -> Arg<'a> {
+    Arg::new("strategies")
+        .long("strategies")
+        .help("The strategies of the bots, each strategy takes one seat")
+        .takes_value(true)
+        .value_name("STRATEGIES")
+        .use_delimiter(true)
+        .multiple_values(true)
+        .possible_values(["greedy", "heuristic", "monte-carlo", "random"])
+        .default_value("greedy,heuristic,random")
+}
+
+fn bot_playouts_arg<'a>() -> Arg<'a> {
+    Arg::new("bot_playouts")
+        .long("bot-playouts")
+        .help("Set how many playouts the monte-carlo bots play for each decision")
+        .long_help("Set how many playouts the monte-carlo bots play for each decision. More playouts make the bots stronger but slower.")
+        .takes_value(true)
+        .value_name("NUMBER")
+        .default_value("100")
+        .validator(|value| value.parse::<u32>())
+}
+
+/// Adds the options that change how the game is shown to the players that play on this pc
+fn player_args<'a>(app: App<'a>, defaults: &Defaults<'a>) -> App<'a> {
+    let mut color = Arg::new("color")
+        .long("color")
+        .help("Set the color in which your name is printed")
+        .long_help("Set the color in which your name is printed as hex code like #ff8700. In a local game it is the color of the first player, when a lan game is hosted it is the color of the host.")
+        .takes_value(true)
+        .value_name("COLOR")
+        .validator(|value| hex_color(value).ok_or("The color has to be a hex code like #ff8700"));
+    if let Some(default) = defaults.color {
+        color = color.default_value(default);
+    }
+    app.arg(privacy_arg()
+            .long_help("Set how much information is shown to the players. When played via lan clients can choose a stricter level for themselves.\n - open: A little star next to your bought stocks displays if you are the largest (golden star) or second largest shareholder for that chain (silver star).\n - hidden: The stars are hidden.\n - strict: The stats table and the stars are hidden. Only the names of the largest shareholders are shown."))
+        .arg(small_board_arg())
+        .arg(plain_arg())
+        .arg(color)
+        .arg(Arg::new("skip_dialogues")
+            .long("skip-dialogues")
+            .alias("fast")
+            .help("Use to play in fast mode without dialogues")
+            .long_help("Use to play in fast mode without dialogues, for groups that know the game well. The drawn cards and prompts that only wait for enter are skipped, confirmations are answered with yes and the summaries of stock transactions are shown in one line. When the game is played in hot-seat mode the players still press enter before private information is hidden. Can also be set with --fast."))
+        .arg(Arg::new("advisor")
+            .long("advisor")
+            .help("Use to show how much each card would change your position")
+            .long_help("Use to show how much each card would change your position. Below your cards the advisor shows by how much your lead over the strongest other player would change when the card is played now. When a lan game is hosted the advisor is shown to all players, clients can not enable it themselves."))
+        .arg(Arg::new("language")
+            .long("language")
+            .help("Set the language in which the messages of the game are shown")
+            .long_help("Set the language in which the messages of the game are shown. When a lan game is hosted the clients are shown the messages in this language as well.\n - en: English\n - de: German")
+            .takes_value(true)
+            .value_name("LANGUAGE")
+            .possible_values(["en", "de"])
+            .default_value(defaults.language))
+        .arg(Arg::new("no_auto_sort")
+            .long("no-auto-sort")
+            .help("Use to keep your hand cards in the order in which you put them")
+            .long_help("Use to keep your hand cards in the order in which you put them. New cards are added at the end and `move <card> <slot>` can be entered instead of a card to reorder them. Every player can turn sorting on or off during the game by entering `sort` instead of a card. When a lan game is hosted this is used for all players."))
+}
+
+/// Adds the options that set what happens with the results of the game
+fn result_args(app: App) -> App {
+    app.arg(Arg::new("history_file")
+            .long("history-file")
+            .help("Set the file to which the results of the game are added")
+            .long_help("Set the file to which the results of the game are added. After the final account the name, placement and money of each player are appended to the file together with the rules and the date. Use the stats subcommand to see the lifetime statistics of the players.")
+            .takes_value(true)
+            .value_name("FILE")
+            .default_value(DEFAULT_HISTORY_FILE)
+            .conflicts_with("no_history"))
+        .arg(Arg::new("no_history")
+            .long("no-history")
+            .help("Use to not store the results of the game"))
+        .arg(Arg::new("export_summary")
+            .long("export-summary")
+            .help("Write the summary of the game to a json file")
+            .long_help("Write the summary of the game to a json file after the final account. The summary contains the final standings, the statistics of each player, the size of each chain that is still on the board and every majority shareholder bonus that has been paid, so that the results can be used by other tools.")
+            .takes_value(true)
+            .value_name("FILE"))
+        .arg(Arg::new("export_finances")
+            .long("export-finances")
+            .help("Write the finances of each player in each round to a csv file")
+            .long_help("Write the finances of each player in each round to a csv file after the final account. The file contains one row per round and player with the money, the value of the stocks and the net worth of the player at the end of the round, so that the economy of the game can be charted.")
+            .takes_value(true)
+            .value_name("FILE"))
+        .arg(Arg::new("export_report")
+            .long("export-report")
+            .help("Write a report of the game to a html file")
+            .long_help("Write a report of the game to a html file after the final account. The report can be opened in any browser and contains the final standings, charts of the net worth of the players and of the stock prices and for each round the board and what has happened in it.")
+            .takes_value(true)
+            .value_name("FILE"))
+        .arg(Arg::new("event_stream")
+            .long("event-stream")
+            .help("Write every event of the game as a line of json to a file or pipe while the game runs")
+            .long_help("Write every event of the game as a line of json to a file or pipe while the game runs, so that dashboards, stream overlays and loggers can follow the game. Each line contains the round, the id and name of the player and what has happened. When a named pipe is given the game waits until another program reads from it.")
+            .takes_value(true)
+            .value_name("FILE"))
+        .arg(Arg::new("webhook")
+            .long("webhook")
+            .help("Post the results of the game to a webhook")
+            .long_help("Post the results of the game as json to the url after the final account. The json contains a line for each player in `content` and `text`, so Discord and Slack webhooks show it as message, and the results in the format of the history file in `results`.")
+            .takes_value(true)
+            .value_name("URL"))
+}
+
+/// Adds the options of a new game: the rules, the bots and what is shown to the players
+fn game_args<'a>(app: App<'a>, defaults: &Defaults<'a>) -> App<'a> {
+    result_args(player_args(app, defaults))
+        .arg(Arg::new("neutral_player")
+            .long("neutral-player")
+            .help("Use to add a neutral player to a game with two players")
+            .long_help("Use to add a neutral player to a game with two players. At the end of each round the neutral player places a hotel that does not start or fuse a chain. The neutral player does not own any money or stocks."))
+        .arg(Arg::new("teams")
+            .long("teams")
+            .help("Use to play in teams of two")
+            .long_help("Use to play in teams of two. Can only be used with 4 or 6 players. Team mates can see the stocks of each other and the money of the team mates is summed up at the end of the game. Team mates do not play directly after each other."))
+        .arg(Arg::new("sell_during_buy_phase")
+            .long("sell-during-buy-phase")
+            .help("House rule: Use to allow players to sell stocks when they can buy stocks")
+            .long_help("House rule: Use to allow players to sell stocks back to the bank when they can buy stocks. The stocks are sold for the current stock price before new stocks are bought."))
+        .arg(Arg::new("third_shareholder_bonus")
+            .long("third-shareholder-bonus")
+            .help("Use to pay a bonus to the third largest shareholders")
+            .long_help("Use to pay a bonus to the third largest shareholders when a chain is fused or the game ends. The bonus is three times the stock price."))
+        .arg(Arg::new("bots")
+            .long("bots")
+            .help("The number of players that are played by bots")
+            .long_help("The number of players that are played by bots. The bots take the last seats, use `play -p 2 --bots 1` to play alone against one bot. When a lan game is hosted the bots take seats that would otherwise be taken by client players, use `host -p 5 --bots 2` to play with two clients and two bots.")
+            .takes_value(true)
+            .value_name("NUMBER")
+            .possible_values(["0", "1", "2", "3", "4", "5", "6"])
+            .default_value("0"))
+        .arg(Arg::new("bot_strategy")
+            .long("bot-strategy")
+            .help("Set the strategy that the bots use")
+            .long_help("Set the strategy that the bots use.\n - greedy: The bots do what seems to be the best in the current moment\n - heuristic: The bots fight for the majorities of the chains\n - monte-carlo: The bots try out their options by playing the game on randomly\n - random: The bots choose a random legal action, useful to test the game")
+            .takes_value(true)
+            .value_name("STRATEGY")
+            .possible_values(["greedy", "heuristic", "monte-carlo", "random"])
+            .default_value("greedy")
+            .requires("bots"))
+        .arg(Arg::new("difficulty")
+            .long("difficulty")
+            .help("Set how strong the bots play")
+            .long_help("Set how strong the bots play. This is a shortcut for `--bot-strategy`.\n - easy: The bots choose a random legal action\n - normal: The bots do what seems to be the best in the current moment\n - hard: The bots try out their options by playing the game on randomly")
+            .takes_value(true)
+            .value_name("DIFFICULTY")
+            .possible_values(["easy", "normal", "hard"])
+            .requires("bots")
+            .conflicts_with("bot_strategy"))
+        .arg(bot_playouts_arg()
+            .requires("bots"))
+        .arg(Arg::new("bot_personalities")
+            .long("bot-personalities")
+            .help("Set the personalities of the heuristic and monte-carlo bots")
+            .long_help("Set the personalities of the heuristic and monte-carlo bots separated by commas. The bots take the personalities in order, when there are more bots than personalities the personalities are repeated. Example: cautious,shark,gambler\n - balanced: The bot has no special preferences.\n - cautious: The bot avoids risks and keeps some money.\n - gambler: The bot founds chains it can not hold and spends all its money.\n - hoarder: The bot keeps most of its money and only buys stocks to win majorities.\n - shark: The bot fuses chains whenever it can get a bonus.")
+            .takes_value(true)
+            .value_name("PERSONALITIES")
+            .use_delimiter(true)
+            .multiple_values(true)
+            .possible_values(["balanced", "cautious", "gambler", "hoarder", "shark"])
+            .default_value("balanced")
+            .requires("bots"))
+        .arg(Arg::new("bot_time")
+            .long("bot-time")
+            .help("Set how many milliseconds the monte-carlo bots may think for each decision")
+            .long_help("Set how many milliseconds the monte-carlo bots may think for each decision. The bots play as many playouts as fit into this time instead of the number set with --bot-playouts. Use a low value to let the bots play instantly and a high value to let them analyze the game more thoroughly.")
+            .takes_value(true)
+            .value_name("MILLISECONDS")
+            .validator(|value| value.parse::<u32>())
+            .requires("bots"))
+        .arg(Arg::new("turn_order")
+            .long("turn-order")
+            .help("Set how the turn order is determined")
+            .long_help("Set how the turn order is determined at the start of the game. Each player draws a card that is placed on the board in every mode.\n - lowest-tile: The player that drew the lowest card starts\n - random: The turn order is random\n - host: The host decides the turn order")
+            .takes_value(true)
+            .value_name("MODE")
+            .possible_values(["lowest-tile", "random", "host"])
+            .default_value("lowest-tile"))
+        .arg(Arg::new("seed")
+            .long("seed")
+            .help("The seed that is used to deal the cards, draw the turn order and create the bots")
+            .long_help("The seed that is used to deal the cards, draw a random turn order and create the bots. The same seed and the same decisions lead to the same game, so a game can be played again. When no seed is set a random seed is used.")
+            .takes_value(true)
+            .value_name("SEED")
+            .validator(|value| value.parse::<u64>()))
+        .arg(Arg::new("rules")
+            .long("rules")
+            .help("Set the rule preset that is used")
+            .long_help("Set the rule preset that is used. Single rules can be changed with the other rule flags.\n - classic: The rules of the original game\n - 1999: The rules of the 1999 edition. Cards that can never be played are replaced at the end of each turn.\n - house: Popular house rules. Third largest shareholders get a bonus, stocks can be sold during the buy phase, each split bonus is rounded up and cards that can never be played are replaced.")
+            .takes_value(true)
+            .value_name("PRESET")
+            .possible_values(["classic", "1999", "house"])
+            .default_value(defaults.rules))
+        .arg(Arg::new("tie_handling")
+            .long("tie-handling")
+            .help("Set how bonuses are split when shareholders are tied")
+            .long_help("Set how bonuses are split when multiple players are tied for a shareholder place.\n - exact: The bonuses are summed up and split exactly\n - round-up: Each bonus is split on its own and rounded up to the next 100\n - official: The bonuses are summed up, split and rounded up to the next 100")
+            .takes_value(true)
+            .value_name("MODE")
+            .possible_values(["exact", "round-up", "official"]))
+        .arg(Arg::new("founder_bonus")
+            .long("founder-bonus")
+            .help("Set how many stocks the founder of a chain gets for free")
+            .takes_value(true)
+            .value_name("NUMBER")
+            .possible_values(["0", "1", "2"]))
+}
+
+/// Returns the bot strategies that have been set with `--strategies`
+fn strategies_of(matches: &ArgMatches) -> Vec<BotStrategy> {
+    matches
+        .values_of("strategies")
+        .unwrap()
+        .map(|name| BotStrategy::from_name(name).unwrap())
+        .collect()
+}
+
+/// Creates the settings from the options that are added by [`player_args`]
+fn player_settings(matches: &ArgMatches, config: &Config) -> Settings {
+    let mut settings = Settings::new(
+        matches.is_present("small_board") || config.small_board,
+        Privacy::from_name(matches.value_of("privacy").unwrap()).unwrap(),
         matches.is_present("skip_dialogues"),
     );
-    if matches.is_present("demo") {
-        test_things(&matches, settings)?;
-    } else if matches.is_present("lan_server") {
-        start_server(&matches, settings)?;
-    } else if matches.is_present("lan_client") {
-        start_client(&matches)?;
-    } else if matches.is_present("info_card") {
-        print_info_card();
-    } else {
-        let mut game_manager = GameManager::new(
-            matches.value_of("players").unwrap().parse().unwrap(),
-            settings,
-        )?;
-        game_manager.start_game()?;
+    settings.advisor = matches.is_present("advisor");
+    settings.player_color = matches.value_of("color").and_then(hex_color);
+    settings.auto_sort = !matches.is_present("no_auto_sort");
+    settings.plain = matches.is_present("plain") || config.plain;
+    settings.language = Language::from_name(matches.value_of("language").unwrap()).unwrap();
+    settings
+}
+
+/// Sets the settings of the options that are added by [`result_args`]
+fn set_result_settings(settings: &mut Settings, matches: &ArgMatches) -> miette::Result<()> {
+    if !matches.is_present("no_history") {
+        settings.history_file = matches.value_of("history_file").map(String::from);
     }
+    settings.summary_file = matches.value_of("export_summary").map(String::from);
+    settings.finances_file = matches.value_of("export_finances").map(String::from);
+    settings.report_file = matches.value_of("export_report").map(String::from);
+    settings.webhook_url = matches.value_of("webhook").map(String::from);
+    settings.event_stream = matches
+        .value_of("event_stream")
+        .map(EventStream::create)
+        .transpose()?;
     Ok(())
 }
 
+/// Creates the settings from the options that are added by [`game_args`]
+fn game_settings(matches: &ArgMatches, config: &Config) -> miette::Result<Settings> {
+    let mut settings = player_settings(matches, config);
+    set_result_settings(&mut settings, matches)?;
+    settings.neutral_player = matches.is_present("neutral_player");
+    settings.teams = matches.is_present("teams");
+    settings.bots = matches.value_of("bots").unwrap().parse().unwrap();
+    settings.bot_strategy = match matches.value_of("difficulty") {
+        Some(difficulty) => BotStrategy::from_difficulty(difficulty).unwrap(),
+        None => BotStrategy::from_name(matches.value_of("bot_strategy").unwrap()).unwrap(),
+    };
+    settings.bot_personalities = matches
+        .values_of("bot_personalities")
+        .unwrap()
+        .map(|name| BotPersonality::from_name(name).unwrap())
+        .collect();
+    settings.bot_playouts = matches.value_of("bot_playouts").unwrap().parse().unwrap();
+    settings.bot_time = matches
+        .value_of("bot_time")
+        .map(|millis| millis.parse().unwrap());
+    settings.turn_order = TurnOrder::from_name(matches.value_of("turn_order").unwrap()).unwrap();
+    settings.seed = matches.value_of("seed").map(|seed| seed.parse().unwrap());
+    settings.rule_preset = RulePreset::from_name(matches.value_of("rules").unwrap()).unwrap();
+    settings.rules = settings.rule_preset.rules();
+    // Single rules overwrite the preset
+    if matches.is_present("sell_during_buy_phase") {
+        settings.rules.sell_during_buy_phase = true;
+    }
+    if matches.is_present("third_shareholder_bonus") {
+        settings.rules.third_shareholder_bonus = true;
+    }
+    if let Some(founder_bonus) = matches.value_of("founder_bonus") {
+        settings.rules.founder_bonus_stocks = founder_bonus.parse().unwrap();
+    }
+    match matches.value_of("tie_handling") {
+        Some("exact") => settings.rules.tie_handling = TieHandling::Exact,
+        Some("round-up") => settings.rules.tie_handling = TieHandling::RoundUp,
+        Some("official") => settings.rules.tie_handling = TieHandling::Official,
+        _ => (),
+    }
+    Ok(settings)
+}
+
+/// Creates the settings with which bots play against each other
+fn bot_settings(matches: &ArgMatches) -> Settings {
+    let mut settings = Settings::new(false, Privacy::default(), true);
+    settings.bot_playouts = matches.value_of("bot_playouts").unwrap().parse().unwrap();
+    settings
+}
+
 fn print_welcome() {
-    println!("Welcome to the Game Acquire!");
+    println!("{}", text("welcome", &[]));
 }
 
 // If the os is windows the virtual terminal will be set to true
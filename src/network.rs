@@ -1,17 +1,35 @@
+#[cfg(feature = "lan")]
 use std::{
-    io::{self, stdin, stdout, BufRead, BufReader, Write},
-    net::{IpAddr, SocketAddrV4, TcpListener, TcpStream},
-    str, thread, time,
+    collections::VecDeque,
+    fs,
+    io::{self, stdin, stdout, BufRead, BufReader},
+    net::{IpAddr, SocketAddrV4, TcpListener},
+    process, str,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{self, Duration, Instant},
 };
+use std::{io::Write, net::TcpStream};
 
+#[cfg(feature = "lan")]
 use clap::ArgMatches;
-use miette::{miette, IntoDiagnostic, Result};
-use owo_colors::{AnsiColors, OwoColorize};
+#[cfg(feature = "lan")]
+use miette::IntoDiagnostic;
+use miette::{miette, Result};
+use owo_colors::{AnsiColors, OwoColorize, Rgb};
 
+use crate::base_game::{player::Player, settings::Privacy, ui::plain_text};
+#[cfg(feature = "lan")]
 use crate::{
-    base_game::{player::Player, settings::Settings},
-    data_stream::read_enter,
+    base_game::{
+        player::CancellationToken,
+        settings::Settings,
+        ui::{console_text, is_plain, set_plain, shareholder_status_ui},
+    },
     game::GameManager,
+    journal::{last_entry, recover_game, DEFAULT_JOURNAL_FILE},
+    lobby::run_lobby,
+    locale::text,
 };
 
 /// Starts a client of the game.
@@ -21,15 +39,37 @@ use crate::{
 /// * `$Print` - Prints the text
 /// * `$Println` - Prints the text with new line
 /// * `$Input` - Prompts the user to input something
+/// * `$Shareholders` - Prints the shareholder stars of the player, see
+///   [`crate::base_game::ui::shareholder_status`]
+/// * `$UiLine` and `$UiShow` - Update and print the copy of the main ui that the client keeps,
+///   see [`main_ui_message`]
 ///
 /// Everything emidiadly after the command is printed to the player.
 /// A message always ends with `\n`.
-pub fn start_client(matches: &ArgMatches) -> Result<()> {
+///
+/// With `--binary` the client asks the host to send each message as one binary frame instead:
+/// The length of the payload as four byte big endian number followed by the command and the
+/// text encoded with bincode. The text of a frame can contain line breaks, it is not split
+/// into lines. The host answers with the line `$Binary` when it sends frames, hosts that do not
+/// know the option keep sending lines.
+///
+/// When the host sends the prompt again while the player has not answered yet, the prompt is
+/// printed again.
+///
+/// When the player is asked for input after they have waited longer than `--notify-after`
+/// seconds the terminal bell is rung, so that players that do something else in the meantime
+/// notice that it is their turn. With `--banner` a banner is printed as well.
+/// # Arguments
+/// * `small_board` - If the client shows the board small
+/// * `plain` - If the client shows plain text, see
+///   [`Settings::plain`](crate::base_game::settings::Settings::plain)
+#[cfg(feature = "lan")]
+pub fn start_client(matches: &ArgMatches, small_board: bool, plain: bool) -> Result<()> {
     let stdin = io::stdin();
     let ip = if matches.is_present("ip") {
         String::from(matches.value_of("ip").unwrap())
     } else {
-        // Ip was not privided fia command line
+        // Ip was not provided via command line
         let mut buffer = String::new();
         print!("Enter ip and port: ");
         stdout().flush().into_diagnostic()?;
@@ -49,36 +89,96 @@ pub fn start_client(matches: &ArgMatches) -> Result<()> {
                 stdin.read_line(&mut buffer).into_diagnostic()?;
                 buffer.trim().to_string()
             };
+            let privacy = Privacy::from_name(matches.value_of("privacy").unwrap()).unwrap();
+            set_plain(plain);
+            let binary = matches.is_present("binary");
             tcp_stream
                 .write_all(
-                    format!("$Init{}$Name{}\n", matches.is_present("small_board"), name).as_bytes(),
+                    format!(
+                        "$Init{}$Privacy{}$Plain{}$Binary{}$Deltatrue$Name{}\n",
+                        small_board,
+                        privacy.name(),
+                        plain,
+                        binary,
+                        name
+                    )
+                    .as_bytes(),
                 )
                 .into_diagnostic()?;
+            // The host switches to binary frames when it has answered with `$Binary`
+            let mut binary_frames = false;
+            // The copy of the main ui that is updated with the lines that have changed
+            let mut ui_lines = Vec::new();
             println!("Waiting for the game to start...");
 
+            let notify_after =
+                Duration::from_secs(matches.value_of("notify_after").unwrap().parse().unwrap());
+            let banner = matches.is_present("banner");
+            // The time since which the player waits for their turn
+            let mut waiting_since = Instant::now();
             let mut br = BufReader::new(tcp_stream.try_clone().into_diagnostic()?);
-            // Player recieving loop
+            let answers = read_answers();
+            // The messages that have been received while the player was asked for input
+            let mut pending: VecDeque<String> = VecDeque::new();
+            // Player receiving loop
             loop {
-                let stdin = io::stdin();
-                let mut input_buffer = String::new();
-                br.read_line(&mut input_buffer).into_diagnostic()?;
-                if input_buffer.starts_with("$Println") {
+                let mut input_buffer = pending.pop_front().unwrap_or_default();
+                if input_buffer.is_empty()
+                    && read_host_message(&mut br, binary_frames, &mut input_buffer)? == 0
+                {
+                    // The host has stopped without ending the game
+                    println!(
+                        "{}",
+                        console_text(
+                            &"The connection to the host has been lost!"
+                                .color(AnsiColors::Red)
+                                .to_string()
+                        )
+                    );
+                    println!("When the host recovers the game, connect again with the same name.");
+                    break;
+                }
+                if binary && input_buffer == "$Binary\n" {
+                    binary_frames = true;
+                } else if let Some(update) = input_buffer.strip_prefix("$UiLine") {
+                    let update = update.strip_suffix('\n').unwrap_or(update);
+                    if let Some((index, line)) = update.split_once(' ') {
+                        if let Ok(index) = index.parse::<usize>() {
+                            if ui_lines.len() <= index {
+                                ui_lines.resize(index + 1, String::new());
+                            }
+                            ui_lines[index] = String::from(line);
+                        }
+                    }
+                } else if let Some(show) = input_buffer.strip_prefix("$UiShow") {
+                    show_main_ui(&mut ui_lines, show.trim_end());
+                } else if input_buffer.starts_with("$Println") {
                     let mut to_print = input_buffer.replacen("$Println", "", 1);
                     to_print.pop();
-                    println!("{}", to_print);
+                    println!("{}", console_text(&to_print));
                 } else if input_buffer.starts_with("$Print") {
                     let mut to_print = input_buffer.replacen("$Print", "", 1);
                     to_print.pop();
-                    print!("{}", to_print);
+                    print!("{}", console_text(&to_print));
                 } else if input_buffer.starts_with("$Input") {
                     let mut to_print = input_buffer.replacen("$Input", "", 1);
                     to_print.pop();
-                    print!("{}", to_print);
+                    if waiting_since.elapsed() >= notify_after {
+                        notify_turn(banner);
+                    }
+                    print!("{}", console_text(&to_print));
                     stdout().flush().into_diagnostic()?;
-                    let mut output_buffer = String::new();
-                    stdin.read_line(&mut output_buffer).into_diagnostic()?;
-                    let output = output_buffer;
-                    tcp_stream.write_all(output.as_bytes()).into_diagnostic()?;
+                    if let Some(output) =
+                        wait_for_answer(&answers, &mut br, binary_frames, &mut pending)?
+                    {
+                        waiting_since = Instant::now();
+                        tcp_stream.write_all(output.as_bytes()).into_diagnostic()?;
+                    }
+                } else if input_buffer.starts_with("$Shareholders") {
+                    let status = input_buffer.replacen("$Shareholders", "", 1);
+                    if let Some(line) = shareholder_status_ui(status.trim_end()) {
+                        println!("{}", console_text(&line));
+                    }
                 } else if input_buffer.starts_with("$Ping") {
                     let _buffer = input_buffer.replacen("$Ping", "", 0);
                     tcp_stream
@@ -86,7 +186,10 @@ pub fn start_client(matches: &ArgMatches) -> Result<()> {
                         .into_diagnostic()?;
                 } else if input_buffer.starts_with("$TERMINATE") {
                     let reason = input_buffer.replacen("$TERMINATE", "", 1);
-                    println!("{}", "Game has been canceled!".color(AnsiColors::Red));
+                    println!(
+                        "{}",
+                        console_text(&"Game has been canceled!".color(AnsiColors::Red).to_string())
+                    );
                     println!("Reason: {}", reason);
                     break;
                 } else if input_buffer.starts_with("$GameEnded") {
@@ -103,37 +206,217 @@ pub fn start_client(matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
+/// Reads the lines that the player enters in the background, so that the client can show the
+/// prompts that the host sends again while the player has not answered.
+/// The channel is closed when the input has been closed.
+#[cfg(feature = "lan")]
+fn read_answers() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        let mut buffer = String::new();
+        match stdin().read_line(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                if sender.send(buffer).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+/// Waits until the player has entered their answer to the prompt. Prompts that the host sends
+/// again in the meantime, see
+/// [`Settings::remind_after`](crate::base_game::settings::Settings::remind_after), are printed.
+/// # Returns
+/// The answer, an empty answer when the input has been closed.
+/// `None` when the host has sent another message, it is added to `pending` and the host does
+/// not wait for the answer anymore.
+#[cfg(feature = "lan")]
+fn wait_for_answer(
+    answers: &Receiver<String>,
+    reader: &mut BufReader<TcpStream>,
+    binary_frames: bool,
+    pending: &mut VecDeque<String>,
+) -> Result<Option<String>> {
+    loop {
+        match answers.recv_timeout(Duration::from_millis(100)) {
+            Ok(answer) => return Ok(Some(answer)),
+            Err(RecvTimeoutError::Disconnected) => return Ok(Some(String::new())),
+            Err(RecvTimeoutError::Timeout) => (),
+        }
+        // Checks without blocking if the host has sent something
+        reader
+            .get_ref()
+            .set_read_timeout(Some(Duration::from_millis(1)))
+            .into_diagnostic()?;
+        let available = match reader.fill_buf() {
+            Ok(buffer) => Some(!buffer.is_empty()),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                None
+            }
+            Err(err) => return Err(err).into_diagnostic(),
+        };
+        reader.get_ref().set_read_timeout(None).into_diagnostic()?;
+        match available {
+            None => continue,
+            // The connection has been closed, this is noticed when the next message is read
+            Some(false) => return Ok(Some(String::new())),
+            Some(true) => (),
+        }
+        let mut message = String::new();
+        read_host_message(reader, binary_frames, &mut message)?;
+        match message.strip_prefix("$Input") {
+            Some(prompt) => {
+                print!("\n{}", console_text(prompt.trim_end_matches('\n')));
+                stdout().flush().into_diagnostic()?;
+            }
+            None => {
+                pending.push_back(message);
+                return Ok(None);
+            }
+        }
+    }
+}
+
+/// Reads the next message of the host into the buffer. A binary frame is converted into the
+/// line `<command><text>\n`, so that it is handled like a message that has been sent as line.
+/// # Returns
+/// The number of bytes that have been read, 0 when the connection has been closed.
+#[cfg(feature = "lan")]
+fn read_host_message<R: BufRead>(
+    reader: &mut R,
+    binary_frames: bool,
+    buffer: &mut String,
+) -> Result<usize> {
+    if !binary_frames {
+        return reader.read_line(buffer).into_diagnostic();
+    }
+    let mut length = [0; 4];
+    match reader.read_exact(&mut length) {
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+        result => result.into_diagnostic()?,
+    }
+    let mut payload = vec![0; u32::from_be_bytes(length) as usize];
+    reader.read_exact(&mut payload).into_diagnostic()?;
+    let (command, text): (String, String) = bincode::deserialize(&payload)
+        .map_err(|err| miette!("Unable to read message of the host: {}", err))?;
+    buffer.push_str(&command);
+    buffer.push_str(&text);
+    buffer.push('\n');
+    Ok(length.len() + payload.len())
+}
+
+/// Prints the copy of the main ui after it has been updated, `show` contains the number of
+/// lines of the main ui and its hash. When the hash differs from that of the copy the main ui
+/// is not printed, the next full update repairs the copy.
+#[cfg(feature = "lan")]
+fn show_main_ui(ui_lines: &mut Vec<String>, show: &str) {
+    let (lines, hash) = show.split_once(' ').unwrap_or((show, ""));
+    ui_lines.resize(lines.parse().unwrap_or_default(), String::new());
+    if hash.parse() != Ok(ui_hash(ui_lines)) {
+        println!("The board could not be updated, it is shown again with the next full update.");
+        return;
+    }
+    for line in ui_lines.iter() {
+        println!("{}", console_text(line));
+    }
+}
+
+/// Rings the terminal bell to tell the player that they have to enter something.
+/// # Arguments
+/// * `banner` - If a banner is printed as well, plain text only shows its message
+#[cfg(feature = "lan")]
+fn notify_turn(banner: bool) {
+    print!("\x07");
+    if !banner {
+        return;
+    }
+    if is_plain() {
+        println!("It is your turn!");
+        return;
+    }
+    let line = "=".repeat(40);
+    println!();
+    println!("{}", line.bright_yellow());
+    println!("{}", format!("{:^40}", "IT IS YOUR TURN!").bright_yellow());
+    println!("{}", line.bright_yellow());
+    println!();
+}
+
 /// Symbolizes a client player.
 pub struct ClientPlayer {
     pub name: String,
     pub tcp_stream: TcpStream,
     pub small_board: bool,
+    pub privacy: Privacy,
+    /// If the client shows plain text, see
+    /// [`Settings::plain`](crate::base_game::settings::Settings::plain)
+    pub plain: bool,
+    /// If the client receives the messages as binary frames, see [`start_client`]
+    pub binary: bool,
+    /// If the client understands delta updates of the main ui, see [`main_ui_message`]
+    pub delta: bool,
 }
 
 impl ClientPlayer {
-    fn new(name: String, tcp_stream: TcpStream, small_board: bool) -> Self {
+    /// Returns the client of the player so that the client can take a seat in a new game.
+    /// `None` when the player does not play on a client.
+    pub fn from_player(player: Player) -> Option<Self> {
+        Some(Self {
+            name: player.name,
+            tcp_stream: player.tcp_stream?,
+            small_board: player.small_board,
+            privacy: player.privacy,
+            plain: player.plain,
+            binary: player.binary,
+            delta: player.sent_ui.is_some(),
+        })
+    }
+
+    #[cfg(feature = "lan")]
+    fn new(
+        name: String,
+        tcp_stream: TcpStream,
+        small_board: bool,
+        privacy: Privacy,
+        plain: bool,
+        binary: bool,
+        delta: bool,
+    ) -> Self {
         Self {
             name,
             tcp_stream,
             small_board,
+            privacy,
+            plain,
+            binary,
+            delta,
         }
     }
 }
 
 /// Starts the server to play the game on multiplayer per lan.
+#[cfg(feature = "lan")]
 pub fn start_server(matches: &ArgMatches, settings: Settings) -> Result<()> {
     // Check if local ip was found
     let local_ip = match local_ip_address::local_ip() {
-	Ok(ip) => ip,
-	Err(_err) => {
-		println!("Local ip could not be determined automatically, please enter it manually.");
-		let mut buffer = String::new();
-		print!("Ip: ");
-		stdout().flush().into_diagnostic()?;
-		io::stdin().read_line(&mut buffer).into_diagnostic()?;
-		let ip = String::from(buffer.trim());
-		ip.parse().into_diagnostic()?
-	},
+        Ok(ip) => ip,
+        Err(_err) => {
+            println!("Local ip could not be determined automatically, please enter it manually.");
+            let mut buffer = String::new();
+            print!("Ip: ");
+            stdout().flush().into_diagnostic()?;
+            io::stdin().read_line(&mut buffer).into_diagnostic()?;
+            let ip = String::from(buffer.trim());
+            ip.parse().into_diagnostic()?
+        }
     };
     let local_ip = match local_ip {
         IpAddr::V4(ip4) => ip4,
@@ -149,35 +432,34 @@ pub fn start_server(matches: &ArgMatches, settings: Settings) -> Result<()> {
     let listener = TcpListener::bind(socket).into_diagnostic()?;
     let port = listener.local_addr().into_diagnostic()?;
     println!("Game has been hosted on {}", port);
+    if matches.is_present("recover") {
+        return recover_server(&listener, settings);
+    }
+    let number_of_players = matches.value_of("players").unwrap().parse::<u32>().unwrap();
+    if settings.bots >= number_of_players {
+        return Err(miette!(
+            "Unable to host game: The host can not be replaced by a bot. Players: {}, bots: {}",
+            number_of_players,
+            settings.bots
+        ));
+    }
+    // The host and the bots take a seat, the remaining seats are taken by the clients
+    let number_of_clients = number_of_players - 1 - settings.bots;
     println!(
-        "The game can be stared when {} more player(s) have connected.",
-        matches.value_of("players").unwrap().parse::<u32>().unwrap() - 1
+        "The game can be started when {} more player(s) have connected.",
+        number_of_clients
     );
     let mut client_players = Vec::new();
     // Number of players determines how many clients can connect to the game.
     // When the last client has been connected the host player can start the game.
-    for i in 1..=matches.value_of("players").unwrap().parse::<u32>().unwrap() - 1 {
-        let (tcp_stream, addr) = listener.accept().into_diagnostic()?;
-        let mut br = BufReader::new(tcp_stream.try_clone().into_diagnostic()?);
-        let mut input_buffer = String::new();
-        br.read_line(&mut input_buffer).into_diagnostic()?;
-        if input_buffer.starts_with("$Init") {
-            let input = input_buffer.replacen("$Init", "", 1);
-            let mut splits = input.splitn(2, "$Name");
-            let small_board = matches!(splits.next().unwrap(), "true");
-            let name = splits.next().unwrap().trim();
-            println!("{} joined from {}!", name, addr);
-            client_players.push(ClientPlayer::new(
-                String::from(name),
-                tcp_stream,
-                small_board,
-            ));
+    for i in 1..=number_of_clients {
+        if let Some(client_player) = accept_client(&listener)? {
+            client_players.push(client_player);
         }
-        let remaining_players =
-            matches.value_of("players").unwrap().parse::<u32>().unwrap() - 1 - i;
+        let remaining_players = number_of_clients - i;
         if remaining_players > 0 {
             println!(
-                "The game can be stared when {} more player(s) have connected.",
+                "The game can be started when {} more player(s) have connected.",
                 remaining_players
             );
         }
@@ -194,10 +476,210 @@ pub fn start_server(matches: &ArgMatches, settings: Settings) -> Result<()> {
         String::from(buffer.trim())
     };
     let mut game_manager = GameManager::new_server(client_players, settings, host_name)?;
+    stop_clients_on_interrupt(
+        &game_manager.players,
+        game_manager.settings.cancellation.clone(),
+    );
     println!("Game has been setup.");
-    println!("Press enter to start the game!");
-    read_enter();
-    if let Err(err) = game_manager.start_game() {
+    run_lobby(&mut game_manager)?;
+    let result = game_manager.start_game();
+    play_rematches(game_manager, result)
+}
+
+/// Waits until the next client has connected.
+/// Returns `None` when the client has not introduced itself.
+/// When the client asks for binary frames the host answers with `$Binary`.
+#[cfg(feature = "lan")]
+fn accept_client(listener: &TcpListener) -> Result<Option<ClientPlayer>> {
+    let (mut tcp_stream, addr) = listener.accept().into_diagnostic()?;
+    let mut br = BufReader::new(tcp_stream.try_clone().into_diagnostic()?);
+    let mut input_buffer = String::new();
+    br.read_line(&mut input_buffer).into_diagnostic()?;
+    if !input_buffer.starts_with("$Init") {
+        return Ok(None);
+    }
+    let input = input_buffer.replacen("$Init", "", 1);
+    let mut splits = input.splitn(2, "$Name");
+    let mut init = splits.next().unwrap().splitn(2, "$Privacy");
+    let small_board = matches!(init.next().unwrap(), "true");
+    // Older clients do not send if they show plain text
+    let mut init = init.next().unwrap_or_default().splitn(2, "$Plain");
+    let privacy = Privacy::from_name(init.next().unwrap()).unwrap_or_default();
+    // Older clients do not send if they want binary frames either
+    let mut init = init.next().unwrap_or_default().splitn(2, "$Binary");
+    let plain = matches!(init.next(), Some("true"));
+    let mut init = init.next().unwrap_or_default().splitn(2, "$Delta");
+    let binary = matches!(init.next(), Some("true"));
+    let delta = matches!(init.next(), Some("true"));
+    let name = splits.next().unwrap_or_default().trim();
+    if binary {
+        tcp_stream.write_all(b"$Binary\n").into_diagnostic()?;
+    }
+    println!("{} joined from {}!", name, addr);
+    Ok(Some(ClientPlayer::new(
+        String::from(name),
+        tcp_stream,
+        small_board,
+        privacy,
+        plain,
+        binary,
+        delta,
+    )))
+}
+
+/// Recovers the game from the journal after the server has stopped.
+///
+/// The game continues once each player that has played on a client has connected again with
+/// the same name. Clients with other names are sent away.
+#[cfg(feature = "lan")]
+fn recover_server(listener: &TcpListener, settings: Settings) -> Result<()> {
+    let path = settings
+        .journal
+        .as_ref()
+        .map_or(DEFAULT_JOURNAL_FILE, |journal| &journal.path)
+        .to_string();
+    let journal = fs::read_to_string(&path).map_err(|err| {
+        miette!(
+            "Unable to recover game: The journal {} can not be read: {}",
+            path,
+            err
+        )
+    })?;
+    let entry = last_entry(&journal)?;
+    println!(
+        "Recovering round {} of the game from {}.",
+        entry.round, path
+    );
+    let mut missing: Vec<&str> = entry.client_names();
+    let mut client_players = Vec::new();
+    while !missing.is_empty() {
+        println!(
+            "The game continues when {} connected again.",
+            match missing.len() {
+                1 => format!("{} has", missing[0]),
+                _ => format!("{} have", missing.join(", ")),
+            }
+        );
+        let mut client_player = match accept_client(listener)? {
+            Some(client_player) => client_player,
+            None => continue,
+        };
+        match missing.iter().position(|name| *name == client_player.name) {
+            Some(index) => {
+                missing.remove(index);
+                client_players.push(client_player);
+            }
+            None => {
+                println!("{} is not a player of the game.", client_player.name);
+                // The client may already be gone, so a failed message is ignored
+                let _ = client_player.tcp_stream.write_all(&encode_message(
+                    "$TERMINATE",
+                    &format!(
+                        "{} is not a player of the recovered game",
+                        client_player.name
+                    ),
+                    client_player.binary,
+                ));
+            }
+        }
+    }
+    let mut game_manager = recover_game(&entry, client_players, settings)?;
+    stop_clients_on_interrupt(
+        &game_manager.players,
+        game_manager.settings.cancellation.clone(),
+    );
+    println!("Game has been recovered.");
+    let result = game_manager.resume_game(entry.round, entry.next_player, entry.progress);
+    play_rematches(game_manager, result)
+}
+
+/// Starts new games with the same players and connections for as long as every player accepts a
+/// rematch after the game has ended, see [`rematch_accepted`]. Then the game is ended like
+/// [`end_server_game`] does.
+#[cfg(feature = "lan")]
+fn play_rematches(mut game_manager: GameManager, result: Result<()>) -> Result<()> {
+    let mut result = result;
+    loop {
+        match result.and_then(|()| rematch_accepted(&game_manager.players)) {
+            Ok(true) => {
+                game_manager = game_manager.rematch()?;
+                result = game_manager.start_game();
+            }
+            Ok(false) => return end_server_game(game_manager, Ok(())),
+            Err(err) => return end_server_game(game_manager, Err(err)),
+        }
+    }
+}
+
+/// Asks every player that is not a bot if they would like to play a rematch.
+/// A client that does not answer declines the rematch.
+/// # Returns
+/// `true` when every player has accepted the rematch
+#[cfg(feature = "lan")]
+fn rematch_accepted(players: &[Player]) -> Result<bool> {
+    let mut declined = Vec::new();
+    for player in players.iter().filter(|player| player.bot.is_none()) {
+        broadcast_others(
+            &text("rematch.waiting", &[&player.colored_name()]),
+            player.id,
+            players,
+        )?;
+        let accepted = match player.read_yes_no(text("rematch.prompt", &[])) {
+            Ok(answer) => answer,
+            Err(_) if player.tcp_stream.is_some() => false,
+            Err(err) => return Err(err),
+        };
+        if !accepted {
+            declined.push(player.colored_name());
+        }
+    }
+    match declined.is_empty() {
+        true => broadcast(&text("rematch.starting", &[]), players)?,
+        false => broadcast(&text("rematch.declined", &[&declined.join(", ")]), players)?,
+    }
+    Ok(declined.is_empty())
+}
+
+/// The time in which the game has to stop after the host has pressed Ctrl-C, before the host
+/// exits without waiting for it, see [`stop_clients_on_interrupt`]
+#[cfg(feature = "lan")]
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Cancels the `cancellation` token when the host presses Ctrl-C. A client that is asked for
+/// input stops then and the game ends like after an error, so that the clients do not wait for a
+/// host that is gone. When the game has not ended within the [`STOP_GRACE_PERIOD`], e.g. because
+/// the host is asked for input, the clients are told that the game has been stopped and the host
+/// exits. The journal is kept, so that the game can be recovered.
+#[cfg(feature = "lan")]
+fn stop_clients_on_interrupt(players: &[Player], cancellation: CancellationToken) {
+    let clients: Vec<(TcpStream, bool)> = players
+        .iter()
+        .filter_map(|player| Some((player.tcp_stream.as_ref()?.try_clone().ok()?, player.binary)))
+        .collect();
+    let handler = ctrlc::set_handler(move || {
+        cancellation.cancel();
+        thread::sleep(STOP_GRACE_PERIOD);
+        for (stream, binary) in &clients {
+            let mut stream: &TcpStream = stream;
+            // The client may already be gone, so a failed message is ignored
+            let _ = stream.write_all(&encode_message(
+                "$TERMINATE",
+                "The host has stopped the game",
+                *binary,
+            ));
+        }
+        process::exit(130);
+    });
+    if let Err(err) = handler {
+        println!("Ctrl-C will not stop the clients: {}", err);
+    }
+}
+
+/// Cancels the game when it has been stopped by an error, otherwise the clients are told that
+/// the game has ended. The streams to the clients are closed.
+#[cfg(feature = "lan")]
+fn end_server_game(game_manager: GameManager, result: Result<()>) -> Result<()> {
+    if let Err(err) = result {
         // Some error occured because of which the game is canceled
         println!(
             "{}",
@@ -209,7 +691,7 @@ pub fn start_server(matches: &ArgMatches, settings: Settings) -> Result<()> {
     }
     // game is over, stream will be closed
     for player in game_manager.players {
-        if player.tcp_stream.is_some() {
+        if let Some(stream) = &player.tcp_stream {
             if let Err(err) = send_string(&player, "", "$GameEnded") {
                 println!(
                     "Error: Could not send game ended signal to player {}. Reason: {}",
@@ -217,9 +699,7 @@ pub fn start_server(matches: &ArgMatches, settings: Settings) -> Result<()> {
                     err
                 );
             }
-            player
-                .tcp_stream
-                .unwrap()
+            stream
                 .shutdown(std::net::Shutdown::Both)
                 .into_diagnostic()?;
         }
@@ -229,12 +709,13 @@ pub fn start_server(matches: &ArgMatches, settings: Settings) -> Result<()> {
 
 /// Send a message to every player (including the local player).
 /// If the game is only played local the message is only written once to the console.
+/// Bots do not receive the message.
 /// # Returns
 /// * `Ok(())` - When the message was send successfully
 /// * `Err(err)` - When the mesage could not be sent to at least one player
 pub fn broadcast(message: &str, players: &[Player]) -> Result<()> {
     let mut written_to_console = false;
-    for player in players {
+    for player in players.iter().filter(|player| player.bot.is_none()) {
         if player.tcp_stream.is_none() {
             if !written_to_console {
                 player.print_text_ln(message)?;
@@ -247,18 +728,77 @@ pub fn broadcast(message: &str, players: &[Player]) -> Result<()> {
     Ok(())
 }
 
-/// Send a message to every player except for the player that currently has their turn.
+/// Sends every player the message that is made for them, like [`broadcast`] does with the
+/// same message.
+/// The local players share the console, it is shown the message for `None` once. When only
+/// one player plays locally the console shows the message for that player.
+/// Bots do not receive the message.
+/// # Returns
+/// * `Ok(())` - When the messages were send successfully
+/// * `Err(err)` - When a message could not be sent to at least one player
+pub fn broadcast_each<F: Fn(Option<&Player>) -> String>(
+    message: F,
+    players: &[Player],
+) -> Result<()> {
+    let local_players: Vec<&Player> = players
+        .iter()
+        .filter(|player| player.bot.is_none() && player.tcp_stream.is_none())
+        .collect();
+    let console = match local_players.as_slice() {
+        [player] => Some(*player),
+        _ => None,
+    };
+    let mut written_to_console = false;
+    for player in players.iter().filter(|player| player.bot.is_none()) {
+        if player.tcp_stream.is_none() {
+            if !written_to_console {
+                player.print_text_ln(&message(console))?;
+                written_to_console = true;
+            }
+        } else {
+            player.print_text_ln(&message(Some(player)))?;
+        }
+    }
+    Ok(())
+}
+
+/// Sends the reasons for the decisions the bots have made to every player and clears them.
+/// # Returns
+/// * `Ok(())` - When the reasons were send successfully
+/// * `Err(err)` - When the reasons could not be sent to at least one player
+pub fn broadcast_bot_reasons(players: &mut [Player]) -> Result<()> {
+    let mut reasons = Vec::new();
+    for player in players.iter_mut() {
+        let name = player.colored_name();
+        for reason in player.bot_reasons.drain(..) {
+            reasons.push(format!("{} {}", name, reason.color(Rgb(105, 105, 105))));
+        }
+    }
+    for reason in reasons {
+        broadcast(&reason, players)?;
+    }
+    Ok(())
+}
+
+/// Send a message to every player except for the player with the id `current_player_id`, usually
+/// the player that currently has their turn.
 /// If the game is only played local the message is only written once to the console.
+/// Bots do not receive the message.
 /// # Returns
 /// * `Ok(())` - When the message was send successfully
 /// * `Err(err)` - When the message was not sent to at least one player
-pub fn broadcast_others(
-    message: &str,
-    current_player_name: &str,
-    players: &[Player],
-) -> Result<()> {
-    for player in players {
-        if player.name != *current_player_name {
+pub fn broadcast_others(message: &str, current_player_id: u32, players: &[Player]) -> Result<()> {
+    let mut written_to_console = false;
+    for player in players
+        .iter()
+        .filter(|player| player.id != current_player_id && player.bot.is_none())
+    {
+        if player.tcp_stream.is_none() {
+            if !written_to_console {
+                player.print_text_ln(message)?;
+                written_to_console = true;
+            }
+        } else {
             player.print_text_ln(message)?;
         }
     }
@@ -266,28 +806,133 @@ pub fn broadcast_others(
 }
 
 /// Sends a string to the client.
-/// The text is split at `\n`. These slices are send individually.
+/// The text is split at `\n`, each line is sent with the command. All lines are written to the
+/// connection at once.
 /// # Returns
 /// * `Ok(())` - When the string was send successfully
 /// * `Err(err)` - When the string could not be sent
 pub fn send_string(player: &Player, text: &str, command: &str) -> Result<()> {
-    let mut stream = player.tcp_stream.as_ref().unwrap();
-    let text = String::from(text);
-    let text = text.split('\n');
-    for split in text {
-        let mut out = String::new();
-        out.push_str(command);
-        out.push_str(split);
-        out.push('\n');
-        if let Err(err) = stream.write_all(out.as_bytes()) {
-            return Err(miette!(
-                "Unable to send data to player {}: {}",
-                player.name,
-                err
+    send_message(player, &client_message(player, text, command))
+}
+
+/// Returns the text in the form in which it is sent to the client: Each line of the text is
+/// sent with the command in front of it, or the text is sent as one binary frame when the client
+/// has asked for it. The text is made plain when the player has chosen plain text.
+///
+/// Messages can be appended to each other to send them with one write, see [`send_message`].
+pub fn client_message(player: &Player, text: &str, command: &str) -> Vec<u8> {
+    let text = match player.plain {
+        true => plain_text(text),
+        false => String::from(text),
+    };
+    encode_message(command, &text, player.binary)
+}
+
+/// Encodes the message as lines or as binary frame, see [`start_client`]
+fn encode_message(command: &str, text: &str, binary: bool) -> Vec<u8> {
+    #[cfg(feature = "lan")]
+    if binary {
+        let payload = bincode::serialize(&(command, text)).expect("Strings can always be encoded");
+        let mut frame = (payload.len() as u32).to_be_bytes().to_vec();
+        frame.extend(payload);
+        return frame;
+    }
+    #[cfg(not(feature = "lan"))]
+    let _ = binary;
+    let mut message = Vec::with_capacity(text.len() + command.len() + 1);
+    for line in text.split('\n') {
+        message.extend_from_slice(command.as_bytes());
+        message.extend_from_slice(line.as_bytes());
+        message.push(b'\n');
+    }
+    message
+}
+
+/// After how many updates of the main ui the whole main ui is sent again to a client that
+/// receives delta updates
+pub const FULL_UPDATE_INTERVAL: u32 = 10;
+
+/// The main ui that has last been sent to a client that receives delta updates, see
+/// [`main_ui_message`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SentUi {
+    /// The lines of the main ui as the client shows them
+    pub lines: Vec<String>,
+    /// The number of updates since the whole main ui has been sent
+    pub updates: u32,
+}
+
+/// Returns the message with which the main ui is shown to the client.
+///
+/// A client that understands delta updates is only sent the lines that have changed since the
+/// last update as `$UiLine<index> <line>`, so that a placed hotel or changed money and stocks
+/// do not send the whole board again. `$UiShow<lines> <hash>` tells the client how many lines
+/// the main ui has and the hash of all lines, see [`ui_hash`], with which the client checks its
+/// copy before it prints it. Every [`FULL_UPDATE_INTERVAL`] updates all lines are sent again.
+/// Other clients are sent the whole main ui as `$Println` lines.
+pub fn main_ui_message(player: &mut Player, frame: &str) -> Vec<u8> {
+    if player.sent_ui.is_none() {
+        return client_message(player, frame, "$Println");
+    }
+    let frame = match player.plain {
+        true => plain_text(frame),
+        false => String::from(frame),
+    };
+    let binary = player.binary;
+    let sent_ui = player.sent_ui.as_mut().unwrap();
+    if sent_ui.lines.is_empty() || sent_ui.updates >= FULL_UPDATE_INTERVAL {
+        sent_ui.lines.clear();
+        sent_ui.updates = 0;
+    } else {
+        sent_ui.updates += 1;
+    }
+    let lines: Vec<String> = frame.lines().map(String::from).collect();
+    let mut message = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if sent_ui.lines.get(index) != Some(line) {
+            message.extend(encode_message(
+                "$UiLine",
+                &format!("{} {}", index, line),
+                binary,
             ));
         }
     }
-    Ok(())
+    message.extend(encode_message(
+        "$UiShow",
+        &format!("{} {}", lines.len(), ui_hash(&lines)),
+        binary,
+    ));
+    sent_ui.lines = lines;
+    message
+}
+
+/// Returns the hash of the lines of the main ui. FNV-1a is used, so that host and client
+/// calculate the same hash even when they have been built with different versions of Rust.
+pub fn ui_hash(lines: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in lines.iter().flat_map(|line| line.bytes().chain([b'\n'])) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Writes the message that has been built with [`client_message`] to the client with one write,
+/// so that a whole frame of the ui does not arrive in many small packets.
+///
+/// A player that has left the game may have closed the connection, so the message is dropped
+/// when it can not be sent to them.
+/// # Returns
+/// * `Ok(())` - When the message was send successfully
+/// * `Err(err)` - When the message could not be sent
+pub fn send_message(player: &Player, message: &[u8]) -> Result<()> {
+    let mut stream = player.tcp_stream.as_ref().unwrap();
+    match stream.write_all(message) {
+        Err(_) if player.resigned => Ok(()),
+        result => {
+            result.map_err(|err| miette!("Unable to send data to player {}: {}", player.name, err))
+        }
+    }
 }
 
 /// Sends a message to each player that the game is canceled
@@ -297,7 +942,7 @@ pub fn abort_game(players: &[Player], reason: String) {
         if player.tcp_stream.is_some() {
             if let Ok(()) = send_string(
                 player,
-                &(&reason).color(AnsiColors::Red).to_string(),
+                &reason.color(AnsiColors::Red).to_string(),
                 "$TERMINATE",
             ) {
                 println!("Stop command has been sent to {}", &player.name);
@@ -305,3 +950,332 @@ pub fn abort_game(players: &[Player], reason: String) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use owo_colors::{AnsiColors, OwoColorize};
+
+    use crate::base_game::player::Player;
+
+    use super::{client_message, main_ui_message, ui_hash, SentUi, FULL_UPDATE_INTERVAL};
+
+    #[test]
+    fn messages_are_sent_line_by_line() {
+        let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+        let text = format!("Round 1\n{}", "Luxor".color(AnsiColors::Red));
+        let mut message = client_message(&player, &text, "$Println");
+        message.extend(client_message(&player, "{}", "$Shareholders"));
+        assert_eq!(
+            message,
+            b"$PrintlnRound 1\n$Println\x1b[31mLuxor\x1b[39m\n$Shareholders{}\n"
+        );
+        player.plain = true;
+        assert_eq!(
+            client_message(&player, &text, "$Println"),
+            b"$PrintlnRound 1\n$PrintlnLuxor\n"
+        );
+    }
+
+    #[test]
+    fn only_changed_lines_of_the_main_ui_are_sent() {
+        let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+        player.sent_ui = Some(SentUi::default());
+        let show = |lines: &[&str]| {
+            let lines: Vec<String> = lines.iter().map(|line| String::from(*line)).collect();
+            format!("$UiShow{} {}\n", lines.len(), ui_hash(&lines))
+        };
+        assert_eq!(
+            String::from_utf8(main_ui_message(&mut player, "Round 1\nA |   |")).unwrap(),
+            format!(
+                "$UiLine0 Round 1\n$UiLine1 A |   |\n{}",
+                show(&["Round 1", "A |   |"])
+            )
+        );
+        let mut update =
+            || String::from_utf8(main_ui_message(&mut player, "Round 2\nA |   |")).unwrap();
+        assert_eq!(
+            update(),
+            format!("$UiLine0 Round 2\n{}", show(&["Round 2", "A |   |"]))
+        );
+        for _ in 1..FULL_UPDATE_INTERVAL {
+            assert_eq!(update(), show(&["Round 2", "A |   |"]));
+        }
+        // The whole main ui is sent again after some updates
+        assert!(update().starts_with("$UiLine0 Round 2\n$UiLine1 A |   |\n"));
+        player.sent_ui = None;
+        assert_eq!(
+            main_ui_message(&mut player, "Round 1"),
+            b"$PrintlnRound 1\n"
+        );
+    }
+
+    #[test]
+    fn each_client_gets_their_own_message() -> miette::Result<()> {
+        use std::{
+            io::Read,
+            net::{TcpListener, TcpStream},
+        };
+
+        use miette::IntoDiagnostic;
+
+        use crate::base_game::settings::Privacy;
+
+        use super::broadcast_each;
+
+        let listener = TcpListener::bind("127.0.0.1:0").into_diagnostic()?;
+        let mut clients = Vec::new();
+        let mut players = Vec::new();
+        for id in 0..2 {
+            clients.push(
+                TcpStream::connect(listener.local_addr().into_diagnostic()?).into_diagnostic()?,
+            );
+            let (stream, _) = listener.accept().into_diagnostic()?;
+            players.push(Player::new_client(
+                vec![],
+                id,
+                format!("Player {}", id + 1),
+                stream,
+                false,
+                Privacy::default(),
+            ));
+        }
+        broadcast_each(
+            |recipient| recipient.map_or(String::from("console"), |player| player.name.clone()),
+            &players,
+        )?;
+        drop(players);
+        for (id, client) in clients.iter_mut().enumerate() {
+            let mut sent = String::new();
+            client.read_to_string(&mut sent).into_diagnostic()?;
+            assert_eq!(sent, format!("$PrintlnPlayer {}\n", id + 1));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn clients_that_do_not_answer_are_detected() -> miette::Result<()> {
+        use std::net::{TcpListener, TcpStream};
+
+        use miette::IntoDiagnostic;
+
+        use crate::base_game::{player::InputError, settings::Privacy};
+
+        let listener = TcpListener::bind("127.0.0.1:0").into_diagnostic()?;
+        let client =
+            TcpStream::connect(listener.local_addr().into_diagnostic()?).into_diagnostic()?;
+        let (stream, _) = listener.accept().into_diagnostic()?;
+        let player = Player::new_client(
+            vec![],
+            0,
+            String::from("Player 1"),
+            stream,
+            false,
+            Privacy::default(),
+        );
+        player.set_input_timeout(Some(1))?;
+        // A client that should only press enter continues
+        player.get_enter("Press enter")?;
+        let err = player
+            .read_input(String::from("Card: "), vec![1])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<InputError>(),
+            Some(&InputError::TimedOut {
+                name: String::from("Player 1"),
+                seconds: 1
+            })
+        );
+        // The safe default is taken for a decision that has not been answered
+        assert!(!player
+            .answer_or_default(player.read_yes_no(String::from("End the game?")), || false)?);
+        drop(client);
+        player.get_enter("Press enter")?;
+        let err = player
+            .read_input(String::from("Card: "), vec![1])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<InputError>(),
+            Some(&InputError::Closed {
+                name: String::from("Player 1")
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cancelled_games_stop_the_clients() -> miette::Result<()> {
+        use std::{
+            net::{TcpListener, TcpStream},
+            time::{Duration, Instant},
+        };
+
+        use miette::IntoDiagnostic;
+
+        use crate::base_game::{
+            player::{CancellationToken, InputError},
+            settings::Privacy,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").into_diagnostic()?;
+        let _client =
+            TcpStream::connect(listener.local_addr().into_diagnostic()?).into_diagnostic()?;
+        let (stream, _) = listener.accept().into_diagnostic()?;
+        let mut player = Player::new_client(
+            vec![],
+            0,
+            String::from("Player 1"),
+            stream,
+            false,
+            Privacy::default(),
+        );
+        let cancellation = CancellationToken::default();
+        player.cancellation = cancellation.clone();
+        let started = Instant::now();
+        cancellation.cancel();
+        // The client is stopped although they have unlimited time
+        let err = player.get_enter("Press enter").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<InputError>(),
+            Some(&InputError::Cancelled {
+                name: String::from("Player 1")
+            })
+        );
+        assert!(started.elapsed() < Duration::from_secs(1));
+        // No default is taken for a game that has been stopped
+        assert!(player
+            .answer_or_default(player.read_input(String::from("Card: "), vec![1]), || 1)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn idle_clients_are_reminded() -> miette::Result<()> {
+        use std::{
+            io::Read,
+            net::{TcpListener, TcpStream},
+            time::Duration,
+        };
+
+        use miette::IntoDiagnostic;
+
+        use crate::base_game::{player::InputError, settings::Privacy};
+
+        let listener = TcpListener::bind("127.0.0.1:0").into_diagnostic()?;
+        let mut client =
+            TcpStream::connect(listener.local_addr().into_diagnostic()?).into_diagnostic()?;
+        let (stream, _) = listener.accept().into_diagnostic()?;
+        let mut player = Player::new_client(
+            vec![],
+            0,
+            String::from("Player 1"),
+            stream,
+            false,
+            Privacy::default(),
+        );
+        player.set_input_timeout(Some(1))?;
+        player.remind_after = Some(Duration::from_millis(400));
+        let err = player
+            .read_input(String::from("Card: "), vec![1])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<InputError>(),
+            Some(&InputError::TimedOut {
+                name: String::from("Player 1"),
+                seconds: 1
+            })
+        );
+        // The input timeout is not changed by the reminders
+        assert_eq!(
+            player
+                .tcp_stream
+                .as_ref()
+                .unwrap()
+                .read_timeout()
+                .into_diagnostic()?,
+            Some(Duration::from_secs(1))
+        );
+        drop(player);
+        let mut sent = String::new();
+        client.read_to_string(&mut sent).into_diagnostic()?;
+        assert_eq!(
+            sent,
+            "$InputCard: \n$Input(still waiting) Card: \n$Input(still waiting) Card: \n"
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "lan")]
+    #[test]
+    fn rematch_keeps_the_seats() -> miette::Result<()> {
+        use std::net::{TcpListener, TcpStream};
+
+        use miette::IntoDiagnostic;
+
+        use crate::{
+            base_game::settings::{Privacy, Settings},
+            game::GameManager,
+            network::ClientPlayer,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").into_diagnostic()?;
+        let mut clients = Vec::new();
+        let mut client_players = Vec::new();
+        for name in ["Anna", "Ben"] {
+            clients.push(
+                TcpStream::connect(listener.local_addr().into_diagnostic()?).into_diagnostic()?,
+            );
+            let (tcp_stream, _) = listener.accept().into_diagnostic()?;
+            client_players.push(ClientPlayer {
+                name: String::from(name),
+                tcp_stream,
+                small_board: false,
+                privacy: Privacy::default(),
+                plain: name == "Ben",
+                binary: false,
+                delta: true,
+            });
+        }
+        let mut settings = Settings::new(false, Privacy::default(), false);
+        settings.bots = 1;
+        let game_manager = GameManager::new_server(client_players, settings, String::from("Host"))?;
+        let names = |game_manager: &GameManager| -> Vec<String> {
+            game_manager
+                .players
+                .iter()
+                .map(|player| player.name.clone())
+                .collect()
+        };
+        let seats = names(&game_manager);
+        let rematch = game_manager.rematch()?;
+        assert_eq!(names(&rematch), seats);
+        assert!(rematch.players[1..3]
+            .iter()
+            .all(|player| player.tcp_stream.is_some() && player.sent_ui.is_some()));
+        let ben = rematch.players.iter().find(|player| player.name == "Ben");
+        assert!(ben.unwrap().plain);
+        assert!(rematch.players[3].bot.is_some());
+        Ok(())
+    }
+
+    #[cfg(feature = "lan")]
+    #[test]
+    fn binary_frames_keep_line_breaks() -> miette::Result<()> {
+        use std::io::Cursor;
+
+        use super::read_host_message;
+
+        let mut player = Player::new(vec![], 0, false, String::from("Player 1"));
+        player.binary = true;
+        let mut message = client_message(&player, "Round 1\nLuxor", "$Println");
+        message.extend(client_message(&player, "", "$Input"));
+        let mut reader = Cursor::new(message);
+        let mut buffer = String::new();
+        read_host_message(&mut reader, true, &mut buffer)?;
+        assert_eq!(buffer, "$PrintlnRound 1\nLuxor\n");
+        buffer.clear();
+        read_host_message(&mut reader, true, &mut buffer)?;
+        assert_eq!(buffer, "$Input\n");
+        assert_eq!(read_host_message(&mut reader, true, &mut buffer)?, 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,808 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use miette::{miette, Result};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    base_game::{
+        bank::Bank,
+        board::{Board, Position},
+        hotel_chains::HotelChain,
+        player::Player,
+        settings::{Rules, Settings, PURCHASE_LIMIT},
+    },
+    evaluation::{evaluate, GameState},
+    game::{hotel_chain_manager::HotelChainManager, turn_order_by_cards, GameManager},
+    logic::{
+        can_game_continue, check_end_condition,
+        place_hotel::{analyze_position, IllegalPlacement, PlaceHotelCase},
+    },
+};
+
+/// The public information of the game that a player can see at the start of their turn.
+///
+/// The cards and the money of the other players are hidden. It is assumed that the other players
+/// have as much money as the player.
+pub struct Observation {
+    /// A copy of the board
+    pub board: Board,
+    /// Copies of the players, only the cards of the observing player are known
+    pub players: Vec<Player>,
+}
+
+impl Observation {
+    /// Creates a new observation of the game from the view of the player.
+    pub fn new(player: &Player, board: &Board, players: &[Player]) -> Self {
+        let players = players
+            .iter()
+            .map(|other| {
+                if other.id == player.id {
+                    other.headless_copy()
+                } else {
+                    let mut copy = other.headless_copy();
+                    copy.analyzed_cards = Vec::new();
+                    copy.money = player.money;
+                    copy
+                }
+            })
+            .collect();
+        Self {
+            board: board.clone(),
+            players,
+        }
+    }
+}
+
+/// A decision of a bot that has been recorded during a playout.
+pub struct RecordedDecision {
+    /// The id of the player that made the decision
+    pub player_id: u32,
+    /// The game as the player has seen it before the decision was made
+    pub state: Value,
+    /// What the player has decided
+    pub action: Value,
+}
+
+/// A copy of the game that is played without any player interaction. It is used by bots to try
+/// out moves and to see how the game could continue and to let bots play against each other.
+///
+/// The game is advanced one turn at a time with [`Playout::step`]. Players that have a bot let
+/// the bot decide, all other players place cards and buy stocks randomly. When chains are fused
+/// the longest chain survives and players without bot sell all stocks of the dead chains.
+pub struct Playout {
+    pub board: Board,
+    pub bank: Bank,
+    pub hotel_chain_manager: HotelChainManager,
+    /// The players in the order in which they play, the index is the id of the player
+    pub players: Vec<Player>,
+    /// The cards that can still be drawn
+    position_cards: Vec<Position>,
+    /// The index of the player that currently has their turn
+    current_player: usize,
+    /// The number of turns that have been played
+    turns: u32,
+    rng: StdRng,
+    /// The decisions of the bots, only recorded when [`Playout::record_decisions`] was called
+    recorded_decisions: Option<Vec<RecordedDecision>>,
+}
+
+impl Playout {
+    /// Creates a new playout from the view of the player.
+    ///
+    /// The cards of the other players are not known. They are dealt randomly from the cards that
+    /// are neither on the board nor in the hand of the player.
+    pub fn new(
+        player: &Player,
+        observation: &Observation,
+        bank: &Bank,
+        hotel_chain_manager: &HotelChainManager,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut players: Vec<Player> = observation
+            .players
+            .iter()
+            .map(|other| {
+                if other.id == player.id {
+                    player.headless_copy()
+                } else {
+                    other.headless_copy()
+                }
+            })
+            .collect();
+        let own_cards: Vec<Position> = player
+            .analyzed_cards
+            .iter()
+            .map(|card| card.position)
+            .collect();
+        let mut position_cards: Vec<Position> = GameManager::init_position_cards()
+            .into_iter()
+            .filter(|position| {
+                observation.board.is_hotel_placed(position).is_none()
+                    && !own_cards.contains(position)
+            })
+            .collect();
+        position_cards.shuffle(&mut rng);
+        for other in players
+            .iter_mut()
+            .filter(|other| other.id != player.id && !other.resigned)
+        {
+            for _i in 0..6 {
+                if let Some(card) = position_cards.pop() {
+                    other.add_card(&card, &observation.board, hotel_chain_manager);
+                }
+            }
+        }
+        let current_player = players
+            .iter()
+            .position(|other| other.id == player.id)
+            .unwrap();
+        Self {
+            board: observation.board.clone(),
+            bank: bank.clone(),
+            hotel_chain_manager: hotel_chain_manager.clone(),
+            players,
+            position_cards,
+            current_player,
+            turns: 0,
+            rng,
+            recorded_decisions: None,
+        }
+    }
+
+    /// Creates a new game for the players.
+    ///
+    /// Each player draws a card that is placed on the board, the player with the lowest card
+    /// starts. Then six cards are dealt to each player.
+    /// # Arguments
+    /// * `players` - The players without cards, the index has to be the id of the player
+    pub fn new_game(mut players: Vec<Player>, rules: Rules, seed: u64) -> Result<Self> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = Board::new();
+        let mut bank = Bank::new();
+        bank.rules = rules;
+        let hotel_chain_manager = HotelChainManager::new();
+        let mut position_cards = GameManager::init_position_cards();
+        position_cards.shuffle(&mut rng);
+        let mut start_cards = Vec::new();
+        for _player in &players {
+            let card = position_cards.pop().unwrap();
+            board.place_hotel(&card)?;
+            start_cards.push(card);
+        }
+        for player in players.iter_mut() {
+            for _i in 0..6 {
+                let card = position_cards.pop().unwrap();
+                player.add_card(&card, &board, &hotel_chain_manager);
+            }
+        }
+        Ok(Self {
+            board,
+            bank,
+            hotel_chain_manager,
+            players,
+            position_cards,
+            current_player: turn_order_by_cards(&start_cards)[0],
+            turns: 0,
+            rng,
+            recorded_decisions: None,
+        })
+    }
+
+    /// Creates a playout that continues the game from the position.
+    /// # Arguments
+    /// * `players` - The players with their cards, the index has to be the id of the player
+    /// * `position_cards` - The cards that can still be drawn, the last card is drawn first
+    /// * `current_player` - The index of the player that has the next turn
+    pub fn from_position(
+        board: Board,
+        bank: Bank,
+        hotel_chain_manager: HotelChainManager,
+        players: Vec<Player>,
+        position_cards: Vec<Position>,
+        current_player: usize,
+        seed: u64,
+    ) -> Self {
+        Self {
+            board,
+            bank,
+            hotel_chain_manager,
+            players,
+            position_cards,
+            current_player,
+            turns: 0,
+            rng: StdRng::seed_from_u64(seed),
+            recorded_decisions: None,
+        }
+    }
+
+    /// Returns a copy of the playout in which no player has a bot. The copy continues exactly
+    /// like the playout when the same bots are given to the players again.
+    pub fn headless_copy(&self) -> Playout {
+        Self {
+            board: self.board.clone(),
+            bank: self.bank.clone(),
+            hotel_chain_manager: self.hotel_chain_manager.clone(),
+            players: self.players.iter().map(Player::headless_copy).collect(),
+            position_cards: self.position_cards.clone(),
+            current_player: self.current_player,
+            turns: self.turns,
+            rng: self.rng.clone(),
+            recorded_decisions: None,
+        }
+    }
+
+    /// Returns the number of turns that have been played
+    pub fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    /// Turns the playout into a game that is continued with the settings.
+    ///
+    /// The players are ordered so that the player that has the next turn plays first, their ids
+    /// are assigned again by the new order.
+    pub fn into_game(mut self, settings: Settings) -> GameManager {
+        self.players.rotate_left(self.current_player);
+        let old_ids: Vec<u32> = self.players.iter().map(|player| player.id).collect();
+        for (index, player) in self.players.iter_mut().enumerate() {
+            player.id = index as u32;
+        }
+        for payout in &mut self.bank.bonus_payouts {
+            payout.player_id = old_ids
+                .iter()
+                .position(|id| *id == payout.player_id)
+                .unwrap() as u32;
+        }
+        // The shareholders are stored by the ids of the players
+        for chain in HotelChain::iterator() {
+            self.bank.mark_traded(chain);
+        }
+        self.bank.update_largest_shareholders(&self.players);
+        GameManager::from_position(
+            self.board,
+            self.bank,
+            self.hotel_chain_manager,
+            // The game draws from the front, the playout from the back
+            self.position_cards.into_iter().rev().collect(),
+            self.players,
+            settings,
+        )
+    }
+
+    /// Starts to record the decisions of the bots together with the game state in which they
+    /// have been made.
+    pub fn record_decisions(&mut self) {
+        self.recorded_decisions = Some(Vec::new());
+    }
+
+    /// Returns the decisions that have been recorded so far and clears the record.
+    pub fn take_recorded_decisions(&mut self) -> Vec<RecordedDecision> {
+        match &mut self.recorded_decisions {
+            Some(decisions) => std::mem::take(decisions),
+            None => Vec::new(),
+        }
+    }
+
+    /// Records the decision of the player when decisions are recorded.
+    fn record(&mut self, player_index: usize, action: Value) {
+        if self.recorded_decisions.is_none() {
+            return;
+        }
+        let decision = RecordedDecision {
+            player_id: self.players[player_index].id,
+            state: self.state_json(player_index),
+            action,
+        };
+        self.recorded_decisions.as_mut().unwrap().push(decision);
+    }
+
+    /// Shows the game to the bot of the player before it makes a decision, see
+    /// [`crate::bot::Bot::observe_state`].
+    fn show_state(&self, player_index: usize) {
+        if let Some(bot) = &self.players[player_index].bot {
+            bot.observe_state(&|| self.state_json(player_index));
+        }
+    }
+
+    /// Returns the game as the player sees it. Only the cards of the player are included.
+    fn state_json(&self, player_index: usize) -> Value {
+        let hotels: Vec<Value> = GameManager::init_position_cards()
+            .iter()
+            .filter_map(|position| {
+                self.board.is_hotel_placed(position).map(|chain| {
+                    json!({
+                        "position": position.to_string(),
+                        "chain": chain.map(|chain| chain.name().to_string()),
+                    })
+                })
+            })
+            .collect();
+        let chains: Vec<Value> = HotelChain::iterator()
+            .filter(|chain| self.hotel_chain_manager.chain_status(chain))
+            .map(|chain| {
+                json!({
+                    "chain": chain.name(),
+                    "hotels": self.hotel_chain_manager.chain_length(chain),
+                    "price": Bank::stock_price(&self.hotel_chain_manager, chain),
+                    "safe": self.hotel_chain_manager.is_chain_safe(chain),
+                    "stocks_available": self.bank.stocks_available(chain, &self.hotel_chain_manager),
+                })
+            })
+            .collect();
+        let players: Vec<Value> = self
+            .players
+            .iter()
+            .map(|player| {
+                json!({
+                    "id": player.id,
+                    "money": player.money,
+                    "stocks": stocks_json(&player.owned_stocks.iter().collect()),
+                })
+            })
+            .collect();
+        let hand: Vec<String> = self.players[player_index]
+            .analyzed_cards
+            .iter()
+            .map(|card| card.position.to_string())
+            .collect();
+        json!({
+            "turn": self.turns,
+            "hotels": hotels,
+            "chains": chains,
+            "players": players,
+            "hand": hand,
+        })
+    }
+
+    /// Plays a turn for the current player.
+    ///
+    /// When a game ending condition is met the player can end the game at the start of their
+    /// turn.
+    /// # Returns
+    /// * `Ok(true)` - The turn has been played
+    /// * `Ok(false)` - The game has ended, no turn has been played
+    pub fn step(&mut self) -> Result<bool> {
+        if !can_game_continue(
+            &self.players,
+            &self.board,
+            &self.hotel_chain_manager,
+            !self.position_cards.is_empty(),
+        ) {
+            return Ok(false);
+        }
+        let player = &mut self.players[self.current_player];
+        player.analyze_cards(&self.board, &self.hotel_chain_manager);
+        let player = &self.players[self.current_player];
+        if check_end_condition(&self.board, &self.hotel_chain_manager).is_some() {
+            let end_game = match &player.bot {
+                Some(bot) => {
+                    self.show_state(self.current_player);
+                    let end_game = bot
+                        .end_game(player, &self.players, &self.hotel_chain_manager)
+                        .choice;
+                    self.record(
+                        self.current_player,
+                        json!({"type": "end_game", "end": end_game}),
+                    );
+                    end_game
+                }
+                None => true,
+            };
+            if end_game {
+                return Ok(false);
+            }
+        }
+        let player = &self.players[self.current_player];
+        if player.only_illegal_cards() {
+            // Cards that would start an illegal fusion can never be played, they are replaced
+            // at the end of the turn
+            self.players[self.current_player]
+                .analyzed_cards
+                .retain(|card| {
+                    card.place_hotel_case
+                        != PlaceHotelCase::Illegal(IllegalPlacement::FusionIllegal)
+                });
+        } else {
+            let card = match &player.bot {
+                Some(bot) => {
+                    self.show_state(self.current_player);
+                    bot.observe(player, &self.board, &self.players);
+                    let card = bot
+                        .choose_card(player, &self.bank, &self.hotel_chain_manager)
+                        .choice;
+                    self.record(
+                        self.current_player,
+                        json!({"type": "place_card", "card": card.to_string()}),
+                    );
+                    card
+                }
+                None => {
+                    let legal_cards: Vec<Position> = player
+                        .analyzed_cards
+                        .iter()
+                        .filter(|card| !card.is_illegal())
+                        .map(|card| card.position)
+                        .collect();
+                    *legal_cards.choose(&mut self.rng).unwrap()
+                }
+            };
+            self.place_card(card)?;
+        }
+        if !self.hotel_chain_manager.active_chains().is_empty() {
+            self.buy_phase()?;
+        }
+        self.end_turn();
+        Ok(true)
+    }
+
+    /// Places the card of the current player on the board.
+    /// Chains are founded, extended and fused like in the real game.
+    pub fn place_card(&mut self, position: Position) -> Result<()> {
+        let place_hotel_case = analyze_position(&position, &self.board, &self.hotel_chain_manager);
+        self.players[self.current_player].remove_card(&position)?;
+        self.board.place_hotel(&position)?;
+        match place_hotel_case {
+            PlaceHotelCase::SingleHotel => (),
+            PlaceHotelCase::NewChain(positions) => {
+                let available_chains = self.hotel_chain_manager.available_chains().unwrap();
+                let player = &self.players[self.current_player];
+                let chain = match &player.bot {
+                    Some(bot) => {
+                        self.show_state(self.current_player);
+                        let chain = bot
+                            .choose_chain_to_found(
+                                player,
+                                &available_chains,
+                                &self.bank,
+                                &self.hotel_chain_manager,
+                            )
+                            .choice;
+                        self.record(
+                            self.current_player,
+                            json!({"type": "found_chain", "chain": chain.name()}),
+                        );
+                        chain
+                    }
+                    None => *available_chains.choose(&mut self.rng).unwrap(),
+                };
+                self.hotel_chain_manager.start_chain(
+                    chain,
+                    positions,
+                    &mut self.board,
+                    &mut self.players[self.current_player],
+                    &mut self.bank,
+                )?;
+            }
+            PlaceHotelCase::ExtendsChain(chain, positions) => {
+                for position in positions {
+                    self.hotel_chain_manager.add_hotel_to_chain(
+                        &chain,
+                        position,
+                        &mut self.board,
+                    )?;
+                }
+            }
+            PlaceHotelCase::Fusion(chains, origin) => self.fuse_chains(chains, origin)?,
+            PlaceHotelCase::Illegal(reason) => {
+                return Err(miette!(
+                    "Unable to place card {} in playout: {}",
+                    position,
+                    reason.description()
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Fuses the chains into the longest chain. If multiple chains are the longest the current
+    /// player decides which chain survives. The bonuses are payed and starting with the current
+    /// player each player handles their stocks of the dead chains.
+    fn fuse_chains(&mut self, mut chains: Vec<HotelChain>, origin: Position) -> Result<()> {
+        chains.shuffle(&mut self.rng);
+        chains.sort_by_key(|chain| Reverse(self.hotel_chain_manager.chain_length(chain)));
+        let longest_chains: Vec<HotelChain> = chains
+            .iter()
+            .filter(|chain| {
+                self.hotel_chain_manager.chain_length(chain)
+                    == self.hotel_chain_manager.chain_length(&chains[0])
+            })
+            .copied()
+            .collect();
+        let player = &self.players[self.current_player];
+        let alive = match &player.bot {
+            Some(bot) if longest_chains.len() > 1 => {
+                self.show_state(self.current_player);
+                let alive = bot
+                    .choose_surviving_chain(
+                        player,
+                        &longest_chains,
+                        &self.bank,
+                        &self.hotel_chain_manager,
+                    )
+                    .choice;
+                self.record(
+                    self.current_player,
+                    json!({"type": "choose_surviving_chain", "chain": alive.name()}),
+                );
+                alive
+            }
+            _ => chains[0],
+        };
+        for dead in chains.iter().filter(|chain| **chain != alive) {
+            self.bank.update_largest_shareholders(&self.players);
+            self.bank.give_majority_shareholder_bonuses(
+                &mut self.players,
+                dead,
+                &self.hotel_chain_manager,
+                false,
+            )?;
+            for i in 0..self.players.len() {
+                let player_index = (self.current_player + i) % self.players.len();
+                self.handle_fusion_stocks(player_index, dead, &alive)?;
+            }
+            self.hotel_chain_manager
+                .fuse_chains(&alive, dead, &mut self.board)?;
+        }
+        if let PlaceHotelCase::ExtendsChain(chain, positions) =
+            analyze_position(&origin, &self.board, &self.hotel_chain_manager)
+        {
+            for position in positions {
+                self.hotel_chain_manager
+                    .add_hotel_to_chain(&chain, position, &mut self.board)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The player exchanges and sells their stocks of the dead chain.
+    /// Players without bot sell all stocks.
+    fn handle_fusion_stocks(
+        &mut self,
+        player_index: usize,
+        dead: &HotelChain,
+        alive: &HotelChain,
+    ) -> Result<()> {
+        let player = &self.players[player_index];
+        let stocks = *player.owned_stocks.stocks_for_hotel(dead);
+        if stocks == 0 {
+            return Ok(());
+        }
+        let (exchange, sell) = match &player.bot {
+            Some(bot) => {
+                self.show_state(player_index);
+                let (exchange, sell) = bot
+                    .handle_fusion_stocks(
+                        player,
+                        dead,
+                        alive,
+                        &self.bank,
+                        &self.hotel_chain_manager,
+                    )
+                    .choice;
+                self.record(
+                    player_index,
+                    json!({
+                        "type": "handle_fusion_stocks",
+                        "dead": dead.name(),
+                        "alive": alive.name(),
+                        "exchange": exchange,
+                        "sell": sell,
+                    }),
+                );
+                (exchange, sell)
+            }
+            None => (0, stocks),
+        };
+        let mut transaction = self.bank.transaction(&mut self.players[player_index]);
+        if exchange > 0 {
+            transaction.exchange_stock(exchange, dead, alive)?;
+        }
+        if sell > 0 {
+            transaction.sell_stock(sell, dead, &self.hotel_chain_manager)?;
+        }
+        transaction.commit();
+        Ok(())
+    }
+
+    /// The current player may sell stocks, when the house rule is active, and buys stocks.
+    fn buy_phase(&mut self) -> Result<()> {
+        let player = &self.players[self.current_player];
+        let bot = match &player.bot {
+            Some(bot) => bot,
+            None => return self.buy_random_stocks(),
+        };
+        bot.observe(player, &self.board, &self.players);
+        self.show_state(self.current_player);
+        if self.bank.rules.sell_during_buy_phase {
+            let stocks_sold = bot
+                .sell_stocks(player, &self.bank, &self.hotel_chain_manager)
+                .choice;
+            self.record(
+                self.current_player,
+                json!({"type": "sell_stocks", "stocks": stocks_json(&stocks_sold)}),
+            );
+            for (chain, amount) in stocks_sold {
+                self.bank.sell_stock(
+                    &mut self.players[self.current_player],
+                    amount,
+                    &chain,
+                    &self.hotel_chain_manager,
+                )?;
+            }
+        }
+        if self.bank.rules.sell_during_buy_phase {
+            self.show_state(self.current_player);
+        }
+        let player = &self.players[self.current_player];
+        let stocks_bought = player
+            .bot
+            .as_ref()
+            .unwrap()
+            .buy_stocks(player, &self.bank, &self.hotel_chain_manager)
+            .choice;
+        self.record(
+            self.current_player,
+            json!({"type": "buy_stocks", "stocks": stocks_json(&stocks_bought)}),
+        );
+        self.buy_stocks(&stocks_bought)
+    }
+
+    /// The current player buys the stocks.
+    /// When not all stocks can be bought, no stock is bought.
+    pub fn buy_stocks(&mut self, stocks: &HashMap<HotelChain, u32>) -> Result<()> {
+        let mut transaction = self
+            .bank
+            .transaction(&mut self.players[self.current_player]);
+        transaction.buy_stocks(&self.hotel_chain_manager, stocks)?;
+        transaction.commit();
+        self.bank.update_largest_shareholders(&self.players);
+        Ok(())
+    }
+
+    /// The current player buys random stocks that they can afford.
+    fn buy_random_stocks(&mut self) -> Result<()> {
+        let number_of_stocks = self.rng.gen_range(0..=PURCHASE_LIMIT);
+        for _i in 0..number_of_stocks {
+            let player = &self.players[self.current_player];
+            let purchasable: Vec<HotelChain> = HotelChain::iterator()
+                .copied()
+                .filter(|chain| {
+                    self.hotel_chain_manager.chain_status(chain)
+                        && *self.bank.stocks_available(chain, &self.hotel_chain_manager) > 0
+                        && Bank::stock_price(&self.hotel_chain_manager, chain) <= player.money
+                })
+                .collect();
+            match purchasable.choose(&mut self.rng) {
+                Some(chain) => self.bank.buy_stock(
+                    &self.hotel_chain_manager,
+                    chain,
+                    &mut self.players[self.current_player],
+                )?,
+                None => break,
+            }
+        }
+        self.bank.update_largest_shareholders(&self.players);
+        Ok(())
+    }
+
+    /// The current player draws new cards until they have six cards and the next player that
+    /// has not left the game gets their turn.
+    pub fn end_turn(&mut self) {
+        let player = &mut self.players[self.current_player];
+        while player.analyzed_cards.len() < 6 {
+            match self.position_cards.pop() {
+                Some(card) => player.add_card(&card, &self.board, &self.hotel_chain_manager),
+                None => break,
+            }
+        }
+        for _i in 0..self.players.len() {
+            self.current_player = (self.current_player + 1) % self.players.len();
+            if !self.players[self.current_player].resigned {
+                break;
+            }
+        }
+        self.turns += 1;
+    }
+
+    /// Pays the bonuses for all active chains and sells all stocks like it is done at the end of
+    /// the game.
+    pub fn final_account(&mut self) -> Result<()> {
+        self.bank.update_largest_shareholders(&self.players);
+        for chain in HotelChain::iterator() {
+            if !self.hotel_chain_manager.chain_status(chain) {
+                continue;
+            }
+            self.bank.give_majority_shareholder_bonuses(
+                &mut self.players,
+                chain,
+                &self.hotel_chain_manager,
+                false,
+            )?;
+            for player in self.players.iter_mut() {
+                let stocks = *player.owned_stocks.stocks_for_hotel(chain);
+                if stocks > 0 {
+                    self.bank
+                        .sell_stock(player, stocks, chain, &self.hotel_chain_manager)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns how much more the position of the player is worth than the position of the
+    /// strongest other player. The positions are rated with [`evaluate`].
+    pub fn score(&self, player_id: u32) -> i64 {
+        let state = GameState::new(
+            &self.board,
+            &self.bank,
+            &self.hotel_chain_manager,
+            &self.players,
+        );
+        let own_value = evaluate(&state, player_id);
+        let best_rival_value = self
+            .players
+            .iter()
+            .filter(|player| player.id != player_id)
+            .map(|player| evaluate(&state, player.id))
+            .fold(0.0, f64::max);
+        (own_value - best_rival_value).round() as i64
+    }
+}
+
+/// Returns the stocks as object that maps the names of the chains to the number of stocks.
+/// Chains without stocks are left out.
+fn stocks_json(stocks: &HashMap<HotelChain, u32>) -> Value {
+    let mut object = Map::new();
+    for chain in HotelChain::iterator() {
+        if let Some(amount) = stocks.get(chain).filter(|amount| **amount > 0) {
+            object.insert(chain.name().to_string(), json!(amount));
+        }
+    }
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::base_game::{
+        bank::Bank,
+        board::{Board, Position},
+        player::Player,
+    };
+    use crate::game::hotel_chain_manager::HotelChainManager;
+
+    use super::{Observation, Playout};
+
+    #[test]
+    fn playout_is_reproducible() -> Result<()> {
+        let board = Board::new();
+        let bank = Bank::new();
+        let hotel_chain_manager = HotelChainManager::new();
+        let players = vec![
+            Player::new(
+                vec![Position::new('A', 1), Position::new('B', 1)],
+                0,
+                false,
+                String::from("Player 1"),
+            ),
+            Player::new(vec![], 1, false, String::from("Player 2")),
+        ];
+        let observation = Observation::new(&players[0], &board, &players);
+        let mut scores = Vec::new();
+        for _i in 0..2 {
+            let mut playout =
+                Playout::new(&players[0], &observation, &bank, &hotel_chain_manager, 42);
+            for _turn in 0..40 {
+                if !playout.step()? {
+                    break;
+                }
+            }
+            scores.push(playout.score(0));
+        }
+        assert_eq!(scores[0], scores[1]);
+        Ok(())
+    }
+}
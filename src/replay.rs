@@ -0,0 +1,605 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use miette::{miette, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_json::{json, Value};
+
+use crate::{
+    base_game::{
+        bank::Bank,
+        board::Position,
+        hotel_chains::HotelChain,
+        player::Player,
+        settings::{Privacy, RulePreset, Settings},
+    },
+    bot::{Bot, BotStrategy, Decision},
+    engine::{chain_of, stocks_of},
+    game::{hotel_chain_manager::HotelChainManager, GameManager},
+    playout::{Playout, RecordedDecision},
+    simulation::{bot_players, MAX_TURNS},
+    utils::unique_name,
+};
+
+/// The name of the replay format, stored in the header of each replay.
+pub const REPLAY_FORMAT: &str = "acq";
+
+/// The version of the replay format that is written.
+///
+/// Replays of an older version can always be checked, replays of a newer version are refused.
+/// Replays without a version have been recorded before the format has been versioned and are
+/// read as version 0, their layout is the same as of version 1. Since version 2 only every
+/// [`KEYFRAME_INTERVAL`]th decision contains the whole game state.
+///
+/// The version has to be raised when a field is removed or its meaning is changed. Fields that
+/// are added do not need a new version: fields of the header that are unknown are ignored and
+/// fields that are missing in a recorded line are not checked, see [`check_replay`].
+pub const REPLAY_VERSION: u64 = 2;
+
+/// The number of decisions after which the whole game state is stored again in a replay.
+///
+/// The first decision and every decision after it that is a multiple of the interval away is a
+/// keyframe. The other decisions only contain the player, the action and the turn, their state is
+/// restored by playing the recorded actions again, see [`replay_states`].
+pub const KEYFRAME_INTERVAL: usize = 50;
+
+/// Describes a game between bots. The same description always leads to the same game.
+///
+/// The description is stored in the first line of a replay.
+pub struct ReplayHeader {
+    /// The strategies of the bots, the first bot sits on the first seat
+    pub strategies: Vec<BotStrategy>,
+    /// The seed that is used to deal the cards and for the random decisions of the bots
+    pub seed: u64,
+    /// The rules with which the game is played
+    pub rule_preset: RulePreset,
+    /// The number of playouts the Monte Carlo bots play for each decision
+    pub bot_playouts: u32,
+}
+
+impl ReplayHeader {
+    fn to_json(&self) -> Value {
+        let strategies: Vec<&str> = self
+            .strategies
+            .iter()
+            .map(|strategy| strategy.name())
+            .collect();
+        json!({
+            "format": REPLAY_FORMAT,
+            "version": REPLAY_VERSION,
+            "strategies": strategies,
+            "seed": self.seed,
+            "rules": self.rule_preset.name(),
+            "bot_playouts": self.bot_playouts,
+        })
+    }
+
+    fn from_json(header: &Value) -> Result<Self> {
+        let invalid = || miette!("Unable to read replay: The first line is not a valid header");
+        if !header["format"].is_null() && header["format"] != REPLAY_FORMAT {
+            return Err(invalid());
+        }
+        let version = match &header["version"] {
+            Value::Null => 0,
+            version => version.as_u64().ok_or_else(invalid)?,
+        };
+        if version > REPLAY_VERSION {
+            return Err(miette!(
+                "Unable to read replay: The replay has been recorded with version {} of the replay format but only versions up to {} are supported, update acquire_rs to check it",
+                version,
+                REPLAY_VERSION
+            ));
+        }
+        let strategies = header["strategies"]
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|name| name.as_str().and_then(BotStrategy::from_name))
+            .collect::<Option<Vec<BotStrategy>>>()
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            strategies,
+            seed: header["seed"].as_u64().ok_or_else(invalid)?,
+            rule_preset: header["rules"]
+                .as_str()
+                .and_then(RulePreset::from_name)
+                .ok_or_else(invalid)?,
+            bot_playouts: header["bot_playouts"].as_u64().ok_or_else(invalid)? as u32,
+        })
+    }
+
+    /// Returns the settings with which the game is played
+    fn settings(&self) -> Settings {
+        let mut settings = Settings::new(false, Privacy::default(), true);
+        settings.rule_preset = self.rule_preset;
+        settings.rules = self.rule_preset.rules();
+        settings.bot_playouts = self.bot_playouts;
+        settings
+    }
+
+    /// Deals the cards and seats the bots, no turn has been played yet
+    fn deal(&self) -> Result<Playout> {
+        let settings = self.settings();
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let players = bot_players(&self.strategies, &settings, &mut rng);
+        Playout::new_game(players, settings.rules, rng.gen())
+    }
+}
+
+/// Returns the line of the replay that contains the decision
+fn decision_json(decision: RecordedDecision) -> Value {
+    json!({
+        "player": decision.player_id,
+        "state": decision.state,
+        "action": decision.action,
+    })
+}
+
+/// Returns the line of the replay that only contains the player, the action and the turn of the
+/// decision. It is written for every decision that is no keyframe, see [`KEYFRAME_INTERVAL`].
+fn compact_decision_json(decision: &Value) -> Value {
+    json!({
+        "player": decision["player"],
+        "state": {"turn": decision["state"]["turn"]},
+        "action": decision["action"],
+    })
+}
+
+/// Plays the game that is described by the header.
+/// # Returns
+/// * `Ok((decisions, result))` - Every decision with the whole game state in which it has been
+///   made and the money of the players after the final account
+/// * `Err` - The number of bots is invalid
+fn play_replay(header: &ReplayHeader) -> Result<(Vec<Value>, Value)> {
+    if !(2..=6).contains(&header.strategies.len()) {
+        return Err(miette!(
+            "Unable to record replay: The amount of bots is invalid. Valid: 2-6, entered: {}",
+            header.strategies.len()
+        ));
+    }
+    let mut playout = header.deal()?;
+    playout.record_decisions();
+    while playout.turns() < MAX_TURNS && playout.step()? {}
+    playout.final_account()?;
+    let decisions = playout
+        .take_recorded_decisions()
+        .into_iter()
+        .map(decision_json)
+        .collect();
+    let money: Vec<u32> = playout.players.iter().map(|player| player.money).collect();
+    Ok((decisions, json!({"turns": playout.turns(), "money": money})))
+}
+
+/// Plays the game that is described by the header and returns the replay.
+///
+/// The replay contains one json object per line. The first line is the header, then each
+/// decision of the bots follows. Keyframes contain the game state in which the decision has been
+/// made, the other decisions only the turn, see [`KEYFRAME_INTERVAL`]. The last line contains the
+/// money of the players after the final account.
+pub fn record_replay(header: &ReplayHeader) -> Result<Vec<String>> {
+    let (decisions, result) = play_replay(header)?;
+    let mut replay = vec![header.to_json().to_string()];
+    for (index, decision) in decisions.iter().enumerate() {
+        if index % KEYFRAME_INTERVAL == 0 {
+            replay.push(decision.to_string());
+        } else {
+            replay.push(compact_decision_json(decision).to_string());
+        }
+    }
+    replay.push(result.to_string());
+    Ok(replay)
+}
+
+/// Restores the game state of every decision of a replay by playing the recorded actions again.
+///
+/// The bots do not think again, so this is fast even for replays of Monte Carlo bots.
+/// # Arguments
+/// * `decisions` - The lines of the replay between the header and the result
+/// # Returns
+/// * `Ok(decisions)` - Every decision with the whole game state in which it has been made
+/// * `Err` - The actions can not be played again or lead to another game than the one that is
+///   recorded, the error names the first line that differs
+pub fn replay_states(header: &ReplayHeader, decisions: &[Value]) -> Result<Vec<Value>> {
+    let actions: VecDeque<Value> = decisions
+        .iter()
+        .map(|decision| decision["action"].clone())
+        .collect();
+    let actions = Arc::new(Mutex::new(actions));
+    let mut playout = header.deal()?;
+    for player in &mut playout.players {
+        player.bot = Some(Box::new(RecordedBot {
+            actions: Arc::clone(&actions),
+        }));
+    }
+    playout.record_decisions();
+    while !actions.lock().unwrap().is_empty() && playout.turns() < MAX_TURNS && playout.step()? {}
+    let replayed: Vec<Value> = playout
+        .take_recorded_decisions()
+        .into_iter()
+        .map(decision_json)
+        .collect();
+    for (index, recorded) in decisions.iter().enumerate() {
+        match replayed.get(index) {
+            Some(replayed) if contains(replayed, recorded) => (),
+            _ => {
+                return Err(miette!(
+                    "Unable to read replay: The recorded actions lead to another game at line {}",
+                    index + 2
+                ))
+            }
+        }
+    }
+    Ok(replayed)
+}
+
+/// Takes the seat of a bot while the states of a replay are restored and makes the decisions
+/// that have been recorded.
+///
+/// When an action can not be read a fallback is returned, the game then differs from the replay
+/// and this is detected by [`replay_states`].
+struct RecordedBot {
+    /// The actions of all bots that have not been made yet, in the order of the replay
+    actions: Arc<Mutex<VecDeque<Value>>>,
+}
+
+impl RecordedBot {
+    /// Makes the next recorded action.
+    /// # Arguments
+    /// * `parse` - Reads the choice from the action
+    /// * `fallback` - Is returned when the action can not be read or no action is left
+    fn decide<T>(&self, parse: impl FnOnce(&Value) -> Option<T>, fallback: T) -> Decision<T> {
+        let action = self.actions.lock().unwrap().pop_front().unwrap_or_default();
+        Decision::new(parse(&action).unwrap_or(fallback), "recorded in the replay")
+    }
+}
+
+impl Bot for RecordedBot {
+    fn choose_card(
+        &self,
+        player: &Player,
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<Position> {
+        self.decide(
+            |action| action["card"].as_str().and_then(Position::from_name),
+            player.analyzed_cards[0].position,
+        )
+    }
+
+    fn choose_chain_to_found(
+        &self,
+        _player: &Player,
+        available_chains: &[HotelChain],
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        self.decide(
+            |action| chain_of(action, available_chains).ok(),
+            available_chains[0],
+        )
+    }
+
+    fn choose_surviving_chain(
+        &self,
+        _player: &Player,
+        chains: &[HotelChain],
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HotelChain> {
+        self.decide(|action| chain_of(action, chains).ok(), chains[0])
+    }
+
+    fn handle_fusion_stocks(
+        &self,
+        _player: &Player,
+        _dead: &HotelChain,
+        _alive: &HotelChain,
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<(u32, u32)> {
+        self.decide(
+            |action| {
+                Some((
+                    action["exchange"].as_u64()? as u32,
+                    action["sell"].as_u64()? as u32,
+                ))
+            },
+            (0, 0),
+        )
+    }
+
+    fn buy_stocks(
+        &self,
+        _player: &Player,
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        self.decide(|action| stocks_of(action).ok(), HashMap::new())
+    }
+
+    fn sell_stocks(
+        &self,
+        _player: &Player,
+        _bank: &Bank,
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<HashMap<HotelChain, u32>> {
+        self.decide(|action| stocks_of(action).ok(), HashMap::new())
+    }
+
+    fn end_game(
+        &self,
+        _player: &Player,
+        _players: &[Player],
+        _hotel_chain_manager: &HotelChainManager,
+    ) -> Decision<bool> {
+        self.decide(|action| action["end"].as_bool(), true)
+    }
+}
+
+/// Reads the lines of the replay.
+/// # Returns
+/// * `Ok((header, lines))` - The header and every line of the replay as json, the header included
+/// * `Err` - The replay is empty, a line is no json or the header is invalid
+pub fn read_replay(replay: &str) -> Result<(ReplayHeader, Vec<Value>)> {
+    let lines: Vec<Value> = replay
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|error| {
+                miette!(
+                    "Unable to read replay: Line {} is invalid: {}",
+                    index + 1,
+                    error
+                )
+            })
+        })
+        .collect::<Result<_>>()?;
+    let header = ReplayHeader::from_json(
+        lines
+            .first()
+            .ok_or_else(|| miette!("Unable to read replay: The replay is empty"))?,
+    )?;
+    Ok((header, lines))
+}
+
+/// Plays the game of the replay again and checks that every decision and the result are the same.
+///
+/// The header is only compared by the game it describes. Fields that are missing in a recorded
+/// line are not checked, so replays that have been recorded before a field has been added to the
+/// game state can still be checked. Decisions that are no keyframe are checked by their player,
+/// action and turn.
+/// # Returns
+/// * `Ok(())` - The game has been played like it is recorded
+/// * `Err` - The game has diverged, the error names the first line that differs
+pub fn check_replay(replay: &str) -> Result<()> {
+    let (header, recorded) = read_replay(replay)?;
+    let (decisions, result) = play_replay(&header)?;
+    let mut replayed = vec![header.to_json()];
+    replayed.extend(decisions);
+    replayed.push(result);
+    for (index, replayed) in replayed.iter().enumerate().skip(1) {
+        match recorded.get(index) {
+            Some(recorded) if contains(replayed, recorded) => (),
+            Some(recorded) => {
+                return Err(miette!(
+                    "The game has diverged from the replay at line {}.\nRecorded: {}\nReplayed: {}",
+                    index + 1,
+                    recorded,
+                    replayed
+                ))
+            }
+            None => {
+                return Err(miette!(
+                    "The game has diverged from the replay: The game continues after the last line of the replay with: {}",
+                    replayed
+                ))
+            }
+        }
+    }
+    if recorded.len() > replayed.len() {
+        return Err(miette!(
+            "The game has diverged from the replay: The game has ended at line {} but the replay continues",
+            replayed.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Plays the game of the replay again until the turn and lets it continue as a live game.
+///
+/// The decisions before the turn are checked like in [`check_replay`]. The seats in `humans` are
+/// taken by humans that get the cards of the bot, all other seats are kept by their bot.
+/// # Arguments
+/// * `turn` - The turn with which the game continues, the first turn is turn 1
+/// * `humans` - The seats that are taken by humans, the first seat is seat 1
+/// * `settings` - The settings of the live game, the rules are taken from the replay
+pub fn branch_replay(
+    replay: &str,
+    turn: u32,
+    humans: &[u32],
+    mut settings: Settings,
+) -> Result<GameManager> {
+    let (header, recorded) = read_replay(replay)?;
+    let mut humans = humans.to_vec();
+    humans.sort_unstable();
+    humans.dedup();
+    if turn == 0 {
+        return Err(miette!("Unable to branch replay: The first turn is turn 1"));
+    }
+    if let Some(seat) = humans
+        .iter()
+        .find(|seat| !(1..=header.strategies.len() as u32).contains(seat))
+    {
+        return Err(miette!(
+            "Unable to branch replay: Seat {} does not exist. Valid: 1-{}",
+            seat,
+            header.strategies.len()
+        ));
+    }
+    let mut playout = header.deal()?;
+    playout.record_decisions();
+    while playout.turns() < turn - 1 {
+        if !playout.step()? {
+            return Err(miette!(
+                "Unable to branch replay: The game has ended after {} turns",
+                playout.turns()
+            ));
+        }
+    }
+    for (index, decision) in playout.take_recorded_decisions().into_iter().enumerate() {
+        let replayed = decision_json(decision);
+        match recorded.get(index + 1) {
+            Some(recorded) if contains(&replayed, recorded) => (),
+            _ => {
+                return Err(miette!(
+                    "Unable to branch replay: The game has diverged from the replay at line {}",
+                    index + 2
+                ))
+            }
+        }
+    }
+    for seat in &humans {
+        let names: Vec<String> = playout
+            .players
+            .iter()
+            .map(|player| player.name.clone())
+            .collect();
+        let player = &mut playout.players[*seat as usize - 1];
+        player.bot = None;
+        player.name = unique_name(&format!("Player {}", seat), &names);
+        player.small_board = settings.small_board;
+        player.privacy = settings.privacy;
+        player.auto_sort = settings.auto_sort;
+        player.skip_dialogues = settings.skip_dialogues;
+    }
+    settings.rule_preset = header.rule_preset;
+    settings.rules = header.rule_preset.rules();
+    settings.bots = header.strategies.len() as u32 - humans.len() as u32;
+    Ok(playout.into_game(settings))
+}
+
+/// Returns true when every field of `recorded` is contained in `replayed` with the same value.
+/// Arrays have to contain the same number of elements.
+fn contains(replayed: &Value, recorded: &Value) -> bool {
+    match (replayed, recorded) {
+        (Value::Object(replayed), Value::Object(recorded)) => recorded
+            .iter()
+            .all(|(key, value)| replayed.get(key).is_some_and(|v| contains(v, value))),
+        (Value::Array(replayed), Value::Array(recorded)) => {
+            replayed.len() == recorded.len()
+                && replayed.iter().zip(recorded).all(|(a, b)| contains(a, b))
+        }
+        (replayed, recorded) => replayed == recorded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use serde_json::json;
+
+    use crate::{
+        base_game::settings::{Privacy, Settings},
+        game::GameManager,
+    };
+
+    use super::{
+        branch_replay, check_replay, play_replay, read_replay, replay_states, KEYFRAME_INTERVAL,
+        REPLAY_VERSION,
+    };
+
+    #[test]
+    fn golden_replays_are_reproduced() -> Result<()> {
+        check_replay(include_str!("../replays/greedy-heuristic-random.jsonl"))?;
+        check_replay(include_str!(
+            "../replays/heuristic-heuristic-house-rules.jsonl"
+        ))?;
+        check_replay(include_str!("../replays/random-greedy-1999-rules.acq"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn games_can_be_branched_from_a_replay() -> Result<()> {
+        let replay = include_str!("../replays/greedy-heuristic-random.jsonl");
+        let settings = || Settings::new(false, Privacy::default(), true);
+        let game = branch_replay(replay, 8, &[2], settings())?;
+        // The position at the start of turn 8 is the state of the first decision of that turn
+        let (_, lines) = read_replay(replay)?;
+        let line = lines
+            .iter()
+            .find(|line| line["state"]["turn"] == 7)
+            .unwrap();
+        let state = &line["state"];
+        let hotels = GameManager::init_position_cards()
+            .iter()
+            .filter(|position| game.board.is_hotel_placed(position).is_some())
+            .count();
+        assert_eq!(hotels, state["hotels"].as_array().unwrap().len());
+        // The player whose turn it is plays first
+        let current = state["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|player| player["id"] == line["player"])
+            .unwrap();
+        assert_eq!(game.players[0].id, 0);
+        assert_eq!(current["money"], game.players[0].money);
+        let human = game
+            .players
+            .iter()
+            .find(|player| player.name == "Player 2")
+            .unwrap();
+        assert!(human.bot.is_none());
+        assert_eq!(game.players.iter().filter(|p| p.bot.is_some()).count(), 2);
+        assert!(branch_replay(replay, 1000, &[], settings()).is_err());
+        assert!(branch_replay(replay, 8, &[4], settings()).is_err());
+        // A game that is only played by bots can be continued to the end
+        let mut game = branch_replay(replay, 8, &[], settings())?;
+        game.continue_game()?;
+        Ok(())
+    }
+
+    #[test]
+    fn replays_stay_compatible() -> Result<()> {
+        let replay = include_str!("../replays/random-greedy-1999-rules.acq");
+        // Fields that are unknown in the header and fields that are missing in a line are ignored
+        let changed = replay
+            .replacen("\"format\"", "\"comment\":\"added later\",\"format\"", 1)
+            .replacen(",\"turn\":0}", "}", 1);
+        assert_ne!(changed, replay);
+        check_replay(&changed)?;
+        // Replays of newer versions of the format are refused
+        let newer = replay.replacen(
+            &format!("\"version\":{}", REPLAY_VERSION),
+            &format!("\"version\":{}", REPLAY_VERSION + 1),
+            1,
+        );
+        assert!(check_replay(&newer).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn states_are_restored_between_keyframes() -> Result<()> {
+        let replay = include_str!("../replays/random-greedy-1999-rules.acq");
+        let (header, lines) = read_replay(replay)?;
+        let decisions = &lines[1..lines.len() - 1];
+        assert!(decisions[1]["state"]["hotels"].is_null());
+        assert!(!decisions[KEYFRAME_INTERVAL]["state"]["hotels"].is_null());
+        let (replayed, _) = play_replay(&header)?;
+        assert_eq!(replay_states(&header, decisions)?, replayed);
+        // An action that can not be made again is detected
+        let mut changed = decisions.to_vec();
+        changed[1]["action"] = json!({"type": "changed"});
+        assert!(replay_states(&header, &changed).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn divergence_is_detected() {
+        let replay = include_str!("../replays/greedy-heuristic-random.jsonl");
+        let changed = replay.replacen("\"place_card\"", "\"changed\"", 1);
+        assert!(check_replay(&changed).is_err());
+    }
+}
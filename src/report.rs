@@ -0,0 +1,289 @@
+use std::fs;
+
+use miette::{IntoDiagnostic, Result};
+use owo_colors::Rgb;
+
+use crate::{
+    base_game::{board::Board, hotel_chains::HotelChain, player::Player},
+    game::{player_by_id, ranking, GameManager},
+    history::today,
+};
+
+/// The width of the charts in pixels
+const CHART_WIDTH: u32 = 640;
+
+/// The height of the charts in pixels
+const CHART_HEIGHT: u32 = 240;
+
+/// The space around the charts in which the axes are labeled, in pixels
+const CHART_MARGIN: u32 = 40;
+
+/// The style sheet of the report
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em auto; max-width: 60em; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid #999; padding: 0.2em 0.6em; }
+table.board td { width: 1.6em; height: 1.6em; padding: 0; text-align: center; color: #fff; }
+table.board td.empty { background: #eee; }
+table.board td.hotel { background: #555; }
+.legend span { margin-right: 1em; }
+.round { display: flex; gap: 2em; align-items: flex-start; }";
+
+/// Returns the report of a finished game as standalone html page.
+///
+/// The report contains the final standings, charts of the net worth of the players and of the
+/// stock prices and for each round the board at the end of the round and what has happened in it.
+pub fn game_report(game: &GameManager) -> String {
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n<title>Acquire game report</title>\n");
+    html.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+    html.push_str("<h1>Acquire game report</h1>\n");
+    html.push_str(&format!(
+        "<p>Played on {} with the {} rules.</p>\n",
+        today(),
+        game.settings.rule_preset.name()
+    ));
+    html.push_str("<h2>Final standings</h2>\n<table>\n");
+    html.push_str("<tr><th>Rank</th><th>Player</th><th>Money</th></tr>\n");
+    for (rank, player) in ranking(&game.players) {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}€</td></tr>\n",
+            rank,
+            player_html(player),
+            player.money
+        ));
+    }
+    html.push_str("</table>\n");
+    let rounds = game.boards.len();
+    let net_worth: Vec<(String, Rgb, Vec<Option<u32>>)> = game
+        .players
+        .iter()
+        .map(|player| {
+            let mut values = vec![None; rounds];
+            for finances in game
+                .finances
+                .iter()
+                .filter(|finances| finances.player_id == player.id)
+            {
+                if let Some(value) = values.get_mut(finances.round as usize - 1) {
+                    *value = Some(finances.net_worth());
+                }
+            }
+            (player.name.clone(), player.color, values)
+        })
+        .collect();
+    html.push_str("<h2>Net worth at the end of each round</h2>\n");
+    html.push_str(&line_chart(&net_worth));
+    let prices: Vec<(String, Rgb, Vec<Option<u32>>)> = HotelChain::iterator()
+        .map(|chain| {
+            (
+                chain.name().to_string(),
+                chain.color(),
+                game.bank.market_history.prices(chain),
+            )
+        })
+        .filter(|(_, _, prices)| prices.iter().any(Option::is_some))
+        .collect();
+    html.push_str("<h2>Stock prices at the end of each round</h2>\n");
+    html.push_str(&line_chart(&prices));
+    html.push_str("<h2>Rounds</h2>\n");
+    for (index, board) in game.boards.iter().enumerate() {
+        let round = index as u32 + 1;
+        html.push_str(&format!(
+            "<h3>Round {}</h3>\n<div class=\"round\">\n",
+            round
+        ));
+        html.push_str(&board_html(board));
+        html.push_str("<ul>\n");
+        for event in game.events.iter().filter(|event| event.round == round) {
+            let name = match event
+                .player_id
+                .and_then(|id| player_by_id(id, &game.players))
+            {
+                Some(player) => player_html(player),
+                None => String::from("Neutral player"),
+            };
+            html.push_str(&format!("<li>{}: {}</li>\n", name, escape(&event.text)));
+        }
+        html.push_str("</ul>\n</div>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Writes the report of the game to the file, an existing file is overwritten.
+pub fn export_report(path: &str, game: &GameManager) -> Result<()> {
+    fs::write(path, game_report(game)).into_diagnostic()
+}
+
+/// Returns the name of the player in their color
+fn player_html(player: &Player) -> String {
+    format!(
+        "<span style=\"color: {}\">{}</span>",
+        hex(player.color),
+        escape(&player.name)
+    )
+}
+
+/// Returns the board as table, the hotels of a chain are shown in the color of the chain
+fn board_html(board: &Board) -> String {
+    let mut html = String::from("<table class=\"board\">\n");
+    for line in board.lines() {
+        html.push_str("<tr>");
+        for piece in line {
+            match (piece.piece_set, piece.chain) {
+                (false, _) => html.push_str("<td class=\"empty\"></td>"),
+                (true, None) => html.push_str(&format!(
+                    "<td class=\"hotel\" title=\"{}\"></td>",
+                    piece.position
+                )),
+                (true, Some(chain)) => html.push_str(&format!(
+                    "<td style=\"background: {}\" title=\"{} {}\">{}</td>",
+                    hex(chain.color()),
+                    piece.position,
+                    chain.name(),
+                    chain.identifier()
+                )),
+            }
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// Returns a svg chart with one line for each series, the values are given per round.
+///
+/// A series has no value in a round when it is `None`, the line is interrupted there.
+fn line_chart(series: &[(String, Rgb, Vec<Option<u32>>)]) -> String {
+    let rounds = series
+        .iter()
+        .map(|(_, _, values)| values.len())
+        .max()
+        .unwrap_or(0);
+    let max = series
+        .iter()
+        .flat_map(|(_, _, values)| values.iter().flatten())
+        .max()
+        .copied()
+        .unwrap_or(0)
+        .max(1);
+    let width = CHART_WIDTH - 2 * CHART_MARGIN;
+    let height = CHART_HEIGHT - 2 * CHART_MARGIN;
+    let x = |round: usize| {
+        CHART_MARGIN as f64 + width as f64 * round as f64 / (rounds.max(2) - 1) as f64
+    };
+    let y = |value: u32| CHART_MARGIN as f64 + height as f64 * (1.0 - value as f64 / max as f64);
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
+        CHART_WIDTH, CHART_HEIGHT
+    );
+    svg.push_str(&format!(
+        "<polyline points=\"{m},{m} {m},{b} {r},{b}\" fill=\"none\" stroke=\"#999\"/>\n",
+        m = CHART_MARGIN,
+        b = CHART_HEIGHT - CHART_MARGIN,
+        r = CHART_WIDTH - CHART_MARGIN
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"12\">{}</text>\n",
+        CHART_MARGIN - 4,
+        CHART_MARGIN + 4,
+        max
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"12\">0</text>\n",
+        CHART_MARGIN - 4,
+        CHART_HEIGHT - CHART_MARGIN + 4
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"12\">Round</text>\n",
+        CHART_WIDTH / 2,
+        CHART_HEIGHT - CHART_MARGIN / 3
+    ));
+    for (_, color, values) in series {
+        // Each part of the line in which the series has values is drawn on its own
+        for part in values
+            .iter()
+            .enumerate()
+            .collect::<Vec<(usize, &Option<u32>)>>()
+            .split(|(_, value)| value.is_none())
+            .filter(|part| !part.is_empty())
+        {
+            if let [(round, Some(value))] = part {
+                // A single value would not be visible as line
+                svg.push_str(&format!(
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"2\" fill=\"{}\"/>\n",
+                    x(*round),
+                    y(*value),
+                    hex(*color)
+                ));
+                continue;
+            }
+            let points: Vec<String> = part
+                .iter()
+                .map(|(round, value)| format!("{:.1},{:.1}", x(*round), y(value.unwrap())))
+                .collect();
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+                points.join(" "),
+                hex(*color)
+            ));
+        }
+    }
+    svg.push_str("</svg>\n<p class=\"legend\">");
+    for (name, color, _) in series {
+        svg.push_str(&format!(
+            "<span style=\"color: {}\">&#9632; {}</span>",
+            hex(*color),
+            escape(name)
+        ));
+    }
+    svg.push_str("</p>\n");
+    svg
+}
+
+/// Returns the color as hex code like `#00afff`
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+/// Replaces the characters that have a meaning in html
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::settings::{Privacy, Settings},
+        replay::branch_replay,
+    };
+
+    use super::game_report;
+
+    #[test]
+    fn report_contains_each_round() -> Result<()> {
+        let replay = include_str!("../replays/greedy-heuristic-random.jsonl");
+        let settings = Settings::new(false, Privacy::default(), true);
+        let mut game = branch_replay(replay, 1, &[], settings)?;
+        game.players[0].name = String::from("<Anna & Ben>");
+        game.continue_game()?;
+        assert!(!game.boards.is_empty());
+        assert_eq!(game.finances.len(), game.boards.len() * game.players.len());
+        assert!(game
+            .events
+            .iter()
+            .any(|event| event.text.starts_with("founded")));
+        let report = game_report(&game);
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains(&format!("<h3>Round {}</h3>", game.boards.len())));
+        assert!(report.contains("&lt;Anna &amp; Ben&gt;"));
+        assert!(!report.contains("<Anna"));
+        assert_eq!(report.matches("<svg").count(), 2);
+        Ok(())
+    }
+}
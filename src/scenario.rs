@@ -0,0 +1,159 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use miette::{miette, IntoDiagnostic, Result};
+use serde_json::Value;
+
+use crate::engine::Engine;
+
+/// The directory in which the scenarios are stored
+pub const SCENARIO_DIR: &str = "scenarios";
+
+/// Plays the scenario and checks that the game turns out like it is expected.
+///
+/// A scenario is a json file that contains the configuration of the game, see [`Engine::new`],
+/// usually with a `position` from which the game starts. The `steps` are played one after
+/// another:
+/// ```json
+/// {"description": "Buying stocks of a chain without stocks left is refused",
+///  "seats": ["external", "external"], "seed": 1,
+///  "position": {"hotels": [{"position": "A1", "chain": "Luxor"}, {"position": "A2", "chain": "Luxor"}],
+///               "players": [{"stocks": {"Luxor": 25}, "hand": ["I12"]}]},
+///  "steps": [{"player": 0, "action": {"type": "place_card", "card": "I12"}},
+///            {"action": {"type": "buy_stocks", "stocks": {"Luxor": 1}}, "rejected": true},
+///            {"expect": {"decision": {"type": "buy_stocks"}, "state": {"players": [{"money": 6000}, {}]}}}]}
+/// ```
+/// Each step can contain the `player` that has to decide, the `action` that is applied, if the
+/// action is `rejected` and what is `expect`ed of the state of the game afterwards, see
+/// [`Engine::state`]. Only the fields that are given are compared, arrays have to contain the
+/// same number of elements.
+/// # Returns
+/// * `Ok(())` - The game has turned out like it is expected
+/// * `Err` - A step has failed, the error names the step
+pub fn run_scenario(scenario: &str) -> Result<()> {
+    let scenario: Value = serde_json::from_str(scenario).map_err(|err| {
+        miette!(
+            "Unable to read scenario: The file is not valid json: {}",
+            err
+        )
+    })?;
+    let mut engine = Engine::new(&scenario)?;
+    let steps = scenario["steps"]
+        .as_array()
+        .ok_or_else(|| miette!("Unable to read scenario: The steps are missing"))?;
+    for (index, step) in steps.iter().enumerate() {
+        run_step(&mut engine, step).map_err(|err| err.wrap_err(format!("Step {}", index + 1)))?;
+    }
+    Ok(())
+}
+
+/// Applies the action of the step and compares the state of the game with the expectation.
+fn run_step(engine: &mut Engine, step: &Value) -> Result<()> {
+    if let Some(player) = step.get("player") {
+        let deciding = &engine.state()["player"];
+        if deciding != player {
+            return Err(miette!(
+                "Player {} has to decide but player {} is expected",
+                deciding,
+                player
+            ));
+        }
+    }
+    if let Some(action) = step.get("action") {
+        let rejected = step["rejected"].as_bool().unwrap_or_default();
+        match (engine.apply(action), rejected) {
+            (Ok(()), true) => return Err(miette!("The action {} has not been rejected", action)),
+            (Err(err), false) => return Err(err),
+            _ => (),
+        }
+    }
+    if let Some(expected) = step.get("expect") {
+        if let Some(difference) = difference(&engine.state(), expected, "") {
+            return Err(miette!(
+                "The game differs from the expectation: {}",
+                difference
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns where `actual` differs from `expected` when a field of `expected` is missing in
+/// `actual` or has another value. Arrays have to contain the same number of elements.
+fn difference(actual: &Value, expected: &Value, path: &str) -> Option<String> {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            expected.iter().find_map(|(key, value)| {
+                let path = format!("{}.{}", path, key);
+                match actual.get(key) {
+                    Some(actual) => difference(actual, value, &path),
+                    None => Some(format!("{} is missing", path)),
+                }
+            })
+        }
+        (Value::Array(actual), Value::Array(expected)) if actual.len() == expected.len() => actual
+            .iter()
+            .zip(expected)
+            .enumerate()
+            .find_map(|(index, (actual, expected))| {
+                difference(actual, expected, &format!("{}[{}]", path, index))
+            }),
+        (actual, expected) if actual == expected => None,
+        (actual, expected) => Some(format!("{} is {} instead of {}", path, actual, expected)),
+    }
+}
+
+/// Returns the scenario files at the paths. The `.json` files of a directory are returned in the
+/// order of their names.
+pub fn scenario_files<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            files.push(path.to_path_buf());
+            continue;
+        }
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(path).into_diagnostic()? {
+            let entry = entry.into_diagnostic()?.path();
+            if entry
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                entries.push(entry);
+            }
+        }
+        entries.sort();
+        files.append(&mut entries);
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use miette::{IntoDiagnostic, Result};
+
+    use super::{run_scenario, scenario_files, SCENARIO_DIR};
+
+    #[test]
+    fn scenarios_turn_out_as_expected() -> Result<()> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(SCENARIO_DIR);
+        let files = scenario_files(&[dir])?;
+        assert!(!files.is_empty());
+        for file in files {
+            let scenario = fs::read_to_string(&file).into_diagnostic()?;
+            run_scenario(&scenario).map_err(|err| err.wrap_err(file.display().to_string()))?;
+        }
+        // A wrong expectation is detected
+        let scenario = r#"{"seats": ["external", "external"], "seed": 1,
+            "position": {"players": [{"money": 100}]},
+            "steps": [{"expect": {"state": {"players": [{"money": 200}, {}]}}}]}"#;
+        let err = run_scenario(scenario).unwrap_err();
+        assert_eq!(err.to_string(), "Step 1");
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+use crate::base_game::{
+    hotel_chains::{HotelChain, NUMBER_OF_CHAINS},
+    player::Player,
+};
+
+/// Used to store if the player is a largest or second largest shareholder.
+///
+/// The shareholders of each chain are stored by [`HotelChain::index`].
+#[derive(Clone, Default)]
+pub struct LargestShareholders {
+    /// Contains what the player ids of the largest shareholder for each chain are
+    largest_shareholder: [Vec<u32>; NUMBER_OF_CHAINS],
+    /// Contains what the player ids of the second largest shareholder for each chain are
+    second_largest_shareholder: [Vec<u32>; NUMBER_OF_CHAINS],
+    /// Contains what the player ids of the third largest shareholder for each chain are.
+    /// Players that are largest or second largest shareholder are not included.
+    third_largest_shareholder: [Vec<u32>; NUMBER_OF_CHAINS],
+    /// Contains how many stocks each player owns of each chain as `(player id, stocks)`, sorted
+    /// by player id. Players that do not own any stocks of the chain are not included.
+    stocks: [Vec<(u32, u32)>; NUMBER_OF_CHAINS],
+}
+
+impl LargestShareholders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the player ids of the largest shareholders of the chain
+    pub fn largest(&self, chain: &HotelChain) -> &[u32] {
+        &self.largest_shareholder[chain.index()]
+    }
+
+    /// Returns the player ids of the second largest shareholders of the chain
+    pub fn second_largest(&self, chain: &HotelChain) -> &[u32] {
+        &self.second_largest_shareholder[chain.index()]
+    }
+
+    /// Returns the player ids of the third largest shareholders of the chain
+    pub fn third_largest(&self, chain: &HotelChain) -> &[u32] {
+        &self.third_largest_shareholder[chain.index()]
+    }
+
+    /// Returns how many stocks each player owns of the chain as `(player id, stocks)`, sorted by
+    /// player id. Players that do not own any stocks of the chain are not included.
+    pub fn stocks(&self, chain: &HotelChain) -> &[(u32, u32)] {
+        &self.stocks[chain.index()]
+    }
+
+    /// Returns true when the players own the same stocks of the chain as when the shareholders
+    /// of the chain have been determined.
+    pub fn holdings_match(&self, chain: &HotelChain, players: &[Player]) -> bool {
+        let stocks = self.stocks(chain);
+        let mut holders = 0;
+        for player in players {
+            let owned = *player.owned_stocks.stocks_for_hotel(chain);
+            if owned == 0 {
+                continue;
+            }
+            holders += 1;
+            match stocks.binary_search_by_key(&player.id, |(id, _)| *id) {
+                Ok(index) if stocks[index].1 == owned => (),
+                _ => return false,
+            }
+        }
+        holders == stocks.len()
+    }
+
+    /// Determines the largest, second largest and third largest shareholders of the chain
+    /// again, the other chains are not changed.
+    ///
+    /// The shareholders are stored by player id, the order of the players does not matter.
+    /// Players that do not own stocks of the chain are never shareholders of it.
+    /// - A player that is the only one to own stocks of the chain is largest and second largest
+    ///   shareholder.
+    /// - When multiple players share the most stocks they are all largest and second largest
+    ///   shareholders, the players with the next most stocks are third largest shareholders.
+    /// - Otherwise the players with the second most stocks are second largest shareholders and
+    ///   the players with the third most stocks are third largest shareholders.
+    pub fn update_chain(&mut self, chain: &HotelChain, players: &[Player]) {
+        let mut stocks: Vec<(u32, u32)> = players
+            .iter()
+            .map(|player| (player.id, *player.owned_stocks.stocks_for_hotel(chain)))
+            .filter(|(_, stocks)| *stocks > 0)
+            .collect();
+        stocks.sort_unstable();
+        let tiers = tiers(&stocks);
+        let (largest, second, third) = match tiers.as_slice() {
+            [] => (Vec::new(), Vec::new(), Vec::new()),
+            [largest] => (largest.clone(), largest.clone(), Vec::new()),
+            [largest, rest @ ..] if largest.len() > 1 => {
+                (largest.clone(), largest.clone(), rest[0].clone())
+            }
+            [largest, second, rest @ ..] => (
+                largest.clone(),
+                second.clone(),
+                rest.first().cloned().unwrap_or_default(),
+            ),
+        };
+        let index = chain.index();
+        self.largest_shareholder[index] = largest;
+        self.second_largest_shareholder[index] = second;
+        self.third_largest_shareholder[index] = third;
+        self.stocks[index] = stocks;
+    }
+}
+
+/// Groups the player ids by the number of stocks they own, the players with the most stocks
+/// first. The ids in each group are sorted.
+fn tiers(stocks: &[(u32, u32)]) -> Vec<Vec<u32>> {
+    let mut amounts: Vec<u32> = stocks.iter().map(|(_, stocks)| *stocks).collect();
+    amounts.sort_unstable_by(|a, b| b.cmp(a));
+    amounts.dedup();
+    amounts
+        .iter()
+        .map(|amount| {
+            stocks
+                .iter()
+                .filter(|(_, stocks)| stocks == amount)
+                .map(|(id, _)| *id)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base_game::{hotel_chains::HotelChain, player::Player};
+
+    use super::LargestShareholders;
+
+    const CHAIN: HotelChain = HotelChain::Airport;
+
+    /// Determines the shareholders of all chains
+    fn compute_shareholders(players: &[Player]) -> LargestShareholders {
+        let mut shareholders = LargestShareholders::new();
+        for chain in HotelChain::iterator() {
+            shareholders.update_chain(chain, players);
+        }
+        shareholders
+    }
+
+    /// Creates one player per entry that owns that many stocks of [`CHAIN`]. The ids are given in
+    /// reverse to make sure that ids and indices are not mixed up.
+    fn players(stocks: &[u32]) -> Vec<Player> {
+        stocks
+            .iter()
+            .enumerate()
+            .map(|(index, stocks)| {
+                let id = (10 - index) as u32;
+                let mut player = Player::new(vec![], id, false, format!("Player {}", id));
+                player.owned_stocks.set_stocks(&CHAIN, *stocks);
+                player
+            })
+            .collect()
+    }
+
+    /// Returns the largest, second largest and third largest shareholders of [`CHAIN`] as
+    /// indices into the players.
+    fn ranks(players: &[Player]) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+        let shareholders = compute_shareholders(players);
+        let indices = |ids: &[u32]| {
+            let mut indices: Vec<usize> = ids
+                .iter()
+                .map(|id| players.iter().position(|player| player.id == *id).unwrap())
+                .collect();
+            indices.sort_unstable();
+            indices
+        };
+        (
+            indices(shareholders.largest(&CHAIN)),
+            indices(shareholders.second_largest(&CHAIN)),
+            indices(shareholders.third_largest(&CHAIN)),
+        )
+    }
+
+    #[test]
+    fn no_players_no_shareholders() {
+        let shareholders = compute_shareholders(&[]);
+        for chain in HotelChain::iterator() {
+            assert!(shareholders.largest(chain).is_empty());
+            assert!(shareholders.second_largest(chain).is_empty());
+            assert!(shareholders.third_largest(chain).is_empty());
+            assert!(shareholders.stocks(chain).is_empty());
+        }
+    }
+
+    #[test]
+    fn players_without_stocks_are_no_shareholders() {
+        assert_eq!(ranks(&players(&[0, 0, 0])), (vec![], vec![], vec![]));
+        assert!(compute_shareholders(&players(&[0, 0]))
+            .stocks(&CHAIN)
+            .is_empty());
+    }
+
+    #[test]
+    fn single_shareholder_is_largest_and_second_largest() {
+        assert_eq!(ranks(&players(&[3])), (vec![0], vec![0], vec![]));
+        assert_eq!(ranks(&players(&[0, 3, 0])), (vec![1], vec![1], vec![]));
+    }
+
+    #[test]
+    fn two_shareholders() {
+        assert_eq!(ranks(&players(&[5, 2])), (vec![0], vec![1], vec![]));
+        assert_eq!(ranks(&players(&[2, 5])), (vec![1], vec![0], vec![]));
+        assert_eq!(ranks(&players(&[4, 4])), (vec![0, 1], vec![0, 1], vec![]));
+    }
+
+    #[test]
+    fn ties_for_largest_shareholder() {
+        assert_eq!(
+            ranks(&players(&[4, 1, 4, 1])),
+            (vec![0, 2], vec![0, 2], vec![1, 3])
+        );
+        assert_eq!(
+            ranks(&players(&[3, 3, 3, 2, 1])),
+            (vec![0, 1, 2], vec![0, 1, 2], vec![3])
+        );
+    }
+
+    #[test]
+    fn ties_for_second_largest_shareholder() {
+        assert_eq!(
+            ranks(&players(&[2, 2, 6, 2])),
+            (vec![2], vec![0, 1, 3], vec![])
+        );
+        assert_eq!(
+            ranks(&players(&[1, 6, 3, 3, 1])),
+            (vec![1], vec![2, 3], vec![0, 4])
+        );
+    }
+
+    #[test]
+    fn third_largest_shareholders() {
+        assert_eq!(
+            ranks(&players(&[1, 2, 3, 4, 5, 6])),
+            (vec![5], vec![4], vec![3])
+        );
+        assert_eq!(
+            ranks(&players(&[6, 2, 4, 2, 1])),
+            (vec![0], vec![2], vec![1, 3])
+        );
+    }
+
+    #[test]
+    fn order_of_players_does_not_matter() {
+        let stocks = [3, 5, 3, 1, 5, 0];
+        let mut players = players(&stocks);
+        let expected = compute_shareholders(&players);
+        players.reverse();
+        let reversed = compute_shareholders(&players);
+        assert_eq!(expected.largest(&CHAIN), reversed.largest(&CHAIN));
+        assert_eq!(
+            expected.second_largest(&CHAIN),
+            reversed.second_largest(&CHAIN)
+        );
+        assert_eq!(
+            expected.third_largest(&CHAIN),
+            reversed.third_largest(&CHAIN)
+        );
+        assert_eq!(expected.stocks(&CHAIN), reversed.stocks(&CHAIN));
+    }
+
+    #[test]
+    fn chains_are_computed_independently() {
+        let mut players = players(&[2, 1]);
+        players[1].owned_stocks.set_stocks(&HotelChain::Luxor, 7);
+        let shareholders = compute_shareholders(&players);
+        let luxor_holder = players[1].id;
+        assert_eq!(shareholders.largest(&HotelChain::Luxor), [luxor_holder]);
+        assert_eq!(
+            shareholders.second_largest(&HotelChain::Luxor),
+            [luxor_holder]
+        );
+        assert_eq!(shareholders.largest(&CHAIN), [players[0].id]);
+        assert!(shareholders.largest(&HotelChain::Festival).is_empty());
+    }
+
+    #[test]
+    fn changed_holdings_are_detected() {
+        let mut players = players(&[3, 1]);
+        let mut shareholders = compute_shareholders(&players);
+        assert!(shareholders.holdings_match(&CHAIN, &players));
+        players[1].owned_stocks.set_stocks(&CHAIN, 4);
+        assert!(!shareholders.holdings_match(&CHAIN, &players));
+        assert!(shareholders.holdings_match(&HotelChain::Luxor, &players));
+        shareholders.update_chain(&CHAIN, &players);
+        assert!(shareholders.holdings_match(&CHAIN, &players));
+        assert_eq!(shareholders.largest(&CHAIN), [players[1].id]);
+        // A player that no longer owns stocks is detected as well
+        players[0].owned_stocks.set_stocks(&CHAIN, 0);
+        assert!(!shareholders.holdings_match(&CHAIN, &players));
+        assert!(!LargestShareholders::new().holdings_match(&CHAIN, &players));
+    }
+}
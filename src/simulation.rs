@@ -0,0 +1,236 @@
+use std::io::Write;
+
+use miette::{miette, IntoDiagnostic, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_json::json;
+
+use crate::{
+    base_game::{player::Player, settings::Settings},
+    bot::{BotPersonality, BotStrategy},
+    playout::Playout,
+};
+
+/// The maximum number of turns a simulated game can last. Games that reach this limit are
+/// ended and counted like every other game.
+pub const MAX_TURNS: u32 = 1000;
+
+/// The results of one strategy over all simulated games.
+pub struct StrategyStatistics {
+    pub strategy: BotStrategy,
+    /// The number of seats the strategy has played, a strategy can play multiple seats per game
+    pub seats: u32,
+    /// The number of seats that finished on the first place, shared first places count as win
+    pub wins: u32,
+    /// The money of all seats summed up after the final account
+    pub total_money: u64,
+    /// The turns of all games the strategy has played summed up, counted once per seat
+    pub total_turns: u64,
+}
+
+impl StrategyStatistics {
+    fn new(strategy: BotStrategy) -> Self {
+        Self {
+            strategy,
+            seats: 0,
+            wins: 0,
+            total_money: 0,
+            total_turns: 0,
+        }
+    }
+
+    /// Returns the share of seats that have won the game
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.seats.max(1) as f64
+    }
+
+    /// Returns the average money at the end of the game
+    pub fn average_money(&self) -> f64 {
+        self.total_money as f64 / self.seats.max(1) as f64
+    }
+
+    /// Returns the average number of turns the games lasted
+    pub fn average_turns(&self) -> f64 {
+        self.total_turns as f64 / self.seats.max(1) as f64
+    }
+}
+
+/// Lets bots play against each other and collects statistics about their results.
+///
+/// Each strategy takes one seat, the seats are rotated each game so that every strategy starts
+/// equally often from each seat. The same seed leads to the same results.
+/// # Arguments
+/// * `strategies` - The strategies of the bots, between 2 and 6
+/// * `games` - The number of games that should be played
+/// * `seed` - The seed from which the seeds of the games and bots are derived
+/// * `settings` - The rules and bot settings that are used in the games
+/// * `export` - When set, each decision of the bots is written as one json line that contains
+///   the game state, the decision and the final result of the bot
+/// # Returns
+/// The statistics for each distinct strategy in the order in which they first appear
+pub fn simulate_bots(
+    strategies: &[BotStrategy],
+    games: u32,
+    seed: u64,
+    settings: &Settings,
+    mut export: Option<&mut dyn Write>,
+) -> Result<Vec<StrategyStatistics>> {
+    if !(2..=6).contains(&strategies.len()) {
+        return Err(miette!(
+            "Unable to simulate games: The amount of bots is invalid. Valid: 2-6, entered: {}",
+            strategies.len()
+        ));
+    }
+    let mut statistics: Vec<StrategyStatistics> = Vec::new();
+    for strategy in strategies {
+        if !statistics.iter().any(|entry| entry.strategy == *strategy) {
+            statistics.push(StrategyStatistics::new(*strategy));
+        }
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    for game in 0..games {
+        let seats: Vec<BotStrategy> = (0..strategies.len())
+            .map(|seat| strategies[(seat + game as usize) % strategies.len()])
+            .collect();
+        let players = bot_players(&seats, settings, &mut rng);
+        let mut playout = Playout::new_game(players, settings.rules, rng.gen())?;
+        if export.is_some() {
+            playout.record_decisions();
+        }
+        while playout.turns() < MAX_TURNS && playout.step()? {}
+        playout.final_account()?;
+        let best_money = playout
+            .players
+            .iter()
+            .map(|player| player.money)
+            .max()
+            .unwrap();
+        if let Some(export) = export.as_mut() {
+            for decision in playout.take_recorded_decisions() {
+                let money = playout.players[decision.player_id as usize].money;
+                let best_rival_money = playout
+                    .players
+                    .iter()
+                    .filter(|player| player.id != decision.player_id)
+                    .map(|player| player.money)
+                    .max()
+                    .unwrap_or(0);
+                let line = json!({
+                    "game": game,
+                    "player": decision.player_id,
+                    "strategy": seats[decision.player_id as usize].name(),
+                    "state": decision.state,
+                    "action": decision.action,
+                    "result": {
+                        "money": money,
+                        "won": money == best_money,
+                        "lead": money as i64 - best_rival_money as i64,
+                    },
+                });
+                writeln!(export, "{}", line).into_diagnostic()?;
+            }
+        }
+        for (player, strategy) in playout.players.iter().zip(&seats) {
+            let entry = statistics
+                .iter_mut()
+                .find(|entry| entry.strategy == *strategy)
+                .unwrap();
+            entry.seats += 1;
+            if player.money == best_money {
+                entry.wins += 1;
+            }
+            entry.total_money += player.money as u64;
+            entry.total_turns += playout.turns() as u64;
+        }
+    }
+    Ok(statistics)
+}
+
+/// Creates one player without cards for each strategy. The player with the index 0 takes the
+/// first strategy. The seeds of the bots are drawn from the rng.
+pub fn bot_players(
+    strategies: &[BotStrategy],
+    settings: &Settings,
+    rng: &mut StdRng,
+) -> Vec<Player> {
+    strategies
+        .iter()
+        .enumerate()
+        .map(|(id, strategy)| {
+            let mut player = Player::new(
+                Vec::new(),
+                id as u32,
+                settings.small_board,
+                format!("Bot {}", id + 1),
+            );
+            player.bot = Some(strategy.create(BotPersonality::default(), settings, rng.gen()));
+            player
+        })
+        .collect()
+}
+
+/// Prints the statistics as table
+pub fn print_statistics(statistics: &[StrategyStatistics], games: u32, seed: u64) {
+    println!("Simulated {} games with seed {}:", games, seed);
+    println!(
+        "{:<12} {:>8} {:>12} {:>12}",
+        "Strategy", "Win rate", "Avg. money", "Avg. turns"
+    );
+    for entry in statistics {
+        println!(
+            "{:<12} {:>7.1}% {:>11.0}€ {:>12.1}",
+            entry.strategy.name(),
+            entry.win_rate() * 100.0,
+            entry.average_money(),
+            entry.average_turns()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+    use serde_json::Value;
+
+    use crate::{
+        base_game::settings::{Privacy, Settings},
+        bot::BotStrategy,
+    };
+
+    use super::simulate_bots;
+
+    #[test]
+    fn simulation_is_reproducible() -> Result<()> {
+        let settings = Settings::new(false, Privacy::default(), true);
+        let strategies = [
+            BotStrategy::Random,
+            BotStrategy::Greedy,
+            BotStrategy::Heuristic,
+        ];
+        let first = simulate_bots(&strategies, 3, 7, &settings, None)?;
+        let second = simulate_bots(&strategies, 3, 7, &settings, None)?;
+        for (first, second) in first.iter().zip(&second) {
+            assert_eq!(first.seats, 3);
+            assert_eq!(first.wins, second.wins);
+            assert_eq!(first.total_money, second.total_money);
+            assert_eq!(first.total_turns, second.total_turns);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn simulation_exports_decisions() -> Result<()> {
+        let settings = Settings::new(false, Privacy::default(), true);
+        let strategies = [BotStrategy::Greedy, BotStrategy::Heuristic];
+        let mut export = Vec::new();
+        simulate_bots(&strategies, 1, 7, &settings, Some(&mut export))?;
+        let export = String::from_utf8(export).unwrap();
+        assert!(export.lines().count() > 0);
+        for line in export.lines() {
+            let decision: Value = serde_json::from_str(line).unwrap();
+            assert!(decision["state"]["hand"].is_array());
+            assert!(decision["action"]["type"].is_string());
+            assert!(decision["result"]["won"].is_boolean());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use miette::Result;
+
+use crate::{
+    base_game::{
+        bank::Bank, board::Position, hotel_chains::HotelChain, player::Player,
+        settings::PURCHASE_LIMIT,
+    },
+    game::hotel_chain_manager::HotelChainManager,
+    logic::check_end_condition,
+    playout::{Observation, Playout},
+};
+
+/// The best turn with which a player ends the game, found by [`solve_ending_turn`].
+pub struct EndingTurn {
+    /// The card that is played
+    pub card: Position,
+    /// The stocks that are bought before the game is ended
+    pub stocks: HashMap<HotelChain, u32>,
+    /// How much more money the player has than the richest other player after the final
+    /// account. Negative when another player is richer.
+    pub lead: i64,
+}
+
+impl EndingTurn {
+    /// Returns true when the player wins the game by ending it with this turn.
+    pub fn wins(&self) -> bool {
+        self.lead > 0
+    }
+}
+
+/// Searches all cards and all stock purchases of the player for the best way to end the game in
+/// this turn.
+///
+/// The search is only done when all chains are safe. Then no chain can be fused anymore and the
+/// game can be ended as soon as the end condition is met, so the current turn is small enough to
+/// be searched exhaustively: each card and each purchase is played on a copy of the game that is
+/// ended with the final account. Stocks are not sold during the search.
+///
+/// Only this turn is searched. Whether waiting for a later turn would be better is not, because
+/// the cards of the other players are not known.
+///
+/// The money of the other players is taken from the observation.
+/// # Returns
+/// * `Ok(Some(turn))` - The turn with the largest lead after the final account
+/// * `Ok(None)` - Not all chains are safe or no card allows to end the game
+pub fn solve_ending_turn(
+    player: &Player,
+    observation: &Observation,
+    bank: &Bank,
+    hotel_chain_manager: &HotelChainManager,
+) -> Result<Option<EndingTurn>> {
+    let active_chains = hotel_chain_manager.active_chains();
+    if active_chains.is_empty()
+        || active_chains
+            .iter()
+            .any(|chain| !hotel_chain_manager.is_chain_safe(chain))
+    {
+        return Ok(None);
+    }
+    let mut best: Option<EndingTurn> = None;
+    for card in player
+        .analyzed_cards
+        .iter()
+        .filter(|card| !card.is_illegal())
+    {
+        let mut placed = Playout::new(player, observation, bank, hotel_chain_manager, 0);
+        placed.place_card(card.position)?;
+        if check_end_condition(&placed.board, &placed.hotel_chain_manager).is_none() {
+            continue;
+        }
+        for stocks in purchases(&placed.hotel_chain_manager.active_chains(), PURCHASE_LIMIT) {
+            let mut playout = Playout::new(player, observation, bank, hotel_chain_manager, 0);
+            playout.place_card(card.position)?;
+            if playout.buy_stocks(&stocks).is_err() {
+                // The player can not afford the stocks or the bank has not enough stocks left
+                continue;
+            }
+            playout.final_account()?;
+            let lead = final_lead(&playout, player.id);
+            if best.as_ref().is_none_or(|best| lead > best.lead) {
+                best = Some(EndingTurn {
+                    card: card.position,
+                    stocks,
+                    lead,
+                });
+            }
+        }
+    }
+    Ok(best)
+}
+
+/// Returns how much more money the player has than the richest other player.
+fn final_lead(playout: &Playout, player_id: u32) -> i64 {
+    let own = playout.players[player_id as usize].money as i64;
+    let richest_rival = playout
+        .players
+        .iter()
+        .filter(|other| other.id != player_id)
+        .map(|other| other.money as i64)
+        .max()
+        .unwrap_or(0);
+    own - richest_rival
+}
+
+/// Returns every way to buy up to `limit` stocks of the chains.
+fn purchases(chains: &[HotelChain], limit: u32) -> Vec<HashMap<HotelChain, u32>> {
+    let mut purchases = vec![HashMap::new()];
+    for chain in HotelChain::iterator().filter(|chain| chains.contains(chain)) {
+        let mut extended = Vec::new();
+        for purchase in &purchases {
+            let bought: u32 = purchase.values().sum();
+            for amount in 1..=limit - bought {
+                let mut purchase = purchase.clone();
+                purchase.insert(*chain, amount);
+                extended.push(purchase);
+            }
+        }
+        purchases.append(&mut extended);
+    }
+    purchases
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::{
+            bank::Bank,
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+        },
+        game::hotel_chain_manager::HotelChainManager,
+        playout::Observation,
+    };
+
+    use super::solve_ending_turn;
+
+    #[test]
+    fn ending_turn_is_solved_when_all_chains_are_safe() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(
+                vec![Position::new('I', 12)],
+                0,
+                false,
+                String::from("Player 1"),
+            ),
+            Player::new(
+                vec![Position::new('I', 10)],
+                1,
+                false,
+                String::from("Player 2"),
+            ),
+        ];
+        // A chain of 41 hotels is safe and allows to end the game
+        let mut positions = Vec::new();
+        for letter in ['A', 'B', 'C'] {
+            for number in 1..=12 {
+                positions.push(Position::new(letter, number));
+            }
+        }
+        for number in 1..=5 {
+            positions.push(Position::new('D', number));
+        }
+        for position in &positions {
+            board.place_hotel(position)?;
+        }
+        hotel_chain_manager.start_chain(
+            HotelChain::Imperial,
+            positions,
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        players[0].owned_stocks.set_stocks(&HotelChain::Imperial, 5);
+        players[1].owned_stocks.set_stocks(&HotelChain::Imperial, 3);
+        for player in players.iter_mut() {
+            player.analyze_cards(&board, &hotel_chain_manager);
+        }
+        bank.update_largest_shareholders(&players);
+        let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Imperial) as i64;
+        // The largest shareholder wins by ending the game without buying more stocks
+        let observation = Observation::new(&players[0], &board, &players);
+        let ending_turn =
+            solve_ending_turn(&players[0], &observation, &bank, &hotel_chain_manager)?.unwrap();
+        assert_eq!(ending_turn.card, Position::new('I', 12));
+        assert!(ending_turn.stocks.is_empty());
+        assert_eq!(ending_turn.lead, 2 * price + 5 * price);
+        assert!(ending_turn.wins());
+        // The second largest shareholder takes over the majority before ending the game
+        let observation = Observation::new(&players[1], &board, &players);
+        let ending_turn =
+            solve_ending_turn(&players[1], &observation, &bank, &hotel_chain_manager)?.unwrap();
+        assert_eq!(ending_turn.stocks.get(&HotelChain::Imperial), Some(&3));
+        assert_eq!(ending_turn.lead, 3 * price);
+        // Nothing is solved while a chain is not safe
+        board.place_hotel(&Position::new('G', 1))?;
+        board.place_hotel(&Position::new('G', 2))?;
+        hotel_chain_manager.start_chain(
+            HotelChain::Luxor,
+            vec![Position::new('G', 1), Position::new('G', 2)],
+            &mut board,
+            &mut players[1],
+            &mut bank,
+        )?;
+        let observation = Observation::new(&players[0], &board, &players);
+        assert!(
+            solve_ending_turn(&players[0], &observation, &bank, &hotel_chain_manager)?.is_none()
+        );
+        Ok(())
+    }
+}
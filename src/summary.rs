@@ -0,0 +1,227 @@
+use std::fs;
+
+use miette::{IntoDiagnostic, Result};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    base_game::{bank::Bank, hotel_chains::HotelChain, player::Player, settings::Settings},
+    game::{hotel_chain_manager::HotelChainManager, player_by_id, ranking, round::RoundFinances},
+    history::today,
+};
+
+/// Returns the summary of a game after the final account as json.
+///
+/// The summary contains the final standings, the statistics of each player, the size of each
+/// chain that is still on the board and every majority shareholder bonus that has been paid
+/// during the game.
+pub fn game_summary(
+    players: &[Player],
+    bank: &Bank,
+    hotel_chain_manager: &HotelChainManager,
+    settings: &Settings,
+) -> Value {
+    let name = |id: u32| player_by_id(id, players).map(|player| player.name.clone());
+    let standings: Vec<Value> = ranking(players)
+        .iter()
+        .map(|(rank, player)| {
+            json!({
+                "rank": rank,
+                "name": player.name,
+                "money": player.money,
+                "team": player.team.map(|team| team + 1),
+            })
+        })
+        .collect();
+    let statistics: Vec<Value> = players
+        .iter()
+        .map(|player| {
+            let profit: Map<String, Value> = HotelChain::iterator()
+                .filter(|chain| player.cost_basis.realized_profit(chain) != 0)
+                .map(|chain| {
+                    (
+                        chain.name().to_string(),
+                        json!(player.cost_basis.realized_profit(chain)),
+                    )
+                })
+                .collect();
+            let statistics = &player.statistics;
+            json!({
+                "name": player.name,
+                "stocks_bought": statistics.stocks_bought,
+                "money_spent": statistics.money_spent,
+                "stocks_sold": statistics.stocks_sold,
+                "money_earned": statistics.money_earned,
+                "bonuses": statistics.bonuses,
+                "founder_stocks": statistics.founder_stocks,
+                "profit": profit,
+                "resigned": player.resigned,
+            })
+        })
+        .collect();
+    let chains: Vec<Value> = HotelChain::iterator()
+        .filter(|chain| hotel_chain_manager.chain_status(chain))
+        .map(|chain| {
+            json!({
+                "chain": chain.name(),
+                "size": hotel_chain_manager.chain_length(chain),
+                "safe": hotel_chain_manager.is_chain_safe(chain),
+            })
+        })
+        .collect();
+    let bonuses: Vec<Value> = bank
+        .bonus_payouts
+        .iter()
+        .map(|payout| {
+            json!({
+                "chain": payout.chain.name(),
+                "player": name(payout.player_id),
+                "rank": payout.rank.name(),
+                "bonus": payout.bonus,
+            })
+        })
+        .collect();
+    json!({
+        "date": today(),
+        "rules": settings.rule_preset.name(),
+        "standings": standings,
+        "players": statistics,
+        "chains": chains,
+        "bonuses": bonuses,
+    })
+}
+
+/// Writes the summary of the game to the file, an existing file is overwritten.
+pub fn export_summary(
+    path: &str,
+    players: &[Player],
+    bank: &Bank,
+    hotel_chain_manager: &HotelChainManager,
+    settings: &Settings,
+) -> Result<()> {
+    let summary = game_summary(players, bank, hotel_chain_manager, settings);
+    fs::write(path, format!("{:#}\n", summary)).into_diagnostic()
+}
+
+/// Returns the finances as csv with one row per round and player.
+///
+/// The first row contains the names of the columns: `round,player,money,stock_value,net_worth`.
+pub fn finances_csv(finances: &[RoundFinances], players: &[Player]) -> String {
+    let mut csv = String::from("round,player,money,stock_value,net_worth\n");
+    for finances in finances {
+        let name = player_by_id(finances.player_id, players)
+            .map(|player| player.name.as_str())
+            .unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            finances.round,
+            csv_field(name),
+            finances.money,
+            finances.stock_value,
+            finances.net_worth()
+        ));
+    }
+    csv
+}
+
+/// Writes the finances of each round to the file as csv, an existing file is overwritten.
+pub fn export_finances(path: &str, finances: &[RoundFinances], players: &[Player]) -> Result<()> {
+    fs::write(path, finances_csv(finances, players)).into_diagnostic()
+}
+
+/// Quotes the field when it contains a character that has a meaning in csv.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use crate::{
+        base_game::{
+            bank::Bank,
+            board::{Board, Position},
+            hotel_chains::HotelChain,
+            player::Player,
+            settings::{Privacy, Settings},
+        },
+        game::{final_account, hotel_chain_manager::HotelChainManager, round::Round},
+    };
+
+    use super::{finances_csv, game_summary};
+
+    #[test]
+    fn summary_contains_the_results_of_the_game() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Player 1")),
+            Player::new(vec![], 1, false, String::from("Player 2")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Luxor,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        bank.buy_stock(&hotel_chain_manager, &HotelChain::Luxor, &mut players[1])?;
+        bank.buy_stock(&hotel_chain_manager, &HotelChain::Luxor, &mut players[1])?;
+        bank.update_largest_shareholders(&players);
+        final_account(&mut players, &mut bank, &hotel_chain_manager)?;
+        let settings = Settings::new(false, Privacy::default(), true);
+        let summary = game_summary(&players, &bank, &hotel_chain_manager, &settings);
+        assert_eq!(summary["rules"], "classic");
+        assert_eq!(summary["standings"][0]["name"], "Player 2");
+        assert_eq!(summary["standings"][0]["rank"], 1);
+        assert_eq!(summary["players"][1]["stocks_bought"], 2);
+        assert_eq!(summary["players"][0]["founder_stocks"], 1);
+        assert_eq!(summary["chains"][0]["chain"], "Luxor");
+        assert_eq!(summary["chains"][0]["size"], 2);
+        let bonuses = summary["bonuses"].as_array().unwrap();
+        assert_eq!(bonuses.len(), 2);
+        assert_eq!(bonuses[0]["player"], "Player 1");
+        assert_eq!(bonuses[0]["rank"], "second largest");
+        assert_eq!(bonuses[1]["player"], "Player 2");
+        assert_eq!(bonuses[1]["rank"], "largest");
+        Ok(())
+    }
+
+    #[test]
+    fn finances_are_written_as_csv() -> Result<()> {
+        let mut board = Board::new();
+        let mut hotel_chain_manager = HotelChainManager::new();
+        let mut bank = Bank::new();
+        let mut players = vec![
+            Player::new(vec![], 0, false, String::from("Player 1")),
+            Player::new(vec![], 1, false, String::from("Smith, \"Jo\"")),
+        ];
+        hotel_chain_manager.start_chain(
+            HotelChain::Luxor,
+            vec![Position::new('A', 1), Position::new('A', 2)],
+            &mut board,
+            &mut players[0],
+            &mut bank,
+        )?;
+        let mut round = Round::new(1);
+        round.record_finances(&players, &hotel_chain_manager);
+        let price = Bank::stock_price(&hotel_chain_manager, &HotelChain::Luxor);
+        assert_eq!(round.finances[0].stock_value, price);
+        assert_eq!(round.finances[0].net_worth(), 6000 + price);
+        let csv = finances_csv(&round.finances, &players);
+        assert_eq!(
+            csv,
+            format!(
+                "round,player,money,stock_value,net_worth\n1,Player 1,6000,{},{}\n1,\"Smith, \"\"Jo\"\"\",6000,0,6000\n",
+                price,
+                6000 + price
+            )
+        );
+        Ok(())
+    }
+}
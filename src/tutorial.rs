@@ -0,0 +1,129 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    mem::{discriminant, Discriminant},
+    thread,
+    time::Duration,
+};
+
+use miette::Result;
+use owo_colors::OwoColorize;
+
+use crate::base_game::{hotel_chains::HotelChain, player::Player};
+
+/// The time the game waits after each step of a bot so that the players can follow what the bot
+/// does.
+const BOT_PAUSE: Duration = Duration::from_millis(1500);
+
+/// The events of the game that are explained in the tutorial.
+pub enum TutorialEvent {
+    /// The game has been started
+    GameStart,
+    /// A human player has their first turn
+    PlayerTurn,
+    /// A hotel has been placed that does not belong to any chain
+    SingleHotel,
+    /// A new chain has been founded
+    ChainFounded(HotelChain),
+    /// A chain has grown
+    ChainExtended(HotelChain),
+    /// Two chains are fused
+    Fusion { alive: HotelChain, dead: HotelChain },
+    /// A chain has become safe
+    SafeChain(HotelChain),
+    /// Stocks can be bought
+    BuyStocks,
+    /// A game ending condition is met
+    EndCondition,
+    /// A player has only cards left that can not be played
+    IllegalCards,
+}
+
+impl TutorialEvent {
+    /// Returns the explanation of the event
+    fn explanation(&self) -> String {
+        match self {
+            TutorialEvent::GameStart => String::from("Welcome to Acquire! Everybody starts with 6000€ and six hotel cards. Hotels that are placed next to each other form chains, players buy stocks of these chains and get bonuses when the chains are fused. The player with the most money at the end of the game wins."),
+            TutorialEvent::PlayerTurn => String::from("It is your turn. Each turn has three steps: you place one of your hotel cards on the board, you buy up to three stocks of the active chains and you draw a new card."),
+            TutorialEvent::SingleHotel => String::from("The hotel does not touch any other hotel, so nothing else happens. It can become part of a chain later."),
+            TutorialEvent::ChainFounded(chain) => format!("A new chain has been founded: two hotels are now next to each other and belong to {}. The founder gets a free {} stock. The price of a stock depends on the number of hotels in the chain.", chain, chain),
+            TutorialEvent::ChainExtended(chain) => format!("The hotel has been placed next to {} and now belongs to it. Larger chains have more valuable stocks and pay higher bonuses.", chain),
+            TutorialEvent::Fusion { alive, dead } => format!("A merger happened: the larger chain {} survives and takes over the hotels of {}. The largest and the second largest shareholders of {} get a bonus. Now everyone who owns {} stocks decides what to do with them: keep them, sell them for the current price or trade two of them for one {} stock.", alive, dead, dead, dead, alive),
+            TutorialEvent::SafeChain(chain) => format!("{} has become so large that it is safe: it can no longer be fused into another chain. Cards that would fuse two safe chains can not be played.", chain),
+            TutorialEvent::BuyStocks => String::from("Now stocks can be bought. Try to become the largest or second largest shareholder of a chain, because only they get a bonus when the chain is fused or the game ends."),
+            TutorialEvent::EndCondition => String::from("A game ending condition is met. The player whose turn it is can end the game now. At the end all bonuses are paid and all stocks are sold, then the richest player wins."),
+            TutorialEvent::IllegalCards => String::from("None of the cards can be played because they would fuse safe chains. These cards are dead and can be exchanged for new cards."),
+        }
+    }
+}
+
+/// Explains the events of the game to the players the first time they happen and lets the bots
+/// play slowly.
+pub struct Tutorial {
+    /// The events that have already been explained
+    explained: RefCell<HashSet<Discriminant<TutorialEvent>>>,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self {
+            explained: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Explains the event to all players that are not played by a bot.
+    /// Nothing is done when an event of the same kind has already been explained.
+    pub fn explain(&self, event: TutorialEvent, players: &[Player]) -> Result<()> {
+        if !self.explained.borrow_mut().insert(discriminant(&event)) {
+            return Ok(());
+        }
+        for player in players.iter().filter(|player| player.bot.is_none()) {
+            player.print_text_ln(&format!(
+                "{} {}",
+                "Tutorial:".bright_cyan(),
+                event.explanation()
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Waits a moment when the player is a bot so that the other players can follow the game.
+    pub fn pause_for_bot(&self, player: &Player) {
+        if player.bot.is_some() {
+            thread::sleep(BOT_PAUSE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::discriminant;
+
+    use miette::Result;
+
+    use crate::base_game::hotel_chains::HotelChain;
+
+    use super::{Tutorial, TutorialEvent};
+
+    #[test]
+    fn events_are_explained_once() -> Result<()> {
+        let tutorial = Tutorial::new();
+        tutorial.explain(TutorialEvent::ChainFounded(HotelChain::Luxor), &[])?;
+        tutorial.explain(TutorialEvent::ChainFounded(HotelChain::Airport), &[])?;
+        tutorial.explain(TutorialEvent::BuyStocks, &[])?;
+        let explained = tutorial.explained.borrow();
+        assert_eq!(explained.len(), 2);
+        assert!(
+            explained.contains(&discriminant(&TutorialEvent::ChainFounded(
+                HotelChain::Imperial
+            )))
+        );
+        Ok(())
+    }
+}
@@ -34,6 +34,18 @@ pub fn remove_content_from_vec<T: PartialEq>(to_remove: T, vec: &mut Vec<T>) ->
     }
 }
 
+/// Returns the name when no player has taken it yet. Otherwise a number is appended to the name
+/// that makes it unique, like `Player (2)`.
+pub fn unique_name(name: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|other| other == name) {
+        return String::from(name);
+    }
+    (2..)
+        .map(|number| format!("{} ({})", name, number))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap()
+}
+
 /// Writes the chains contained in the chain array into a nicely formatted string.
 ///
 /// This output could look like this: `Oriental, Prestige, Festival` (The chain names will be
@@ -51,3 +63,17 @@ pub fn chains_to_print(chains: &[HotelChain]) -> String {
     }
     chains_to_print
 }
+
+#[cfg(test)]
+mod tests {
+    use super::unique_name;
+
+    #[test]
+    fn names_are_made_unique() {
+        let taken = vec![String::from("Anna"), String::from("Anna (2)")];
+        assert_eq!(unique_name("Ben", &taken), "Ben");
+        assert_eq!(unique_name("Anna", &taken), "Anna (3)");
+        assert_eq!(unique_name("Anna (2)", &taken), "Anna (2) (2)");
+        assert_eq!(unique_name("Anna", &[]), "Anna");
+    }
+}
@@ -0,0 +1,371 @@
+use std::io::stdin;
+
+use miette::{miette, IntoDiagnostic, Result};
+use owo_colors::{OwoColorize, Rgb};
+use serde_json::Value;
+
+use crate::{
+    base_game::{
+        board::{Board, BoardStyle},
+        hotel_chains::HotelChain,
+        player::PLAYER_COLORS,
+        ui::clear_console,
+    },
+    game::GameManager,
+    replay::{read_replay, replay_states, ReplayHeader},
+};
+
+/// A command that can be entered in the replay viewer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ViewerCommand {
+    /// Shows the next decisions, the number of steps is given
+    Forward(usize),
+    /// Shows the previous decisions, the number of steps is given
+    Back(usize),
+    /// Shows the first decision of the round
+    Round(u32),
+    Quit,
+}
+
+impl ViewerCommand {
+    /// Reads the command from the input of the user.
+    ///
+    /// An empty input, `n` and the right or down arrow key step forward, `b` and the left or up
+    /// arrow key step back. When the arrow keys are pressed multiple times one step is made for
+    /// each press. `r 5` jumps to round 5 and `q` quits the viewer.
+    pub fn parse(input: &str) -> Option<ViewerCommand> {
+        let input = input.trim();
+        let forward = input.matches("\x1B[C").count() + input.matches("\x1B[B").count();
+        let back = input.matches("\x1B[D").count() + input.matches("\x1B[A").count();
+        if forward + back > 0 {
+            return match forward.cmp(&back) {
+                std::cmp::Ordering::Less => Some(ViewerCommand::Back(back - forward)),
+                _ => Some(ViewerCommand::Forward(forward - back)),
+            };
+        }
+        match input.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            [] | ["n"] | ["next"] => Some(ViewerCommand::Forward(1)),
+            ["b"] | ["back"] => Some(ViewerCommand::Back(1)),
+            ["q"] | ["quit"] => Some(ViewerCommand::Quit),
+            ["r", round] | ["round", round] => round.parse().ok().map(ViewerCommand::Round),
+            _ => None,
+        }
+    }
+}
+
+/// Steps through the decisions of a replay.
+///
+/// For each decision the board, the market and the money of the players are shown like they
+/// have been before the decision was made. After the last decision the result is shown.
+pub struct ReplayViewer {
+    header: ReplayHeader,
+    /// The decisions of the replay, each with the game state in which it has been made
+    decisions: Vec<Value>,
+    /// The last line of the replay that contains the result of the game
+    result: Value,
+    /// The index of the decision that is shown, the result is shown when it is equal to the
+    /// number of decisions
+    position: usize,
+}
+
+impl ReplayViewer {
+    /// Loads the replay, the first decision is shown.
+    ///
+    /// The states of the decisions that are no keyframe are restored by playing the recorded
+    /// actions again, see [`replay_states`].
+    pub fn new(replay: &str) -> Result<Self> {
+        let (header, mut lines) = read_replay(replay)?;
+        let result = match lines.pop() {
+            Some(result) if lines.len() > 1 && !result["money"].is_null() => result,
+            _ => {
+                return Err(miette!(
+                    "Unable to read replay: The replay does not contain the result of the game"
+                ))
+            }
+        };
+        let mut decisions: Vec<Value> = lines.into_iter().skip(1).collect();
+        if decisions
+            .iter()
+            .any(|decision| decision["state"]["players"].is_null())
+        {
+            decisions = replay_states(&header, &decisions)?;
+        }
+        Ok(Self {
+            header,
+            decisions,
+            result,
+            position: 0,
+        })
+    }
+
+    /// Shows the decision that is `steps` decisions after the current one, at most the result.
+    pub fn forward(&mut self, steps: usize) {
+        self.position = (self.position + steps).min(self.decisions.len());
+    }
+
+    /// Shows the decision that is `steps` decisions before the current one, at least the first.
+    pub fn back(&mut self, steps: usize) {
+        self.position = self.position.saturating_sub(steps);
+    }
+
+    /// Shows the first decision of the round. The result is shown when the game has ended
+    /// before the round.
+    pub fn jump_to_round(&mut self, round: u32) -> Result<()> {
+        if round == 0 {
+            return Err(miette!("The first round is round 1"));
+        }
+        self.position = (0..self.decisions.len())
+            .find(|position| self.round(*position) >= round)
+            .unwrap_or(self.decisions.len());
+        Ok(())
+    }
+
+    /// Returns the number of turns that have been played before the decision
+    fn turn(&self, position: usize) -> u32 {
+        let turn = match self.decisions.get(position) {
+            Some(decision) => &decision["state"]["turn"],
+            None => &self.result["turns"],
+        };
+        turn.as_u64().unwrap_or_default() as u32
+    }
+
+    /// Returns the round in which the decision has been made, the first round is round 1
+    fn round(&self, position: usize) -> u32 {
+        self.turn(position) / self.header.strategies.len() as u32 + 1
+    }
+
+    /// Returns the name of the bot with the id in its color
+    fn bot_name(&self, id: u32) -> String {
+        let strategy = self
+            .header
+            .strategies
+            .get(id as usize)
+            .map(|strategy| strategy.name())
+            .unwrap_or_default();
+        format!("Bot {} ({})", id + 1, strategy)
+            .color(PLAYER_COLORS[id as usize % PLAYER_COLORS.len()])
+            .to_string()
+    }
+
+    /// Returns the lines that show the decision or the result that is currently shown.
+    pub fn frame_ui(&self) -> Vec<String> {
+        let mut ui = vec![format!(
+            "Replay with seed {} and {} rules",
+            self.header.seed,
+            self.header.rule_preset.name()
+        )];
+        let decision = match self.decisions.get(self.position) {
+            Some(decision) => decision,
+            None => {
+                ui.push(format!(
+                    "The game has ended after {} turns:",
+                    self.turn(self.position)
+                ));
+                let money = self.result["money"].as_array().cloned().unwrap_or_default();
+                for (id, money) in money.iter().enumerate() {
+                    ui.push(format!("{}: {}€", self.bot_name(id as u32), money));
+                }
+                return ui;
+            }
+        };
+        let state = &decision["state"];
+        ui.push(format!(
+            "Decision {}/{} | Round {} | Turn {}",
+            self.position + 1,
+            self.decisions.len(),
+            self.round(self.position),
+            self.turn(self.position) + 1
+        ));
+        ui.append(&mut board(state).render_console(BoardStyle::Large));
+        ui.push(String::new());
+        ui.push(format!(
+            "{:12} {:>6} {:>6} {:>11}",
+            "Chain", "Hotels", "Price", "Stocks left"
+        ));
+        for chain in state["chains"].as_array().into_iter().flatten() {
+            let hotel_chain = match chain_by_name(&chain["chain"]) {
+                Some(hotel_chain) => hotel_chain,
+                None => continue,
+            };
+            ui.push(format!(
+                "{:12} {:>6} {:>5}€ {:>11}{}",
+                hotel_chain.name().color(hotel_chain.color()),
+                chain["hotels"].as_u64().unwrap_or_default(),
+                chain["price"].as_u64().unwrap_or_default(),
+                chain["stocks_available"].as_u64().unwrap_or_default(),
+                if chain["safe"] == true { " (safe)" } else { "" }
+            ));
+        }
+        ui.push(String::new());
+        for player in state["players"].as_array().into_iter().flatten() {
+            let id = player["id"].as_u64().unwrap_or_default() as u32;
+            ui.push(
+                format!(
+                    "{}: {}€ {}",
+                    self.bot_name(id),
+                    player["money"],
+                    stocks_ui(&player["stocks"])
+                )
+                .trim_end()
+                .to_string(),
+            );
+        }
+        ui.push(String::new());
+        let player = decision["player"].as_u64().unwrap_or_default() as u32;
+        ui.push(format!(
+            "Next: {} {}",
+            self.bot_name(player),
+            action_ui(&decision["action"])
+        ));
+        ui
+    }
+
+    /// Shows the replay until the user quits the viewer.
+    ///
+    /// The console is only redrawn when another decision is shown or a message has to be shown,
+    /// a step back at the first decision does not redraw the console.
+    pub fn run(&mut self) -> Result<()> {
+        let mut message = None;
+        let mut dirty = true;
+        loop {
+            if dirty {
+                clear_console();
+                for line in self.frame_ui() {
+                    println!("{}", line);
+                }
+                if let Some(message) = message.take() {
+                    println!("{}", message);
+                }
+                println!(
+                    "{}",
+                    "Press enter or → to step forward, ← or b to step back, r <round> to jump to a round and q to quit"
+                        .color(Rgb(105, 105, 105))
+                );
+            }
+            let mut buffer = String::new();
+            if stdin().read_line(&mut buffer).into_diagnostic()? == 0 {
+                return Ok(());
+            }
+            let shown = self.position;
+            match ViewerCommand::parse(&buffer) {
+                Some(ViewerCommand::Forward(steps)) => self.forward(steps),
+                Some(ViewerCommand::Back(steps)) => self.back(steps),
+                Some(ViewerCommand::Round(round)) => {
+                    if let Err(err) = self.jump_to_round(round) {
+                        message = Some(err.to_string());
+                    }
+                }
+                Some(ViewerCommand::Quit) => return Ok(()),
+                None => message = Some(format!("Unknown command: {}", buffer.trim())),
+            }
+            dirty = self.position != shown || message.is_some();
+        }
+    }
+}
+
+/// Returns the chain with the name
+fn chain_by_name(name: &Value) -> Option<HotelChain> {
+    name.as_str().and_then(HotelChain::from_name)
+}
+
+/// Places the hotels of the game state on a new board
+fn board(state: &Value) -> Board {
+    let mut board = Board::new();
+    let hotels = state["hotels"].as_array().cloned().unwrap_or_default();
+    for position in GameManager::init_position_cards() {
+        let name = position.to_string();
+        if let Some(hotel) = hotels
+            .iter()
+            .find(|hotel| hotel["position"] == name.as_str())
+        {
+            // The positions are taken from the board, so placing them can not fail
+            board.place_hotel(&position).unwrap();
+            if let Some(chain) = chain_by_name(&hotel["chain"]) {
+                board.update_hotel(chain, &position).unwrap();
+            }
+        }
+    }
+    // The order in which the hotels have been placed is not stored
+    board.last_placed = None;
+    board
+}
+
+/// Returns the stocks like `2 Luxor, 1 Imperial`
+fn stocks_ui(stocks: &Value) -> String {
+    let stocks: Vec<String> = HotelChain::iterator()
+        .filter_map(|chain| {
+            stocks[chain.name()]
+                .as_u64()
+                .map(|amount| format!("{} {}", amount, chain.name().color(chain.color())))
+        })
+        .collect();
+    stocks.join(", ")
+}
+
+/// Describes what the bot has decided
+fn action_ui(action: &Value) -> String {
+    let chain = |key: &str| {
+        chain_by_name(&action[key])
+            .map(|chain| chain.name().color(chain.color()).to_string())
+            .unwrap_or_default()
+    };
+    match action["type"].as_str() {
+        Some("place_card") => format!("places {}", action["card"].as_str().unwrap_or_default()),
+        Some("found_chain") => format!("founds {}", chain("chain")),
+        Some("choose_surviving_chain") => {
+            format!("lets {} survive the fusion", chain("chain"))
+        }
+        Some("handle_fusion_stocks") => format!(
+            "exchanges {} and sells {} stocks of {} for {}",
+            action["exchange"],
+            action["sell"],
+            chain("dead"),
+            chain("alive")
+        ),
+        Some("sell_stocks") => format!("sells {}", stocks_ui(&action["stocks"])),
+        Some("buy_stocks") if stocks_ui(&action["stocks"]).is_empty() => {
+            String::from("buys no stocks")
+        }
+        Some("buy_stocks") => format!("buys {}", stocks_ui(&action["stocks"])),
+        Some("end_game") if action["end"] == true => String::from("ends the game"),
+        Some("end_game") => String::from("continues the game"),
+        _ => action.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Result;
+
+    use super::{ReplayViewer, ViewerCommand};
+
+    #[test]
+    fn viewer_steps_through_the_replay() -> Result<()> {
+        let replay = include_str!("../replays/greedy-heuristic-random.jsonl");
+        let mut viewer = ReplayViewer::new(replay)?;
+        let decisions = replay.lines().count() - 2;
+        assert!(viewer.frame_ui()[1].starts_with(&format!("Decision 1/{}", decisions)));
+        assert_eq!(
+            ViewerCommand::parse("\x1B[C\x1B[C\n"),
+            Some(ViewerCommand::Forward(2))
+        );
+        assert_eq!(ViewerCommand::parse("\x1B[D"), Some(ViewerCommand::Back(1)));
+        assert_eq!(ViewerCommand::parse("r 3"), Some(ViewerCommand::Round(3)));
+        assert_eq!(ViewerCommand::parse("x"), None);
+        viewer.forward(2);
+        viewer.back(5);
+        assert_eq!(viewer.position, 0);
+        viewer.jump_to_round(3)?;
+        assert!(viewer.frame_ui()[1].contains("Round 3 | Turn 7"));
+        viewer.back(1);
+        assert!(viewer.frame_ui()[1].contains("Round 2"));
+        assert!(viewer.jump_to_round(0).is_err());
+        viewer.jump_to_round(1000)?;
+        assert_eq!(viewer.position, decisions);
+        assert!(viewer.frame_ui()[1].starts_with("The game has ended"));
+        // The states between the keyframes are restored
+        let viewer = ReplayViewer::new(include_str!("../replays/random-greedy-1999-rules.acq"))?;
+        assert!(viewer.decisions[1]["state"]["players"].is_array());
+        Ok(())
+    }
+}
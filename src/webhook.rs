@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use miette::{miette, Result};
+use serde_json::{json, Value};
+
+use crate::{history::GameResult, locale::text};
+
+/// How long the webhook may take to answer
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns the json that is posted to the webhook.
+///
+/// The standings are written as message to `content` for Discord and to `text` for Slack, the
+/// results are added like they are stored in the history file.
+pub fn webhook_payload(results: &[GameResult]) -> Value {
+    let mut lines = vec![text("webhook.ended", &[])];
+    for result in results {
+        lines.push(text(
+            "webhook.result",
+            &[&result.placement, &result.name, &result.money],
+        ));
+    }
+    let message = lines.join("\n");
+    let results: Vec<Value> = results.iter().map(GameResult::to_json).collect();
+    json!({
+        "content": message,
+        "text": message,
+        "results": results,
+    })
+}
+
+/// Posts the results of the game to the webhook.
+pub fn send_results(url: &str, results: &[GameResult]) -> Result<()> {
+    ureq::post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .send_string(&webhook_payload(results).to_string())
+        .map_err(|err| miette!("{}", err))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use miette::Result;
+
+    use crate::history::GameResult;
+
+    use super::send_results;
+
+    #[test]
+    fn results_are_posted_to_the_webhook() -> Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            // The request is complete when the json has been closed
+            while !request.ends_with(b"}") {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        let result = GameResult {
+            name: String::from("Anna"),
+            placement: 1,
+            money: 12400,
+            players: 2,
+            bots: 1,
+            rules: String::from("classic"),
+            date: String::from("2022-03-01"),
+        };
+        send_results(&url, &[result])?;
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /hook HTTP/1.1"));
+        assert!(request.contains("1. Anna with 12400€"));
+        assert!(request.contains("\"results\":[{"));
+        assert!(send_results("http://127.0.0.1:1/hook", &[]).is_err());
+        Ok(())
+    }
+}